@@ -443,6 +443,8 @@ impl<R: rand::RngCore + rand::CryptoRng> ConfigBuilder<R> {
                         default_end_of_epoch_broadcast_channel_capacity(),
                     checkpoint_executor_config: Default::default(),
                     metrics: None,
+                    state_snapshot_config: None,
+                    checkpoint_archive_config: None,
                     supported_protocol_versions: Some(supported_protocol_versions),
                 }
             })
@@ -15,6 +15,7 @@ use std::collections::BTreeMap;
 use std::net::SocketAddr;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::Duration;
 use std::usize;
 use sui_keys::keypair_file::{read_authority_keypair_from_file, read_keypair_from_file};
 use sui_protocol_config::SupportedProtocolVersions;
@@ -27,6 +28,7 @@ use sui_types::crypto::NetworkPublicKey;
 use sui_types::crypto::PublicKey as AccountsPublicKey;
 use sui_types::crypto::SuiKeyPair;
 use sui_types::crypto::{get_key_pair_from_rng, AccountKeyPair, AuthorityKeyPair};
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 
 // Default max number of concurrent requests served
 pub const DEFAULT_GRPC_CONCURRENCY_LIMIT: usize = 20000000000;
@@ -94,6 +96,14 @@ pub struct NodeConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metrics: Option<MetricsConfig>,
 
+    /// If set, the node writes a snapshot of the live object set at the end of every epoch.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_snapshot_config: Option<StateSnapshotConfig>,
+
+    /// If set, the node appends every checkpoint it syncs to a checkpoint archive.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checkpoint_archive_config: Option<CheckpointArchiveConfig>,
+
     /// In a `sui-node` binary, this is set to SupportedProtocolVersions::SYSTEM_DEFAULT
     /// in sui-node/src/main.rs. It is present in the config so that it can be changed by tests in
     /// order to test protocol upgrades.
@@ -279,6 +289,60 @@ impl Default for CheckpointExecutorConfig {
     }
 }
 
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct StateSnapshotConfig {
+    /// Directory under which the snapshot of epoch `N` is written, as `epoch_N`.
+    pub snapshot_path: PathBuf,
+}
+
+impl StateSnapshotConfig {
+    pub fn epoch_snapshot_path(&self, epoch: u64) -> PathBuf {
+        self.snapshot_path.join(format!("epoch_{}", epoch))
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CheckpointArchiveConfig {
+    /// Directory of the archive.
+    pub archive_path: PathBuf,
+
+    /// Number of checkpoints in each bundle of the archive.
+    ///
+    /// If unspecified, this will default to `1,000`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checkpoints_per_bundle: Option<u64>,
+
+    /// First checkpoint of a new archive. Ignored once the archive holds checkpoints, as it is
+    /// then extended from its last checkpoint.
+    ///
+    /// If unspecified, a new archive starts at the lowest checkpoint which hasn't been pruned.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_checkpoint: Option<CheckpointSequenceNumber>,
+
+    /// Check for new checkpoints to archive every interval period.
+    ///
+    /// If unspecified, this will default to `10,000` milliseconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interval_period_ms: Option<u64>,
+}
+
+impl CheckpointArchiveConfig {
+    pub fn checkpoints_per_bundle(&self) -> u64 {
+        const CHECKPOINTS_PER_BUNDLE: u64 = 1_000;
+
+        self.checkpoints_per_bundle
+            .unwrap_or(CHECKPOINTS_PER_BUNDLE)
+    }
+
+    pub fn interval_period(&self) -> Duration {
+        const INTERVAL_PERIOD_MS: u64 = 10_000;
+
+        Duration::from_millis(self.interval_period_ms.unwrap_or(INTERVAL_PERIOD_MS))
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct AuthorityStorePruningConfig {
@@ -288,6 +352,10 @@ pub struct AuthorityStorePruningConfig {
     pub num_latest_epoch_dbs_to_retain: usize,
     pub epoch_db_pruning_period_secs: u64,
     pub num_epochs_to_retain: u64,
+    /// Whether checkpoints are only pruned once they have been archived. Set by the node when it
+    /// keeps a checkpoint archive.
+    #[serde(skip)]
+    pub retain_unarchived_checkpoints: bool,
 }
 
 impl Default for AuthorityStorePruningConfig {
@@ -299,6 +367,7 @@ impl Default for AuthorityStorePruningConfig {
             num_latest_epoch_dbs_to_retain: usize::MAX,
             epoch_db_pruning_period_secs: u64::MAX,
             num_epochs_to_retain: u64::MAX,
+            retain_unarchived_checkpoints: false,
         }
     }
 }
@@ -314,6 +383,7 @@ impl AuthorityStorePruningConfig {
             num_latest_epoch_dbs_to_retain: 3,
             epoch_db_pruning_period_secs: 60 * 60,
             num_epochs_to_retain: if cfg!(msim) { 1 } else { u64::MAX },
+            retain_unarchived_checkpoints: false,
         }
     }
     pub fn fullnode_config() -> Self {
@@ -324,6 +394,7 @@ impl AuthorityStorePruningConfig {
             num_latest_epoch_dbs_to_retain: 3,
             epoch_db_pruning_period_secs: 60 * 60,
             num_epochs_to_retain: if cfg!(msim) { 1 } else { u64::MAX },
+            retain_unarchived_checkpoints: false,
         }
     }
 }
@@ -1,7 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{net::SocketAddr, num::NonZeroU32, time::Duration};
+use std::{net::SocketAddr, num::NonZeroU32, path::PathBuf, time::Duration};
 
 use multiaddr::Multiaddr;
 use serde::{Deserialize, Serialize};
@@ -138,6 +138,13 @@ pub struct StateSyncConfig {
     /// If unspecified, this will default to no limit.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub get_transaction_and_effects_rate_limit: Option<NonZeroU32>,
+
+    /// Path of a local checkpoint archive to read checkpoints from when none of our peers are
+    /// able to provide them.
+    ///
+    /// If unspecified, checkpoints are only synced from peers.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archive_path: Option<PathBuf>,
 }
 
 impl StateSyncConfig {
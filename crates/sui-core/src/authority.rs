@@ -8,7 +8,7 @@ use chrono::prelude::*;
 use fastcrypto::encoding::Base58;
 use fastcrypto::encoding::Encoding;
 use fastcrypto::traits::KeyPair;
-use move_bytecode_utils::module_cache::SyncModuleCache;
+use move_bytecode_utils::module_cache::{GetModule, SyncModuleCache};
 use move_core_types::account_address::AccountAddress;
 use move_core_types::identifier::Identifier;
 use move_core_types::language_storage::ModuleId;
@@ -23,7 +23,7 @@ use prometheus::{
     IntCounterVec, IntGauge, Registry,
 };
 use serde::de::DeserializeOwned;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Duration;
@@ -55,7 +55,9 @@ use sui_json_rpc_types::{
 };
 use sui_macros::nondeterministic;
 use sui_protocol_config::SupportedProtocolVersions;
-use sui_storage::indexes::{ObjectIndexChanges, MAX_GET_OWNED_OBJECT_SIZE};
+use sui_storage::indexes::{
+    ObjectIndexChanges, TransactionCheckpointReader, MAX_GET_OWNED_OBJECT_SIZE,
+};
 use sui_storage::write_ahead_log::WriteAheadLog;
 use sui_storage::{
     event_store::{EventStore, EventStoreType, StoredEvent},
@@ -93,6 +95,7 @@ use crate::authority::authority_per_epoch_store::{
     AuthorityPerEpochStore, EpochStartConfiguration,
 };
 use crate::authority::authority_per_epoch_store_pruner::AuthorityPerEpochStorePruner;
+use crate::authority::authority_read_snapshot::AuthorityReadSnapshot;
 use crate::authority::authority_store::{ExecutionLockReadGuard, ObjectLockStatus};
 use crate::authority::authority_store_pruner::AuthorityStorePruner;
 use crate::checkpoints::CheckpointStore;
@@ -131,6 +134,7 @@ mod tbls_tests;
 pub mod authority_per_epoch_store;
 pub mod authority_per_epoch_store_pruner;
 
+pub mod authority_read_snapshot;
 pub mod authority_store_pruner;
 pub mod authority_store_tables;
 
@@ -425,12 +429,15 @@ pub struct AuthorityState {
     /// Manages pending certificates and their missing input objects.
     transaction_manager: Arc<TransactionManager>,
 
+    /// The protocol versions this authority can run.
+    supported_protocol_versions: SupportedProtocolVersions,
+
     /// Shuts down the execution task. Used only in testing.
     #[allow(unused)]
     tx_execution_shutdown: Mutex<Option<oneshot::Sender<()>>>,
 
     pub metrics: Arc<AuthorityMetrics>,
-    _objects_pruner: AuthorityStorePruner,
+    objects_pruner: AuthorityStorePruner,
     _authority_per_epoch_pruner: AuthorityPerEpochStorePruner,
 }
 
@@ -1217,7 +1224,12 @@ impl AuthorityState {
                     let Some(o) = self.database.get_object_by_key(&oref.0, oref.1)? else{
                         continue;
                     };
-                    let Some(df_info) = self.try_create_dynamic_field_info(&o)? else{
+                    let df_info = try_create_dynamic_field_info(
+                        &self.database,
+                        self.module_cache.as_ref(),
+                        &o,
+                    )?;
+                    let Some(df_info) = df_info else {
                         // Skip indexing for non dynamic field objects.
                         continue;
                     };
@@ -1235,63 +1247,6 @@ impl AuthorityState {
         })
     }
 
-    fn try_create_dynamic_field_info(&self, o: &Object) -> SuiResult<Option<DynamicFieldInfo>> {
-        // Skip if not a move object
-        let Some(move_object) =  o.data.try_as_move().cloned() else {
-            return Ok(None);
-        };
-        // We only index dynamic field objects
-        if !DynamicFieldInfo::is_dynamic_field(&move_object.type_) {
-            return Ok(None);
-        }
-        let move_struct = move_object.to_move_struct_with_resolver(
-            ObjectFormatOptions::default(),
-            self.module_cache.as_ref(),
-        )?;
-
-        let (name_value, type_, object_id) =
-            DynamicFieldInfo::parse_move_object(&move_struct).tap_err(|e| warn!("{e}"))?;
-
-        let name_type = DynamicFieldInfo::try_extract_field_name(&move_object.type_, &type_)?;
-
-        let name = DynamicFieldName {
-            type_: name_type,
-            value: SuiMoveValue::from(name_value).to_json_value(),
-        };
-
-        Ok(Some(match type_ {
-            DynamicFieldType::DynamicObject => {
-                // Find the actual object from storage using the object id obtained from the wrapper.
-                let Some(object) = self.database.find_object_lt_or_eq_version(object_id, o.version()) else{
-                    return Err(UserInputError::ObjectNotFound {
-                        object_id,
-                        version: Some(o.version()),
-                    }.into())
-                };
-                let version = object.version();
-                let digest = object.digest();
-                let object_type = object.data.type_().unwrap();
-
-                DynamicFieldInfo {
-                    name,
-                    type_,
-                    object_type: object_type.to_string(),
-                    object_id,
-                    version,
-                    digest,
-                }
-            }
-            DynamicFieldType::DynamicField { .. } => DynamicFieldInfo {
-                name,
-                type_,
-                object_type: move_object.type_.type_params[1].to_string(),
-                object_id: o.id(),
-                version: o.version(),
-                digest: o.digest(),
-            },
-        }))
-    }
-
     #[instrument(level = "debug", skip_all, err)]
     async fn post_process_one_tx(
         &self,
@@ -1517,7 +1472,7 @@ impl AuthorityState {
                 .expect("We defined natives to not fail here"),
         );
         let module_cache = Arc::new(SyncModuleCache::new(ResolverWrapper(store.clone())));
-        let event_handler = event_store.map(|es| {
+        let event_handler = event_store.clone().map(|es| {
             let handler = EventHandler::new(es, module_cache.clone());
             handler.regular_cleanup_task();
             Arc::new(handler)
@@ -1532,11 +1487,14 @@ impl AuthorityState {
         ));
         let (tx_execution_shutdown, rx_execution_shutdown) = oneshot::channel();
 
-        let _objects_pruner = AuthorityStorePruner::new(
+        let objects_pruner = AuthorityStorePruner::new(
             store.perpetual_tables.clone(),
             checkpoint_store.clone(),
+            indexes.clone(),
+            event_store,
             pruning_config,
             epoch_duration_ms,
+            prometheus_registry,
         );
         let _authority_per_epoch_pruner =
             AuthorityPerEpochStorePruner::new(epoch_store.get_parent_path(), pruning_config);
@@ -1556,9 +1514,10 @@ impl AuthorityState {
             checkpoint_store,
             committee_store,
             transaction_manager,
+            supported_protocol_versions,
             tx_execution_shutdown: Mutex::new(Some(tx_execution_shutdown)),
             metrics,
-            _objects_pruner,
+            objects_pruner,
             _authority_per_epoch_pruner,
         });
 
@@ -1665,6 +1624,34 @@ impl AuthorityState {
         &self.transaction_manager
     }
 
+    pub fn objects_pruner(&self) -> &AuthorityStorePruner {
+        &self.objects_pruner
+    }
+
+    /// Creates RocksDB checkpoints of the authority store, the tables of the current epoch, the
+    /// checkpoint store and the committee store under `path`, laid out like the node's db path.
+    /// `path` must not exist yet.
+    pub fn checkpoint_db(&self, path: &Path) -> SuiResult {
+        if path.exists() {
+            return Err(SuiError::GenericStorageError(format!(
+                "DB checkpoint path {} already exists",
+                path.display()
+            )));
+        }
+        let store_path = path.join("store");
+        std::fs::create_dir_all(&store_path)
+            .map_err(|e| SuiError::GenericStorageError(e.to_string()))?;
+        // Each database is checkpointed separately. Checkpoint the watermarks before the
+        // authority store, so that they can only lag behind it, in which case the checkpoint
+        // executor re-executes the missing checkpoints after a restart.
+        self.checkpoint_store
+            .checkpoint_db(&path.join("checkpoints"))?;
+        self.committee_store.checkpoint_db(&path.join("epochs"))?;
+        self.load_epoch_store_one_call_per_task()
+            .checkpoint_db(&store_path)?;
+        self.database.checkpoint_db(&store_path)
+    }
+
     /// Adds certificates to the pending certificate store and transaction manager for ordered execution.
     pub fn enqueue_certificates_for_execution(
         &self,
@@ -1734,31 +1721,11 @@ impl AuthorityState {
             return Ok(());
         }
 
-        let mut new_owners = vec![];
-        let mut new_dynamic_fields = vec![];
-        for o in genesis_objects.iter() {
-            match o.owner {
-                Owner::AddressOwner(addr) => new_owners.push((
-                    (addr, o.id()),
-                    ObjectInfo::new(&o.compute_object_reference(), o),
-                )),
-                Owner::ObjectOwner(object_id) => {
-                    let id = o.id();
-                    let Some(info) = self.try_create_dynamic_field_info(o)? else{
-                        continue;
-                    };
-                    new_dynamic_fields.push(((ObjectID::from(object_id), id), info));
-                }
-                _ => {}
-            }
-        }
-
-        index_store.insert_genesis_objects(ObjectIndexChanges {
-            deleted_owners: vec![],
-            deleted_dynamic_fields: vec![],
-            new_owners,
-            new_dynamic_fields,
-        })
+        index_store.insert_genesis_objects(live_object_index_changes(
+            &self.database,
+            self.module_cache.as_ref(),
+            genesis_objects,
+        )?)
     }
 
     pub async fn reconfigure(
@@ -1792,6 +1759,10 @@ impl AuthorityState {
         self.database.clone()
     }
 
+    pub fn supported_protocol_versions(&self) -> SupportedProtocolVersions {
+        self.supported_protocol_versions
+    }
+
     pub fn current_epoch_for_testing(&self) -> EpochId {
         self.epoch_store_for_testing().epoch()
     }
@@ -1840,6 +1811,18 @@ impl AuthorityState {
         self.database.get_sui_system_state_object()
     }
 
+    /// Returns a consistent view of the objects, transactions and effects of this authority. Reads
+    /// that must observe the same state, such as the reads serving a single RPC request, should
+    /// all go through one snapshot. Taking a snapshot locks the DB mutex, so it is not worth it
+    /// for a single read.
+    pub fn read_snapshot(&self) -> SuiResult<AuthorityReadSnapshot<'_>> {
+        Ok(AuthorityReadSnapshot::new(
+            self.database.perpetual_tables.snapshot()?,
+            &self.checkpoint_store,
+            &self.module_cache,
+        ))
+    }
+
     pub fn get_transaction_checkpoint(
         &self,
         digest: &TransactionDigest,
@@ -2075,12 +2058,8 @@ impl AuthorityState {
         &self,
         digest: TransactionDigest,
     ) -> Result<(VerifiedTransaction, TransactionEffects), anyhow::Error> {
-        let transaction = self.database.get_transaction(&digest)?;
-        let effects = self.database.get_executed_effects(&digest)?;
-        match (transaction, effects) {
-            (Some(transaction), Some(effects)) => Ok((transaction, effects)),
-            _ => Err(anyhow!(SuiError::TransactionNotFound { digest })),
-        }
+        self.read_snapshot()?
+            .get_executed_transaction_and_effects(digest)
     }
 
     fn get_indexes(&self) -> SuiResult<Arc<IndexStore>> {
@@ -2100,7 +2079,7 @@ impl AuthorityState {
         reverse: bool,
     ) -> Result<Vec<TransactionDigest>, anyhow::Error> {
         self.get_indexes()?
-            .get_transactions(query, cursor, limit, reverse)
+            .get_transactions(query, self, cursor, limit, reverse)
     }
 
     fn get_checkpoint_store(&self) -> Arc<CheckpointStore> {
@@ -2259,6 +2238,13 @@ impl AuthorityState {
                 es.events_by_type(event_type, tx_num, event_num, limit, descending)
                     .await?
             }
+            EventQuery::MoveEventField { .. }
+            | EventQuery::MatchAll(_)
+            | EventQuery::MatchAny(_)
+            | EventQuery::Not(_) => {
+                es.events_by_query(&query, tx_num, event_num, limit, descending)
+                    .await?
+            }
         };
         let mut events = StoredEvent::into_event_envelopes(stored_events)?;
         // populate parsed json event
@@ -2785,3 +2771,123 @@ impl AuthorityState {
             .unwrap();
     }
 }
+
+/// Returns the index entry of `o` if it is a dynamic field object.
+pub fn try_create_dynamic_field_info(
+    store: &AuthorityStore,
+    resolver: &impl GetModule,
+    o: &Object,
+) -> SuiResult<Option<DynamicFieldInfo>> {
+    // Skip if not a move object
+    let Some(move_object) =  o.data.try_as_move().cloned() else {
+        return Ok(None);
+    };
+    // We only index dynamic field objects
+    if !DynamicFieldInfo::is_dynamic_field(&move_object.type_) {
+        return Ok(None);
+    }
+    let move_struct =
+        move_object.to_move_struct_with_resolver(ObjectFormatOptions::default(), resolver)?;
+
+    let (name_value, type_, object_id) =
+        DynamicFieldInfo::parse_move_object(&move_struct).tap_err(|e| warn!("{e}"))?;
+
+    let name_type = DynamicFieldInfo::try_extract_field_name(&move_object.type_, &type_)?;
+
+    let name = DynamicFieldName {
+        type_: name_type,
+        value: SuiMoveValue::from(name_value).to_json_value(),
+    };
+
+    Ok(Some(match type_ {
+        DynamicFieldType::DynamicObject => {
+            // Find the actual object from storage using the object id obtained from the wrapper.
+            let Some(object) = store.find_object_lt_or_eq_version(object_id, o.version()) else{
+                return Err(UserInputError::ObjectNotFound {
+                    object_id,
+                    version: Some(o.version()),
+                }.into())
+            };
+            let version = object.version();
+            let digest = object.digest();
+            let object_type = object.data.type_().unwrap();
+
+            DynamicFieldInfo {
+                name,
+                type_,
+                object_type: object_type.to_string(),
+                object_id,
+                version,
+                digest,
+            }
+        }
+        DynamicFieldType::DynamicField { .. } => DynamicFieldInfo {
+            name,
+            type_,
+            object_type: move_object.type_.type_params[1].to_string(),
+            object_id: o.id(),
+            version: o.version(),
+            digest: o.digest(),
+        },
+    }))
+}
+
+/// Returns the owner and dynamic field index entries of live `objects`, as written when the
+/// index is created at genesis or rebuilt from the live object set.
+pub fn live_object_index_changes<'a>(
+    store: &AuthorityStore,
+    resolver: &impl GetModule,
+    objects: impl IntoIterator<Item = &'a Object>,
+) -> SuiResult<ObjectIndexChanges> {
+    let mut new_owners = vec![];
+    let mut new_dynamic_fields = vec![];
+    for o in objects {
+        match o.owner {
+            Owner::AddressOwner(addr) => new_owners.push((
+                (addr, o.id()),
+                ObjectInfo::new(&o.compute_object_reference(), o),
+            )),
+            Owner::ObjectOwner(object_id) => {
+                let id = o.id();
+                let Some(info) = try_create_dynamic_field_info(store, resolver, o)? else{
+                    continue;
+                };
+                new_dynamic_fields.push(((ObjectID::from(object_id), id), info));
+            }
+            _ => {}
+        }
+    }
+    Ok(ObjectIndexChanges {
+        deleted_owners: vec![],
+        deleted_dynamic_fields: vec![],
+        new_owners,
+        new_dynamic_fields,
+    })
+}
+
+impl TransactionCheckpointReader for AuthorityState {
+    fn get_checkpoint_of_transaction(
+        &self,
+        digest: &TransactionDigest,
+    ) -> SuiResult<Option<CheckpointSequenceNumber>> {
+        Ok(self
+            .database
+            .get_transaction_checkpoint(digest)?
+            .map(|(_, checkpoint)| checkpoint))
+    }
+
+    fn get_transactions_of_checkpoint(
+        &self,
+        sequence_number: CheckpointSequenceNumber,
+    ) -> SuiResult<Vec<TransactionDigest>> {
+        let Some(checkpoint) = self
+            .checkpoint_store
+            .get_checkpoint_by_sequence_number(sequence_number)? else { return Ok(vec![]); };
+        let contents = self
+            .checkpoint_store
+            .get_checkpoint_contents(&checkpoint.into_inner().content_digest())?;
+        Ok(contents
+            .map(|contents| contents.iter().map(|digests| digests.transaction).collect())
+            .unwrap_or_default())
+    }
+}
@@ -404,6 +404,17 @@ impl AuthorityPerEpochStore {
         self.parent_path.clone()
     }
 
+    /// Creates a RocksDB checkpoint of the tables of this epoch under `parent_path`, at the same
+    /// relative location they are opened from.
+    pub fn checkpoint_db(&self, parent_path: &Path) -> SuiResult {
+        self.tables
+            .transactions
+            .rocksdb
+            .checkpoint(&AuthorityEpochTables::path(self.epoch(), parent_path))
+            .map_err(TypedStoreError::from)?;
+        Ok(())
+    }
+
     /// Returns `&Arc<EpochStartConfiguration>`
     /// User can treat this `Arc` as `&EpochStartConfiguration`, or clone the Arc to pass as owned object
     pub fn epoch_start_configuration(&self) -> &Arc<EpochStartConfiguration> {
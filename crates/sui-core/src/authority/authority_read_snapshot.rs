@@ -0,0 +1,213 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::anyhow;
+use move_bytecode_utils::module_cache::SyncModuleCache;
+use sui_types::base_types::{ObjectID, ObjectInfo, ObjectRef, SequenceNumber, TransactionDigest};
+use sui_types::error::{SuiError, SuiResult, UserInputError};
+use sui_types::messages::{TransactionEffects, VerifiedTransaction};
+use sui_types::messages_checkpoint::VerifiedCheckpoint;
+use sui_types::object::{Object, ObjectFormatOptions, ObjectRead, Owner};
+use sui_types::storage::ObjectKey;
+use tracing::error;
+
+use super::authority_store_tables::AuthorityPerpetualTablesSnapshot;
+use super::{AuthorityStore, ResolverWrapper};
+use crate::checkpoints::CheckpointStore;
+
+#[cfg(test)]
+#[path = "../unit_tests/authority_read_snapshot_tests.rs"]
+mod authority_read_snapshot_tests;
+
+/// A consistent view of the objects, transactions and effects of an authority.
+///
+/// All reads go through a single snapshot of the perpetual tables, so they never observe a
+/// transaction that was committed in between them: an object read through the latest parent
+/// entry is always the version the entry points to, and effects always match their transaction.
+/// Certified checkpoints are kept in a separate store and are read as of the time of the call,
+/// which is safe as they are never modified once written.
+///
+/// Taking a snapshot locks the DB mutex, so reads that are consistent on their own should go
+/// through [AuthorityState](super::AuthorityState) instead. Reading an object through its latest
+/// parent entry is one of them, as object versions are never modified once written.
+pub struct AuthorityReadSnapshot<'a> {
+    tables: AuthorityPerpetualTablesSnapshot<'a>,
+    checkpoint_store: &'a CheckpointStore,
+    module_cache: &'a SyncModuleCache<ResolverWrapper<AuthorityStore>>,
+}
+
+impl<'a> AuthorityReadSnapshot<'a> {
+    pub(crate) fn new(
+        tables: AuthorityPerpetualTablesSnapshot<'a>,
+        checkpoint_store: &'a CheckpointStore,
+        module_cache: &'a SyncModuleCache<ResolverWrapper<AuthorityStore>>,
+    ) -> Self {
+        Self {
+            tables,
+            checkpoint_store,
+            module_cache,
+        }
+    }
+
+    pub fn get_object_by_key(
+        &self,
+        object_id: &ObjectID,
+        version: SequenceNumber,
+    ) -> SuiResult<Option<Object>> {
+        Ok(self.tables.objects.get(&ObjectKey(*object_id, version))?)
+    }
+
+    pub fn get_object_read(&self, object_id: &ObjectID) -> SuiResult<ObjectRead> {
+        match self.tables.get_latest_parent_entry(*object_id)? {
+            None => Ok(ObjectRead::NotExists(*object_id)),
+            Some((obj_ref, _)) => {
+                if obj_ref.2.is_alive() {
+                    match self.get_object_by_key(object_id, obj_ref.1)? {
+                        None => {
+                            error!("Object with in parent_entry is missing from object store, datastore is inconsistent");
+                            Err(UserInputError::ObjectNotFound {
+                                object_id: *object_id,
+                                version: Some(obj_ref.1),
+                            }
+                            .into())
+                        }
+                        Some(object) => {
+                            let layout = object
+                                .get_layout(ObjectFormatOptions::default(), self.module_cache)?;
+                            Ok(ObjectRead::Exists(obj_ref, object, layout))
+                        }
+                    }
+                } else {
+                    Ok(ObjectRead::Deleted(obj_ref))
+                }
+            }
+        }
+    }
+
+    /// Reads the latest version of an object, if it is alive in this snapshot.
+    fn get_live_object(&self, object_id: &ObjectID) -> SuiResult<Option<(ObjectRef, Object)>> {
+        match self.tables.get_latest_parent_entry(*object_id)? {
+            Some((obj_ref, _)) if obj_ref.2.is_alive() => Ok(self
+                .get_object_by_key(object_id, obj_ref.1)?
+                .map(|object| (obj_ref, object))),
+            _ => Ok(None),
+        }
+    }
+
+    /// Reconciles entries of the owner index with this snapshot. The index is kept in a separate
+    /// DB and is updated after the objects are written, so an entry read before the snapshot was
+    /// taken may list an object that has since been mutated, transferred or deleted. Objects that
+    /// still have the owner they are listed with are returned at their version in the snapshot,
+    /// the others are left out.
+    pub fn reconcile_owned_objects(
+        &self,
+        objects: impl IntoIterator<Item = ObjectInfo>,
+    ) -> SuiResult<Vec<ObjectInfo>> {
+        let mut reconciled = vec![];
+        for info in objects {
+            match self.tables.get_latest_parent_entry(info.object_id)? {
+                Some((obj_ref, _)) if obj_ref == ObjectRef::from(&info) => reconciled.push(info),
+                Some((obj_ref, _)) if obj_ref.2.is_alive() => {
+                    match self.get_object_by_key(&info.object_id, obj_ref.1)? {
+                        Some(object) if object.owner == info.owner => {
+                            reconciled.push(ObjectInfo::new(&obj_ref, &object))
+                        }
+                        _ => {}
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(reconciled)
+    }
+
+    /// Reads `object_id`, listed by the dynamic field index as a field of `parent`, if it still is
+    /// one in this snapshot. The object of a dynamic field is owned by `parent`, and the object of
+    /// a dynamic object field by a field wrapper that is owned by `parent`.
+    pub fn get_dynamic_field_object_read(
+        &self,
+        parent: ObjectID,
+        object_id: &ObjectID,
+    ) -> SuiResult<Option<ObjectRead>> {
+        let object_read = self.get_object_read(object_id)?;
+        let ObjectRead::Exists(_, object, _) = &object_read else { return Ok(None); };
+        let is_field = match object.owner {
+            Owner::ObjectOwner(owner) if owner == parent.into() => true,
+            Owner::ObjectOwner(wrapper) => matches!(
+                self.get_live_object(&wrapper.into())?,
+                Some((_, wrapper)) if wrapper.owner == Owner::ObjectOwner(parent.into())
+            ),
+            _ => false,
+        };
+        Ok(is_field.then_some(object_read))
+    }
+
+    /// Reads an object at a version known to exist, such as the one listed for it by the owner
+    /// index once reconciled with this snapshot.
+    pub fn get_object_read_at_version(
+        &self,
+        object_id: &ObjectID,
+        version: SequenceNumber,
+    ) -> SuiResult<ObjectRead> {
+        match self.get_object_by_key(object_id, version)? {
+            None => Err(UserInputError::ObjectNotFound {
+                object_id: *object_id,
+                version: Some(version),
+            }
+            .into()),
+            Some(object) => {
+                let layout =
+                    object.get_layout(ObjectFormatOptions::default(), self.module_cache)?;
+                Ok(ObjectRead::Exists(
+                    object.compute_object_reference(),
+                    object,
+                    layout,
+                ))
+            }
+        }
+    }
+
+    pub fn get_transaction(
+        &self,
+        digest: &TransactionDigest,
+    ) -> SuiResult<Option<VerifiedTransaction>> {
+        Ok(self.tables.transactions.get(digest)?.map(|v| v.into()))
+    }
+
+    pub fn get_executed_effects(
+        &self,
+        digest: &TransactionDigest,
+    ) -> SuiResult<Option<TransactionEffects>> {
+        match self.tables.executed_effects.get(digest)? {
+            Some(effects_digest) => Ok(self.tables.effects.get(&effects_digest)?),
+            None => Ok(None),
+        }
+    }
+
+    pub fn get_executed_transaction_and_effects(
+        &self,
+        digest: TransactionDigest,
+    ) -> Result<(VerifiedTransaction, TransactionEffects), anyhow::Error> {
+        let transaction = self.get_transaction(&digest)?;
+        let effects = self.get_executed_effects(&digest)?;
+        match (transaction, effects) {
+            (Some(transaction), Some(effects)) => Ok((transaction, effects)),
+            _ => Err(anyhow!(SuiError::TransactionNotFound { digest })),
+        }
+    }
+
+    pub fn get_transaction_checkpoint(
+        &self,
+        digest: &TransactionDigest,
+    ) -> SuiResult<Option<VerifiedCheckpoint>> {
+        let checkpoint = self
+            .tables
+            .executed_transactions_to_checkpoint
+            .get(digest)?;
+        let Some((_, checkpoint)) = checkpoint else { return Ok(None); };
+        let checkpoint = self
+            .checkpoint_store
+            .get_checkpoint_by_sequence_number(checkpoint)?;
+        Ok(checkpoint)
+    }
+}
@@ -143,6 +143,12 @@ impl AuthorityStore {
         Ok(store)
     }
 
+    /// Creates a RocksDB checkpoint of the perpetual tables under `path`, which is laid out
+    /// like the `path` the store is opened from.
+    pub fn checkpoint_db(&self, path: &Path) -> SuiResult {
+        self.perpetual_tables.checkpoint_db(path)
+    }
+
     pub fn get_recovery_epoch_at_restart(&self) -> SuiResult<EpochId> {
         self.perpetual_tables.get_recovery_epoch_at_restart()
     }
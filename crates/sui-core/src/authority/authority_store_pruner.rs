@@ -3,12 +3,20 @@
 
 use crate::checkpoints::CheckpointStore;
 use mysten_metrics::monitored_scope;
+use prometheus::{
+    register_int_counter_with_registry, register_int_gauge_with_registry, IntCounter, IntGauge,
+    Registry,
+};
 use std::cmp::max;
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::{sync::Arc, time::Duration};
 use sui_config::node::AuthorityStorePruningConfig;
+use sui_storage::event_store::{EventStore, EventStoreType};
+use sui_storage::IndexStore;
 use sui_types::base_types::SequenceNumber;
 use sui_types::messages::TransactionEffects;
+use sui_types::messages_checkpoint::CheckpointContents;
 use sui_types::object::Object;
 use sui_types::{
     base_types::{ObjectID, VersionNumber},
@@ -26,11 +34,63 @@ use super::authority_store_tables::AuthorityPerpetualTables;
 
 const MAX_OPS_IN_ONE_WRITE_BATCH: u64 = 10000;
 
+pub struct AuthorityStorePrunerMetrics {
+    pub last_pruned_checkpoint: IntGauge,
+    pub num_pruned_objects: IntCounter,
+    pub num_pruned_transactions: IntCounter,
+    pub num_pruned_events: IntCounter,
+}
+
+impl AuthorityStorePrunerMetrics {
+    pub fn new(registry: &Registry) -> Arc<Self> {
+        let this = Self {
+            last_pruned_checkpoint: register_int_gauge_with_registry!(
+                "pruner_last_pruned_checkpoint",
+                "Last checkpoint whose transactions, effects and events have been pruned",
+                registry
+            )
+            .unwrap(),
+            num_pruned_objects: register_int_counter_with_registry!(
+                "pruner_num_pruned_objects",
+                "Number of object versions pruned by the checkpoint pruner",
+                registry
+            )
+            .unwrap(),
+            num_pruned_transactions: register_int_counter_with_registry!(
+                "pruner_num_pruned_transactions",
+                "Number of transactions pruned along with their effects",
+                registry
+            )
+            .unwrap(),
+            num_pruned_events: register_int_counter_with_registry!(
+                "pruner_num_pruned_events",
+                "Number of events pruned from the event store",
+                registry
+            )
+            .unwrap(),
+        };
+        Arc::new(this)
+    }
+}
+
 pub struct AuthorityStorePruner {
     _objects_pruner_cancel_handle: oneshot::Sender<()>,
+    /// While set, pruning runs are skipped. Used by operators to stop pruning temporarily, e.g.
+    /// while taking a DB snapshot.
+    paused: Arc<AtomicBool>,
 }
 
 impl AuthorityStorePruner {
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::Relaxed)
+    }
+
+    /// Pauses or resumes pruning. A pruning run that is already in progress is not interrupted.
+    pub fn set_paused(&self, paused: bool) {
+        info!("Object pruning paused: {paused}");
+        self.paused.store(paused, Ordering::Relaxed);
+    }
+
     fn prune_objects(num_versions_to_retain: u64, objects: &DBMap<ObjectKey, Object>) -> u64 {
         let iter = objects.iter().skip_to_last().reverse();
         let mut total_keys_scanned = 0;
@@ -160,19 +220,96 @@ impl AuthorityStorePruner {
         Ok(pruned)
     }
 
-    fn process_checkpoints(
+    /// Deletes the transactions of a checkpoint, along with their effects, the records of their
+    /// execution and their entries in the transaction indexes. `effects` are the effects of the
+    /// transactions, which are needed to find their index entries.
+    fn prune_transactions(
+        perpetual_db: &AuthorityPerpetualTables,
+        index_store: Option<&Arc<IndexStore>>,
+        contents: &CheckpointContents,
+        effects: &[TransactionEffects],
+    ) -> anyhow::Result<()> {
+        if let Some(index_store) = index_store {
+            let transactions = perpetual_db
+                .transactions
+                .multi_get(contents.iter().map(|digests| digests.transaction))?;
+            for (transaction, effects) in transactions.into_iter().zip(effects) {
+                // Transactions are deleted after their index entries, so a missing transaction
+                // has no index entries left
+                let Some(transaction) = transaction else {
+                    continue;
+                };
+                let data = &transaction.inner().data().intent_message.value;
+                index_store.prune_tx(
+                    data.sender(),
+                    data.input_objects()?.iter().map(|o| o.object_id()),
+                    effects
+                        .all_mutated()
+                        .map(|(obj_ref, owner, _kind)| (*obj_ref, *owner)),
+                    data.move_calls()
+                        .iter()
+                        .map(|mc| (mc.package, mc.module.clone(), mc.function.clone())),
+                    &effects.transaction_digest,
+                )?;
+            }
+        }
+
+        let transactions = contents.iter().map(|digests| digests.transaction);
+        perpetual_db
+            .transactions
+            .batch()
+            .delete_batch(&perpetual_db.transactions, transactions.clone())?
+            .delete_batch(
+                &perpetual_db.effects,
+                contents.iter().map(|digests| digests.effects),
+            )?
+            .delete_batch(&perpetual_db.executed_effects, transactions.clone())?
+            .delete_batch(
+                &perpetual_db.executed_transactions_to_checkpoint,
+                transactions,
+            )?
+            .write()?;
+        Ok(())
+    }
+
+    /// Prunes every executed checkpoint which is more than `num_epochs_to_retain` epochs old:
+    /// the object versions its transactions made obsolete, the events they emitted, their index
+    /// entries, the transactions and effects themselves, and the checkpoint contents. If
+    /// `retain_unarchived_checkpoints` is set, checkpoints past the highest archived checkpoint
+    /// are retained as well. Checkpoints are pruned in order, and the highest pruned checkpoint
+    /// watermark is only advanced once everything a checkpoint refers to has been deleted, so an
+    /// interrupted run is resumed by the next one.
+    async fn process_checkpoints(
         perpetual_db: &Arc<AuthorityPerpetualTables>,
         checkpoint_store: &Arc<CheckpointStore>,
+        index_store: Option<&Arc<IndexStore>>,
+        event_store: Option<&Arc<EventStoreType>>,
         num_epochs_to_retain: u64,
+        retain_unarchived_checkpoints: bool,
+        metrics: &AuthorityStorePrunerMetrics,
     ) -> anyhow::Result<()> {
         let mut pruned_seq_number = checkpoint_store
             .get_highest_pruned_checkpoint_seq_number()?
             .unwrap_or_default();
-        let current_epoch = checkpoint_store
-            .get_highest_executed_checkpoint()?
-            .map(|c| c.epoch())
-            .unwrap_or_default();
+        let Some(highest_executed) = checkpoint_store.get_highest_executed_checkpoint()? else {
+            return Ok(());
+        };
+        let highest_prunable = if retain_unarchived_checkpoints {
+            // Nothing is pruned until the archive writer has published its first bundle
+            let Some(highest_archived) = checkpoint_store.get_highest_archived_checkpoint_seq_number()? else {
+                return Ok(());
+            };
+            highest_archived.min(highest_executed.sequence_number())
+        } else {
+            highest_executed.sequence_number()
+        };
+        let current_epoch = highest_executed.epoch();
         loop {
+            // Only executed checkpoints are pruned, since the effects of a checkpoint which has
+            // not been executed yet are expected to be missing.
+            if pruned_seq_number >= highest_prunable {
+                return Ok(());
+            }
             let Some(checkpoint) = checkpoint_store.get_checkpoint_by_sequence_number(pruned_seq_number + 1)?
                 else {return Ok(());};
             // checkpoint's epoch is too new. Skipping for now
@@ -186,11 +323,36 @@ impl AuthorityStorePruner {
                 .effects
                 .multi_get(content.iter().map(|tx| tx.effects))?;
 
-            if effects.iter().any(|effect| effect.is_none()) {
+            let num_missing = effects.iter().filter(|effect| effect.is_none()).count();
+            if num_missing == 0 {
+                let effects: Vec<_> = effects.into_iter().flatten().collect();
+                let pruned_objects =
+                    Self::handle_checkpoint(effects.iter().cloned(), &perpetual_db.objects)?;
+                metrics.num_pruned_objects.inc_by(pruned_objects as u64);
+
+                if let Some(event_store) = event_store {
+                    let transactions: Vec<_> =
+                        content.iter().map(|digests| digests.transaction).collect();
+                    let pruned_events = event_store
+                        .delete_events_by_transactions(&transactions)
+                        .await?;
+                    metrics.num_pruned_events.inc_by(pruned_events);
+                }
+
+                Self::prune_transactions(perpetual_db, index_store, &content, &effects)?;
+                metrics
+                    .num_pruned_transactions
+                    .inc_by(content.size() as u64);
+            } else if num_missing < effects.len() {
                 return Err(anyhow::anyhow!("transaction effects data is missing"));
             }
-            Self::handle_checkpoint(effects.into_iter().flatten(), &perpetual_db.objects)?;
-            checkpoint_store.update_highest_pruned_checkpoint(&checkpoint)?;
+            // Otherwise the effects of every transaction are gone: a previous run pruned this
+            // checkpoint but was interrupted before it could advance the watermark.
+
+            checkpoint_store.prune_checkpoint_contents(&checkpoint)?;
+            metrics
+                .last_pruned_checkpoint
+                .set(checkpoint.sequence_number() as i64);
             pruned_seq_number += 1;
         }
     }
@@ -201,8 +363,13 @@ impl AuthorityStorePruner {
         pruning_initial_delay: Duration,
         num_epochs_to_retain: u64,
         epoch_duration_ms: u64,
+        retain_unarchived_checkpoints: bool,
         perpetual_db: Arc<AuthorityPerpetualTables>,
         checkpoint_store: Arc<CheckpointStore>,
+        index_store: Option<Arc<IndexStore>>,
+        event_store: Option<Arc<EventStoreType>>,
+        paused: Arc<AtomicBool>,
+        metrics: Arc<AuthorityStorePrunerMetrics>,
     ) -> Sender<()> {
         let (sender, mut recv) = tokio::sync::oneshot::channel();
         info!(
@@ -223,6 +390,9 @@ impl AuthorityStorePruner {
             loop {
                 tokio::select! {
                     _ = prune_interval.tick(), if num_versions_to_retain != u64::MAX => {
+                        if paused.load(Ordering::Relaxed) {
+                            continue;
+                        }
                         info!("Starting pruning of objects table");
                         let num_pruned = Self::prune_objects(num_versions_to_retain, &perpetual_db.objects);
                         info!("Finished pruning with total object versions pruned = {}", num_pruned);
@@ -233,9 +403,12 @@ impl AuthorityStorePruner {
                         }
                     },
                     _ = live_prune_interval.tick(), if num_epochs_to_retain != u64::MAX => {
-                        match Self::process_checkpoints(&perpetual_db, &checkpoint_store, num_epochs_to_retain) {
+                        if paused.load(Ordering::Relaxed) {
+                            continue;
+                        }
+                        match Self::process_checkpoints(&perpetual_db, &checkpoint_store, index_store.as_ref(), event_store.as_ref(), num_epochs_to_retain, retain_unarchived_checkpoints, &metrics).await {
                             Ok(()) => info!("Pruned checkpoints"),
-                            Err(err) => error!("Failed to prune objects: {:?}", err),
+                            Err(err) => error!("Failed to prune checkpoints: {:?}", err),
                         }
                     },
                     _ = &mut recv => break,
@@ -247,9 +420,13 @@ impl AuthorityStorePruner {
     pub fn new(
         perpetual_db: Arc<AuthorityPerpetualTables>,
         checkpoint_store: Arc<CheckpointStore>,
+        index_store: Option<Arc<IndexStore>>,
+        event_store: Option<Arc<EventStoreType>>,
         pruning_config: &AuthorityStorePruningConfig,
         epoch_duration_ms: u64,
+        registry: &Registry,
     ) -> Self {
+        let paused = Arc::new(AtomicBool::new(false));
         AuthorityStorePruner {
             _objects_pruner_cancel_handle: Self::setup_objects_pruning(
                 pruning_config.objects_num_latest_versions_to_retain,
@@ -257,9 +434,15 @@ impl AuthorityStorePruner {
                 Duration::from_secs(pruning_config.objects_pruning_initial_delay_secs),
                 pruning_config.num_epochs_to_retain,
                 epoch_duration_ms,
+                pruning_config.retain_unarchived_checkpoints,
                 perpetual_db,
                 checkpoint_store,
+                index_store,
+                event_store,
+                paused.clone(),
+                AuthorityStorePrunerMetrics::new(registry),
             ),
+            paused,
         }
     }
 }
@@ -276,8 +459,9 @@ mod tests {
     use crate::authority::authority_store_tables::AuthorityPerpetualTables;
     #[cfg(not(target_env = "msvc"))]
     use pprof::Symbol;
-    use sui_types::base_types::VersionNumber;
+    use sui_types::base_types::{ExecutionDigests, VersionNumber};
     use sui_types::messages::TransactionEffects;
+    use sui_types::messages_checkpoint::CheckpointContents;
     use sui_types::{
         base_types::{ObjectID, SequenceNumber},
         object::Object,
@@ -286,7 +470,20 @@ mod tests {
     use typed_store::rocks::{DBMap, MetricConf, ReadWriteOptions};
     use typed_store::Map;
 
-    use super::AuthorityStorePruner;
+    use super::{AuthorityStorePruner, AuthorityStorePrunerMetrics};
+    use crate::checkpoints::CheckpointStore;
+    use prometheus::Registry;
+    use sui_storage::indexes::ObjectIndexChanges;
+    use sui_storage::IndexStore;
+    use sui_types::committee::{Committee, EpochId, ProtocolVersion};
+    use sui_types::crypto::{AuthorityKeyPair, KeypairTraits};
+    use sui_types::gas::GasCostSummary;
+    use sui_types::messages::VerifiedTransaction;
+    use sui_types::messages_checkpoint::{
+        CertifiedCheckpointSummary, CheckpointSequenceNumber, SignedCheckpointSummary,
+        VerifiedCheckpoint,
+    };
+    use sui_types::utils::make_committee_key;
 
     fn get_keys_after_pruning(db_path: PathBuf) -> anyhow::Result<HashSet<ObjectKey>> {
         let perpetual_db_path = db_path.join(Path::new("perpetual"));
@@ -402,6 +599,52 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_transactions_pruning() {
+        let path = tempfile::tempdir().unwrap().into_path();
+        let db = AuthorityPerpetualTables::open(&path, None);
+
+        let pruned: Vec<_> = (0..10).map(|_| ExecutionDigests::random()).collect();
+        let retained: Vec<_> = (0..10).map(|_| ExecutionDigests::random()).collect();
+        for digests in pruned.iter().chain(&retained) {
+            db.effects
+                .insert(&digests.effects, &TransactionEffects::default())
+                .unwrap();
+            db.executed_effects
+                .insert(&digests.transaction, &digests.effects)
+                .unwrap();
+            db.executed_transactions_to_checkpoint
+                .insert(&digests.transaction, &(0, 1))
+                .unwrap();
+        }
+
+        let contents = CheckpointContents::new_with_causally_ordered_transactions(pruned.clone());
+        AuthorityStorePruner::prune_transactions(&db, None, &contents, &[]).unwrap();
+
+        for digests in pruned {
+            assert!(!db.effects.contains_key(&digests.effects).unwrap());
+            assert!(!db
+                .executed_effects
+                .contains_key(&digests.transaction)
+                .unwrap());
+            assert!(!db
+                .executed_transactions_to_checkpoint
+                .contains_key(&digests.transaction)
+                .unwrap());
+        }
+        for digests in retained {
+            assert!(db.effects.contains_key(&digests.effects).unwrap());
+            assert!(db
+                .executed_effects
+                .contains_key(&digests.transaction)
+                .unwrap());
+            assert!(db
+                .executed_transactions_to_checkpoint
+                .contains_key(&digests.transaction)
+                .unwrap());
+        }
+    }
+
     async fn test_pruning(
         primary_path: PathBuf,
         num_versions_per_object: u64,
@@ -602,4 +845,275 @@ mod tests {
         }
         Ok(())
     }
+
+    /// The stores a node prunes, opened at `path`.
+    struct PrunedStores {
+        perpetual_db: Arc<AuthorityPerpetualTables>,
+        checkpoint_store: Arc<CheckpointStore>,
+        index_store: Arc<IndexStore>,
+    }
+
+    impl PrunedStores {
+        fn open(path: &Path) -> Self {
+            Self {
+                perpetual_db: Arc::new(AuthorityPerpetualTables::open(&path.join("store"), None)),
+                checkpoint_store: CheckpointStore::new(&path.join("checkpoints")),
+                index_store: Arc::new(IndexStore::new(path.join("indexes"))),
+            }
+        }
+
+        async fn prune(
+            &self,
+            num_epochs_to_retain: u64,
+            retain_unarchived_checkpoints: bool,
+        ) -> Option<CheckpointSequenceNumber> {
+            AuthorityStorePruner::process_checkpoints(
+                &self.perpetual_db,
+                &self.checkpoint_store,
+                Some(&self.index_store),
+                None,
+                num_epochs_to_retain,
+                retain_unarchived_checkpoints,
+                &AuthorityStorePrunerMetrics::new(&Registry::new()),
+            )
+            .await
+            .unwrap();
+            self.checkpoint_store
+                .get_highest_pruned_checkpoint_seq_number()
+                .unwrap()
+        }
+
+        fn is_retained(
+            &self,
+            checkpoint: &VerifiedCheckpoint,
+            transaction: &VerifiedTransaction,
+        ) -> bool {
+            let digest = transaction.digest();
+            let retained = self
+                .checkpoint_store
+                .get_checkpoint_contents(&checkpoint.content_digest())
+                .unwrap()
+                .is_some();
+            assert_eq!(
+                retained,
+                self.perpetual_db.transactions.contains_key(digest).unwrap()
+            );
+            assert_eq!(
+                retained,
+                self.perpetual_db
+                    .executed_effects
+                    .contains_key(digest)
+                    .unwrap()
+            );
+            assert_eq!(
+                retained,
+                self.index_store
+                    .get_transaction_seq(digest)
+                    .unwrap()
+                    .is_some()
+            );
+            let sender = transaction.data().intent_message.value.sender();
+            assert_eq!(
+                retained,
+                self.index_store
+                    .get_transactions_from_addr(sender, 0, None, false)
+                    .unwrap()
+                    .contains(digest)
+            );
+            retained
+        }
+    }
+
+    fn certified_checkpoint(
+        keys: &[AuthorityKeyPair],
+        committee: &Committee,
+        epoch: EpochId,
+        sequence_number: CheckpointSequenceNumber,
+        contents: &CheckpointContents,
+    ) -> VerifiedCheckpoint {
+        let committee = Committee::new(
+            epoch,
+            ProtocolVersion::MIN,
+            committee.voting_rights.iter().cloned().collect(),
+        )
+        .unwrap();
+        let signed_checkpoints = keys
+            .iter()
+            .map(|key| {
+                SignedCheckpointSummary::new(
+                    epoch,
+                    sequence_number,
+                    sequence_number,
+                    key.public().into(),
+                    key,
+                    contents,
+                    None,
+                    GasCostSummary::default(),
+                    None,
+                    0,
+                )
+            })
+            .collect();
+        VerifiedCheckpoint::new_unchecked(
+            CertifiedCheckpointSummary::aggregate(signed_checkpoints, &committee).unwrap(),
+        )
+    }
+
+    /// Writes checkpoints 0 to 5, each holding one executed and indexed transaction, of which
+    /// 0 to 2 are in epoch 0, 3 and 4 in epoch 1, and 5 in epoch 2.
+    fn write_checkpoints(stores: &PrunedStores) -> Vec<(VerifiedCheckpoint, VerifiedTransaction)> {
+        let (keys, committee) = make_committee_key(&mut rand::thread_rng());
+        let epochs = [0, 0, 0, 1, 1, 2];
+        let mut checkpoints = vec![];
+        for (sequence_number, epoch) in epochs.into_iter().enumerate() {
+            let sequence_number = sequence_number as CheckpointSequenceNumber;
+            let transaction =
+                VerifiedTransaction::new_consensus_commit_prologue(epoch, sequence_number, 0);
+            let effects = TransactionEffects {
+                transaction_digest: *transaction.digest(),
+                ..Default::default()
+            };
+            let contents = CheckpointContents::new_with_causally_ordered_transactions([
+                ExecutionDigests::new(*transaction.digest(), effects.digest()),
+            ]);
+            let checkpoint =
+                certified_checkpoint(&keys, &committee, epoch, sequence_number, &contents);
+
+            let db = &stores.perpetual_db;
+            db.transactions
+                .insert(transaction.digest(), transaction.serializable_ref())
+                .unwrap();
+            db.effects.insert(&effects.digest(), &effects).unwrap();
+            db.executed_effects
+                .insert(transaction.digest(), &effects.digest())
+                .unwrap();
+            db.executed_transactions_to_checkpoint
+                .insert(transaction.digest(), &(epoch, sequence_number))
+                .unwrap();
+            let data = &transaction.data().intent_message.value;
+            stores
+                .index_store
+                .index_tx(
+                    data.sender(),
+                    data.input_objects().unwrap().iter().map(|o| o.object_id()),
+                    effects
+                        .all_mutated()
+                        .map(|(obj_ref, owner, _kind)| (*obj_ref, *owner)),
+                    data.move_calls()
+                        .iter()
+                        .map(|mc| (mc.package, mc.module.clone(), mc.function.clone())),
+                    ObjectIndexChanges::default(),
+                    transaction.digest(),
+                    0,
+                )
+                .unwrap();
+
+            let checkpoint_store = &stores.checkpoint_store;
+            checkpoint_store
+                .insert_verified_checkpoint(checkpoint.clone())
+                .unwrap();
+            checkpoint_store
+                .insert_checkpoint_contents(contents)
+                .unwrap();
+            checkpoint_store
+                .update_highest_executed_checkpoint(&checkpoint)
+                .unwrap();
+            checkpoints.push((checkpoint, transaction));
+        }
+        checkpoints
+    }
+
+    #[tokio::test]
+    async fn test_checkpoint_retention_window() {
+        let path = tempfile::tempdir().unwrap();
+        let stores = PrunedStores::open(path.path());
+        let checkpoints = write_checkpoints(&stores);
+
+        // The current epoch is 2, so checkpoints of epoch 0 are more than one epoch old. Genesis
+        // is never pruned.
+        assert_eq!(stores.prune(2, false).await, Some(2));
+        let retained: Vec<_> = checkpoints
+            .iter()
+            .map(|(checkpoint, transaction)| stores.is_retained(checkpoint, transaction))
+            .collect();
+        assert_eq!(retained, [true, false, false, true, true, true]);
+
+        // Pruning again is a no-op
+        assert_eq!(stores.prune(2, false).await, Some(2));
+
+        // Checkpoints of epoch 1 are pruned once only one epoch is retained, but not those of
+        // the current epoch
+        assert_eq!(stores.prune(1, false).await, Some(4));
+        let retained: Vec<_> = checkpoints
+            .iter()
+            .map(|(checkpoint, transaction)| stores.is_retained(checkpoint, transaction))
+            .collect();
+        assert_eq!(retained, [true, false, false, false, false, true]);
+        // Other transactions of the same sender are still indexed
+        let sender = checkpoints[5].1.data().intent_message.value.sender();
+        assert_eq!(
+            stores
+                .index_store
+                .get_transactions_from_addr(sender, 0, None, false)
+                .unwrap(),
+            vec![*checkpoints[0].1.digest(), *checkpoints[5].1.digest()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_checkpoint_pruning_waits_for_archive_and_resumes_after_restart() {
+        let path = tempfile::tempdir().unwrap();
+        let checkpoints = {
+            let stores = PrunedStores::open(path.path());
+            let checkpoints = write_checkpoints(&stores);
+
+            // Nothing is pruned before the archive writer publishes its watermark
+            assert_eq!(stores.prune(1, true).await, None);
+
+            // Only archived checkpoints are pruned
+            stores
+                .checkpoint_store
+                .update_highest_archived_checkpoint(&checkpoints[2].0)
+                .unwrap();
+            assert_eq!(stores.prune(1, true).await, Some(2));
+            assert!(stores.is_retained(&checkpoints[3].0, &checkpoints[3].1));
+
+            // Checkpoint 3 is archived, and a run is interrupted after pruning its transactions
+            // but before advancing the pruned watermark
+            stores
+                .checkpoint_store
+                .update_highest_archived_checkpoint(&checkpoints[5].0)
+                .unwrap();
+            let contents = stores
+                .checkpoint_store
+                .get_checkpoint_contents(&checkpoints[3].0.content_digest())
+                .unwrap()
+                .unwrap();
+            let effects: Vec<_> = stores
+                .perpetual_db
+                .effects
+                .multi_get(contents.iter().map(|digests| digests.effects))
+                .unwrap()
+                .into_iter()
+                .flatten()
+                .collect();
+            AuthorityStorePruner::prune_transactions(
+                &stores.perpetual_db,
+                Some(&stores.index_store),
+                &contents,
+                &effects,
+            )
+            .unwrap();
+            checkpoints
+        };
+
+        // The next run after a restart picks up where the interrupted one stopped
+        let stores = PrunedStores::open(path.path());
+        assert_eq!(stores.prune(1, true).await, Some(4));
+        let retained: Vec<_> = checkpoints
+            .iter()
+            .map(|(checkpoint, transaction)| stores.is_retained(checkpoint, transaction))
+            .collect();
+        assert_eq!(retained, [true, false, false, false, false, true]);
+    }
 }
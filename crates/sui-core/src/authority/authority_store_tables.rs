@@ -10,7 +10,7 @@ use sui_types::accumulator::Accumulator;
 use sui_types::base_types::SequenceNumber;
 use sui_types::storage::ObjectStore;
 use typed_store::metrics::SamplingInterval;
-use typed_store::rocks::{DBMap, DBOptions, MetricConf, ReadWriteOptions};
+use typed_store::rocks::{DBMap, DBOptions, MetricConf, ReadWriteOptions, TypedStoreError};
 use typed_store::traits::{Map, TableSummary, TypedStoreDebug};
 
 use typed_store_derive::DBMapUtils;
@@ -126,19 +126,7 @@ impl AuthorityPerpetualTables {
         &self,
         object_id: ObjectID,
     ) -> Result<Option<(ObjectRef, TransactionDigest)>, SuiError> {
-        let mut iterator = self
-            .parent_sync
-            .iter()
-            // Make the max possible entry for this object ID.
-            .skip_prior_to(&(object_id, SequenceNumber::MAX, ObjectDigest::MAX))?;
-
-        Ok(iterator.next().and_then(|(obj_ref, tx_digest)| {
-            if obj_ref.0 == object_id {
-                Some((obj_ref, tx_digest))
-            } else {
-                None
-            }
-        }))
+        latest_parent_entry(self.parent_sync.iter(), object_id)
     }
 
     pub fn get_recovery_epoch_at_restart(&self) -> SuiResult<EpochId> {
@@ -153,6 +141,16 @@ impl AuthorityPerpetualTables {
         Ok(())
     }
 
+    /// Creates a RocksDB checkpoint of these tables under `parent_path`, at the same relative
+    /// location they are opened from.
+    pub fn checkpoint_db(&self, parent_path: &Path) -> SuiResult {
+        self.objects
+            .rocksdb
+            .checkpoint(&Self::path(parent_path))
+            .map_err(TypedStoreError::from)?;
+        Ok(())
+    }
+
     pub fn database_is_empty(&self) -> SuiResult<bool> {
         Ok(self
             .objects
@@ -170,6 +168,15 @@ impl AuthorityPerpetualTables {
     }
 }
 
+impl AuthorityPerpetualTablesSnapshot<'_> {
+    pub fn get_latest_parent_entry(
+        &self,
+        object_id: ObjectID,
+    ) -> Result<Option<(ObjectRef, TransactionDigest)>, SuiError> {
+        latest_parent_entry(self.parent_sync.iter(), object_id)
+    }
+}
+
 impl ObjectStore for &AuthorityPerpetualTables {
     /// Read an object and return it, or Ok(None) if the object was not found.
     fn get_object(&self, object_id: &ObjectID) -> Result<Option<Object>, SuiError> {
@@ -210,6 +217,28 @@ impl ObjectStore for &AuthorityPerpetualTables {
     }
 }
 
+type ParentSyncIter<'a> =
+    <DBMap<ObjectRef, TransactionDigest> as Map<'a, ObjectRef, TransactionDigest>>::Iterator;
+
+/// Returns the latest entry of `object_id` in `parent_sync`, an iterator over the parent sync
+/// table of either the live tables or a snapshot of them.
+fn latest_parent_entry(
+    parent_sync: ParentSyncIter<'_>,
+    object_id: ObjectID,
+) -> Result<Option<(ObjectRef, TransactionDigest)>, SuiError> {
+    let mut iterator = parent_sync
+        // Make the max possible entry for this object ID.
+        .skip_prior_to(&(object_id, SequenceNumber::MAX, ObjectDigest::MAX))?;
+
+    Ok(iterator.next().and_then(|(obj_ref, tx_digest)| {
+        if obj_ref.0 == object_id {
+            Some((obj_ref, tx_digest))
+        } else {
+            None
+        }
+    }))
+}
+
 pub struct LiveSetIter<'a> {
     iter: <DBMap<ObjectRef, TransactionDigest> as Map<'a, ObjectRef, TransactionDigest>>::Keys,
     prev: Option<ObjectRef>,
@@ -137,7 +137,8 @@ impl CheckpointExecutor {
             .as_ref()
             .map(|c| c.sequence_number() + 1)
             .unwrap_or_else(|| {
-                // TODO this invariant may no longer hold once we introduce snapshots
+                // A node restored from a state snapshot starts with the last checkpoint of
+                // the snapshot as its highest executed checkpoint.
                 assert_eq!(epoch_store.epoch(), 0);
                 0
             });
@@ -254,6 +254,16 @@ impl CheckpointStore {
             .transpose()
     }
 
+    /// The highest checkpoint written to the checkpoint archive, if this node keeps one.
+    pub fn get_highest_archived_checkpoint_seq_number(
+        &self,
+    ) -> Result<Option<CheckpointSequenceNumber>, TypedStoreError> {
+        self.watermarks
+            .get(&CheckpointWatermark::HighestArchived)?
+            .map(|(sequence_number, _)| Ok(sequence_number))
+            .transpose()
+    }
+
     pub fn get_checkpoint_contents(
         &self,
         digest: &CheckpointContentsDigest,
@@ -339,6 +349,36 @@ impl CheckpointStore {
         )
     }
 
+    pub fn update_highest_archived_checkpoint(
+        &self,
+        checkpoint: &VerifiedCheckpoint,
+    ) -> Result<(), TypedStoreError> {
+        self.watermarks.insert(
+            &CheckpointWatermark::HighestArchived,
+            &(checkpoint.sequence_number(), checkpoint.digest()),
+        )
+    }
+
+    /// Deletes the contents of a checkpoint whose transactions have been pruned, and marks it as
+    /// the highest pruned checkpoint. Both are written atomically, so the pruned watermark never
+    /// points past contents that are still stored. The checkpoint summary itself is retained.
+    pub fn prune_checkpoint_contents(
+        &self,
+        checkpoint: &VerifiedCheckpoint,
+    ) -> Result<(), TypedStoreError> {
+        self.checkpoint_content
+            .batch()
+            .delete_batch(&self.checkpoint_content, [checkpoint.content_digest()])?
+            .insert_batch(
+                &self.watermarks,
+                [(
+                    &CheckpointWatermark::HighestPruned,
+                    &(checkpoint.sequence_number(), checkpoint.digest()),
+                )],
+            )?
+            .write()
+    }
+
     pub fn insert_checkpoint_contents(
         &self,
         contents: CheckpointContents,
@@ -359,6 +399,11 @@ impl CheckpointStore {
         Ok(checkpoint)
     }
 
+    /// Creates a RocksDB checkpoint of the store at `path`, which must not exist yet.
+    pub fn checkpoint_db(&self, path: &Path) -> Result<(), TypedStoreError> {
+        Ok(self.certified_checkpoints.rocksdb.checkpoint(path)?)
+    }
+
     /// Given the epoch ID, and the last checkpoint of the epoch, derive a few statistics of the epoch.
     pub fn get_epoch_stats(
         &self,
@@ -392,6 +437,7 @@ pub enum CheckpointWatermark {
     HighestSynced,
     HighestExecuted,
     HighestPruned,
+    HighestArchived,
 }
 
 pub struct CheckpointBuilder {
@@ -2,12 +2,12 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use rocksdb::Options;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use sui_storage::default_db_options;
 use sui_types::base_types::ObjectID;
 use sui_types::committee::{Committee, EpochId};
 use sui_types::error::SuiResult;
-use typed_store::rocks::{DBMap, DBOptions, MetricConf};
+use typed_store::rocks::{DBMap, DBOptions, MetricConf, TypedStoreError};
 use typed_store::traits::{TableSummary, TypedStoreDebug};
 
 use typed_store::Map;
@@ -66,6 +66,15 @@ impl CommitteeStore {
         Ok(self.committee_map.get(epoch_id)?)
     }
 
+    /// Creates a RocksDB checkpoint of the store at `path`, which must not exist yet.
+    pub fn checkpoint_db(&self, path: &Path) -> SuiResult {
+        self.committee_map
+            .rocksdb
+            .checkpoint(path)
+            .map_err(TypedStoreError::from)?;
+        Ok(())
+    }
+
     pub fn get_latest_committee(&self) -> Committee {
         self.committee_map
             .iter()
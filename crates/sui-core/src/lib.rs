@@ -23,6 +23,7 @@ pub mod quorum_driver;
 pub mod safe_client;
 mod stake_aggregator;
 pub mod state_accumulator;
+pub mod state_snapshot;
 pub mod storage;
 pub mod streamer;
 pub mod tbls;
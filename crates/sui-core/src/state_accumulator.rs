@@ -7,18 +7,23 @@ use tracing::debug;
 use typed_store::Map;
 
 use std::sync::Arc;
-use sui_types::base_types::ObjectDigest;
+use sui_types::base_types::{ObjectDigest, ObjectID, ObjectRef, SequenceNumber};
 
 use fastcrypto::hash::{Digest, MultisetHash};
 use sui_types::accumulator::Accumulator;
-use sui_types::error::SuiResult;
+use sui_types::error::{SuiResult, UserInputError};
 use sui_types::messages::TransactionEffects;
 use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use sui_types::storage::ObjectKey;
 use typed_store::rocks::TypedStoreError;
 
 use crate::authority::authority_per_epoch_store::AuthorityPerEpochStore;
 use crate::authority::AuthorityStore;
 
+#[cfg(test)]
+#[path = "unit_tests/state_accumulator_tests.rs"]
+mod state_accumulator_tests;
+
 pub struct StateAccumulator {
     authority_store: Arc<AuthorityStore>,
 }
@@ -41,46 +46,16 @@ impl StateAccumulator {
             return Ok(acc);
         }
 
+        // The accumulator tracks the live object set: every object version written by the
+        // checkpoint is inserted, and the version each modified object had before is removed.
         let mut acc = Accumulator::default();
-
         acc.insert_all(
             effects
                 .iter()
-                .flat_map(|fx| {
-                    fx.created
-                        .clone()
-                        .into_iter()
-                        .map(|(obj_ref, _)| obj_ref.2)
-                        .collect::<Vec<ObjectDigest>>()
-                })
-                .collect::<Vec<ObjectDigest>>(),
-        );
-        acc.remove_all(
-            effects
-                .iter()
-                .flat_map(|fx| {
-                    fx.deleted
-                        .clone()
-                        .into_iter()
-                        .map(|obj_ref| obj_ref.2)
-                        .collect::<Vec<ObjectDigest>>()
-                })
-                .collect::<Vec<ObjectDigest>>(),
-        );
-
-        // TODO almost certainly not currectly handling "mutated" effects.
-        acc.insert_all(
-            effects
-                .iter()
-                .flat_map(|fx| {
-                    fx.mutated
-                        .clone()
-                        .into_iter()
-                        .map(|(obj_ref, _)| obj_ref.2)
-                        .collect::<Vec<ObjectDigest>>()
-                })
+                .flat_map(|fx| fx.all_mutated().map(|(obj_ref, _, _)| obj_ref.2))
                 .collect::<Vec<ObjectDigest>>(),
         );
+        acc.remove_all(self.previous_version_digests(&effects)?);
 
         epoch_store.insert_state_hash_for_checkpoint(&checkpoint_seq_num, &acc)?;
         debug!("Accumulated checkpoint {}", checkpoint_seq_num);
@@ -92,6 +67,78 @@ impl StateAccumulator {
         Ok(acc)
     }
 
+    /// Digests of the versions that the objects modified (mutated, wrapped or deleted) by
+    /// `effects` had before they were modified. Objects that were unwrapped then deleted left
+    /// the live object set when they were wrapped, so they are skipped. Versions that have
+    /// already been pruned from the objects table are looked up in `parent_sync`, which keeps
+    /// the reference of every version.
+    fn previous_version_digests(
+        &self,
+        effects: &[TransactionEffects],
+    ) -> SuiResult<Vec<ObjectDigest>> {
+        let keys: Vec<_> = effects
+            .iter()
+            .flat_map(|fx| {
+                fx.modified_at_versions
+                    .iter()
+                    .filter(|(id, _)| {
+                        !fx.unwrapped_then_deleted
+                            .iter()
+                            .any(|obj_ref| obj_ref.0 == *id)
+                    })
+                    .map(|(id, version)| ObjectKey(*id, *version))
+            })
+            .collect();
+        let objects = self
+            .authority_store
+            .perpetual_tables
+            .objects
+            .multi_get(&keys)?;
+        keys.into_iter()
+            .zip(objects)
+            .map(|(ObjectKey(object_id, version), object)| match object {
+                Some(object) => Ok(object.digest()),
+                None => self.get_pruned_version_digest(object_id, version),
+            })
+            .collect()
+    }
+
+    fn get_pruned_version_digest(
+        &self,
+        object_id: ObjectID,
+        version: SequenceNumber,
+    ) -> SuiResult<ObjectDigest> {
+        self.authority_store
+            .perpetual_tables
+            .parent_sync
+            .iter()
+            .skip_prior_to(&(object_id, version, ObjectDigest::MAX))?
+            .next()
+            .and_then(|((id, v, digest), _)| (id == object_id && v == version).then_some(digest))
+            .ok_or_else(|| {
+                UserInputError::ObjectNotFound {
+                    object_id,
+                    version: Some(version),
+                }
+                .into()
+            })
+    }
+
+    /// Accumulator of a set of live objects, which equals the root state hash of the epoch at
+    /// which they are the live object set.
+    pub fn accumulate_live_object_set<'a>(
+        objects: impl IntoIterator<Item = &'a ObjectRef>,
+    ) -> Accumulator {
+        let mut acc = Accumulator::default();
+        acc.insert_all(
+            objects
+                .into_iter()
+                .map(|obj_ref| obj_ref.2)
+                .collect::<Vec<_>>(),
+        );
+        acc
+    }
+
     /// Unions all checkpoint accumulators at the end of the epoch to generate the
     /// root state hash and saves it. This function is idempotent. Can be called on
     /// non-consecutive epochs, e.g. to accumulate epoch 3 after having last
@@ -0,0 +1,543 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Snapshots of the live object set at the end of an epoch, used to bootstrap a node without
+//! syncing and executing every checkpoint since genesis.
+//!
+//! A snapshot is a directory containing a `MANIFEST` file and content-addressed chunks under
+//! `chunks/`, each named by the hex encoded Sha3-256 digest of its contents. A chunk is a BCS
+//! encoded list of objects. The manifest lists the chunks, the root state hash of the epoch as
+//! computed by `StateAccumulator::accumulate_epoch`, and the certified last checkpoint of every
+//! epoch up to the snapshot epoch, so that the checkpoint the snapshot was taken at can be
+//! verified starting from the genesis committee.
+//!
+//! The root state hash is not yet committed to by checkpoints (`root_state_digest` is left at
+//! its default value), so until it is, restoring a snapshot verifies that the objects match the
+//! manifest, but the digest of the manifest's root state hash must be supplied by the operator
+//! from a trusted source. Snapshots whose last checkpoint neither commits to a root state hash
+//! nor is the genesis checkpoint are rejected without one.
+
+use anyhow::{anyhow, bail, ensure};
+use fastcrypto::encoding::{Encoding, Hex};
+use fastcrypto::hash::{Digest, HashFunction, MultisetHash, Sha3_256};
+use prometheus::Registry;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use sui_config::genesis::Genesis;
+use sui_types::accumulator::Accumulator;
+use sui_types::base_types::{AuthorityName, ObjectRef, TransactionDigest};
+use sui_types::committee::{Committee, EpochId};
+use sui_types::messages_checkpoint::{
+    CertifiedCheckpointSummary, CheckpointSequenceNumber, EndOfEpochData, VerifiedCheckpoint,
+};
+use sui_types::object::Object;
+use sui_types::storage::ObjectKey;
+use sui_types::sui_system_state::get_sui_system_state;
+use tracing::info;
+use typed_store::Map;
+
+use crate::authority::authority_per_epoch_store::{
+    AuthorityPerEpochStore, EpochStartConfiguration,
+};
+use crate::authority::authority_store_tables::AuthorityPerpetualTables;
+use crate::authority::AuthorityStore;
+use crate::checkpoints::CheckpointStore;
+use crate::epoch::committee_store::CommitteeStore;
+use crate::epoch::epoch_metrics::EpochMetrics;
+use crate::state_accumulator::StateAccumulator;
+
+#[cfg(test)]
+#[path = "unit_tests/state_snapshot_tests.rs"]
+mod state_snapshot_tests;
+
+pub const MANIFEST_FILE_NAME: &str = "MANIFEST";
+const CHUNKS_DIR_NAME: &str = "chunks";
+
+/// Number of objects written to a single chunk.
+const OBJECTS_PER_CHUNK: usize = 10_000;
+
+pub type ChunkDigest = [u8; 32];
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SnapshotManifest {
+    pub epoch: EpochId,
+    pub root_state_hash: Accumulator,
+    /// The certified last checkpoint of every epoch from genesis up to and including `epoch`.
+    pub end_of_epoch_checkpoints: Vec<CertifiedCheckpointSummary>,
+    pub chunks: Vec<SnapshotChunk>,
+}
+
+impl SnapshotManifest {
+    /// The checkpoint the snapshot was taken at.
+    pub fn last_checkpoint(&self) -> &CertifiedCheckpointSummary {
+        self.end_of_epoch_checkpoints
+            .last()
+            .expect("A manifest always contains the checkpoint it was taken at")
+    }
+
+    pub fn object_count(&self) -> u64 {
+        self.chunks.iter().map(|chunk| chunk.object_count).sum()
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SnapshotChunk {
+    pub digest: ChunkDigest,
+    pub object_count: u64,
+}
+
+impl SnapshotChunk {
+    fn path(&self, snapshot_dir: &Path) -> PathBuf {
+        snapshot_dir
+            .join(CHUNKS_DIR_NAME)
+            .join(Hex::encode(self.digest))
+    }
+}
+
+/// Writes the snapshot of `epoch` to `snapshot_dir`, which must not contain a snapshot yet.
+///
+/// The root state hash of the epoch must have been accumulated, and the object versions live
+/// at the end of the epoch must not have been pruned. The node does not need to be stopped:
+/// object versions written by transactions finalized after the end of the epoch are ignored.
+pub fn write_state_snapshot(
+    store: &AuthorityStore,
+    checkpoint_store: &CheckpointStore,
+    epoch: EpochId,
+    snapshot_dir: &Path,
+) -> anyhow::Result<SnapshotManifest> {
+    let tables = &store.perpetual_tables;
+    let manifest_path = snapshot_dir.join(MANIFEST_FILE_NAME);
+    ensure!(
+        !manifest_path.exists(),
+        "A snapshot already exists at {snapshot_dir:?}"
+    );
+    let (last_checkpoint, root_state_hash) = tables
+        .root_state_hash_by_epoch
+        .get(&epoch)?
+        .ok_or_else(|| anyhow!("The root state hash of epoch {epoch} has not been accumulated"))?;
+    let end_of_epoch_checkpoints = (0..=epoch)
+        .map(|epoch| {
+            checkpoint_store
+                .get_epoch_last_checkpoint(epoch)?
+                .map(VerifiedCheckpoint::into_inner)
+                .ok_or_else(|| anyhow!("Last checkpoint of epoch {epoch} not found"))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    ensure!(
+        end_of_epoch_checkpoints.last().unwrap().sequence_number() == last_checkpoint,
+        "The root state hash of epoch {epoch} was not accumulated at its last checkpoint"
+    );
+
+    info!(
+        epoch,
+        last_checkpoint,
+        ?snapshot_dir,
+        "Writing state snapshot"
+    );
+    fs::create_dir_all(snapshot_dir.join(CHUNKS_DIR_NAME))?;
+    let mut writer = ChunkWriter {
+        tables,
+        snapshot_dir,
+        pending: Vec::with_capacity(OBJECTS_PER_CHUNK),
+        chunks: vec![],
+        accumulator: Accumulator::default(),
+    };
+
+    // parent_sync holds every version of every object, ordered by object id and version. The
+    // live version of an object at the end of the epoch is its last version written by a
+    // transaction finalized at or before the last checkpoint of the epoch.
+    let mut live_version: Option<ObjectRef> = None;
+    for (obj_ref, tx_digest) in tables.parent_sync.iter() {
+        if let Some(prev) = live_version {
+            if prev.0 != obj_ref.0 {
+                live_version = None;
+                writer.add(prev)?;
+            }
+        }
+        if is_finalized_at(tables, &tx_digest, last_checkpoint)? {
+            live_version = Some(obj_ref);
+        }
+    }
+    if let Some(prev) = live_version {
+        writer.add(prev)?;
+    }
+    writer.flush()?;
+
+    ensure!(
+        writer.accumulator == root_state_hash,
+        "Live object set does not match the root state hash of epoch {epoch}"
+    );
+    let manifest = SnapshotManifest {
+        epoch,
+        root_state_hash,
+        end_of_epoch_checkpoints,
+        chunks: writer.chunks,
+    };
+    // The manifest is written last, so that its presence marks the snapshot as complete.
+    fs::write(&manifest_path, bcs::to_bytes(&manifest)?)?;
+    info!(
+        epoch,
+        objects = manifest.object_count(),
+        chunks = manifest.chunks.len(),
+        "Wrote state snapshot"
+    );
+    Ok(manifest)
+}
+
+/// Transactions of checkpoints which have been pruned no longer have an entry in
+/// `executed_transactions_to_checkpoint`, but are known to be finalized since only checkpoints
+/// older than the retention window are pruned. This relies on the pruner retaining at least one
+/// epoch, so that the checkpoints of the epoch being snapshotted are still around.
+fn is_finalized_at(
+    tables: &AuthorityPerpetualTables,
+    tx_digest: &TransactionDigest,
+    checkpoint: CheckpointSequenceNumber,
+) -> anyhow::Result<bool> {
+    match tables.executed_transactions_to_checkpoint.get(tx_digest)? {
+        Some((_, seq)) => Ok(seq <= checkpoint),
+        None => Ok(!tables.executed_effects.contains_key(tx_digest)?),
+    }
+}
+
+struct ChunkWriter<'a> {
+    tables: &'a AuthorityPerpetualTables,
+    snapshot_dir: &'a Path,
+    pending: Vec<ObjectRef>,
+    chunks: Vec<SnapshotChunk>,
+    accumulator: Accumulator,
+}
+
+impl ChunkWriter<'_> {
+    fn add(&mut self, obj_ref: ObjectRef) -> anyhow::Result<()> {
+        if obj_ref.2.is_alive() {
+            self.pending.push(obj_ref);
+            if self.pending.len() == OBJECTS_PER_CHUNK {
+                self.flush()?;
+            }
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> anyhow::Result<()> {
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+        let keys: Vec<_> = self.pending.iter().map(ObjectKey::from).collect();
+        let objects = self
+            .tables
+            .objects
+            .multi_get(&keys)?
+            .into_iter()
+            .zip(&self.pending)
+            .map(|(object, obj_ref)| {
+                let object = object.ok_or_else(|| {
+                    anyhow!("Object {obj_ref:?} not found, it may have been pruned")
+                })?;
+                ensure!(
+                    object.digest() == obj_ref.2,
+                    "Digest of object {obj_ref:?} does not match its reference"
+                );
+                Ok(object)
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        let bytes = bcs::to_bytes(&objects)?;
+        let chunk = SnapshotChunk {
+            digest: Sha3_256::digest(&bytes).digest,
+            object_count: objects.len() as u64,
+        };
+        fs::write(chunk.path(self.snapshot_dir), bytes)?;
+        self.accumulator
+            .union(&StateAccumulator::accumulate_live_object_set(&self.pending));
+        self.pending.clear();
+        self.chunks.push(chunk);
+        Ok(())
+    }
+}
+
+pub struct StateSnapshotReader {
+    snapshot_dir: PathBuf,
+    manifest: SnapshotManifest,
+}
+
+impl StateSnapshotReader {
+    pub fn new(snapshot_dir: &Path) -> anyhow::Result<Self> {
+        let manifest_path = snapshot_dir.join(MANIFEST_FILE_NAME);
+        let manifest =
+            bcs::from_bytes(&fs::read(&manifest_path).map_err(|e| {
+                anyhow!("Failed to read snapshot manifest at {manifest_path:?}: {e}")
+            })?)?;
+        Ok(Self {
+            snapshot_dir: snapshot_dir.to_path_buf(),
+            manifest,
+        })
+    }
+
+    pub fn manifest(&self) -> &SnapshotManifest {
+        &self.manifest
+    }
+
+    /// Verifies the chain of end of epoch checkpoints, starting from the genesis committee, and
+    /// returns the committees of the epochs following each of them. The root state hash of the
+    /// snapshot must match the digest its last checkpoint commits to, or
+    /// `trusted_root_state_digest` if the checkpoint doesn't commit to one.
+    pub fn verify_checkpoints(
+        &self,
+        genesis_committee: &Committee,
+        trusted_root_state_digest: Option<&Digest<32>>,
+    ) -> anyhow::Result<Vec<Committee>> {
+        let checkpoints = &self.manifest.end_of_epoch_checkpoints;
+        ensure!(
+            checkpoints.len() as u64 == self.manifest.epoch + 1,
+            "Snapshot of epoch {} must contain {} end of epoch checkpoints, found {}",
+            self.manifest.epoch,
+            self.manifest.epoch + 1,
+            checkpoints.len()
+        );
+        let mut committee = genesis_committee.clone();
+        let mut committees = Vec::with_capacity(checkpoints.len());
+        for checkpoint in checkpoints {
+            ensure!(
+                checkpoint.epoch() == committee.epoch,
+                "Expected the last checkpoint of epoch {}, found a checkpoint of epoch {}",
+                committee.epoch,
+                checkpoint.epoch()
+            );
+            checkpoint.verify(&committee, None)?;
+            let Some(EndOfEpochData {
+                next_epoch_committee,
+                next_epoch_protocol_version,
+                ..
+            }) = &checkpoint.summary.end_of_epoch_data else {
+                bail!(
+                    "Checkpoint {} is not the last checkpoint of epoch {}",
+                    checkpoint.sequence_number(),
+                    committee.epoch
+                );
+            };
+            committee = Committee::new(
+                committee.epoch + 1,
+                *next_epoch_protocol_version,
+                next_epoch_committee.iter().cloned().collect(),
+            )?;
+            committees.push(committee.clone());
+        }
+
+        let last_checkpoint = self.manifest.last_checkpoint();
+        let root_state_digest = &last_checkpoint
+            .summary
+            .end_of_epoch_data
+            .as_ref()
+            .unwrap()
+            .root_state_digest;
+        let expected_digest = if *root_state_digest != Accumulator::default().digest() {
+            root_state_digest
+        } else if last_checkpoint.sequence_number() == 0 {
+            // The genesis checkpoint commits to the genesis state rather than to a root state
+            // hash, which is the default digest.
+            return Ok(committees);
+        } else {
+            trusted_root_state_digest.ok_or_else(|| {
+                anyhow!(
+                    "Checkpoint {} does not commit to a root state hash, a trusted root state digest is required",
+                    last_checkpoint.sequence_number()
+                )
+            })?
+        };
+        ensure!(
+            *expected_digest == self.manifest.root_state_hash.digest(),
+            "Root state hash of the snapshot does not match its last checkpoint"
+        );
+        Ok(committees)
+    }
+
+    /// Reads the objects of a chunk, after checking them against the chunk's digest.
+    pub fn read_chunk(&self, chunk: &SnapshotChunk) -> anyhow::Result<Vec<Object>> {
+        let bytes = fs::read(chunk.path(&self.snapshot_dir))?;
+        ensure!(
+            Sha3_256::digest(&bytes).digest == chunk.digest,
+            "Snapshot chunk {} is corrupted",
+            Hex::encode(chunk.digest)
+        );
+        let objects: Vec<Object> = bcs::from_bytes(&bytes)?;
+        ensure!(
+            objects.len() as u64 == chunk.object_count,
+            "Snapshot chunk {} contains {} objects, expected {}",
+            Hex::encode(chunk.digest),
+            objects.len(),
+            chunk.object_count
+        );
+        Ok(objects)
+    }
+
+    /// Checks that the objects of the snapshot accumulate to its root state hash.
+    pub fn verify_objects(&self) -> anyhow::Result<()> {
+        let mut accumulator = Accumulator::default();
+        for chunk in &self.manifest.chunks {
+            let objects = self.read_chunk(chunk)?;
+            accumulator.insert_all(objects.iter().map(Object::digest).collect::<Vec<_>>());
+        }
+        ensure!(
+            accumulator == self.manifest.root_state_hash,
+            "Objects of the snapshot do not match its root state hash"
+        );
+        Ok(())
+    }
+}
+
+/// Bootstraps the DB of a node at `db_path` from the snapshot at `snapshot_dir`. The snapshot
+/// is fully verified before anything is written, and the node DB must not exist yet.
+/// `trusted_root_state_digest` is required as long as checkpoints don't commit to the root
+/// state hash, see the module documentation.
+///
+/// The restored node starts at the first epoch after the snapshot, with the last checkpoint of
+/// the snapshot epoch as its highest executed checkpoint. Transactions, effects and checkpoint
+/// contents from before the snapshot are not restored.
+pub fn restore_state_snapshot(
+    snapshot_dir: &Path,
+    db_path: &Path,
+    genesis: &Genesis,
+    name: AuthorityName,
+    trusted_root_state_digest: Option<&Digest<32>>,
+) -> anyhow::Result<()> {
+    let store_path = db_path.join("store");
+    ensure!(
+        !store_path.exists(),
+        "Authority store already exists at {store_path:?}"
+    );
+    let reader = StateSnapshotReader::new(snapshot_dir)?;
+    let manifest = reader.manifest();
+    let epoch = manifest.epoch;
+    let genesis_committee = genesis.committee()?;
+    let committees = reader.verify_checkpoints(&genesis_committee, trusted_root_state_digest)?;
+    reader.verify_objects()?;
+    let last_checkpoint = VerifiedCheckpoint::new_unchecked(manifest.last_checkpoint().clone());
+    info!(
+        epoch,
+        last_checkpoint = last_checkpoint.sequence_number(),
+        objects = manifest.object_count(),
+        ?db_path,
+        "Verified state snapshot, restoring it"
+    );
+
+    let tables = AuthorityPerpetualTables::open(&store_path, None);
+    for chunk in &manifest.chunks {
+        insert_objects(&tables, &reader.read_chunk(chunk)?, epoch, &last_checkpoint)?;
+    }
+    tables.root_state_hash_by_epoch.insert(
+        &epoch,
+        &(
+            last_checkpoint.sequence_number(),
+            manifest.root_state_hash.clone(),
+        ),
+    )?;
+    tables.set_recovery_epoch(epoch + 1)?;
+
+    let committee_store = CommitteeStore::new(db_path.join("epochs"), &genesis_committee, None);
+    for committee in &committees {
+        committee_store.insert_new_committee(committee)?;
+    }
+
+    let checkpoint_store = CheckpointStore::new(&db_path.join("checkpoints"));
+    checkpoint_store.insert_checkpoint_contents(genesis.checkpoint_contents().clone())?;
+    checkpoint_store.insert_verified_checkpoint(genesis.checkpoint())?;
+    for checkpoint in &manifest.end_of_epoch_checkpoints {
+        checkpoint_store
+            .insert_verified_checkpoint(VerifiedCheckpoint::new_unchecked(checkpoint.clone()))?;
+    }
+    checkpoint_store.update_highest_synced_checkpoint(&last_checkpoint)?;
+    checkpoint_store.update_highest_executed_checkpoint(&last_checkpoint)?;
+    // The checkpoints before the snapshot are treated as pruned, as their contents are missing.
+    checkpoint_store.update_highest_pruned_checkpoint(&last_checkpoint)?;
+
+    let system_state = get_sui_system_state(&tables)?;
+    ensure!(
+        system_state.epoch == epoch + 1,
+        "System state of the snapshot is at epoch {}, expected {}",
+        system_state.epoch,
+        epoch + 1
+    );
+    AuthorityPerEpochStore::new(
+        name,
+        committees.last().unwrap().clone(),
+        &store_path,
+        None,
+        EpochMetrics::new(&Registry::new()),
+        Some(EpochStartConfiguration {
+            system_state,
+            epoch_digest: last_checkpoint.digest(),
+        }),
+    );
+    info!(epoch, "Restored state snapshot");
+    Ok(())
+}
+
+/// Inserts objects restored from a snapshot, along with their parent_sync entries and locks,
+/// as genesis objects are. The transactions that wrote them are recorded as finalized at the
+/// snapshot checkpoint, which lets later snapshots include them.
+fn insert_objects(
+    tables: &AuthorityPerpetualTables,
+    objects: &[Object],
+    epoch: EpochId,
+    last_checkpoint: &VerifiedCheckpoint,
+) -> anyhow::Result<()> {
+    let ref_and_objects: Vec<_> = objects
+        .iter()
+        .map(|o| (o.compute_object_reference(), o))
+        .collect();
+    tables
+        .objects
+        .batch()
+        .insert_batch(
+            &tables.objects,
+            ref_and_objects
+                .iter()
+                .map(|(oref, o)| (ObjectKey::from(oref), *o)),
+        )?
+        .insert_batch(
+            &tables.parent_sync,
+            ref_and_objects
+                .iter()
+                .map(|(oref, o)| (oref, o.previous_transaction)),
+        )?
+        .insert_batch(
+            &tables.executed_transactions_to_checkpoint,
+            objects.iter().map(|o| {
+                (
+                    o.previous_transaction,
+                    (epoch, last_checkpoint.sequence_number()),
+                )
+            }),
+        )?
+        .insert_batch(
+            &tables.owned_object_transaction_locks,
+            ref_and_objects
+                .iter()
+                .filter(|(_, o)| !o.is_child_object())
+                .map(|(oref, _)| (oref, None)),
+        )?
+        .write()?;
+    Ok(())
+}
+
+/// Accumulates the root state hash of `epoch`, which ended at `last_checkpoint`, and writes its
+/// snapshot to `snapshot_dir`.
+pub async fn accumulate_and_write_state_snapshot(
+    accumulator: Arc<StateAccumulator>,
+    store: Arc<AuthorityStore>,
+    checkpoint_store: Arc<CheckpointStore>,
+    epoch_store: Arc<AuthorityPerEpochStore>,
+    last_checkpoint: CheckpointSequenceNumber,
+    snapshot_dir: PathBuf,
+) -> anyhow::Result<SnapshotManifest> {
+    let epoch = epoch_store.epoch();
+    accumulator
+        .accumulate_epoch(&epoch, last_checkpoint, epoch_store)
+        .await?;
+    tokio::task::spawn_blocking(move || {
+        write_state_snapshot(&store, &checkpoint_store, epoch, &snapshot_dir)
+    })
+    .await?
+}
@@ -80,6 +80,14 @@ impl ReadStore for RocksDbStore {
             })
     }
 
+    fn get_lowest_available_checkpoint(&self) -> Result<CheckpointSequenceNumber, Self::Error> {
+        Ok(self
+            .checkpoint_store
+            .get_highest_pruned_checkpoint_seq_number()?
+            .map(|pruned| pruned + 1)
+            .unwrap_or_default())
+    }
+
     fn get_checkpoint_contents(
         &self,
         digest: &CheckpointContentsDigest,
@@ -135,6 +143,14 @@ impl WriteStore for RocksDbStore {
             .update_highest_synced_checkpoint(checkpoint)
     }
 
+    fn update_highest_archived_checkpoint(
+        &self,
+        checkpoint: &VerifiedCheckpoint,
+    ) -> Result<(), Self::Error> {
+        self.checkpoint_store
+            .update_highest_archived_checkpoint(checkpoint)
+    }
+
     fn insert_checkpoint_contents(&self, contents: CheckpointContents) -> Result<(), Self::Error> {
         self.checkpoint_store.insert_checkpoint_contents(contents)
     }
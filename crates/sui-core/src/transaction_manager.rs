@@ -336,6 +336,17 @@ impl TransactionManager {
             .collect()
     }
 
+    // Returns the number of transactions waiting for missing input objects.
+    pub fn pending_certificates_len(&self) -> usize {
+        self.inner.read().pending_certificates.len()
+    }
+
+    // Returns the number of transactions with all inputs available that have not finished
+    // execution.
+    pub fn executing_certificates_len(&self) -> usize {
+        self.inner.read().executing_certificates.len()
+    }
+
     // Reconfigures the TransactionManager for a new epoch. Existing transactions will be dropped
     // because they are no longer relevant and may be incorrect in the new epoch.
     pub(crate) fn reconfigure(&self, new_epoch: EpochId) {
@@ -0,0 +1,136 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use sui_types::base_types::{dbg_addr, ObjectID, ObjectInfo};
+use sui_types::crypto::{get_key_pair, AccountKeyPair};
+use sui_types::object::Object;
+
+use crate::authority::authority_tests::{
+    init_state_with_ids, init_transfer_transaction, send_and_confirm_transaction,
+};
+
+#[tokio::test]
+async fn test_snapshot_does_not_observe_later_writes() {
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let object_id = ObjectID::random();
+    let gas_object_id = ObjectID::random();
+    let state = init_state_with_ids(vec![(sender, object_id), (sender, gas_object_id)]).await;
+    let object = state.database.get_object(&object_id).unwrap().unwrap();
+    let gas_object = state.database.get_object(&gas_object_id).unwrap().unwrap();
+
+    let snapshot = state.read_snapshot().unwrap();
+    let transaction = init_transfer_transaction(
+        sender,
+        &sender_key,
+        dbg_addr(2),
+        object.compute_object_reference(),
+        gas_object.compute_object_reference(),
+    );
+    let (_, effects) = send_and_confirm_transaction(&state, transaction)
+        .await
+        .unwrap();
+    let digest = effects.into_data().transaction_digest;
+
+    // The snapshot taken before the transfer still sees the object as it was
+    let read = snapshot.get_object_read(&object_id).unwrap();
+    assert_eq!(read.into_object().unwrap(), object);
+    assert!(snapshot
+        .get_executed_transaction_and_effects(digest)
+        .is_err());
+    drop(snapshot);
+
+    let snapshot = state.read_snapshot().unwrap();
+    let transferred = snapshot
+        .get_object_read(&object_id)
+        .unwrap()
+        .into_object()
+        .unwrap();
+    assert!(transferred.version() > object.version());
+    assert_eq!(transferred.owner, dbg_addr(2));
+    assert!(snapshot
+        .get_executed_transaction_and_effects(digest)
+        .is_ok());
+}
+
+#[tokio::test]
+async fn test_reconcile_owned_objects() {
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let transferred_id = ObjectID::random();
+    let untouched_id = ObjectID::random();
+    let gas_object_id = ObjectID::random();
+    let state = init_state_with_ids(vec![
+        (sender, transferred_id),
+        (sender, untouched_id),
+        (sender, gas_object_id),
+    ])
+    .await;
+
+    // The owner index entries of the sender, as read before the transfer
+    let listed: Vec<_> = [transferred_id, untouched_id, gas_object_id]
+        .iter()
+        .map(|id| {
+            let object = state.database.get_object(id).unwrap().unwrap();
+            ObjectInfo::new(&object.compute_object_reference(), &object)
+        })
+        .collect();
+
+    let transaction = init_transfer_transaction(
+        sender,
+        &sender_key,
+        dbg_addr(2),
+        (&listed[0]).into(),
+        (&listed[2]).into(),
+    );
+    send_and_confirm_transaction(&state, transaction)
+        .await
+        .unwrap();
+
+    let reconciled = state
+        .read_snapshot()
+        .unwrap()
+        .reconcile_owned_objects(listed.clone())
+        .unwrap();
+
+    // The transferred object is left out, the untouched one is listed as is and the gas object,
+    // which the sender still owns, is listed at its new version.
+    let gas_object = state.database.get_object(&gas_object_id).unwrap().unwrap();
+    assert_eq!(
+        reconciled,
+        vec![
+            listed[1].clone(),
+            ObjectInfo::new(&gas_object.compute_object_reference(), &gas_object),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_dynamic_field_object_read() {
+    let parent_id = ObjectID::random();
+    let state = init_state_with_ids(vec![(dbg_addr(1), parent_id)]).await;
+
+    // A dynamic field is owned by its parent, and the object of a dynamic object field by a
+    // wrapper that is owned by the parent.
+    let field = Object::with_object_owner_for_testing(ObjectID::random(), parent_id);
+    let wrapper = Object::with_object_owner_for_testing(ObjectID::random(), parent_id);
+    let wrapped = Object::with_object_owner_for_testing(ObjectID::random(), wrapper.id());
+    // A field of another object
+    let other_field = Object::with_object_owner_for_testing(ObjectID::random(), ObjectID::random());
+    for object in [&field, &wrapper, &wrapped, &other_field] {
+        state.insert_genesis_object(object.clone()).await;
+    }
+
+    let snapshot = state.read_snapshot().unwrap();
+    for object in [&field, &wrapped] {
+        let read = snapshot
+            .get_dynamic_field_object_read(parent_id, &object.id())
+            .unwrap()
+            .unwrap();
+        assert_eq!(read.into_object().unwrap(), *object);
+    }
+    for id in [other_field.id(), ObjectID::random()] {
+        assert!(snapshot
+            .get_dynamic_field_object_read(parent_id, &id)
+            .unwrap()
+            .is_none());
+    }
+}
@@ -0,0 +1,74 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use fastcrypto::hash::MultisetHash;
+use sui_types::accumulator::Accumulator;
+use sui_types::base_types::{dbg_addr, ObjectID};
+use sui_types::crypto::{get_key_pair, AccountKeyPair};
+use sui_types::storage::ObjectKey;
+use typed_store::Map;
+
+use crate::authority::authority_tests::{
+    init_state_with_ids, init_transfer_transaction, send_and_confirm_transaction,
+};
+use crate::state_accumulator::StateAccumulator;
+
+#[tokio::test]
+async fn test_accumulate_checkpoint_with_pruned_history() {
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let recipient = dbg_addr(2);
+    let object_id = ObjectID::random();
+    let gas_object_id = ObjectID::random();
+    let authority_state =
+        init_state_with_ids(vec![(sender, object_id), (sender, gas_object_id)]).await;
+    let store = &authority_state.database;
+    let object = store.get_object(&object_id).unwrap().unwrap();
+    let gas_object = store.get_object(&gas_object_id).unwrap().unwrap();
+
+    let transaction = init_transfer_transaction(
+        sender,
+        &sender_key,
+        recipient,
+        object.compute_object_reference(),
+        gas_object.compute_object_reference(),
+    );
+    let (_, effects) = send_and_confirm_transaction(&authority_state, transaction)
+        .await
+        .unwrap();
+    let effects = effects.into_data();
+
+    // The versions written by the transaction replace the versions it modified.
+    let mut expected = Accumulator::default();
+    expected.insert_all(
+        effects
+            .all_mutated()
+            .map(|(obj_ref, _, _)| obj_ref.2)
+            .collect::<Vec<_>>(),
+    );
+    expected.remove_all(vec![object.digest(), gas_object.digest()]);
+
+    // Prune the versions modified by the transaction, as the pruner does once they are older
+    // than the retained versions.
+    store
+        .perpetual_tables
+        .objects
+        .multi_remove([
+            ObjectKey(object_id, object.version()),
+            ObjectKey(gas_object_id, gas_object.version()),
+        ])
+        .unwrap();
+    assert!(store
+        .get_object_by_key(&object_id, object.version())
+        .unwrap()
+        .is_none());
+
+    let accumulator = StateAccumulator::new(store.clone());
+    let acc = accumulator
+        .accumulate_checkpoint(
+            vec![effects],
+            1,
+            authority_state.epoch_store_for_testing().clone(),
+        )
+        .unwrap();
+    assert_eq!(expected, acc);
+}
@@ -0,0 +1,310 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::fs;
+use std::path::Path;
+
+use fastcrypto::hash::MultisetHash;
+use fastcrypto::traits::KeypairTraits;
+use sui_config::NetworkConfig;
+use sui_protocol_config::ProtocolConfig;
+use sui_types::accumulator::Accumulator;
+use sui_types::base_types::{dbg_addr, ObjectID, SequenceNumber, TransactionDigest};
+use sui_types::committee::{Committee, ProtocolVersion};
+use sui_types::crypto::{
+    get_key_pair, AccountKeyPair, AuthoritySignInfo, AuthoritySignature,
+    AuthorityStrongQuorumSignInfo, SuiAuthoritySignature,
+};
+use sui_types::intent::{Intent, IntentMessage, IntentScope};
+use sui_types::messages_checkpoint::{
+    CertifiedCheckpointSummary, CheckpointContents, CheckpointSummary, EndOfEpochData,
+    VerifiedCheckpoint,
+};
+use sui_types::object::{Object, Owner};
+use sui_types::storage::ObjectKey;
+use sui_types::sui_system_state::SuiSystemStateWrapper;
+use sui_types::SUI_SYSTEM_STATE_OBJECT_ID;
+use typed_store::Map;
+
+use crate::authority::authority_store_tables::AuthorityPerpetualTables;
+use crate::authority::authority_tests::{
+    init_state_with_committee, init_transfer_transaction, send_and_confirm_transaction,
+};
+use crate::checkpoints::CheckpointStore;
+use crate::state_accumulator::StateAccumulator;
+use crate::state_snapshot::{restore_state_snapshot, write_state_snapshot};
+
+/// The state written to a snapshot by `write_test_snapshot`.
+struct TestSnapshot {
+    network_config: NetworkConfig,
+    root_state_hash: Accumulator,
+    transferred_object: ObjectID,
+}
+
+/// Writes the snapshot of epoch 0 of a validator which executed a transfer, with the last
+/// checkpoint of the epoch certified by the genesis committee.
+async fn write_test_snapshot(snapshot_dir: &Path) -> TestSnapshot {
+    let dir = tempfile::TempDir::new().unwrap();
+    let network_config = sui_config::builder::ConfigBuilder::new(&dir).build();
+    let genesis = &network_config.genesis;
+    let keypair = network_config.validator_configs[0]
+        .protocol_key_pair()
+        .copy();
+    let state = init_state_with_committee(genesis, &keypair).await;
+    let store = &state.database;
+
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let object = Object::with_id_owner_for_testing(ObjectID::random(), sender);
+    let gas_object = Object::with_id_owner_for_testing(ObjectID::random(), sender);
+    state.insert_genesis_object(object.clone()).await;
+    state.insert_genesis_object(gas_object.clone()).await;
+    let transaction = init_transfer_transaction(
+        sender,
+        &sender_key,
+        dbg_addr(2),
+        object.compute_object_reference(),
+        gas_object.compute_object_reference(),
+    );
+    send_and_confirm_transaction(&state, transaction)
+        .await
+        .unwrap();
+
+    // Advance the system state to the next epoch, as the end of epoch transaction does.
+    let mut system_state_object = store
+        .get_object(&SUI_SYSTEM_STATE_OBJECT_ID)
+        .unwrap()
+        .unwrap();
+    let move_object = system_state_object.data.try_as_move_mut().unwrap();
+    let mut wrapper: SuiSystemStateWrapper = bcs::from_bytes(move_object.contents()).unwrap();
+    wrapper.system_state.epoch = 1;
+    move_object
+        .update_contents(
+            bcs::to_bytes(&wrapper).unwrap(),
+            &ProtocolConfig::get_for_max_version(),
+        )
+        .unwrap();
+    move_object.increment_version_to(SequenceNumber::lamport_increment([move_object.version()]));
+    system_state_object.previous_transaction = TransactionDigest::genesis();
+    state.insert_genesis_object(system_state_object).await;
+
+    // Every executed transaction is finalized by the last checkpoint of the epoch.
+    let tables = &store.perpetual_tables;
+    let last_checkpoint = 1;
+    for digest in tables.executed_effects.keys() {
+        tables
+            .executed_transactions_to_checkpoint
+            .insert(&digest, &(0, last_checkpoint))
+            .unwrap();
+    }
+    let root_state_hash = StateAccumulator::accumulate_live_object_set(
+        &tables.iter_live_object_set().collect::<Vec<_>>(),
+    );
+    tables
+        .root_state_hash_by_epoch
+        .insert(&0, &(last_checkpoint, root_state_hash.clone()))
+        .unwrap();
+
+    let committee = genesis.committee().unwrap();
+    let genesis_checkpoint = genesis.checkpoint();
+    let end_of_epoch_checkpoint = certify_checkpoint(
+        CheckpointSummary {
+            epoch: 0,
+            sequence_number: last_checkpoint,
+            network_total_transactions: genesis_checkpoint.summary.network_total_transactions,
+            content_digest: CheckpointContents::new_with_causally_ordered_transactions(
+                std::iter::empty(),
+            )
+            .digest(),
+            previous_digest: Some(genesis_checkpoint.digest()),
+            epoch_rolling_gas_cost_summary: Default::default(),
+            end_of_epoch_data: Some(EndOfEpochData {
+                next_epoch_committee: committee.voting_rights.clone(),
+                next_epoch_protocol_version: ProtocolVersion::MIN,
+                root_state_digest: Accumulator::default().digest(),
+            }),
+            timestamp_ms: 0,
+            version_specific_data: Vec::new(),
+        },
+        &network_config,
+        &committee,
+    );
+    state
+        .checkpoint_store
+        .insert_verified_checkpoint(end_of_epoch_checkpoint)
+        .unwrap();
+
+    let manifest = write_state_snapshot(store, &state.checkpoint_store, 0, snapshot_dir).unwrap();
+    assert_eq!(root_state_hash, manifest.root_state_hash);
+    assert!(!manifest.chunks.is_empty());
+
+    TestSnapshot {
+        network_config,
+        root_state_hash,
+        transferred_object: object.id(),
+    }
+}
+
+fn certify_checkpoint(
+    summary: CheckpointSummary,
+    network_config: &NetworkConfig,
+    committee: &Committee,
+) -> VerifiedCheckpoint {
+    let intent_msg = IntentMessage::new(
+        Intent::default().with_scope(IntentScope::CheckpointSummary),
+        summary.clone(),
+    );
+    let signatures = network_config
+        .validator_configs
+        .iter()
+        .map(|config| {
+            let key = config.protocol_key_pair();
+            AuthoritySignInfo {
+                epoch: summary.epoch,
+                authority: key.public().into(),
+                signature: AuthoritySignature::new_secure(&intent_msg, &summary.epoch, key),
+            }
+        })
+        .collect();
+    let checkpoint = CertifiedCheckpointSummary {
+        summary,
+        auth_signature: AuthorityStrongQuorumSignInfo::new_from_auth_sign_infos(
+            signatures, committee,
+        )
+        .unwrap(),
+    };
+    VerifiedCheckpoint::new(checkpoint, committee).unwrap()
+}
+
+fn restore(
+    snapshot: &TestSnapshot,
+    snapshot_dir: &Path,
+    db_path: &Path,
+    trusted_root_state_digest: Option<&fastcrypto::hash::Digest<32>>,
+) -> anyhow::Result<()> {
+    let config = &snapshot.network_config.validator_configs[0];
+    restore_state_snapshot(
+        snapshot_dir,
+        db_path,
+        &snapshot.network_config.genesis,
+        config.protocol_public_key(),
+        trusted_root_state_digest,
+    )
+}
+
+#[tokio::test]
+async fn test_state_snapshot_round_trip() {
+    let snapshot_dir = tempfile::tempdir().unwrap();
+    let snapshot = write_test_snapshot(snapshot_dir.path()).await;
+
+    let db_dir = tempfile::tempdir().unwrap();
+    let db_path = db_dir.path().join("db");
+    let root_state_digest = snapshot.root_state_hash.digest();
+    restore(
+        &snapshot,
+        snapshot_dir.path(),
+        &db_path,
+        Some(&root_state_digest),
+    )
+    .unwrap();
+
+    // The restored live object set accumulates to the root state hash of the snapshot.
+    let tables = AuthorityPerpetualTables::open(&db_path.join("store"), None);
+    let restored = StateAccumulator::accumulate_live_object_set(
+        &tables.iter_live_object_set().collect::<Vec<_>>(),
+    );
+    assert_eq!(snapshot.root_state_hash, restored);
+    assert_eq!(root_state_digest, restored.digest());
+    assert_eq!(
+        Some((1, snapshot.root_state_hash.clone())),
+        tables.root_state_hash_by_epoch.get(&0).unwrap()
+    );
+    assert_eq!(1, tables.get_recovery_epoch_at_restart().unwrap());
+
+    // The transferred object is restored at the version written by the transfer.
+    let (object_ref, _) = tables
+        .get_latest_parent_entry(snapshot.transferred_object)
+        .unwrap()
+        .unwrap();
+    let object = tables
+        .objects
+        .get(&ObjectKey::from(&object_ref))
+        .unwrap()
+        .unwrap();
+    assert_eq!(Owner::AddressOwner(dbg_addr(2)), object.owner);
+
+    let checkpoint_store = CheckpointStore::new(&db_path.join("checkpoints"));
+    assert_eq!(
+        Some(1),
+        checkpoint_store
+            .get_highest_executed_checkpoint_seq_number()
+            .unwrap()
+    );
+    assert_eq!(
+        Some(1),
+        checkpoint_store
+            .get_epoch_last_checkpoint(0)
+            .unwrap()
+            .map(|checkpoint| checkpoint.sequence_number())
+    );
+
+    // A node DB is never restored over
+    assert!(restore(
+        &snapshot,
+        snapshot_dir.path(),
+        &db_path,
+        Some(&root_state_digest)
+    )
+    .is_err());
+}
+
+#[tokio::test]
+async fn test_state_snapshot_rejects_corrupted_chunk() {
+    let snapshot_dir = tempfile::tempdir().unwrap();
+    let snapshot = write_test_snapshot(snapshot_dir.path()).await;
+    let root_state_digest = snapshot.root_state_hash.digest();
+
+    let chunk_path = fs::read_dir(snapshot_dir.path().join("chunks"))
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap()
+        .path();
+    let mut bytes = fs::read(&chunk_path).unwrap();
+    let last = bytes.len() - 1;
+    bytes[last] ^= 0xff;
+    fs::write(&chunk_path, bytes).unwrap();
+
+    let db_dir = tempfile::tempdir().unwrap();
+    let db_path = db_dir.path().join("db");
+    let err = restore(
+        &snapshot,
+        snapshot_dir.path(),
+        &db_path,
+        Some(&root_state_digest),
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("is corrupted"), "{err}");
+    // Nothing is written before the snapshot is verified
+    assert!(!db_path.join("store").exists());
+}
+
+#[tokio::test]
+async fn test_state_snapshot_requires_root_state_digest() {
+    let snapshot_dir = tempfile::tempdir().unwrap();
+    let snapshot = write_test_snapshot(snapshot_dir.path()).await;
+    let db_dir = tempfile::tempdir().unwrap();
+    let db_path = db_dir.path().join("db");
+
+    // The last checkpoint commits to the default digest, which is only accepted for genesis.
+    assert!(restore(&snapshot, snapshot_dir.path(), &db_path, None).is_err());
+
+    let other_digest = Accumulator::default().digest();
+    assert!(restore(
+        &snapshot,
+        snapshot_dir.path(),
+        &db_path,
+        Some(&other_digest)
+    )
+    .is_err());
+    assert!(!db_path.join("store").exists());
+}
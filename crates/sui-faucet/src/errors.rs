@@ -28,6 +28,18 @@ pub enum FaucetError {
     #[error("Coin Transfer Failed `{0}`")]
     Transfer(String),
 
+    #[error("Too many requests for {key}, retry after {retry_after_secs} seconds")]
+    TooManyRequests { key: String, retry_after_secs: u64 },
+
+    #[error("Faucet does not serve coins of type `{0}`")]
+    UnsupportedCoinType(String),
+
+    #[error("Unknown batch request `{0}`")]
+    UnknownBatchRequest(String),
+
+    #[error("Too many batch requests are queued, retry later")]
+    BatchQueueFull,
+
     #[error("Internal error: {0}")]
     Internal(String),
 }
@@ -1,14 +1,16 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
-use crate::FaucetError;
+use crate::{Admission, FaucetError};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use sui_types::base_types::{ObjectID, SuiAddress, TransactionDigest};
+use sui_types::gas_coin::GAS;
 use uuid::Uuid;
 
 mod simple_faucet;
 mod write_ahead_log;
 pub use self::simple_faucet::SimpleFaucet;
+pub(crate) use self::write_ahead_log::WriteAheadLog;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct FaucetReceipt {
@@ -20,6 +22,22 @@ pub struct CoinInfo {
     pub amount: u64,
     pub id: ObjectID,
     pub transfer_tx_digest: TransactionDigest,
+    /// The type `T` of the sent `Coin<T>`. Missing from the responses of faucets that only send
+    /// SUI.
+    #[serde(default = "sui_coin_type")]
+    pub coin_type: String,
+}
+
+pub fn sui_coin_type() -> String {
+    GAS::type_tag().to_string()
+}
+
+/// Progress of a request queued with [`Faucet::batch_send`].
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub enum BatchSendStatus {
+    Pending,
+    Succeeded(FaucetReceipt),
+    Failed(String),
 }
 
 #[async_trait]
@@ -31,4 +49,29 @@ pub trait Faucet {
         recipient: SuiAddress,
         amounts: &[u64],
     ) -> Result<FaucetReceipt, FaucetError>;
+
+    /// Send `Coin<T>` of the specified amount to the recipient, where `T` is `coin_type`. The coins
+    /// are split from the faucet's own coins of that type, merging them as needed.
+    async fn send_coins(
+        &self,
+        id: Uuid,
+        recipient: SuiAddress,
+        coin_type: &str,
+        amounts: &[u64],
+    ) -> Result<FaucetReceipt, FaucetError>;
+
+    /// Queue a request to send `Coin<SUI>` of the specified amount to the recipient, to be paid
+    /// out in a single transaction together with the other queued requests. Returns once the
+    /// request is queued; its outcome is available from `get_batch_send_status`. The `admission` of
+    /// the request against the faucet's quotas is refunded if the request fails.
+    async fn batch_send(
+        &self,
+        id: Uuid,
+        recipient: SuiAddress,
+        amounts: &[u64],
+        admission: Admission,
+    ) -> Result<BatchSendStatus, FaucetError>;
+
+    /// Status of a request queued with `batch_send`
+    async fn get_batch_send_status(&self, id: Uuid) -> Result<BatchSendStatus, FaucetError>;
 }
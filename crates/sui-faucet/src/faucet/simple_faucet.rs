@@ -7,14 +7,15 @@ use async_trait::async_trait;
 use prometheus::Registry;
 use tap::tap::TapFallible;
 
-#[cfg(test)]
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::path::Path;
+use std::sync::Arc;
 
 use sui::client_commands::WalletContext;
 use sui_json_rpc_types::{SuiObjectRead, SuiPaySui, SuiTransactionKind, SuiTransactionResponse};
 use sui_keys::keystore::AccountKeystore;
 use sui_types::object::Owner;
+use sui_types::parse_sui_type_tag;
 use sui_types::{
     base_types::{ObjectID, SuiAddress, TransactionDigest},
     gas_coin::GasCoin,
@@ -25,11 +26,14 @@ use tokio::sync::{
     mpsc::{self, Receiver, Sender},
     Mutex,
 };
-use tokio::time::{timeout, Duration};
+use tokio::time::{timeout, Duration, Instant, MissedTickBehavior};
 use tracing::{error, info, warn};
 use uuid::Uuid;
 
-use crate::{CoinInfo, Faucet, FaucetError, FaucetReceipt};
+use crate::{
+    sui_coin_type, Admission, BatchSendStatus, CoinInfo, Faucet, FaucetError, FaucetReceipt,
+    QuotaConfig, RequestQuotas,
+};
 
 use super::write_ahead_log::WriteAheadLog;
 
@@ -40,6 +44,26 @@ pub struct SimpleFaucet {
     consumer: Mutex<Receiver<ObjectID>>,
     metrics: FaucetMetrics,
     wal: Mutex<WriteAheadLog>,
+    batch: Mutex<BatchState>,
+    /// Quotas the requests are counted against, recorded in the WAL DB.
+    quotas: Arc<RequestQuotas>,
+    /// Maximum number of requests queued by `batch_send`, further requests are rejected.
+    batch_queue_capacity: usize,
+    /// Held while sending coins other than SUI. Those are split from the coins the faucet owns at
+    /// the time of the request, so concurrent requests would try to use the same coin objects.
+    coin_lock: Mutex<()>,
+}
+
+/// Requests queued by `batch_send` for the next batch, and the status of recent batch requests.
+#[derive(Default)]
+struct BatchState {
+    queued: Vec<(Uuid, SuiAddress, Vec<u64>)>,
+    /// The quotas counted by the queued requests, refunded if their batch fails.
+    admissions: HashMap<Uuid, Admission>,
+    statuses: HashMap<Uuid, BatchSendStatus>,
+    /// Completed requests in the order they completed, so their statuses can be dropped once
+    /// they are older than `BATCH_STATUS_TTL`.
+    completed: VecDeque<(Instant, Uuid)>,
 }
 
 enum GasCoinResponse {
@@ -54,6 +78,8 @@ const DEFAULT_GAS_BUDGET: u64 = 1000;
 const PAY_SUI_GAS: u64 = 1000;
 const LOCK_TIMEOUT: Duration = Duration::from_secs(10);
 const RECV_TIMEOUT: Duration = Duration::from_secs(5);
+const BATCH_STATUS_TTL: Duration = Duration::from_secs(10 * 60);
+const DEFAULT_BATCH_QUEUE_CAPACITY: usize = 10_000;
 
 impl SimpleFaucet {
     pub async fn new(
@@ -78,6 +104,10 @@ impl SimpleFaucet {
         let metrics = FaucetMetrics::new(prometheus_registry);
 
         let wal = WriteAheadLog::open(wal_path);
+        let quotas = Arc::new(RequestQuotas::new(
+            wal.quota_table(),
+            QuotaConfig::default(),
+        ));
         let mut pending = vec![];
 
         let (producer, consumer) = mpsc::channel(coins.len());
@@ -85,13 +115,13 @@ impl SimpleFaucet {
             let coin_id = *coin.id();
             if let Some(write_ahead_log::Entry {
                 uuid,
-                recipient,
+                recipients,
                 tx,
             }) = wal.reclaim(coin_id).map_err(FaucetError::internal)?
             {
                 let uuid = Uuid::from_bytes(uuid);
-                info!(?uuid, ?recipient, ?coin_id, "Retrying txn from WAL.");
-                pending.push((uuid, recipient, coin_id, tx));
+                info!(?uuid, ?recipients, ?coin_id, "Retrying txn from WAL.");
+                pending.push((uuid, recipients, coin_id, tx));
             } else {
                 producer
                     .send(coin_id)
@@ -112,13 +142,17 @@ impl SimpleFaucet {
             consumer: Mutex::new(consumer),
             metrics,
             wal: Mutex::new(wal),
+            batch: Mutex::new(BatchState::default()),
+            quotas,
+            batch_queue_capacity: DEFAULT_BATCH_QUEUE_CAPACITY,
+            coin_lock: Mutex::new(()),
         };
 
         // Retrying all the pending transactions from the WAL, before continuing.  Ignore return
         // values -- if the executions failed, the pending coins will simply remain in the WAL, and
         // not recycled.
-        futures::future::join_all(pending.into_iter().map(|(uuid, recipient, coin_id, tx)| {
-            faucet.sign_and_execute_txn(uuid, recipient, coin_id, tx)
+        futures::future::join_all(pending.iter().map(|(uuid, recipients, coin_id, tx)| {
+            faucet.sign_and_execute_txn(*uuid, recipients, *coin_id, tx.clone())
         }))
         .await;
 
@@ -151,15 +185,15 @@ impl SimpleFaucet {
     }
 
     /// Pulls a coin from the queue and makes sure it is fit for use (belongs to the faucet, has
-    /// sufficient balance).
-    async fn prepare_gas_coin(&self, total_amount: u64, uuid: Uuid) -> GasCoinResponse {
+    /// sufficient balance to pay `total_amount` and `gas`).
+    async fn prepare_gas_coin(&self, total_amount: u64, gas: u64, uuid: Uuid) -> GasCoinResponse {
         let Some(coin_id) = self.pop_gas_coin(uuid).await else {
             warn!("Failed getting gas coin, try later!");
             return GasCoinResponse::NoGasCoinAvailable;
         };
 
         match self.get_gas_coin(coin_id).await {
-            Ok(Some(gas_coin)) if gas_coin.value() >= total_amount + PAY_SUI_GAS => {
+            Ok(Some(gas_coin)) if gas_coin.value() >= total_amount + gas => {
                 info!(?uuid, ?coin_id, "balance: {}", gas_coin.value());
                 GasCoinResponse::ValidGasCoin(coin_id)
             }
@@ -201,7 +235,7 @@ impl SimpleFaucet {
     async fn sign_and_execute_txn(
         &self,
         uuid: Uuid,
-        recipient: SuiAddress,
+        recipients: &[SuiAddress],
         coin_id: ObjectID,
         tx_data: TransactionData,
     ) -> Result<SuiTransactionResponse, FaucetError> {
@@ -217,7 +251,7 @@ impl SimpleFaucet {
         let tx_digest = *tx.digest();
         info!(
             ?tx_digest,
-            ?recipient,
+            ?recipients,
             ?coin_id,
             ?uuid,
             "PaySui transaction in faucet."
@@ -225,13 +259,13 @@ impl SimpleFaucet {
 
         match timeout(
             Duration::from_secs(300),
-            self.execute_pay_sui_txn_with_retries(&tx, coin_id, recipient, uuid),
+            self.execute_pay_sui_txn_with_retries(&tx, coin_id, recipients, uuid),
         )
         .await
         {
             Err(elapsed) => {
                 warn!(
-                    ?recipient,
+                    ?recipients,
                     ?coin_id,
                     ?uuid,
                     "Failed to execute PaySui transactions in faucet after {elapsed}. Coin will \
@@ -272,37 +306,329 @@ impl SimpleFaucet {
         let number_of_coins = amounts.len();
         let total_amount: u64 = amounts.iter().sum();
 
-        let gas_coin_response = self.prepare_gas_coin(total_amount, uuid).await;
-
-        match gas_coin_response {
-            GasCoinResponse::ValidGasCoin(coin_id) => {
-                let tx_data = self
-                    .build_pay_sui_txn(
-                        coin_id,
-                        self.active_address,
-                        recipient,
-                        amounts,
-                        DEFAULT_GAS_BUDGET,
-                    )
-                    .await
-                    .map_err(FaucetError::internal)?;
-
-                {
-                    // Register the intention to send this transaction before we send it, so that if
-                    // faucet fails or we give up before we get a definite response, we have a
-                    // chance to retry later.
-                    let mut wal = self.wal.lock().await;
-                    wal.reserve(uuid, coin_id, recipient, tx_data.clone())
-                        .map_err(FaucetError::internal)?;
+        let coin_id = self
+            .reserve_gas_coin(total_amount, PAY_SUI_GAS, uuid)
+            .await?;
+        let recipients = std::iter::repeat(recipient).take(amounts.len()).collect();
+        let tx_data = self
+            .build_pay_sui_txn(
+                coin_id,
+                self.active_address,
+                recipients,
+                amounts.to_vec(),
+                DEFAULT_GAS_BUDGET,
+            )
+            .await
+            .map_err(FaucetError::internal)?;
+
+        let response = self
+            .log_and_execute_txn(uuid, &[recipient], coin_id, tx_data)
+            .await?;
+
+        self.check_and_map_transfer_gas_result(response, number_of_coins, &recipient)
+            .await
+    }
+
+    /// Sends `Coin<T>` of `amounts` to `recipient`, where `T` is `coin_type`, paying for gas with a
+    /// coin from the gas coin pool. SUI is only sent from the gas coin pool, with `send`, and coin
+    /// types the faucet has no coins of are not supported.
+    async fn transfer_coins(
+        &self,
+        coin_type: &str,
+        amounts: &[u64],
+        recipient: SuiAddress,
+        uuid: Uuid,
+    ) -> Result<(TransactionDigest, Vec<ObjectID>), FaucetError> {
+        let unsupported = || FaucetError::UnsupportedCoinType(coin_type.to_string());
+        let coin_type = parse_sui_type_tag(coin_type)
+            .map_err(|_| unsupported())?
+            .to_string();
+        if coin_type == sui_coin_type() {
+            return Err(unsupported());
+        }
+
+        let total_amount: u64 = amounts.iter().sum();
+        let _guard = self.coin_lock.lock().await;
+
+        let client = self
+            .wallet
+            .get_client()
+            .await
+            .map_err(|e| FaucetError::Wallet(e.to_string()))?;
+        let balance = client
+            .coin_read_api()
+            .get_balance(self.active_address, Some(coin_type.clone()))
+            .await
+            .map_err(|e| FaucetError::Wallet(e.to_string()))?;
+        if balance.coin_object_count == 0 {
+            return Err(unsupported());
+        }
+        let input_coins = client
+            .coin_read_api()
+            .select_coins(
+                self.active_address,
+                Some(coin_type.clone()),
+                total_amount as u128,
+                None,
+                vec![],
+            )
+            .await
+            .map_err(|e| FaucetError::Wallet(e.to_string()))?
+            .iter()
+            .map(|coin| coin.coin_object_id)
+            .collect();
+
+        let gas_coin_id = self.reserve_gas_coin(0, PAY_SUI_GAS, uuid).await?;
+        let recipients = std::iter::repeat(recipient).take(amounts.len()).collect();
+        let tx_data = client
+            .transaction_builder()
+            .pay(
+                self.active_address,
+                input_coins,
+                recipients,
+                amounts.to_vec(),
+                Some(gas_coin_id),
+                DEFAULT_GAS_BUDGET,
+            )
+            .await
+            .map_err(|e| {
+                FaucetError::internal(format!(
+                    "Failed to build Pay transaction for {coin_type}, with err {e:?}"
+                ))
+            })?;
+
+        let response = self
+            .log_and_execute_txn(uuid, &[recipient], gas_coin_id, tx_data)
+            .await?;
+
+        let coin_ids: Vec<ObjectID> = response
+            .effects
+            .created
+            .iter()
+            .filter(|created| created.owner == Owner::AddressOwner(recipient))
+            .map(|created| created.reference.object_id)
+            .collect();
+        if coin_ids.len() != amounts.len() {
+            return Err(FaucetError::Transfer(format!(
+                "expected {} coins in transaction {:?}, but found {}",
+                amounts.len(),
+                response.effects.transaction_digest,
+                coin_ids.len()
+            )));
+        }
+        Ok((response.effects.transaction_digest, coin_ids))
+    }
+
+    /// Limit the number of requests queued by `batch_send` to `capacity`. Requests are queued in
+    /// memory, so the bound keeps a burst of requests from exhausting it.
+    pub fn with_batch_queue_capacity(mut self, capacity: usize) -> Self {
+        self.batch_queue_capacity = capacity;
+        self
+    }
+
+    /// Enforce the request quotas in `config`, recorded in the write ahead log DB of this faucet.
+    /// By default no request is limited.
+    pub fn with_request_quotas(mut self, config: QuotaConfig) -> Self {
+        self.quotas = Arc::new(RequestQuotas::new(self.wal.get_mut().quota_table(), config));
+        self
+    }
+
+    /// The quotas requests to this faucet are counted against.
+    pub fn request_quotas(&self) -> Arc<RequestQuotas> {
+        self.quotas.clone()
+    }
+
+    /// Pay out the requests queued by `batch_send`, sending one batch of at most
+    /// `max_batch_size` requests every `window`.
+    pub async fn run_batch_sends(&self, window: Duration, max_batch_size: usize) {
+        let mut interval = tokio::time::interval(window);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            self.send_next_batch(max_batch_size).await;
+        }
+    }
+
+    /// Pay out up to `max_batch_size` queued requests in a single PaySui transaction, and record
+    /// the outcome of each of them. The quotas counted by the requests that failed are refunded.
+    async fn send_next_batch(&self, max_batch_size: usize) {
+        let requests = self.next_batch(max_batch_size).await;
+        if requests.is_empty() {
+            return;
+        }
+
+        let batch_id = Uuid::new_v4();
+        info!(?batch_id, "Sending batch of {} requests", requests.len());
+        let result = self.transfer_gases_batch(&requests, batch_id).await;
+        if let Err(e) = &result {
+            warn!(?batch_id, "Failed to send batch: {e:?}");
+        }
+
+        let mut failed = vec![];
+        let mut batch = self.batch.lock().await;
+        let now = Instant::now();
+        while batch.completed.front().map_or(false, |(completed_at, _)| {
+            now.duration_since(*completed_at) >= BATCH_STATUS_TTL
+        }) {
+            let (_, id) = batch.completed.pop_front().unwrap();
+            batch.statuses.remove(&id);
+        }
+
+        for (id, recipient, amounts) in requests {
+            let status = match &result {
+                Ok((digest, sent)) => {
+                    let coin_ids = sent.get(&recipient).map(Vec::as_slice).unwrap_or_default();
+                    if coin_ids.len() == amounts.len() {
+                        BatchSendStatus::Succeeded(FaucetReceipt {
+                            sent: coin_ids
+                                .iter()
+                                .zip(amounts)
+                                .map(|(coin_id, amount)| CoinInfo {
+                                    transfer_tx_digest: *digest,
+                                    amount,
+                                    id: *coin_id,
+                                    coin_type: sui_coin_type(),
+                                })
+                                .collect(),
+                        })
+                    } else {
+                        error!(
+                            uuid = ?id, ?recipient,
+                            "Requested {} coins but got {}",
+                            amounts.len(),
+                            coin_ids.len()
+                        );
+                        BatchSendStatus::Failed(
+                            FaucetError::Transfer(format!(
+                                "expected {} coins in transaction {digest:?}, but found {}",
+                                amounts.len(),
+                                coin_ids.len()
+                            ))
+                            .to_string(),
+                        )
+                    }
                 }
+                Err(e) => BatchSendStatus::Failed(e.to_string()),
+            };
+            let admission = batch.admissions.remove(&id);
+            if matches!(status, BatchSendStatus::Failed(_)) {
+                failed.extend(admission.map(|admission| (id, admission)));
+            }
+            batch.statuses.insert(id, status);
+            batch.completed.push_back((now, id));
+        }
+        drop(batch);
 
-                let response = self
-                    .sign_and_execute_txn(uuid, recipient, coin_id, tx_data)
-                    .await?;
+        for (id, admission) in failed {
+            self.refund(id, admission).await;
+        }
+    }
 
-                self.check_and_map_transfer_gas_result(response, number_of_coins, &recipient)
-                    .await
+    /// Stop counting a request that could not be served against the quotas.
+    async fn refund(&self, id: Uuid, admission: Admission) {
+        if let Err(e) = self.quotas.refund(admission).await {
+            warn!(uuid = ?id, "Failed to refund the request quotas: {e:?}");
+        }
+    }
+
+    /// Take up to `max_batch_size` requests from the queue. A recipient is paid at most once per
+    /// batch, so that the coins created for each request can be told apart by their owner: further
+    /// requests for the same recipient stay queued for a later batch.
+    async fn next_batch(&self, max_batch_size: usize) -> Vec<(Uuid, SuiAddress, Vec<u64>)> {
+        let mut batch = self.batch.lock().await;
+        let mut recipients = HashSet::new();
+        let mut requests = vec![];
+        let mut deferred = vec![];
+        for request in batch.queued.drain(..) {
+            if requests.len() < max_batch_size && recipients.insert(request.1) {
+                requests.push(request);
+            } else {
+                deferred.push(request);
             }
+        }
+        batch.queued = deferred;
+        requests
+    }
+
+    /// Pays all of `requests` in one PaySui transaction, returning its digest and the coins it
+    /// created for each recipient.
+    async fn transfer_gases_batch(
+        &self,
+        requests: &[(Uuid, SuiAddress, Vec<u64>)],
+        batch_id: Uuid,
+    ) -> Result<(TransactionDigest, HashMap<SuiAddress, Vec<ObjectID>>), FaucetError> {
+        let mut recipients = vec![];
+        let mut amounts = vec![];
+        for (_, recipient, request_amounts) in requests {
+            recipients.extend(std::iter::repeat(*recipient).take(request_amounts.len()));
+            amounts.extend_from_slice(request_amounts);
+        }
+        let total_amount: u64 = amounts.iter().sum();
+        let batch_size = requests.len() as u64;
+
+        let coin_id = self
+            .reserve_gas_coin(total_amount, PAY_SUI_GAS * batch_size, batch_id)
+            .await?;
+        let tx_data = self
+            .build_pay_sui_txn(
+                coin_id,
+                self.active_address,
+                recipients,
+                amounts,
+                DEFAULT_GAS_BUDGET * batch_size,
+            )
+            .await
+            .map_err(FaucetError::internal)?;
+
+        let batch_recipients: Vec<_> = requests
+            .iter()
+            .map(|(_, recipient, _)| *recipient)
+            .collect();
+        let response = self
+            .log_and_execute_txn(batch_id, &batch_recipients, coin_id, tx_data)
+            .await?;
+
+        let mut sent: HashMap<SuiAddress, Vec<ObjectID>> = HashMap::new();
+        for created in response.effects.created {
+            if let Owner::AddressOwner(owner) = created.owner {
+                sent.entry(owner)
+                    .or_default()
+                    .push(created.reference.object_id);
+            }
+        }
+        Ok((response.effects.transaction_digest, sent))
+    }
+
+    /// Register the intention to send the transaction in `tx_data` before sending it, so that if
+    /// faucet fails or we give up before we get a definite response, we have a chance to retry
+    /// later.
+    async fn log_and_execute_txn(
+        &self,
+        uuid: Uuid,
+        recipients: &[SuiAddress],
+        coin_id: ObjectID,
+        tx_data: TransactionData,
+    ) -> Result<SuiTransactionResponse, FaucetError> {
+        {
+            let mut wal = self.wal.lock().await;
+            wal.reserve(uuid, coin_id, recipients.to_vec(), tx_data.clone())
+                .map_err(FaucetError::internal)?;
+        }
+
+        self.sign_and_execute_txn(uuid, recipients, coin_id, tx_data)
+            .await
+    }
+
+    /// Pulls a coin fit to pay `total_amount` and `gas` from the queue, discarding coins that turn
+    /// out to be unfit.
+    async fn reserve_gas_coin(
+        &self,
+        total_amount: u64,
+        gas: u64,
+        uuid: Uuid,
+    ) -> Result<ObjectID, FaucetError> {
+        match self.prepare_gas_coin(total_amount, gas, uuid).await {
+            GasCoinResponse::ValidGasCoin(coin_id) => Ok(coin_id),
 
             GasCoinResponse::UnknownGasCoin(coin_id) => {
                 self.recycle_gas_coin(coin_id, uuid).await;
@@ -345,20 +671,22 @@ impl SimpleFaucet {
         &self,
         tx: &VerifiedTransaction,
         coin_id: ObjectID,
-        recipient: SuiAddress,
+        recipients: &[SuiAddress],
         uuid: Uuid,
     ) -> SuiTransactionResponse {
         let mut retry_delay = Duration::from_millis(500);
 
         loop {
-            let res = self.execute_pay_sui_txn(tx, coin_id, recipient, uuid).await;
+            let res = self
+                .execute_pay_sui_txn(tx, coin_id, recipients, uuid)
+                .await;
 
             if let Ok(res) = res {
                 return res;
             }
 
             info!(
-                ?recipient,
+                ?recipients,
                 ?coin_id,
                 ?uuid,
                 ?retry_delay,
@@ -375,7 +703,7 @@ impl SimpleFaucet {
         &self,
         tx: &VerifiedTransaction,
         coin_id: ObjectID,
-        recipient: SuiAddress,
+        recipients: &[SuiAddress],
         uuid: Uuid,
     ) -> Result<SuiTransactionResponse, anyhow::Error> {
         self.metrics.current_executions_in_flight.inc();
@@ -395,7 +723,7 @@ impl SimpleFaucet {
             .tap_err(|e| {
                 error!(
                     ?tx_digest,
-                    ?recipient,
+                    ?recipients,
                     ?coin_id,
                     ?uuid,
                     "Transfer Transaction failed: {:?}",
@@ -408,16 +736,14 @@ impl SimpleFaucet {
         &self,
         coin_id: ObjectID,
         signer: SuiAddress,
-        recipient: SuiAddress,
-        amounts: &[u64],
+        recipients: Vec<SuiAddress>,
+        amounts: Vec<u64>,
         budget: u64,
     ) -> Result<TransactionData, anyhow::Error> {
-        let recipients: Vec<SuiAddress> =
-            std::iter::repeat(recipient).take(amounts.len()).collect();
         let client = self.wallet.get_client().await?;
         client
             .transaction_builder()
-            .pay_sui(signer, vec![coin_id], recipients, amounts.to_vec(), budget)
+            .pay_sui(signer, vec![coin_id], recipients, amounts, budget)
             .await
             .map_err(|e| {
                 anyhow::anyhow!(
@@ -524,16 +850,79 @@ impl Faucet for SimpleFaucet {
                     transfer_tx_digest: digest,
                     amount: sent_amount,
                     id: *coin_id,
+                    coin_type: sui_coin_type(),
                 })
                 .collect(),
         })
     }
+
+    async fn send_coins(
+        &self,
+        id: Uuid,
+        recipient: SuiAddress,
+        coin_type: &str,
+        amounts: &[u64],
+    ) -> Result<FaucetReceipt, FaucetError> {
+        info!(?recipient, uuid = ?id, coin_type, "Getting faucet coin request");
+
+        let (digest, coin_ids) = self
+            .transfer_coins(coin_type, amounts, recipient, id)
+            .await?;
+
+        info!(uuid = ?id, ?recipient, ?digest, coin_type, "Pay txn succeeded");
+        Ok(FaucetReceipt {
+            sent: coin_ids
+                .iter()
+                .zip(amounts)
+                .map(|(coin_id, amount)| CoinInfo {
+                    transfer_tx_digest: digest,
+                    amount: *amount,
+                    id: *coin_id,
+                    coin_type: coin_type.to_string(),
+                })
+                .collect(),
+        })
+    }
+
+    async fn batch_send(
+        &self,
+        id: Uuid,
+        recipient: SuiAddress,
+        amounts: &[u64],
+        admission: Admission,
+    ) -> Result<BatchSendStatus, FaucetError> {
+        info!(?recipient, uuid = ?id, "Queueing batch faucet request");
+        let mut batch = self.batch.lock().await;
+        if batch.queued.len() >= self.batch_queue_capacity {
+            drop(batch);
+            self.refund(id, admission).await;
+            return Err(FaucetError::BatchQueueFull);
+        }
+        batch.queued.push((id, recipient, amounts.to_vec()));
+        batch.admissions.insert(id, admission);
+        batch.statuses.insert(id, BatchSendStatus::Pending);
+        Ok(BatchSendStatus::Pending)
+    }
+
+    async fn get_batch_send_status(&self, id: Uuid) -> Result<BatchSendStatus, FaucetError> {
+        self.batch
+            .lock()
+            .await
+            .statuses
+            .get(&id)
+            .cloned()
+            .ok_or_else(|| FaucetError::UnknownBatchRequest(id.to_string()))
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use move_package::BuildConfig as MoveBuildConfig;
+    use std::path::PathBuf;
+    use std::str::FromStr;
     use sui::client_commands::{SuiClientCommandResult, SuiClientCommands};
-    use sui_json_rpc_types::SuiExecutionStatus;
+    use sui_json::SuiJsonValue;
+    use sui_json_rpc_types::{SuiEvent, SuiExecutionStatus};
     use test_utils::network::TestClusterBuilder;
 
     use super::*;
@@ -762,6 +1151,229 @@ mod tests {
         assert!(candidates.get(&tiny_coin_id).is_none());
     }
 
+    #[tokio::test]
+    async fn test_send_coins() {
+        let test_cluster = TestClusterBuilder::new().build().await.unwrap();
+        let address = test_cluster.get_address_0();
+        let mut context = test_cluster.wallet;
+        let (_, coin_type) = publish_and_mint_managed_coin(&mut context, address, 100).await;
+        let gases = get_current_gases(address, &mut context).await;
+        let gases = HashSet::from_iter(gases.into_iter().map(|gas| *gas.id()));
+
+        let tmp = tempfile::tempdir().unwrap();
+        let prom_registry = Registry::new();
+        let mut faucet = SimpleFaucet::new(context, &prom_registry, &tmp.path().join("faucet.wal"))
+            .await
+            .unwrap();
+
+        let recipient = SuiAddress::random_for_testing_only();
+        let FaucetReceipt { sent } = faucet
+            .send_coins(Uuid::new_v4(), recipient, &coin_type, &[10, 20])
+            .await
+            .unwrap();
+        let mut amounts: Vec<u64> = sent.iter().map(|coin| coin.amount).collect();
+        amounts.sort_unstable();
+        assert_eq!(amounts, vec![10, 20]);
+        assert!(sent.iter().all(|coin| coin.coin_type == coin_type));
+
+        let client = faucet.wallet.get_client().await.unwrap();
+        let balance = client
+            .coin_read_api()
+            .get_balance(recipient, Some(coin_type.clone()))
+            .await
+            .unwrap();
+        assert_eq!(balance.total_balance, 30);
+        let remaining = client
+            .coin_read_api()
+            .get_balance(address, Some(coin_type.clone()))
+            .await
+            .unwrap();
+        assert_eq!(remaining.total_balance, 70);
+
+        // Gas was paid with a coin of the gas coin pool, not with the coins being sent, and that
+        // coin is back in the pool.
+        let response = client
+            .read_api()
+            .get_transaction(sent[0].transfer_tx_digest)
+            .await
+            .unwrap();
+        let gas_coin = response.effects.gas_object.reference.object_id;
+        assert!(gases.contains(&gas_coin));
+        assert!(!sent.iter().any(|coin| coin.id == gas_coin));
+        let candidates = faucet.drain_gas_queue(gases.len()).await;
+        assert_eq!(candidates, gases);
+    }
+
+    #[tokio::test]
+    async fn test_send_unsupported_coins() {
+        let test_cluster = TestClusterBuilder::new().build().await.unwrap();
+        let address = test_cluster.get_address_0();
+        let mut context = test_cluster.wallet;
+        let (package, _) = publish_and_mint_managed_coin(&mut context, address, 100).await;
+
+        let tmp = tempfile::tempdir().unwrap();
+        let prom_registry = Registry::new();
+        let faucet = SimpleFaucet::new(context, &prom_registry, &tmp.path().join("faucet.wal"))
+            .await
+            .unwrap();
+
+        let recipient = SuiAddress::random_for_testing_only();
+        // SUI is only sent from the gas coin pool, and the faucet has no coins of the other types.
+        for coin_type in [
+            sui_coin_type(),
+            format!("{package}::managed::UNKNOWN"),
+            "not a type".to_string(),
+        ] {
+            assert!(matches!(
+                faucet
+                    .send_coins(Uuid::new_v4(), recipient, &coin_type, &[10])
+                    .await,
+                Err(FaucetError::UnsupportedCoinType(_))
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_batch_send() {
+        let test_cluster = TestClusterBuilder::new().build().await.unwrap();
+        let address = test_cluster.get_address_0();
+        let mut context = test_cluster.wallet;
+        let gases = get_current_gases(address, &mut context).await;
+        let gases = HashSet::from_iter(gases.into_iter().map(|gas| *gas.id()));
+
+        let tmp = tempfile::tempdir().unwrap();
+        let prom_registry = Registry::new();
+        let mut faucet = SimpleFaucet::new(context, &prom_registry, &tmp.path().join("faucet.wal"))
+            .await
+            .unwrap()
+            .with_batch_queue_capacity(6);
+
+        let amounts = vec![1, 2, 3];
+        let recipients: Vec<_> = (0..5)
+            .map(|_| SuiAddress::random_for_testing_only())
+            .collect();
+        let quotas = faucet.request_quotas();
+        let mut ids = vec![];
+        for recipient in &recipients {
+            let id = Uuid::new_v4();
+            let admission = quotas.admit(*recipient, None).await.unwrap();
+            faucet
+                .batch_send(id, *recipient, &amounts, admission)
+                .await
+                .unwrap();
+            ids.push(id);
+        }
+
+        // A second request for the first recipient has to wait for the next batch.
+        let repeat_id = Uuid::new_v4();
+        let admission = quotas.admit(recipients[0], None).await.unwrap();
+        faucet
+            .batch_send(repeat_id, recipients[0], &amounts, admission)
+            .await
+            .unwrap();
+
+        // The queue is full.
+        let rejected_id = Uuid::new_v4();
+        let admission = quotas.admit(recipients[1], None).await.unwrap();
+        assert!(matches!(
+            faucet
+                .batch_send(rejected_id, recipients[1], &amounts, admission)
+                .await,
+            Err(FaucetError::BatchQueueFull)
+        ));
+        assert!(matches!(
+            faucet.get_batch_send_status(rejected_id).await,
+            Err(FaucetError::UnknownBatchRequest(_))
+        ));
+
+        faucet.send_next_batch(100).await;
+
+        let mut digests = HashSet::new();
+        for id in &ids {
+            let BatchSendStatus::Succeeded(FaucetReceipt { sent }) =
+                faucet.get_batch_send_status(*id).await.unwrap() else {
+                panic!("Batch request {id} did not succeed");
+            };
+            let mut actual_amounts: Vec<u64> = sent.iter().map(|c| c.amount).collect();
+            actual_amounts.sort_unstable();
+            assert_eq!(actual_amounts, amounts);
+            digests.extend(sent.iter().map(|c| c.transfer_tx_digest));
+        }
+        // All requests were served by the same transaction.
+        assert_eq!(digests.len(), 1);
+
+        assert!(matches!(
+            faucet.get_batch_send_status(repeat_id).await.unwrap(),
+            BatchSendStatus::Pending
+        ));
+        faucet.send_next_batch(100).await;
+        assert!(matches!(
+            faucet.get_batch_send_status(repeat_id).await.unwrap(),
+            BatchSendStatus::Succeeded(_)
+        ));
+
+        assert!(matches!(
+            faucet.get_batch_send_status(Uuid::new_v4()).await,
+            Err(FaucetError::UnknownBatchRequest(_))
+        ));
+
+        // The gas coins used by the batches are back in the queue.
+        let candidates = faucet.drain_gas_queue(gases.len()).await;
+        assert_eq!(candidates, gases);
+    }
+
+    #[tokio::test]
+    async fn test_batch_send_refunds_quotas() {
+        let test_cluster = TestClusterBuilder::new().build().await.unwrap();
+        let address = test_cluster.get_address_0();
+        let mut context = test_cluster.wallet;
+        let gas_count = get_current_gases(address, &mut context).await.len();
+
+        let tmp = tempfile::tempdir().unwrap();
+        let prom_registry = Registry::new();
+        let mut faucet = SimpleFaucet::new(context, &prom_registry, &tmp.path().join("faucet.wal"))
+            .await
+            .unwrap()
+            .with_batch_queue_capacity(1)
+            .with_request_quotas(QuotaConfig {
+                max_requests_per_recipient: Some(1),
+                ..Default::default()
+            });
+        let quotas = faucet.request_quotas();
+
+        let queued = SuiAddress::random_for_testing_only();
+        let id = Uuid::new_v4();
+        let admission = quotas.admit(queued, None).await.unwrap();
+        faucet
+            .batch_send(id, queued, &[1], admission)
+            .await
+            .unwrap();
+
+        // A request rejected because the queue is full is refunded right away.
+        let rejected = SuiAddress::random_for_testing_only();
+        let admission = quotas.admit(rejected, None).await.unwrap();
+        assert!(matches!(
+            faucet
+                .batch_send(Uuid::new_v4(), rejected, &[1], admission)
+                .await,
+            Err(FaucetError::BatchQueueFull)
+        ));
+        quotas.admit(rejected, None).await.unwrap();
+
+        // Without gas coins the batch fails, and its request is refunded.
+        assert!(matches!(
+            quotas.admit(queued, None).await,
+            Err(FaucetError::TooManyRequests { .. })
+        ));
+        faucet.drain_gas_queue(gas_count).await;
+        faucet.send_next_batch(100).await;
+        assert!(matches!(
+            faucet.get_batch_send_status(id).await.unwrap(),
+            BatchSendStatus::Failed(_)
+        ));
+        quotas.admit(queued, None).await.unwrap();
+    }
+
     async fn test_basic_interface(faucet: &impl Faucet) {
         let recipient = SuiAddress::random_for_testing_only();
         let amounts = vec![1, 2, 3];
@@ -775,6 +1387,72 @@ mod tests {
         assert_eq!(actual_amounts, amounts);
     }
 
+    /// Publishes the `fungible_tokens` example package and mints `amount` of its `MANAGED` coin to
+    /// `address`, returning the package and the coin type.
+    async fn publish_and_mint_managed_coin(
+        context: &mut WalletContext,
+        address: SuiAddress,
+        amount: u64,
+    ) -> (ObjectID, String) {
+        let package_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../../sui_programmability/examples/fungible_tokens");
+        let response = match (SuiClientCommands::Publish {
+            package_path,
+            build_config: MoveBuildConfig::default(),
+            gas: None,
+            gas_budget: 20_000,
+            skip_dependency_verification: false,
+            with_unpublished_dependencies: false,
+        })
+        .execute(context)
+        .await
+        .unwrap()
+        {
+            SuiClientCommandResult::Publish(response) => response,
+            other => panic!(
+                "Expect SuiClientCommandResult::Publish, but got {:?}",
+                other
+            ),
+        };
+        let package = response
+            .effects
+            .events
+            .iter()
+            .find_map(|event| match event {
+                SuiEvent::Publish { package_id, .. } => Some(*package_id),
+                _ => None,
+            })
+            .unwrap();
+        // The treasury cap is the only object created for the sender.
+        let treasury_cap = response
+            .effects
+            .created
+            .iter()
+            .find(|created| created.owner == Owner::AddressOwner(address))
+            .unwrap()
+            .reference
+            .object_id;
+
+        SuiClientCommands::Call {
+            package,
+            module: "managed".to_string(),
+            function: "mint".to_string(),
+            type_args: vec![],
+            args: vec![
+                SuiJsonValue::from_object_id(treasury_cap),
+                SuiJsonValue::from_str(&format!("\"{amount}\"")).unwrap(),
+                SuiJsonValue::from_str(&format!("\"{address}\"")).unwrap(),
+            ],
+            gas: None,
+            gas_budget: 10_000,
+        }
+        .execute(context)
+        .await
+        .unwrap();
+
+        (package, format!("{package}::managed::MANAGED"))
+    }
+
     async fn get_current_gases(address: SuiAddress, context: &mut WalletContext) -> Vec<GasCoin> {
         // Get the latest list of gas
         let results = SuiClientCommands::Gas {
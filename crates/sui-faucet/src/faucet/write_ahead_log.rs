@@ -13,6 +13,8 @@ use typed_store::Map;
 use typed_store_derive::DBMapUtils;
 use uuid::Uuid;
 
+use crate::QuotaKey;
+
 /// Persistent log of transactions paying out sui from the faucet, keyed by the coin serving the
 /// request.  Transactions are expected to be written to the log before they are sent to full-node,
 /// and removed after receiving a response back, before the coin becomes available for subsequent
@@ -20,18 +22,45 @@ use uuid::Uuid;
 ///
 /// This allows the faucet to go down and back up, and not forget which requests were in-flight that
 /// it needs to confirm succeeded or failed.
+///
+/// The requests counted against the faucet's [`RequestQuotas`](crate::RequestQuotas) are kept in
+/// the same DB.
 #[derive(DBMapUtils)]
 pub struct WriteAheadLog {
-    log: DBMap<ObjectID, Entry>,
+    /// Entries written by faucets that paid a single recipient per transaction. No longer written
+    /// to, but still read so that the transactions in flight when the faucet was upgraded are
+    /// retried.
+    log: DBMap<ObjectID, SingleRecipientEntry>,
+    batch_log: DBMap<ObjectID, Entry>,
+    quotas: DBMap<QuotaKey, Vec<u64>>,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct Entry {
     pub uuid: uuid::Bytes,
-    pub recipient: SuiAddress,
+    /// Every recipient paid by `tx`, several for batched requests.
+    pub recipients: Vec<SuiAddress>,
     pub tx: TransactionData,
 }
 
+/// Layout of the entries in the `log` table.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+struct SingleRecipientEntry {
+    uuid: uuid::Bytes,
+    recipient: SuiAddress,
+    tx: TransactionData,
+}
+
+impl From<SingleRecipientEntry> for Entry {
+    fn from(entry: SingleRecipientEntry) -> Self {
+        Self {
+            uuid: entry.uuid,
+            recipients: vec![entry.recipient],
+            tx: entry.tx,
+        }
+    }
+}
+
 impl WriteAheadLog {
     pub(crate) fn open(path: &Path) -> Self {
         Self::open_tables_read_write(
@@ -42,16 +71,16 @@ impl WriteAheadLog {
         )
     }
 
-    /// Mark `coin` as reserved for transaction `tx` sending coin to `recipient`. Fails if `coin` is
+    /// Mark `coin` as reserved for transaction `tx` sending coins to `recipients`. Fails if `coin` is
     /// already in the WAL pointing to an existing transaction.
     pub(crate) fn reserve(
         &mut self,
         uuid: Uuid,
         coin: ObjectID,
-        recipient: SuiAddress,
+        recipients: Vec<SuiAddress>,
         tx: TransactionData,
     ) -> Result<(), TypedStoreError> {
-        if self.log.contains_key(&coin)? {
+        if self.batch_log.contains_key(&coin)? || self.log.contains_key(&coin)? {
             // Don't permit multiple writes against the same coin
             // TODO: Use a better error type than `TypedStoreError`.
             return Err(TypedStoreError::SerializationError(format!(
@@ -60,11 +89,11 @@ impl WriteAheadLog {
         }
 
         let uuid = *uuid.as_bytes();
-        self.log.insert(
+        self.batch_log.insert(
             &coin,
             &Entry {
                 uuid,
-                recipient,
+                recipients,
                 tx,
             },
         )
@@ -74,13 +103,25 @@ impl WriteAheadLog {
     /// pending transaction exists, `Ok(None)` if not, and `Err(_)` if there was an internal error
     /// accessing the WAL.
     pub(crate) fn reclaim(&self, coin: ObjectID) -> Result<Option<Entry>, TypedStoreError> {
-        self.log.get(&coin)
+        if let Some(entry) = self.batch_log.get(&coin)? {
+            return Ok(Some(entry));
+        }
+        Ok(self.log.get(&coin)?.map(Entry::from))
+    }
+
+    /// The table of the requests served to each quota key.
+    pub(crate) fn quota_table(&self) -> DBMap<QuotaKey, Vec<u64>> {
+        self.quotas.clone()
     }
 
     /// Indicate that the transaction in flight for `coin` has landed, and the entry in the WAL can
     /// be removed.
     pub(crate) fn commit(&mut self, coin: ObjectID) -> Result<(), TypedStoreError> {
-        self.log.remove(&coin)
+        self.batch_log
+            .batch()
+            .delete_batch(&self.batch_log, [coin])?
+            .delete_batch(&self.log, [coin])?
+            .write()
     }
 }
 
@@ -99,7 +140,7 @@ mod tests {
         let coin = random_object_ref();
         let (recv, tx) = random_request(coin);
 
-        assert!(wal.reserve(uuid, coin.0, recv, tx.clone()).is_ok());
+        assert!(wal.reserve(uuid, coin.0, vec![recv], tx.clone()).is_ok());
 
         // Reclaim once
         let Some(entry) = wal.reclaim(coin.0).unwrap() else {
@@ -107,7 +148,7 @@ mod tests {
         };
 
         assert_eq!(uuid, Uuid::from_bytes(entry.uuid));
-        assert_eq!(recv, entry.recipient);
+        assert_eq!(vec![recv], entry.recipients);
         assert_eq!(tx, entry.tx);
 
         // Reclaim again, should still be there.
@@ -116,7 +157,7 @@ mod tests {
         };
 
         assert_eq!(uuid, Uuid::from_bytes(entry.uuid));
-        assert_eq!(recv, entry.recipient);
+        assert_eq!(vec![recv], entry.recipients);
         assert_eq!(tx, entry.tx);
     }
 
@@ -131,11 +172,11 @@ mod tests {
         let (recv1, tx1) = random_request(coin);
 
         // First write goes through
-        wal.reserve(uuid, coin.0, recv0, tx0).unwrap();
+        wal.reserve(uuid, coin.0, vec![recv0], tx0).unwrap();
 
         // Second write fails because it tries to write to the same coin
         assert!(matches!(
-            wal.reserve(uuid, coin.0, recv1, tx1),
+            wal.reserve(uuid, coin.0, vec![recv1], tx1),
             Err(TypedStoreError::SerializationError(_)),
         ));
     }
@@ -149,7 +190,7 @@ mod tests {
         let coin = random_object_ref();
         let (recv, tx) = random_request(coin);
 
-        wal.reserve(uuid, coin.0, recv, tx.clone()).unwrap();
+        wal.reserve(uuid, coin.0, vec![recv], tx.clone()).unwrap();
 
         // Reclaim to show that the entry is there
         let Some(entry) = wal.reclaim(coin.0).unwrap() else {
@@ -157,7 +198,7 @@ mod tests {
         };
 
         assert_eq!(uuid, Uuid::from_bytes(entry.uuid));
-        assert_eq!(recv, entry.recipient);
+        assert_eq!(vec![recv], entry.recipients);
         assert_eq!(tx, entry.tx);
 
         // Commit the transaction, which removes it from the log.
@@ -178,13 +219,63 @@ mod tests {
         let (recv1, tx1) = random_request(coin);
 
         // Write the transaction
-        wal.reserve(uuid, coin.0, recv0, tx0).unwrap();
+        wal.reserve(uuid, coin.0, vec![recv0], tx0).unwrap();
 
         // Commit the transaction, which removes it from the log.
         wal.commit(coin.0).unwrap();
 
         // Write a fresh transaction, which should now pass
-        wal.reserve(uuid, coin.0, recv1, tx1).unwrap();
+        wal.reserve(uuid, coin.0, vec![recv1], tx1).unwrap();
+    }
+
+    /// The WAL as written by faucets paying a single recipient per transaction.
+    #[derive(DBMapUtils)]
+    struct SingleRecipientWriteAheadLog {
+        log: DBMap<ObjectID, SingleRecipientEntry>,
+    }
+
+    #[tokio::test]
+    async fn reopen_single_recipient_log() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("wal");
+
+        let uuid = Uuid::new_v4();
+        let coin = random_object_ref();
+        let (recv, tx) = random_request(coin);
+        {
+            let old = SingleRecipientWriteAheadLog::open_tables_read_write(
+                path.clone(),
+                typed_store::rocks::MetricConf::default(),
+                None,
+                None,
+            );
+            let entry = SingleRecipientEntry {
+                uuid: *uuid.as_bytes(),
+                recipient: recv,
+                tx: tx.clone(),
+            };
+            old.log.insert(&coin.0, &entry).unwrap();
+        }
+
+        let mut wal = WriteAheadLog::open(&path);
+        let Some(entry) = wal.reclaim(coin.0).unwrap() else {
+            panic!("Entry not found for {}", coin.0);
+        };
+
+        assert_eq!(uuid, Uuid::from_bytes(entry.uuid));
+        assert_eq!(vec![recv], entry.recipients);
+        assert_eq!(tx, entry.tx);
+
+        // The coin stays reserved until the old entry is committed.
+        let (recv1, tx1) = random_request(coin);
+        assert!(matches!(
+            wal.reserve(uuid, coin.0, vec![recv1], tx1.clone()),
+            Err(TypedStoreError::SerializationError(_)),
+        ));
+
+        wal.commit(coin.0).unwrap();
+        assert_eq!(Ok(None), wal.reclaim(coin.0));
+        wal.reserve(uuid, coin.0, vec![recv1], tx1).unwrap();
     }
 
     fn random_request(coin: ObjectRef) -> (SuiAddress, TransactionData) {
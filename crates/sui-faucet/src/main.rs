@@ -1,26 +1,35 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use anyhow::anyhow;
 use axum::{
     error_handling::HandleErrorLayer,
-    http::StatusCode,
-    response::IntoResponse,
+    extract::{ConnectInfo, Path},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
     routing::{get, post},
     BoxError, Extension, Json, Router,
 };
 use clap::Parser;
 use http::Method;
 use mysten_metrics::spawn_monitored_task;
+use serde::Serialize;
 use std::{
     borrow::Cow,
+    collections::HashSet,
     net::{IpAddr, Ipv4Addr, SocketAddr},
+    str::FromStr,
     sync::Arc,
     time::Duration,
 };
 use std::{env, path::PathBuf};
 use sui::client_commands::WalletContext;
 use sui_config::{sui_config_dir, SUI_CLIENT_CONFIG};
-use sui_faucet::{Faucet, FaucetRequest, FaucetResponse, RequestMetricsLayer, SimpleFaucet};
+use sui_faucet::{
+    sui_coin_type, Admission, BatchFaucetResponse, BatchStatusFaucetResponse, Faucet, FaucetError,
+    FaucetRequest, FaucetResponse, QuotaConfig, RequestMetricsLayer, RequestQuotas, SimpleFaucet,
+};
+use sui_types::{base_types::SuiAddress, parse_sui_type_tag};
 use tower::{limit::RateLimitLayer, ServiceBuilder};
 use tower_http::cors::{Any, CorsLayer};
 use tracing::{info, warn};
@@ -58,10 +67,97 @@ struct FaucetConfig {
 
     #[clap(long)]
     write_ahead_log: PathBuf,
+
+    /// Maximum number of requests served to a recipient address within the quota window.
+    /// Unlimited if unset.
+    #[clap(long)]
+    max_requests_per_recipient: Option<u32>,
+
+    /// Maximum number of requests served to a client IP within the quota window. Unlimited if
+    /// unset.
+    #[clap(long)]
+    max_requests_per_ip: Option<u32>,
+
+    #[clap(long, default_value_t = 24 * 60 * 60)]
+    quota_window_secs: u64,
+
+    /// Recipient address exempt from the per-recipient quota. Can be repeated.
+    #[clap(long = "allowlisted-recipient")]
+    allowlisted_recipients: Vec<SuiAddress>,
+
+    /// Client IP exempt from the per-IP quota. Can be repeated.
+    #[clap(long = "allowlisted-ip")]
+    allowlisted_ips: Vec<IpAddr>,
+
+    /// Take the client IP from the first `X-Forwarded-For` entry instead of the connection, for
+    /// faucets running behind a proxy.
+    #[clap(long)]
+    trust_x_forwarded_for: bool,
+
+    /// Serve requests to `/v1/gas` in batches, paying out all requests received within a batch
+    /// window in a single transaction. Their outcome is polled from `/v1/status/<task>`.
+    #[clap(long)]
+    batch_enabled: bool,
+
+    #[clap(long, default_value_t = 1000)]
+    batch_request_window_ms: u64,
+
+    #[clap(long, default_value_t = 100)]
+    max_batch_size: usize,
+
+    /// Maximum number of batch requests waiting for a batch. Requests beyond it are rejected with
+    /// 503 Service Unavailable.
+    #[clap(long, default_value_t = 10_000)]
+    max_queued_batch_requests: usize,
+
+    /// Coin type other than SUI that the faucet sends, and the amount it sends per request, as
+    /// `<COIN_TYPE>=<AMOUNT>`, e.g. `0x42::usdc::USDC=1000000`. Can be repeated.
+    #[clap(long = "coin")]
+    coins: Vec<CoinConfig>,
+}
+
+impl FaucetConfig {
+    /// The configuration of the coins of type `coin_type`, or `None` if `coin_type` is SUI, which
+    /// is sent as gas coins.
+    fn coin(&self, coin_type: &str) -> Result<Option<&CoinConfig>, FaucetError> {
+        let unsupported = || FaucetError::UnsupportedCoinType(coin_type.to_string());
+        let coin_type = parse_sui_type_tag(coin_type)
+            .map_err(|_| unsupported())?
+            .to_string();
+        if coin_type == sui_coin_type() {
+            return Ok(None);
+        }
+        self.coins
+            .iter()
+            .find(|coin| coin.coin_type == coin_type)
+            .map(Some)
+            .ok_or_else(unsupported)
+    }
+}
+
+#[derive(Clone, Debug)]
+struct CoinConfig {
+    coin_type: String,
+    amount: u64,
+}
+
+impl FromStr for CoinConfig {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (coin_type, amount) = s
+            .rsplit_once('=')
+            .ok_or_else(|| anyhow!("expected <COIN_TYPE>=<AMOUNT>, got `{s}`"))?;
+        Ok(Self {
+            coin_type: parse_sui_type_tag(coin_type)?.to_string(),
+            amount: amount.parse()?,
+        })
+    }
 }
 
 struct AppState<F = SimpleFaucet> {
     faucet: F,
+    quotas: Arc<RequestQuotas>,
     config: FaucetConfig,
     // TODO: add counter
 }
@@ -89,6 +185,10 @@ async fn main() -> Result<(), anyhow::Error> {
         max_request_per_second,
         wallet_client_timeout_secs,
         ref write_ahead_log,
+        batch_enabled,
+        batch_request_window_ms,
+        max_batch_size,
+        max_queued_batch_requests,
         ..
     } = config;
 
@@ -99,42 +199,69 @@ async fn main() -> Result<(), anyhow::Error> {
     let registry_service = sui_node::metrics::start_prometheus_server(prom_binding);
     let prometheus_registry = registry_service.default_registry();
 
+    let faucet = SimpleFaucet::new(context, &prometheus_registry, write_ahead_log)
+        .await
+        .unwrap()
+        .with_batch_queue_capacity(max_queued_batch_requests)
+        .with_request_quotas(QuotaConfig {
+            max_requests_per_recipient: config.max_requests_per_recipient,
+            max_requests_per_ip: config.max_requests_per_ip,
+            window: Duration::from_secs(config.quota_window_secs),
+            allowlisted_recipients: HashSet::from_iter(config.allowlisted_recipients.clone()),
+            allowlisted_ips: HashSet::from_iter(config.allowlisted_ips.clone()),
+        });
+    let quotas = faucet.request_quotas();
+
     let app_state = Arc::new(AppState {
-        faucet: SimpleFaucet::new(context, &prometheus_registry, write_ahead_log)
-            .await
-            .unwrap(),
+        faucet,
+        quotas,
         config,
     });
 
+    if batch_enabled {
+        let state = app_state.clone();
+        let window = Duration::from_millis(batch_request_window_ms);
+        info!("Sending batched requests every {window:?}, at most {max_batch_size} at a time.");
+        spawn_monitored_task!(
+            async move { state.faucet.run_batch_sends(window, max_batch_size).await }
+        );
+    }
+
     // TODO: restrict access if needed
     let cors = CorsLayer::new()
         .allow_methods(vec![Method::GET, Method::POST])
         .allow_headers(Any)
         .allow_origin(Any);
 
-    let app = Router::new()
+    let mut app = Router::new()
         .route("/", get(health))
-        .route("/gas", post(request_gas))
-        .layer(
-            ServiceBuilder::new()
-                .layer(HandleErrorLayer::new(handle_error))
-                .layer(RequestMetricsLayer::new(&prometheus_registry))
-                .layer(cors)
-                .load_shed()
-                .buffer(request_buffer_size)
-                .layer(RateLimitLayer::new(
-                    max_request_per_second,
-                    Duration::from_secs(1),
-                ))
-                .concurrency_limit(max_concurrency)
-                .layer(Extension(app_state))
-                .into_inner(),
-        );
+        .route("/gas", post(request_gas));
+    if batch_enabled {
+        app = app
+            .route("/v1/gas", post(batch_request_gas))
+            .route("/v1/status/:task_id", get(request_status));
+    }
+
+    let app = app.layer(
+        ServiceBuilder::new()
+            .layer(HandleErrorLayer::new(handle_error))
+            .layer(RequestMetricsLayer::new(&prometheus_registry))
+            .layer(cors)
+            .load_shed()
+            .buffer(request_buffer_size)
+            .layer(RateLimitLayer::new(
+                max_request_per_second,
+                Duration::from_secs(1),
+            ))
+            .concurrency_limit(max_concurrency)
+            .layer(Extension(app_state))
+            .into_inner(),
+    );
 
     let addr = SocketAddr::new(IpAddr::V4(host_ip), port);
     info!("listening on {}", addr);
     axum::Server::bind(&addr)
-        .serve(app.into_make_service())
+        .serve(app.into_make_service_with_connect_info::<SocketAddr>())
         .await?;
     Ok(())
 }
@@ -147,24 +274,72 @@ async fn health() -> &'static str {
 /// handler for all the request_gas requests
 async fn request_gas(
     Extension(state): Extension<Arc<AppState>>,
+    ConnectInfo(connection): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
     Json(payload): Json<FaucetRequest>,
 ) -> impl IntoResponse {
     // ID for traceability
     let id = Uuid::new_v4();
-    info!(uuid = ?id, "Got new gas request.");
+    let ip = client_ip(&headers, connection, state.config.trust_x_forwarded_for);
+    info!(uuid = ?id, %ip, "Got new gas request.");
     let result = match payload {
         FaucetRequest::FixedAmountRequest(requests) => {
+            let admission = match state.quotas.admit(requests.recipient, Some(ip)).await {
+                Ok(admission) => admission,
+                Err(e) => return quota_error_response::<FaucetResponse>(id, e),
+            };
+
             // We spawn a tokio task for this such that connection drop will not interrupt
             // it and impact the reclycing of coins
             spawn_monitored_task!(async move {
-                state
+                let result = state
                     .faucet
                     .send(
                         id,
                         requests.recipient,
                         &vec![state.config.amount; state.config.num_coins],
                     )
-                    .await
+                    .await;
+                refund_on_failure(&state, id, admission, result).await
+            })
+            .await
+            .unwrap()
+        }
+        FaucetRequest::CoinRequest(request) => {
+            let coin = match state.config.coin(&request.coin_type) {
+                Ok(coin) => coin.cloned(),
+                Err(e) => {
+                    warn!(uuid =?id, "Rejected gas request: {e}");
+                    return (StatusCode::BAD_REQUEST, Json(FaucetResponse::from(e)))
+                        .into_response();
+                }
+            };
+            let admission = match state.quotas.admit(request.recipient, Some(ip)).await {
+                Ok(admission) => admission,
+                Err(e) => return quota_error_response::<FaucetResponse>(id, e),
+            };
+
+            spawn_monitored_task!(async move {
+                let result = match coin {
+                    // SUI is sent as gas coins, like for fixed amount requests.
+                    None => {
+                        state
+                            .faucet
+                            .send(
+                                id,
+                                request.recipient,
+                                &vec![state.config.amount; state.config.num_coins],
+                            )
+                            .await
+                    }
+                    Some(coin) => {
+                        state
+                            .faucet
+                            .send_coins(id, request.recipient, &coin.coin_type, &[coin.amount])
+                            .await
+                    }
+                };
+                refund_on_failure(&state, id, admission, result).await
             })
             .await
             .unwrap()
@@ -173,7 +348,7 @@ async fn request_gas(
     match result {
         Ok(v) => {
             info!(uuid =?id, "Request is successfully served");
-            (StatusCode::CREATED, Json(FaucetResponse::from(v)))
+            (StatusCode::CREATED, Json(FaucetResponse::from(v))).into_response()
         }
         Err(v) => {
             warn!(uuid =?id, "Failed to request gas: {:?}", v);
@@ -181,6 +356,138 @@ async fn request_gas(
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(FaucetResponse::from(v)),
             )
+                .into_response()
+        }
+    }
+}
+
+/// handler for the batched request_gas requests, which are queued to be served together with other
+/// requests and respond with the task ID to poll for their outcome
+async fn batch_request_gas(
+    Extension(state): Extension<Arc<AppState>>,
+    ConnectInfo(connection): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Json(payload): Json<FaucetRequest>,
+) -> impl IntoResponse {
+    let id = Uuid::new_v4();
+    let ip = client_ip(&headers, connection, state.config.trust_x_forwarded_for);
+    info!(uuid = ?id, %ip, "Got new batch gas request.");
+    let recipient = match payload {
+        FaucetRequest::FixedAmountRequest(request) => request.recipient,
+        // Batches only send SUI.
+        FaucetRequest::CoinRequest(request) => match state.config.coin(&request.coin_type) {
+            Ok(None) => request.recipient,
+            Ok(Some(_)) | Err(_) => {
+                let e = FaucetError::UnsupportedCoinType(request.coin_type);
+                warn!(uuid =?id, "Rejected batch gas request: {e}");
+                return (StatusCode::BAD_REQUEST, Json(BatchFaucetResponse::from(e)))
+                    .into_response();
+            }
+        },
+    };
+    let admission = match state.quotas.admit(recipient, Some(ip)).await {
+        Ok(admission) => admission,
+        Err(e) => return quota_error_response::<BatchFaucetResponse>(id, e),
+    };
+
+    // The faucet refunds the quotas if the request fails, which can happen after it is queued.
+    let result = state
+        .faucet
+        .batch_send(
+            id,
+            recipient,
+            &vec![state.config.amount; state.config.num_coins],
+            admission,
+        )
+        .await;
+    match result {
+        Ok(_) => {
+            info!(uuid =?id, "Request is queued for the next batch");
+            (StatusCode::ACCEPTED, Json(BatchFaucetResponse::from(id))).into_response()
+        }
+        Err(v) => {
+            warn!(uuid =?id, "Failed to queue gas request: {:?}", v);
+            let status = match v {
+                FaucetError::BatchQueueFull => StatusCode::SERVICE_UNAVAILABLE,
+                _ => StatusCode::INTERNAL_SERVER_ERROR,
+            };
+            (status, Json(BatchFaucetResponse::from(v))).into_response()
+        }
+    }
+}
+
+/// handler for polling the outcome of a batched request_gas request
+async fn request_status(
+    Extension(state): Extension<Arc<AppState>>,
+    Path(task_id): Path<String>,
+) -> impl IntoResponse {
+    let Ok(id) = Uuid::parse_str(&task_id) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(BatchStatusFaucetResponse {
+                status: None,
+                error: Some(format!("Invalid task ID `{task_id}`")),
+            }),
+        );
+    };
+    match state.faucet.get_batch_send_status(id).await {
+        Ok(status) => (
+            StatusCode::OK,
+            Json(BatchStatusFaucetResponse::from(status)),
+        ),
+        Err(e) => (
+            StatusCode::NOT_FOUND,
+            Json(BatchStatusFaucetResponse::from(e)),
+        ),
+    }
+}
+
+/// The IP of the client making the request: the first `X-Forwarded-For` entry if the faucet is
+/// configured to trust it, otherwise the remote address of the connection.
+fn client_ip(headers: &HeaderMap, connection: SocketAddr, trust_x_forwarded_for: bool) -> IpAddr {
+    trust_x_forwarded_for
+        .then(|| headers.get("x-forwarded-for"))
+        .flatten()
+        .and_then(|forwarded| forwarded.to_str().ok())
+        .and_then(|forwarded| forwarded.split(',').next())
+        .and_then(|ip| ip.trim().parse().ok())
+        .unwrap_or_else(|| connection.ip())
+}
+
+/// Stops counting a request against the quotas if it could not be served.
+async fn refund_on_failure<T>(
+    state: &AppState,
+    id: Uuid,
+    admission: Admission,
+    result: Result<T, FaucetError>,
+) -> Result<T, FaucetError> {
+    if result.is_err() {
+        if let Err(e) = state.quotas.refund(admission).await {
+            warn!(uuid =?id, "Failed to refund the request quotas: {:?}", e);
+        }
+    }
+    result
+}
+
+fn quota_error_response<R>(id: Uuid, error: FaucetError) -> Response
+where
+    R: From<FaucetError> + Serialize,
+{
+    match error {
+        FaucetError::TooManyRequests {
+            retry_after_secs, ..
+        } => {
+            warn!(uuid =?id, "Rejected gas request: {error}");
+            (
+                StatusCode::TOO_MANY_REQUESTS,
+                [(header::RETRY_AFTER, retry_after_secs.to_string())],
+                Json(R::from(error)),
+            )
+                .into_response()
+        }
+        error => {
+            warn!(uuid =?id, "Failed to check request quotas: {:?}", error);
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(R::from(error))).into_response()
         }
     }
 }
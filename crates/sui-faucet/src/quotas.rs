@@ -0,0 +1,315 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashSet;
+use std::fmt;
+use std::net::IpAddr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use sui_types::base_types::SuiAddress;
+use tokio::sync::Mutex;
+use typed_store::rocks::DBMap;
+use typed_store::Map;
+
+use crate::FaucetError;
+
+/// Limits on the number of requests the faucet serves to a single recipient address or client IP
+/// within a sliding window. A limit of `None` disables the corresponding quota.
+#[derive(Clone, Debug)]
+pub struct QuotaConfig {
+    pub max_requests_per_recipient: Option<u32>,
+    pub max_requests_per_ip: Option<u32>,
+    pub window: Duration,
+    /// Recipients exempt from the per-recipient quota.
+    pub allowlisted_recipients: HashSet<SuiAddress>,
+    /// Client IPs exempt from the per-IP quota.
+    pub allowlisted_ips: HashSet<IpAddr>,
+}
+
+impl Default for QuotaConfig {
+    fn default() -> Self {
+        Self {
+            max_requests_per_recipient: None,
+            max_requests_per_ip: None,
+            window: Duration::from_secs(24 * 60 * 60),
+            allowlisted_recipients: HashSet::new(),
+            allowlisted_ips: HashSet::new(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum QuotaKey {
+    Recipient(SuiAddress),
+    Ip(IpAddr),
+}
+
+impl fmt::Display for QuotaKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QuotaKey::Recipient(address) => write!(f, "recipient {address}"),
+            QuotaKey::Ip(ip) => write!(f, "IP {ip}"),
+        }
+    }
+}
+
+/// A request counted against the quotas by [`RequestQuotas::admit`], which can be refunded if
+/// the request could not be served.
+#[derive(Debug)]
+pub struct Admission {
+    keys: Vec<QuotaKey>,
+    timestamp: u64,
+}
+
+/// Enforces the sliding-window request quotas of a [`QuotaConfig`].
+///
+/// The requests served to each quota key are recorded in the faucet's write ahead log DB, as
+/// timestamps in milliseconds since the unix epoch, so that quotas survive a restart of the
+/// faucet.
+pub struct RequestQuotas {
+    config: QuotaConfig,
+    requests: Mutex<DBMap<QuotaKey, Vec<u64>>>,
+}
+
+impl RequestQuotas {
+    pub(crate) fn new(requests: DBMap<QuotaKey, Vec<u64>>, config: QuotaConfig) -> Self {
+        Self {
+            config,
+            requests: Mutex::new(requests),
+        }
+    }
+
+    /// Admit a request for `recipient` coming from `ip`, counting it against both quotas. Fails
+    /// with [`FaucetError::TooManyRequests`] if either quota is exhausted, in which case the
+    /// request is not counted against either of them.
+    pub async fn admit(
+        &self,
+        recipient: SuiAddress,
+        ip: Option<IpAddr>,
+    ) -> Result<Admission, FaucetError> {
+        self.admit_at(recipient, ip, SystemTime::now()).await
+    }
+
+    /// Stop counting an admitted request against the quotas, because it failed.
+    pub async fn refund(&self, admission: Admission) -> Result<(), FaucetError> {
+        if admission.keys.is_empty() {
+            return Ok(());
+        }
+
+        let requests = self.requests.lock().await;
+        let mut updates = Vec::with_capacity(admission.keys.len());
+        for key in admission.keys {
+            let mut served = requests
+                .get(&key)
+                .map_err(FaucetError::internal)?
+                .unwrap_or_default();
+            if let Some(position) = served.iter().position(|t| *t == admission.timestamp) {
+                served.remove(position);
+                updates.push((key, served));
+            }
+        }
+
+        requests
+            .batch()
+            .insert_batch(&*requests, updates)
+            .and_then(|batch| batch.write())
+            .map_err(FaucetError::internal)
+    }
+
+    async fn admit_at(
+        &self,
+        recipient: SuiAddress,
+        ip: Option<IpAddr>,
+        now: SystemTime,
+    ) -> Result<Admission, FaucetError> {
+        let mut limits = vec![];
+        if let Some(max) = self.config.max_requests_per_recipient {
+            if !self.config.allowlisted_recipients.contains(&recipient) {
+                limits.push((QuotaKey::Recipient(recipient), max));
+            }
+        }
+        if let (Some(max), Some(ip)) = (self.config.max_requests_per_ip, ip) {
+            if !self.config.allowlisted_ips.contains(&ip) {
+                limits.push((QuotaKey::Ip(ip), max));
+            }
+        }
+        if limits.is_empty() {
+            return Ok(Admission {
+                keys: vec![],
+                timestamp: 0,
+            });
+        }
+
+        let now = now
+            .duration_since(UNIX_EPOCH)
+            .map_err(FaucetError::internal)?
+            .as_millis() as u64;
+        let window = self.config.window.as_millis() as u64;
+
+        let requests = self.requests.lock().await;
+        let mut updates = Vec::with_capacity(limits.len());
+        for (key, max) in limits {
+            let mut served = requests
+                .get(&key)
+                .map_err(FaucetError::internal)?
+                .unwrap_or_default();
+            served.retain(|timestamp| timestamp + window > now);
+
+            if served.len() >= max as usize {
+                // The quota frees up when the oldest request in the window expires.
+                let retry_after = served
+                    .iter()
+                    .min()
+                    .map_or(window, |oldest| oldest + window - now);
+                return Err(FaucetError::TooManyRequests {
+                    key: key.to_string(),
+                    retry_after_secs: (retry_after + 999) / 1000,
+                });
+            }
+
+            served.push(now);
+            updates.push((key, served));
+        }
+
+        let keys = updates.iter().map(|(key, _)| *key).collect();
+        requests
+            .batch()
+            .insert_batch(&*requests, updates)
+            .and_then(|batch| batch.write())
+            .map_err(FaucetError::internal)?;
+        Ok(Admission {
+            keys,
+            timestamp: now,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::faucet::WriteAheadLog;
+    use std::path::Path;
+
+    fn open(path: &Path, config: QuotaConfig) -> RequestQuotas {
+        RequestQuotas::new(WriteAheadLog::open(path).quota_table(), config)
+    }
+
+    fn config() -> QuotaConfig {
+        QuotaConfig {
+            max_requests_per_recipient: Some(2),
+            max_requests_per_ip: Some(3),
+            window: Duration::from_secs(60),
+            ..Default::default()
+        }
+    }
+
+    fn at(secs: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(1_000_000 + secs)
+    }
+
+    #[tokio::test]
+    async fn recipient_quota_slides() {
+        let tmp = tempfile::tempdir().unwrap();
+        let quotas = open(&tmp.path().join("wal"), config());
+        let recipient = SuiAddress::random_for_testing_only();
+
+        quotas.admit_at(recipient, None, at(0)).await.unwrap();
+        quotas.admit_at(recipient, None, at(20)).await.unwrap();
+
+        // The third request within the window is rejected until the first one expires.
+        assert_eq!(
+            quotas.admit_at(recipient, None, at(30)).await,
+            Err(FaucetError::TooManyRequests {
+                key: QuotaKey::Recipient(recipient).to_string(),
+                retry_after_secs: 30,
+            })
+        );
+        quotas.admit_at(recipient, None, at(60)).await.unwrap();
+
+        // Other recipients are unaffected.
+        let other = SuiAddress::random_for_testing_only();
+        quotas.admit_at(other, None, at(60)).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn ip_quota_spans_recipients() {
+        let tmp = tempfile::tempdir().unwrap();
+        let quotas = open(&tmp.path().join("wal"), config());
+        let ip: IpAddr = "10.0.0.1".parse().unwrap();
+
+        for _ in 0..3 {
+            let recipient = SuiAddress::random_for_testing_only();
+            quotas.admit_at(recipient, Some(ip), at(0)).await.unwrap();
+        }
+
+        let recipient = SuiAddress::random_for_testing_only();
+        assert!(matches!(
+            quotas.admit_at(recipient, Some(ip), at(1)).await,
+            Err(FaucetError::TooManyRequests { .. })
+        ));
+
+        // The rejected request was not counted against the recipient's quota.
+        quotas.admit_at(recipient, None, at(1)).await.unwrap();
+        quotas.admit_at(recipient, None, at(1)).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn allowlisted_keys_are_exempt() {
+        let tmp = tempfile::tempdir().unwrap();
+        let recipient = SuiAddress::random_for_testing_only();
+        let ip: IpAddr = "10.0.0.1".parse().unwrap();
+        let quotas = open(
+            &tmp.path().join("wal"),
+            QuotaConfig {
+                allowlisted_recipients: HashSet::from([recipient]),
+                allowlisted_ips: HashSet::from([ip]),
+                ..config()
+            },
+        );
+
+        for _ in 0..10 {
+            quotas.admit_at(recipient, Some(ip), at(0)).await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn quotas_survive_reopen() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("wal");
+        let recipient = SuiAddress::random_for_testing_only();
+
+        {
+            let quotas = open(&path, config());
+            quotas.admit_at(recipient, None, at(0)).await.unwrap();
+            quotas.admit_at(recipient, None, at(0)).await.unwrap();
+        }
+
+        let quotas = open(&path, config());
+        assert!(matches!(
+            quotas.admit_at(recipient, None, at(1)).await,
+            Err(FaucetError::TooManyRequests { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn refunded_requests_are_not_counted() {
+        let tmp = tempfile::tempdir().unwrap();
+        let quotas = open(&tmp.path().join("wal"), config());
+        let recipient = SuiAddress::random_for_testing_only();
+        let ip: IpAddr = "10.0.0.1".parse().unwrap();
+
+        quotas.admit_at(recipient, Some(ip), at(0)).await.unwrap();
+        let failed = quotas.admit_at(recipient, Some(ip), at(10)).await.unwrap();
+        quotas.refund(failed).await.unwrap();
+
+        // Both quotas were refunded: the recipient can be served again, and so can the IP twice.
+        quotas.admit_at(recipient, Some(ip), at(20)).await.unwrap();
+        let other = SuiAddress::random_for_testing_only();
+        quotas.admit_at(other, Some(ip), at(20)).await.unwrap();
+        assert!(matches!(
+            quotas.admit_at(other, Some(ip), at(20)).await,
+            Err(FaucetError::TooManyRequests { .. })
+        ));
+    }
+}
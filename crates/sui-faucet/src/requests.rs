@@ -7,6 +7,7 @@ use sui_types::base_types::SuiAddress;
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub enum FaucetRequest {
     FixedAmountRequest(FixedAmountRequest),
+    CoinRequest(CoinRequest),
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -14,10 +15,28 @@ pub struct FixedAmountRequest {
     pub recipient: SuiAddress,
 }
 
+/// Request for the faucet's fixed amount of `Coin<T>`, where `T` is `coin_type`, e.g.
+/// `0x2::sui::SUI`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CoinRequest {
+    pub recipient: SuiAddress,
+    pub coin_type: String,
+}
+
 impl FaucetRequest {
     pub fn new_fixed_amount_request(recipient: impl Into<SuiAddress>) -> Self {
         Self::FixedAmountRequest(FixedAmountRequest {
             recipient: recipient.into(),
         })
     }
+
+    pub fn new_coin_request(
+        recipient: impl Into<SuiAddress>,
+        coin_type: impl Into<String>,
+    ) -> Self {
+        Self::CoinRequest(CoinRequest {
+            recipient: recipient.into(),
+            coin_type: coin_type.into(),
+        })
+    }
 }
@@ -0,0 +1,4 @@
+DROP INDEX transactions_unwrapped;
+DROP INDEX transactions_created;
+DROP INDEX transactions_mutated;
+ALTER TABLE transactions DROP COLUMN transaction_effects_content;
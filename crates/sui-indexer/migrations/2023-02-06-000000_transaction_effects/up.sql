@@ -0,0 +1,5 @@
+-- serialized transaction effects, NULL for transactions indexed before this column existed
+ALTER TABLE transactions ADD COLUMN transaction_effects_content TEXT;
+CREATE INDEX transactions_mutated ON transactions USING GIN (mutated);
+CREATE INDEX transactions_created ON transactions USING GIN (created);
+CREATE INDEX transactions_unwrapped ON transactions USING GIN (unwrapped);
@@ -0,0 +1,5 @@
+CREATE TABLE custom_handler_logs (
+    handler_name VARCHAR(255) PRIMARY KEY,
+    next_checkpoint_sequence_number BIGINT NOT NULL,
+    caught_up BOOLEAN NOT NULL DEFAULT FALSE
+);
@@ -0,0 +1,23 @@
+CREATE TABLE move_event_logs (
+    id SERIAL PRIMARY KEY,
+    next_cursor_tx_dig TEXT,
+    next_cursor_event_seq BIGINT
+);
+INSERT INTO move_event_logs (id, next_cursor_tx_dig, next_cursor_event_seq) VALUES
+(1, NULL, NULL);
+CREATE TABLE publish_event_logs (
+    id SERIAL PRIMARY KEY,
+    next_cursor_tx_dig TEXT,
+    next_cursor_event_seq BIGINT
+);
+INSERT INTO publish_event_logs (id, next_cursor_tx_dig, next_cursor_event_seq) VALUES
+(1, NULL, NULL);
+CREATE TABLE object_event_logs (
+    id SERIAL PRIMARY KEY,
+    next_cursor_tx_dig TEXT,
+    next_cursor_event_seq BIGINT
+);
+INSERT INTO object_event_logs (id, next_cursor_tx_dig, next_cursor_event_seq) VALUES
+(1, NULL, NULL);
+ALTER TABLE events ADD COLUMN next_cursor_transaction_digest VARCHAR(255);
+DROP INDEX events_transaction_digest_event_sequence;
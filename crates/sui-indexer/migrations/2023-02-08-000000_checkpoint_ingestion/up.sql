@@ -0,0 +1,27 @@
+-- Events used to be inserted again when their transaction was re-fetched, keep the first copy.
+DELETE FROM events a USING events b
+WHERE a.id > b.id
+  AND a.transaction_digest = b.transaction_digest
+  AND a.event_sequence = b.event_sequence;
+-- Re-ingesting a checkpoint must not duplicate its events.
+CREATE UNIQUE INDEX events_transaction_digest_event_sequence ON events (transaction_digest, event_sequence);
+-- The checkpoints table is the only ingestion cursor.
+ALTER TABLE events DROP COLUMN next_cursor_transaction_digest;
+
+-- Object, publish and Move events are now committed with their checkpoint and keyed like the
+-- events table, rebuild them from it.
+DELETE FROM object_events;
+INSERT INTO object_events (transaction_digest, event_sequence, event_time, event_type, event_content)
+SELECT transaction_digest, event_sequence, event_time, event_type, event_content
+FROM events WHERE event_type = 'NewObject' ORDER BY id;
+DELETE FROM publish_events;
+INSERT INTO publish_events (transaction_digest, event_sequence, event_time, event_type, event_content)
+SELECT transaction_digest, event_sequence, event_time, event_type, event_content
+FROM events WHERE event_type = 'Publish' ORDER BY id;
+DELETE FROM move_events;
+INSERT INTO move_events (transaction_digest, event_sequence, event_time, event_type, event_content)
+SELECT transaction_digest, event_sequence, event_time, event_type, event_content
+FROM events WHERE event_type = 'MoveEvent' ORDER BY id;
+DROP TABLE object_event_logs;
+DROP TABLE publish_event_logs;
+DROP TABLE move_event_logs;
@@ -0,0 +1,4 @@
+DROP INDEX objects_owner_type_owner_address;
+ALTER TABLE objects DROP COLUMN object_content;
+ALTER TABLE objects DROP COLUMN previous_transaction;
+ALTER TABLE objects DROP COLUMN object_digest;
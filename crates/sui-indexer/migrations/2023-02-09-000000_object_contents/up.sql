@@ -0,0 +1,6 @@
+-- reference and serialized contents of the latest version of each object,
+-- NULL for objects indexed before these columns existed
+ALTER TABLE objects ADD COLUMN object_digest VARCHAR(255);
+ALTER TABLE objects ADD COLUMN previous_transaction VARCHAR(255);
+ALTER TABLE objects ADD COLUMN object_content TEXT;
+CREATE INDEX objects_owner_type_owner_address ON objects (owner_type, owner_address);
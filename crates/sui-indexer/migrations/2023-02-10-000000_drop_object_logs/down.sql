@@ -0,0 +1,4 @@
+CREATE TABLE object_logs (
+    last_processed_id BIGINT PRIMARY KEY
+);
+INSERT INTO object_logs (last_processed_id) VALUES (0);
@@ -0,0 +1,2 @@
+-- Objects are committed with their checkpoint, the object processor cursor is no longer used.
+DROP TABLE object_logs;
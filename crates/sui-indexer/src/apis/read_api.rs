@@ -2,7 +2,12 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::errors::IndexerError;
-use crate::models::checkpoints::get_latest_checkpoint_sequence_number;
+use crate::models::checkpoints::{
+    get_checkpoint_by_digest, get_checkpoint_by_sequence_number,
+    get_latest_checkpoint_sequence_number,
+};
+use crate::models::objects::{get_object, get_objects_owned_by_address};
+use crate::models::transactions::{get_transaction_by_digest, read_transaction_digests};
 use crate::{get_pg_pool_connection, PgConnectionPool};
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
@@ -10,16 +15,18 @@ use jsonrpsee::http_client::HttpClient;
 use jsonrpsee::RpcModule;
 use std::collections::BTreeMap;
 use std::sync::Arc;
-use sui_json_rpc::api::ReadApiClient;
 use sui_json_rpc::api::ReadApiServer;
+use sui_json_rpc::api::{cap_page_limit, ReadApiClient};
 use sui_json_rpc::SuiRpcModule;
 use sui_json_rpc_types::{
     Checkpoint, CheckpointId, DynamicFieldPage, GetObjectDataResponse, GetPastObjectDataResponse,
-    GetRawObjectDataResponse, MoveFunctionArgType, SuiMoveNormalizedFunction,
-    SuiMoveNormalizedModule, SuiMoveNormalizedStruct, SuiObjectInfo, SuiTransactionResponse,
-    TransactionsPage,
+    GetRawObjectDataResponse, MoveFunctionArgType, ProtocolConfigResponse,
+    SuiMoveNormalizedFunction, SuiMoveNormalizedModule, SuiMoveNormalizedStruct, SuiObjectInfo,
+    SuiTransactionResponse, TransactionsPage,
 };
 use sui_open_rpc::Module;
+use sui_protocol_config::ProtocolVersion;
+use sui_storage::IndexStore;
 use sui_types::base_types::{ObjectID, SequenceNumber, SuiAddress, TxSequenceNumber};
 use sui_types::digests::{CheckpointContentsDigest, CheckpointDigest, TransactionDigest};
 use sui_types::dynamic_field::DynamicFieldName;
@@ -45,6 +52,122 @@ impl ReadApi {
         let mut pg_pool_conn = get_pg_pool_connection(self.pg_connection_pool.clone())?;
         get_latest_checkpoint_sequence_number(&mut pg_pool_conn)
     }
+
+    async fn get_transaction_from_db(
+        &self,
+        digest: TransactionDigest,
+    ) -> Result<Option<SuiTransactionResponse>, IndexerError> {
+        let mut pg_pool_conn = get_pg_pool_connection(self.pg_connection_pool.clone())?;
+        match get_transaction_by_digest(&mut pg_pool_conn, digest.base58_encode())? {
+            Some(txn) => txn.to_transaction_response(),
+            None => Ok(None),
+        }
+    }
+
+    /// Same paging as the fullnode: the cursor is the first transaction of the page, and the
+    /// next cursor is the first transaction of the following page.
+    async fn get_transactions_from_db(
+        &self,
+        query: &TransactionQuery,
+        cursor: Option<TransactionDigest>,
+        limit: Option<usize>,
+        descending_order: bool,
+    ) -> Result<Option<TransactionsPage>, IndexerError> {
+        IndexStore::validate_transaction_query(query)
+            .map_err(|e| IndexerError::InvalidArgumentError(e.to_string()))?;
+        let limit = cap_page_limit(limit);
+        let mut pg_pool_conn = get_pg_pool_connection(self.pg_connection_pool.clone())?;
+        let Some(digests) = read_transaction_digests(
+            &mut pg_pool_conn,
+            query,
+            cursor.map(|digest| digest.base58_encode()),
+            limit + 1,
+            descending_order,
+        )? else {
+            return Ok(None);
+        };
+        let mut data = digests
+            .iter()
+            .map(|digest| {
+                digest.parse().map_err(|e| {
+                    IndexerError::TransactionDigestParsingError(format!(
+                        "Failed parsing transaction digest {:?} with error: {:?}",
+                        digest, e
+                    ))
+                })
+            })
+            .collect::<Result<Vec<TransactionDigest>, _>>()?;
+        let next_cursor = data.get(limit).cloned();
+        data.truncate(limit);
+        Ok(Some(TransactionsPage { data, next_cursor }))
+    }
+
+    async fn get_checkpoint_from_db(
+        &self,
+        id: &CheckpointId,
+    ) -> Result<Option<Checkpoint>, IndexerError> {
+        let mut pg_pool_conn = get_pg_pool_connection(self.pg_connection_pool.clone())?;
+        let checkpoint = match id {
+            CheckpointId::SequenceNumber(seq) => {
+                // Sequence numbers are stored as BIGINT, so larger ones are never indexed.
+                let Ok(seq) = i64::try_from(*seq) else {
+                    return Ok(None);
+                };
+                get_checkpoint_by_sequence_number(&mut pg_pool_conn, seq)?
+            }
+            CheckpointId::Digest(digest) => {
+                get_checkpoint_by_digest(&mut pg_pool_conn, digest.base58_encode())?
+            }
+        };
+        checkpoint.map(|c| c.to_rpc_checkpoint()).transpose()
+    }
+
+    async fn get_object_from_db(
+        &self,
+        object_id: ObjectID,
+    ) -> Result<Option<GetObjectDataResponse>, IndexerError> {
+        let mut pg_pool_conn = get_pg_pool_connection(self.pg_connection_pool.clone())?;
+        match get_object(&mut pg_pool_conn, object_id)? {
+            Some(object) => object.to_object_read(),
+            None => Ok(None),
+        }
+    }
+
+    async fn get_past_object_from_db(
+        &self,
+        object_id: ObjectID,
+        version: SequenceNumber,
+    ) -> Result<Option<GetPastObjectDataResponse>, IndexerError> {
+        let mut pg_pool_conn = get_pg_pool_connection(self.pg_connection_pool.clone())?;
+        match get_object(&mut pg_pool_conn, object_id)? {
+            Some(object) => object.to_past_object_read(version),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns `None` if any of the objects was indexed before object contents were stored.
+    async fn get_objects_owned_by_address_from_db(
+        &self,
+        address: SuiAddress,
+    ) -> Result<Option<Vec<SuiObjectInfo>>, IndexerError> {
+        let mut pg_pool_conn = get_pg_pool_connection(self.pg_connection_pool.clone())?;
+        get_objects_owned_by_address(&mut pg_pool_conn, address)?
+            .iter()
+            .map(|object| object.to_object_info())
+            .collect()
+    }
+
+    async fn get_total_transaction_number_from_db(&self) -> Result<Option<u64>, IndexerError> {
+        let mut pg_pool_conn = get_pg_pool_connection(self.pg_connection_pool.clone())?;
+        let latest_seq = get_latest_checkpoint_sequence_number(&mut pg_pool_conn)?;
+        if latest_seq < 0 {
+            return Ok(None);
+        }
+        Ok(
+            get_checkpoint_by_sequence_number(&mut pg_pool_conn, latest_seq)?
+                .map(|c| c.total_transactions_from_genesis as u64),
+        )
+    }
 }
 
 #[async_trait]
@@ -53,7 +176,10 @@ impl ReadApiServer for ReadApi {
         &self,
         address: SuiAddress,
     ) -> RpcResult<Vec<SuiObjectInfo>> {
-        self.fullnode.get_objects_owned_by_address(address).await
+        match self.get_objects_owned_by_address_from_db(address).await? {
+            Some(objects) => Ok(objects),
+            None => self.fullnode.get_objects_owned_by_address(address).await,
+        }
     }
 
     async fn get_dynamic_fields(
@@ -68,7 +194,10 @@ impl ReadApiServer for ReadApi {
     }
 
     async fn get_object(&self, object_id: ObjectID) -> RpcResult<GetObjectDataResponse> {
-        self.fullnode.get_object(object_id).await
+        match self.get_object_from_db(object_id).await? {
+            Some(object) => Ok(object),
+            None => self.fullnode.get_object(object_id).await,
+        }
     }
 
     async fn get_dynamic_field_object(
@@ -82,7 +211,10 @@ impl ReadApiServer for ReadApi {
     }
 
     async fn get_total_transaction_number(&self) -> RpcResult<u64> {
-        self.fullnode.get_total_transaction_number().await
+        match self.get_total_transaction_number_from_db().await? {
+            Some(total) => Ok(total),
+            None => self.fullnode.get_total_transaction_number().await,
+        }
     }
 
     async fn get_transactions_in_range(
@@ -97,7 +229,10 @@ impl ReadApiServer for ReadApi {
         &self,
         digest: TransactionDigest,
     ) -> RpcResult<SuiTransactionResponse> {
-        self.fullnode.get_transaction(digest).await
+        match self.get_transaction_from_db(digest).await? {
+            Some(txn) => Ok(txn),
+            None => self.fullnode.get_transaction(digest).await,
+        }
     }
 
     async fn get_normalized_move_modules_by_package(
@@ -159,9 +294,19 @@ impl ReadApiServer for ReadApi {
         limit: Option<usize>,
         descending_order: Option<bool>,
     ) -> RpcResult<TransactionsPage> {
-        self.fullnode
-            .get_transactions(query, cursor, limit, descending_order)
-            .await
+        let page = self
+            .get_transactions_from_db(&query, cursor, limit, descending_order.unwrap_or_default())
+            .await?;
+        match page {
+            Some(page) => Ok(page),
+            // The query filters on data the transactions table does not have, or the cursor
+            // transaction has not been indexed yet.
+            None => {
+                self.fullnode
+                    .get_transactions(query, cursor, limit, descending_order)
+                    .await
+            }
+        }
     }
 
     async fn try_get_past_object(
@@ -169,15 +314,26 @@ impl ReadApiServer for ReadApi {
         object_id: ObjectID,
         version: SequenceNumber,
     ) -> RpcResult<GetPastObjectDataResponse> {
-        self.fullnode.try_get_past_object(object_id, version).await
+        // Only the latest version of each object is indexed.
+        match self.get_past_object_from_db(object_id, version).await? {
+            Some(object) => Ok(object),
+            None => self.fullnode.try_get_past_object(object_id, version).await,
+        }
     }
 
     async fn get_latest_checkpoint_sequence_number(&self) -> RpcResult<CheckpointSequenceNumber> {
-        Ok(self.get_latest_checkpoint_sequence_number().await? as u64)
+        match u64::try_from(self.get_latest_checkpoint_sequence_number().await?) {
+            Ok(sequence_number) => Ok(sequence_number),
+            // No checkpoint has been indexed yet.
+            Err(_) => self.fullnode.get_latest_checkpoint_sequence_number().await,
+        }
     }
 
     async fn get_checkpoint(&self, id: CheckpointId) -> RpcResult<Checkpoint> {
-        self.fullnode.get_checkpoint(id).await
+        match self.get_checkpoint_from_db(&id).await? {
+            Some(checkpoint) => Ok(checkpoint),
+            None => self.fullnode.get_checkpoint(id).await,
+        }
     }
 
     async fn get_checkpoint_summary_by_digest(
@@ -211,6 +367,7 @@ impl ReadApiServer for ReadApi {
     }
 
     async fn get_raw_object(&self, object_id: ObjectID) -> RpcResult<GetRawObjectDataResponse> {
+        // Objects are indexed with their parsed contents only.
         self.fullnode.get_raw_object(object_id).await
     }
 
@@ -220,6 +377,13 @@ impl ReadApiServer for ReadApi {
     ) -> RpcResult<BTreeMap<String, String>> {
         self.fullnode.get_display_deprecated(object_id).await
     }
+
+    async fn get_protocol_config(
+        &self,
+        version: Option<ProtocolVersion>,
+    ) -> RpcResult<ProtocolConfigResponse> {
+        self.fullnode.get_protocol_config(version).await
+    }
 }
 
 impl SuiRpcModule for ReadApi {
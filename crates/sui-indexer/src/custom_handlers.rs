@@ -0,0 +1,334 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Extension point for app-specific tables: a [`CustomEventHandler`] receives the Move events it
+//! is interested in, one checkpoint at a time, and writes them to its own tables.
+//!
+//! The checkpoint handler calls the registered handlers from the Postgres transaction that commits
+//! each checkpoint, with the events it ingested, so the handlers' tables always match the
+//! checkpoint cursor. A handler whose `start_checkpoint` is below the checkpoints already indexed
+//! is first backfilled by [`CustomEventHandlerBackfill`] from the indexed events, with a cursor of
+//! its own in `custom_handler_logs`, until it catches up with the checkpoint handler.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use diesel::pg::PgConnection;
+use diesel::result::Error;
+use move_core_types::language_storage::StructTag;
+use sui_json_rpc_types::{SuiEvent, SuiEventEnvelope};
+use sui_types::base_types::ObjectID;
+use sui_types::digests::TransactionDigest;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use sui_types::parse_sui_struct_tag;
+use tracing::info;
+
+use crate::errors::IndexerError;
+use crate::models::checkpoints::get_checkpoint_by_sequence_number;
+use crate::models::custom_handler_logs::{
+    lock_custom_handler_log, read_custom_handler_log, register_custom_handler,
+    update_custom_handler_log,
+};
+use crate::models::events::{read_transaction_events, Event};
+use crate::{get_pg_pool_connection, PgConnectionPool, PgPoolConnection};
+
+/// Selects the Move events delivered to a [`CustomEventHandler`].
+#[derive(Clone, Debug)]
+pub enum MoveEventFilter {
+    /// Events of exactly this struct type, type parameters included.
+    EventType(StructTag),
+    /// Events emitted by any module of this package.
+    Package(ObjectID),
+}
+
+impl MoveEventFilter {
+    pub fn event_type(event_type: &str) -> Result<Self, anyhow::Error> {
+        Ok(Self::EventType(parse_sui_struct_tag(event_type)?))
+    }
+
+    pub fn matches(&self, event: &SuiEvent) -> bool {
+        let SuiEvent::MoveEvent { package_id, type_, .. } = event else {
+            return false;
+        };
+        match self {
+            MoveEventFilter::EventType(event_type) => {
+                parse_sui_struct_tag(type_).map_or(false, |struct_tag| &struct_tag == event_type)
+            }
+            MoveEventFilter::Package(package) => package_id == package,
+        }
+    }
+}
+
+pub trait CustomEventHandler: Send + Sync {
+    /// Unique name of the handler, which keys its backfill cursor in `custom_handler_logs`.
+    fn name(&self) -> &str;
+
+    /// Events matching any of these filters are passed to the handler.
+    fn filters(&self) -> Vec<MoveEventFilter>;
+
+    /// The checkpoint to backfill from the first time the handler runs. Later runs resume where
+    /// the handler left off; delete its `custom_handler_logs` row to backfill again.
+    fn start_checkpoint(&self) -> CheckpointSequenceNumber {
+        0
+    }
+
+    /// Write the matching events of `checkpoint` to the handler's tables. Called for every
+    /// checkpoint from `start_checkpoint`, in order, including those without matching events.
+    /// `conn` is inside the Postgres transaction that commits the checkpoint, or advances the
+    /// handler's backfill cursor, so an error rolls back both and the checkpoint is retried.
+    fn process_checkpoint(
+        &self,
+        conn: &mut PgConnection,
+        checkpoint: CheckpointSequenceNumber,
+        events: &[SuiEventEnvelope],
+    ) -> Result<(), Error>;
+}
+
+/// The events emitted by a transaction, in the form they are delivered to custom handlers.
+pub fn transaction_event_envelopes(
+    tx_digest: TransactionDigest,
+    timestamp_ms: Option<u64>,
+    events: &[SuiEvent],
+) -> impl Iterator<Item = SuiEventEnvelope> + '_ {
+    events
+        .iter()
+        .enumerate()
+        .map(move |(event_seq, event)| SuiEventEnvelope {
+            timestamp: timestamp_ms.unwrap_or_default(),
+            tx_digest,
+            id: (tx_digest, event_seq as i64).into(),
+            event: event.clone(),
+        })
+}
+
+/// Call `handlers` with the events of `checkpoint`, from the Postgres transaction of `conn` that
+/// commits it. Handlers that are still backfilled are skipped, unless their backfill stopped right
+/// before `checkpoint`, in which case the checkpoint handler takes over from the backfill.
+pub fn process_committed_checkpoint(
+    conn: &mut PgConnection,
+    handlers: &[Arc<dyn CustomEventHandler>],
+    checkpoint: CheckpointSequenceNumber,
+    events: &[SuiEventEnvelope],
+) -> Result<(), Error> {
+    for handler in handlers {
+        let mut log = lock_custom_handler_log(conn, handler.name())?;
+        if !log.caught_up {
+            if log.next_checkpoint_sequence_number != checkpoint as i64 {
+                continue;
+            }
+            log.caught_up = true;
+            update_custom_handler_log(conn, &log)?;
+        }
+        let events = matching_events(&handler.filters(), events.iter().cloned());
+        handler.process_checkpoint(conn, checkpoint, &events)?;
+    }
+    Ok(())
+}
+
+fn matching_events(
+    filters: &[MoveEventFilter],
+    events: impl IntoIterator<Item = SuiEventEnvelope>,
+) -> Vec<SuiEventEnvelope> {
+    events
+        .into_iter()
+        .filter(|envelope| filters.iter().any(|f| f.matches(&envelope.event)))
+        .collect()
+}
+
+/// Backfills a [`CustomEventHandler`] with the checkpoints indexed before it caught up with the
+/// checkpoint handler, reading their events from the `events` table.
+pub struct CustomEventHandlerBackfill {
+    pg_connection_pool: Arc<PgConnectionPool>,
+    handler: Arc<dyn CustomEventHandler>,
+}
+
+impl CustomEventHandlerBackfill {
+    pub fn new(
+        pg_connection_pool: Arc<PgConnectionPool>,
+        handler: Arc<dyn CustomEventHandler>,
+    ) -> Self {
+        Self {
+            pg_connection_pool,
+            handler,
+        }
+    }
+
+    /// Backfill the handler from its cursor, returning once the checkpoint handler took over.
+    pub async fn run(&self) -> Result<(), IndexerError> {
+        let name = self.handler.name();
+        info!("Indexer custom handler {} backfill started...", name);
+        let filters = self.handler.filters();
+        let mut pg_pool_conn = get_pg_pool_connection(self.pg_connection_pool.clone())?;
+        register_custom_handler(
+            &mut pg_pool_conn,
+            name,
+            self.handler.start_checkpoint() as i64,
+        )?;
+
+        loop {
+            let log = read_custom_handler_log(&mut pg_pool_conn, name)?.ok_or_else(|| {
+                IndexerError::PostgresReadError(format!(
+                    "Custom handler {} is not registered",
+                    name
+                ))
+            })?;
+            if log.caught_up {
+                info!("Indexer custom handler {} caught up", name);
+                return Ok(());
+            }
+            let next_checkpoint = log.next_checkpoint_sequence_number;
+            let Some(checkpoint) =
+                get_checkpoint_by_sequence_number(&mut pg_pool_conn, next_checkpoint)? else {
+                // The checkpoint handler has not indexed this checkpoint yet, and takes over
+                // once it does.
+                tokio::time::sleep(Duration::from_secs(1)).await;
+                continue;
+            };
+
+            let digests: Vec<String> = checkpoint.transactions.into_iter().flatten().collect();
+            let events = checkpoint_events(&mut pg_pool_conn, &digests)?;
+            let events = matching_events(&filters, events);
+            let committed = pg_pool_conn
+                .build_transaction()
+                .read_write()
+                .run::<_, Error, _>(|conn| {
+                    let mut log = lock_custom_handler_log(conn, name)?;
+                    // The checkpoint handler took over in the meantime.
+                    if log.caught_up || log.next_checkpoint_sequence_number != next_checkpoint {
+                        return Ok(false);
+                    }
+                    self.handler
+                        .process_checkpoint(conn, next_checkpoint as u64, &events)?;
+                    log.next_checkpoint_sequence_number = next_checkpoint + 1;
+                    update_custom_handler_log(conn, &log)?;
+                    Ok(true)
+                })
+                .map_err(|e| {
+                    IndexerError::PostgresWriteError(format!(
+                        "Custom handler {} failed committing checkpoint {} with error: {:?}",
+                        name, next_checkpoint, e
+                    ))
+                })?;
+            if committed {
+                info!(
+                    "Custom handler {} backfilled checkpoint {} with {} events",
+                    name,
+                    next_checkpoint,
+                    events.len()
+                );
+            }
+        }
+    }
+}
+
+/// The indexed events of the transactions with digests `digests`, in the order the transactions
+/// appear in their checkpoint.
+fn checkpoint_events(
+    pg_pool_conn: &mut PgPoolConnection,
+    digests: &[String],
+) -> Result<Vec<SuiEventEnvelope>, IndexerError> {
+    let positions: HashMap<&str, usize> = digests
+        .iter()
+        .enumerate()
+        .map(|(position, digest)| (digest.as_str(), position))
+        .collect();
+    let mut events = read_transaction_events(pg_pool_conn, digests)?;
+    events.sort_by_key(|event| {
+        (
+            positions.get(event.transaction_digest.as_str()).copied(),
+            event.event_sequence,
+        )
+    });
+    events.into_iter().map(indexed_event_envelope).collect()
+}
+
+/// Rebuild the envelope of an event from its row in the `events` table.
+fn indexed_event_envelope(event: Event) -> Result<SuiEventEnvelope, IndexerError> {
+    let tx_digest = event.transaction_digest.parse().map_err(|e| {
+        IndexerError::TransactionDigestParsingError(format!(
+            "Failed parsing transaction digest {:?} with error: {:?}",
+            event.transaction_digest, e
+        ))
+    })?;
+    let sui_event = serde_json::from_str(&event.event_content).map_err(|e| {
+        IndexerError::EventDeserializationError(format!(
+            "Failed deserializing event {:?} with error: {:?}",
+            event.event_content, e
+        ))
+    })?;
+    Ok(SuiEventEnvelope {
+        timestamp: event
+            .event_time
+            .map_or(0, |event_time| event_time.timestamp_millis() as u64),
+        tx_digest,
+        id: (tx_digest, event.event_sequence).into(),
+        event: sui_event,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::events::transaction_events_to_new_events;
+    use sui_types::base_types::{SequenceNumber, SuiAddress};
+    use sui_types::digests::ObjectDigest;
+
+    fn move_event(package_id: ObjectID, type_: &str) -> SuiEvent {
+        SuiEvent::MoveEvent {
+            package_id,
+            transaction_module: "test".to_string(),
+            sender: SuiAddress::random_for_testing_only(),
+            type_: type_.to_string(),
+            fields: None,
+            bcs: vec![],
+        }
+    }
+
+    #[test]
+    fn test_backfilled_events_match_ingested_events() {
+        let package = ObjectID::random();
+        let events = vec![
+            move_event(package, &format!("{package}::test::Minted")),
+            move_event(ObjectID::random(), "0x2::devnet_nft::MintNFTEvent"),
+            SuiEvent::Publish {
+                sender: SuiAddress::random_for_testing_only(),
+                package_id: package,
+                version: SequenceNumber::from_u64(1),
+                digest: ObjectDigest::new([1; 32]),
+            },
+        ];
+        let tx_digest = TransactionDigest::random();
+
+        // The envelopes the checkpoint handler passes to the handlers
+        let ingested: Vec<_> =
+            transaction_event_envelopes(tx_digest, Some(1000), &events).collect();
+        // and the ones the backfill rebuilds from the indexed rows are the same.
+        let backfilled = transaction_events_to_new_events(tx_digest, Some(1000), events)
+            .unwrap()
+            .into_iter()
+            .enumerate()
+            .map(|(id, event)| {
+                indexed_event_envelope(Event {
+                    id: id as i64,
+                    transaction_digest: event.transaction_digest,
+                    event_sequence: event.event_sequence,
+                    event_time: event.event_time,
+                    event_type: event.event_type,
+                    event_content: event.event_content,
+                })
+                .unwrap()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(ingested, backfilled);
+
+        // Only Move events pass filters.
+        let by_package = matching_events(&[MoveEventFilter::Package(package)], ingested.clone());
+        assert_eq!(by_package, ingested[..1]);
+        let by_type = matching_events(
+            &[MoveEventFilter::event_type("0x2::devnet_nft::MintNFTEvent").unwrap()],
+            ingested.clone(),
+        );
+        assert_eq!(by_type, ingested[1..2]);
+    }
+}
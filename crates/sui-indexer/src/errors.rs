@@ -19,6 +19,9 @@ pub enum IndexerError {
     #[error("Indexer failed to convert structs to diesel Insertable with error: `{0}`")]
     InsertableParsingError(String),
 
+    #[error("Indexer received an invalid request: `{0}`")]
+    InvalidArgumentError(String),
+
     #[error("Indexer failed to build JsonRpcServer with error: `{0}`")]
     JsonRpcServerError(#[from] sui_json_rpc::error::Error),
 
@@ -51,6 +54,7 @@ impl IndexerError {
             IndexerError::PostgresReadError(_) => "PostgresReadError".into(),
             IndexerError::PostgresWriteError(_) => "PostgresWriteError".into(),
             IndexerError::InsertableParsingError(_) => "InsertableParsingError".into(),
+            IndexerError::InvalidArgumentError(_) => "InvalidArgumentError".into(),
             IndexerError::DateTimeParsingError(_) => "DateTimeParsingError".into(),
             IndexerError::TransactionDigestParsingError(_) => {
                 "TransactionDigestParsingError".into()
@@ -1,23 +1,39 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use futures::future::try_join_all;
 use prometheus::Registry;
+use std::collections::BTreeMap;
 use std::sync::Arc;
+use sui_json_rpc_types::{
+    CheckpointId, GetPastObjectDataResponse, OwnedObjectRef, SuiObject, SuiParsedData,
+    SuiTransactionResponse,
+};
 use sui_sdk::SuiClient;
+use sui_types::base_types::{ObjectID, TransactionDigest};
 use tracing::{error, info};
 
-use sui_indexer::errors::IndexerError;
-use sui_indexer::metrics::IndexerCheckpointHandlerMetrics;
-use sui_indexer::models::checkpoints::{
-    commit_checkpoint, create_checkpoint, get_checkpoint, get_latest_checkpoint_sequence_number,
-    Checkpoint,
+use crate::custom_handlers::{transaction_event_envelopes, CustomEventHandler};
+use crate::errors::IndexerError;
+use crate::metrics::IndexerCheckpointHandlerMetrics;
+use crate::models::checkpoints::{
+    commit_checkpoint_data, create_checkpoint, get_checkpoint,
+    get_latest_checkpoint_sequence_number, Checkpoint,
 };
-use sui_indexer::{get_pg_pool_connection, PgConnectionPool};
-use sui_json_rpc_types::CheckpointId;
+use crate::models::custom_handler_logs::register_custom_handler;
+use crate::models::events::{transaction_events_to_new_events, NewEvent};
+use crate::models::objects::{written_objects, ObjectChanges};
+use crate::models::transactions::{transaction_response_to_new_transaction, NewTransaction};
+use crate::{get_pg_pool_connection, PgConnectionPool};
 
+/// Ingests checkpoints in order, committing each checkpoint with all of its transactions and
+/// events at once, so that the indexed tables are always consistent at a checkpoint boundary.
+/// The custom handlers are called with the events of each checkpoint in the same Postgres
+/// transaction.
 pub struct CheckpointHandler {
     rpc_client: SuiClient,
     pg_connection_pool: Arc<PgConnectionPool>,
+    custom_handlers: Vec<Arc<dyn CustomEventHandler>>,
     pub checkpoint_handler_metrics: IndexerCheckpointHandlerMetrics,
 }
 
@@ -30,13 +46,30 @@ impl CheckpointHandler {
         Self {
             rpc_client,
             pg_connection_pool,
+            custom_handlers: vec![],
             checkpoint_handler_metrics: IndexerCheckpointHandlerMetrics::new(prometheus_registry),
         }
     }
 
+    /// Call `custom_handlers` with the events of every checkpoint, once they caught up with it.
+    pub fn with_custom_handlers(
+        mut self,
+        custom_handlers: Vec<Arc<dyn CustomEventHandler>>,
+    ) -> Self {
+        self.custom_handlers = custom_handlers;
+        self
+    }
+
     pub async fn start(&self) -> Result<(), IndexerError> {
         info!("Indexer checkpoint handler started...");
         let mut pg_pool_conn = get_pg_pool_connection(self.pg_connection_pool.clone())?;
+        for handler in &self.custom_handlers {
+            register_custom_handler(
+                &mut pg_pool_conn,
+                handler.name(),
+                handler.start_checkpoint() as i64,
+            )?;
+        }
         let mut next_cursor_sequence_number =
             get_latest_checkpoint_sequence_number(&mut pg_pool_conn)? + 1;
         let mut previous_checkpoint = Checkpoint::default();
@@ -79,6 +112,22 @@ impl CheckpointHandler {
                     .get_checkpoint(next_cursor_checkpoint_id.clone())
                     .await;
             }
+            // unwrap here is safe because we checked for error above
+            let checkpoint = checkpoint.unwrap();
+            // A checkpoint is only committed with all of its transactions, so any failure here
+            // fails the handler, which then resumes from the last committed checkpoint.
+            let txn_responses =
+                try_join_all(checkpoint.transactions.iter().map(|tx_digest| {
+                    get_transaction_response(self.rpc_client.clone(), *tx_digest)
+                }))
+                .await?;
+            let object_contents = try_join_all(txn_responses.iter().map(|txn_response| {
+                get_object_contents(
+                    self.rpc_client.clone(),
+                    written_objects(&txn_response.effects).cloned().collect(),
+                )
+            }))
+            .await?;
             request_guard.stop_and_record();
 
             self.checkpoint_handler_metrics
@@ -89,16 +138,105 @@ impl CheckpointHandler {
                 .checkpoint_handler_metrics
                 .db_write_request_latency
                 .start_timer();
-            // unwrap here is safe because we checked for error above
-            let new_checkpoint = create_checkpoint(checkpoint.unwrap(), previous_checkpoint);
-            commit_checkpoint(&mut pg_pool_conn, new_checkpoint.clone())?;
-            info!("Checkpoint {} committed", next_cursor_sequence_number);
+            let mut new_transactions: Vec<NewTransaction> = vec![];
+            let mut new_events: Vec<NewEvent> = vec![];
+            let mut handler_events = vec![];
+            let mut object_changes = ObjectChanges::default();
+            for (txn_response, contents) in txn_responses.into_iter().zip(object_contents) {
+                object_changes.add_transaction(&txn_response.effects, &contents)?;
+                if !self.custom_handlers.is_empty() {
+                    handler_events.extend(transaction_event_envelopes(
+                        txn_response.effects.transaction_digest,
+                        txn_response.timestamp_ms,
+                        &txn_response.effects.events,
+                    ));
+                }
+                new_events.extend(transaction_events_to_new_events(
+                    txn_response.effects.transaction_digest,
+                    txn_response.timestamp_ms,
+                    txn_response.effects.events.clone(),
+                )?);
+                new_transactions.push(transaction_response_to_new_transaction(txn_response)?);
+            }
+            let txn_count = new_transactions.len();
+            let event_count = new_events.len();
+            let new_checkpoint = create_checkpoint(checkpoint, previous_checkpoint);
+            commit_checkpoint_data(
+                &mut pg_pool_conn,
+                new_checkpoint.clone(),
+                new_transactions,
+                new_events,
+                object_changes,
+                &self.custom_handlers,
+                &handler_events,
+            )?;
+            info!(
+                "Checkpoint {} committed with {} transactions and {} events",
+                next_cursor_sequence_number, txn_count, event_count
+            );
             self.checkpoint_handler_metrics
                 .total_checkpoint_processed
                 .inc();
+            self.checkpoint_handler_metrics
+                .total_transactions_processed
+                .inc_by(txn_count as u64);
+            self.checkpoint_handler_metrics
+                .total_events_processed
+                .inc_by(event_count as u64);
             db_guard.stop_and_record();
             previous_checkpoint = Checkpoint::from(new_checkpoint.clone());
             next_cursor_sequence_number += 1;
         }
     }
 }
+
+async fn get_transaction_response(
+    rpc_client: SuiClient,
+    tx_digest: TransactionDigest,
+) -> Result<SuiTransactionResponse, IndexerError> {
+    rpc_client
+        .read_api()
+        .get_transaction(tx_digest)
+        .await
+        .map_err(|e| {
+            IndexerError::FullNodeReadingError(format!(
+                "Failed reading transaction response with tx digest {:?} and err: {:?}",
+                tx_digest, e
+            ))
+        })
+}
+
+/// Read the written objects at the versions written by their transaction. Objects the fullnode no
+/// longer has at that version are indexed without contents.
+async fn get_object_contents(
+    rpc_client: SuiClient,
+    objects: Vec<OwnedObjectRef>,
+) -> Result<BTreeMap<ObjectID, SuiObject<SuiParsedData>>, IndexerError> {
+    let object_reads = try_join_all(objects.iter().map(|object| {
+        let object_id = object.reference.object_id;
+        let version = object.reference.version;
+        let rpc_client = rpc_client.clone();
+        async move {
+            rpc_client
+                .read_api()
+                .try_get_parsed_past_object(object_id, version)
+                .await
+                .map_err(|e| {
+                    IndexerError::FullNodeReadingError(format!(
+                        "Failed reading object {} at version {} and err: {:?}",
+                        object_id, version, e
+                    ))
+                })
+        }
+    }))
+    .await?;
+    Ok(object_reads
+        .into_iter()
+        .filter_map(|object_read| match object_read {
+            GetPastObjectDataResponse::VersionFound(object) => {
+                Some((object.reference.object_id, object))
+            }
+            _ => None,
+        })
+        .collect())
+}
@@ -2,10 +2,4 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod checkpoint_handler;
-pub mod event_handler;
 pub mod handler_orchestrator;
-pub mod transaction_handler;
-// TODO: remove below after wave 2
-pub mod move_event_handler;
-pub mod object_event_handler;
-pub mod publish_event_handler;
@@ -16,7 +16,9 @@ use sui_sdk::{SuiClient, SuiClientBuilder};
 use tracing::{info, warn};
 
 pub mod apis;
+pub mod custom_handlers;
 pub mod errors;
+pub mod handlers;
 pub mod metrics;
 pub mod models;
 pub mod schema;
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use sui_indexer::errors::IndexerError;
+use sui_indexer::handlers::handler_orchestrator::HandlerOrchestrator;
 use sui_indexer::{build_json_rpc_server, new_pg_connection_pool, new_rpc_client};
 use sui_node::metrics::start_prometheus_server;
 
@@ -13,10 +14,8 @@ use tracing::info;
 
 use clap::Parser;
 
-pub mod handlers;
 pub mod processors;
 
-use handlers::handler_orchestrator::HandlerOrchestrator;
 use processors::processor_orchestrator::ProcessorOrchestrator;
 
 #[tokio::main]
@@ -53,8 +52,8 @@ async fn main() -> Result<(), IndexerError> {
     retry(ExponentialBackoff::default(), || async {
         let rpc_client = new_rpc_client(indexer_config.rpc_client_url.clone()).await?;
         let pg_connection_pool = new_pg_connection_pool(indexer_config.db_url.clone()).await?;
-        // NOTE: Each handler is responsible for one type of data from nodes,like transactions and events;
-        // Handler orchestrator runs these handlers in parallel and manage them upon errors etc.
+        // NOTE: The checkpoint handler ingests checkpoints with their transactions, events and objects;
+        // Handler orchestrator runs it and the custom handler backfills in parallel and manage them upon errors etc.
         let handler_rpc_client = rpc_client.clone();
         let handler_pg_pool = pg_connection_pool.clone();
 
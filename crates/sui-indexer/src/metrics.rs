@@ -14,244 +14,13 @@ const LATENCY_SEC_BUCKETS: &[f64] = &[
     80.0, 100.0, 200.0,
 ];
 
-#[derive(Clone, Debug)]
-pub struct IndexerTransactionHandlerMetrics {
-    pub total_transactions_received: IntCounter,
-    pub total_transactions_processed: IntCounter,
-    pub total_transaction_checkpoint_processed: IntCounter,
-    pub total_transaction_handler_error: IntCounter,
-
-    pub db_write_request_latency: Histogram,
-    pub full_node_read_request_latency: Histogram,
-    pub checkpoint_db_read_request_latency: Histogram,
-}
-
-impl IndexerTransactionHandlerMetrics {
-    pub fn new(registry: &Registry) -> Self {
-        Self {
-            total_transactions_received: register_int_counter_with_registry!(
-                "total_transactions_received",
-                "Total number of transactions received",
-                registry,
-            )
-            .unwrap(),
-            total_transactions_processed: register_int_counter_with_registry!(
-                "total_transactions_processed",
-                "Total number of transactions processed",
-                registry,
-            )
-            .unwrap(),
-            total_transaction_checkpoint_processed: register_int_counter_with_registry!(
-                "total_transaction_checkpoint_processed",
-                "Total number of transactions processed",
-                registry,
-            )
-            .unwrap(),
-            total_transaction_handler_error: register_int_counter_with_registry!(
-                "total_transaction_handler_error",
-                "Total number of transaction handler error",
-                registry,
-            )
-            .unwrap(),
-            db_write_request_latency: register_histogram_with_registry!(
-                "transaction_db_write_request_latency",
-                "Time spent commiting a transaction to the db",
-                LATENCY_SEC_BUCKETS.to_vec(),
-                registry,
-            )
-            .unwrap(),
-            full_node_read_request_latency: register_histogram_with_registry!(
-                "transaction_full_node_read_request_latency",
-                "Time spent in waiting for a new transaction from the Full Node",
-                LATENCY_SEC_BUCKETS.to_vec(),
-                registry,
-            )
-            .unwrap(),
-            checkpoint_db_read_request_latency: register_histogram_with_registry!(
-                "transaction_checkpoint_db_read_request_latency",
-                "Time spent in reading a transaction from the checkpoint db",
-                LATENCY_SEC_BUCKETS.to_vec(),
-                registry,
-            )
-            .unwrap(),
-        }
-    }
-}
-
-#[derive(Clone, Debug)]
-pub struct IndexerEventHandlerMetrics {
-    pub total_events_received: IntCounter,
-    pub total_events_processed: IntCounter,
-
-    pub total_event_page_fetch_attempt: IntCounter,
-    pub total_event_page_received: IntCounter,
-    pub total_event_page_committed: IntCounter,
-
-    pub total_event_handler_error: IntCounter,
-
-    pub db_write_request_latency: Histogram,
-    pub full_node_read_request_latency: Histogram,
-}
-
-impl IndexerEventHandlerMetrics {
-    pub fn new(registry: &Registry) -> Self {
-        Self {
-            total_events_received: register_int_counter_with_registry!(
-                "total_events_received",
-                "Total number of events received",
-                registry,
-            )
-            .unwrap(),
-            total_events_processed: register_int_counter_with_registry!(
-                "total_events_processed",
-                "Total number of events processed",
-                registry,
-            )
-            .unwrap(),
-            total_event_page_fetch_attempt: register_int_counter_with_registry!(
-                "total_event_page_fetch_attempt",
-                "Total number of event page fetch attempt",
-                registry,
-            )
-            .unwrap(),
-            total_event_page_received: register_int_counter_with_registry!(
-                "total_event_page_received",
-                "Total number of event page received",
-                registry,
-            )
-            .unwrap(),
-            total_event_page_committed: register_int_counter_with_registry!(
-                "total_event_page_committed",
-                "Total number of event page committed",
-                registry,
-            )
-            .unwrap(),
-            total_event_handler_error: register_int_counter_with_registry!(
-                "total_event_handler_error",
-                "Total number of event handler error",
-                registry,
-            )
-            .unwrap(),
-            db_write_request_latency: register_histogram_with_registry!(
-                "event_db_write_request_latency",
-                "Time spent commiting an event to the db",
-                LATENCY_SEC_BUCKETS.to_vec(),
-                registry,
-            )
-            .unwrap(),
-            full_node_read_request_latency: register_histogram_with_registry!(
-                "event_full_node_read_request_latency",
-                "Time spent in waiting for a new event from the Full Node",
-                LATENCY_SEC_BUCKETS.to_vec(),
-                registry,
-            )
-            .unwrap(),
-        }
-    }
-}
-
-#[derive(Clone, Debug)]
-
-// TODO: remove object and publish event related metrics after wave 2
-pub struct IndexerObjectEventHandlerMetrics {
-    pub total_object_events_received: IntCounter,
-    pub total_object_events_processed: IntCounter,
-    pub total_object_event_handler_error: IntCounter,
-}
-
-impl IndexerObjectEventHandlerMetrics {
-    pub fn new(registry: &Registry) -> Self {
-        Self {
-            total_object_events_received: register_int_counter_with_registry!(
-                "total_object_events_received",
-                "Total number of object events received",
-                registry,
-            )
-            .unwrap(),
-            total_object_events_processed: register_int_counter_with_registry!(
-                "total_object_events_processed",
-                "Total number of object events processed",
-                registry,
-            )
-            .unwrap(),
-            total_object_event_handler_error: register_int_counter_with_registry!(
-                "total_object_event_handler_error",
-                "Total number of object event handler error",
-                registry,
-            )
-            .unwrap(),
-        }
-    }
-}
-
-#[derive(Clone, Debug)]
-pub struct IndexerPublishEventHandlerMetrics {
-    pub total_publish_events_received: IntCounter,
-    pub total_publish_events_processed: IntCounter,
-    pub total_publish_event_handler_error: IntCounter,
-}
-
-impl IndexerPublishEventHandlerMetrics {
-    pub fn new(registry: &Registry) -> Self {
-        Self {
-            total_publish_events_received: register_int_counter_with_registry!(
-                "total_publish_events_received",
-                "Total number of publish events received",
-                registry,
-            )
-            .unwrap(),
-            total_publish_events_processed: register_int_counter_with_registry!(
-                "total_publish_events_processed",
-                "Total number of publish events processed",
-                registry,
-            )
-            .unwrap(),
-            total_publish_event_handler_error: register_int_counter_with_registry!(
-                "total_publish_event_handler_error",
-                "Total number of publish event handler error",
-                registry,
-            )
-            .unwrap(),
-        }
-    }
-}
-
-pub struct IndexerMoveEventHandlerMetrics {
-    pub total_move_events_received: IntCounter,
-    pub total_move_events_processed: IntCounter,
-    pub total_move_event_handler_error: IntCounter,
-}
-
-impl IndexerMoveEventHandlerMetrics {
-    pub fn new(registry: &Registry) -> Self {
-        Self {
-            total_move_events_received: register_int_counter_with_registry!(
-                "total_move_events_received",
-                "Total number of move events received",
-                registry,
-            )
-            .unwrap(),
-            total_move_events_processed: register_int_counter_with_registry!(
-                "total_move_events_processed",
-                "Total number of move events processed",
-                registry,
-            )
-            .unwrap(),
-            total_move_event_handler_error: register_int_counter_with_registry!(
-                "total_move_event_handler_error",
-                "Total number of move event handler error",
-                registry,
-            )
-            .unwrap(),
-        }
-    }
-}
-
 pub struct IndexerCheckpointHandlerMetrics {
     pub total_checkpoint_requested: IntCounter,
     pub total_checkpoint_received: IntCounter,
     pub total_checkpoint_processed: IntCounter,
     pub total_checkpoint_handler_error: IntCounter,
+    pub total_transactions_processed: IntCounter,
+    pub total_events_processed: IntCounter,
     pub db_write_request_latency: Histogram,
     pub full_node_read_request_latency: Histogram,
 }
@@ -283,6 +52,18 @@ impl IndexerCheckpointHandlerMetrics {
                 registry,
             )
             .unwrap(),
+            total_transactions_processed: register_int_counter_with_registry!(
+                "total_transactions_processed",
+                "Total number of transactions committed with their checkpoints",
+                registry,
+            )
+            .unwrap(),
+            total_events_processed: register_int_counter_with_registry!(
+                "total_events_processed",
+                "Total number of events committed with their checkpoints",
+                registry,
+            )
+            .unwrap(),
             db_write_request_latency: register_histogram_with_registry!(
                 "checkpoint_db_write_request_latency",
                 "Time spent commiting a checkpoint to the db",
@@ -292,7 +73,7 @@ impl IndexerCheckpointHandlerMetrics {
             .unwrap(),
             full_node_read_request_latency: register_histogram_with_registry!(
                 "checkpoint_full_node_read_request_latency",
-                "Time spent in waiting for a new checkpoint from the Full Node",
+                "Time spent in waiting for a new checkpoint and its transactions from the Full Node",
                 LATENCY_SEC_BUCKETS.to_vec(),
                 registry,
             )
@@ -327,31 +108,6 @@ impl IndexerAddressProcessorMetrics {
     }
 }
 
-#[derive(Clone, Debug)]
-pub struct IndexerObjectProcessorMetrics {
-    pub total_object_batch_processed: IntCounter,
-    pub total_object_processor_error: IntCounter,
-}
-
-impl IndexerObjectProcessorMetrics {
-    pub fn new(registry: &Registry) -> Self {
-        Self {
-            total_object_batch_processed: register_int_counter_with_registry!(
-                "total_object_batch_processed",
-                "Total number of object batches processed",
-                registry,
-            )
-            .unwrap(),
-            total_object_processor_error: register_int_counter_with_registry!(
-                "total_object_processor_error",
-                "Total number of object processor error",
-                registry,
-            )
-            .unwrap(),
-        }
-    }
-}
-
 #[derive(Clone, Debug)]
 pub struct IndexerPackageProcessorMetrics {
     pub total_package_batch_processed: IntCounter,
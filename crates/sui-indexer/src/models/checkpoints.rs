@@ -1,17 +1,39 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::sync::Arc;
+
 use chrono::NaiveDateTime;
 use diesel::dsl::max;
 use diesel::prelude::*;
 use diesel::result::Error;
-use sui_json_rpc_types::Checkpoint as RpcCheckpoint;
+use fastcrypto::encoding::{Base58, Encoding};
+use sui_json_rpc_types::{Checkpoint as RpcCheckpoint, SuiEventEnvelope};
+use sui_protocol_config::ProtocolVersion;
+use sui_types::digests::{CheckpointDigest, TransactionDigest};
+use sui_types::gas::GasCostSummary;
+use sui_types::messages_checkpoint::EndOfEpochData;
 
+use crate::custom_handlers::{process_committed_checkpoint, CustomEventHandler};
 use crate::errors::IndexerError;
+use crate::models::events::{NewEvent, EVENT_BATCH_SIZE};
+use crate::models::move_events::new_move_events;
+use crate::models::object_events::new_object_events;
+use crate::models::objects::{commit_object_changes, ObjectChanges};
+use crate::models::publish_events::new_publish_events;
+use crate::models::transactions::NewTransaction;
 use crate::schema::checkpoints;
-use crate::schema::checkpoints::dsl::{checkpoints as checkpoints_table, sequence_number};
+use crate::schema::checkpoints::dsl::{
+    checkpoint_digest, checkpoints as checkpoints_table, sequence_number,
+};
+use crate::schema::{events, move_events, object_events, publish_events, transactions};
 use crate::PgPoolConnection;
 
+// NOTE: TRANSACTION_BATCH_SIZE * number of columns in transactions table
+// should be less than 65535, which is the max "parameters" Postgres
+// can take in one query.
+const TRANSACTION_BATCH_SIZE: usize = 1000;
+
 #[derive(Queryable, Debug, Clone)]
 pub struct Checkpoint {
     pub sequence_number: i64,
@@ -103,6 +125,71 @@ impl From<NewCheckpoint> for Checkpoint {
     }
 }
 
+impl Checkpoint {
+    /// Rebuild the fullnode's view of this checkpoint from the indexed row.
+    pub fn to_rpc_checkpoint(&self) -> Result<RpcCheckpoint, IndexerError> {
+        let end_of_epoch_data = match (&self.next_epoch_committee, self.next_epoch_protocol_version)
+        {
+            (Some(committee), Some(version)) => Some(EndOfEpochData {
+                next_epoch_committee: serde_json::from_str(committee).map_err(|e| {
+                    IndexerError::PostgresReadError(format!(
+                        "Failed deserializing next epoch committee of checkpoint {} with error: {:?}",
+                        self.sequence_number, e
+                    ))
+                })?,
+                next_epoch_protocol_version: ProtocolVersion::new(version as u64),
+            }),
+            _ => None,
+        };
+        let transactions = self
+            .transactions
+            .iter()
+            .flatten()
+            .map(|digest| {
+                digest.parse::<TransactionDigest>().map_err(|e| {
+                    IndexerError::TransactionDigestParsingError(format!(
+                        "Failed parsing transaction digest {:?} with error: {:?}",
+                        digest, e
+                    ))
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(RpcCheckpoint {
+            epoch: self.epoch as u64,
+            sequence_number: self.sequence_number as u64,
+            digest: parse_checkpoint_digest(&self.checkpoint_digest)?,
+            network_total_transactions: self.total_transactions_from_genesis as u64,
+            previous_digest: self
+                .previous_checkpoint_digest
+                .as_deref()
+                .map(parse_checkpoint_digest)
+                .transpose()?,
+            epoch_rolling_gas_cost_summary: GasCostSummary {
+                computation_cost: self.total_computation_cost as u64,
+                storage_cost: self.total_storage_cost as u64,
+                storage_rebate: self.total_storage_rebate as u64,
+            },
+            timestamp_ms: self.timestamp_ms as u64,
+            end_of_epoch_data,
+            transactions,
+        })
+    }
+}
+
+fn parse_checkpoint_digest(digest: &str) -> Result<CheckpointDigest, IndexerError> {
+    let bytes: [u8; 32] = Base58::decode(digest)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| {
+            IndexerError::PostgresReadError(format!(
+                "Failed parsing checkpoint digest {:?} read from PostgresDB",
+                digest
+            ))
+        })?;
+    Ok(CheckpointDigest::new(bytes))
+}
+
 pub fn create_checkpoint(
     rpc_checkpoint: RpcCheckpoint,
     previous_checkpoint_commit: Checkpoint,
@@ -201,6 +288,92 @@ fn commit_checkpoint_impl(
     })
 }
 
+/// Commit a checkpoint together with the transactions it contains, the events they emitted and the
+/// objects they changed, in one Postgres transaction. The checkpoints table doubles as the
+/// ingestion cursor, so a checkpoint is visible if and only if all of its rows are. The
+/// `custom_handlers` write the `handler_events` of the checkpoint in the same transaction.
+pub fn commit_checkpoint_data(
+    pg_pool_conn: &mut PgPoolConnection,
+    checkpoint: NewCheckpoint,
+    new_transactions: Vec<NewTransaction>,
+    new_events: Vec<NewEvent>,
+    object_changes: ObjectChanges,
+    custom_handlers: &[Arc<dyn CustomEventHandler>],
+    handler_events: &[SuiEventEnvelope],
+) -> Result<(), IndexerError> {
+    let commit_result: Result<(), Error> = pg_pool_conn
+        .build_transaction()
+        .read_write()
+        .run::<_, Error, _>(|conn| {
+            // Conflicts only happen when a checkpoint is re-ingested, in which case the rows
+            // are identical.
+            for transaction_batch in new_transactions.chunks(TRANSACTION_BATCH_SIZE) {
+                diesel::insert_into(transactions::table)
+                    .values(transaction_batch)
+                    .on_conflict(transactions::transaction_digest)
+                    .do_nothing()
+                    .execute(conn)?;
+            }
+            for event_batch in new_events.chunks(EVENT_BATCH_SIZE) {
+                diesel::insert_into(events::table)
+                    .values(event_batch)
+                    .on_conflict((events::transaction_digest, events::event_sequence))
+                    .do_nothing()
+                    .execute(conn)?;
+            }
+            // TODO: remove below after wave 2
+            for event_batch in new_object_events(&new_events).chunks(EVENT_BATCH_SIZE) {
+                diesel::insert_into(object_events::table)
+                    .values(event_batch)
+                    .on_conflict((
+                        object_events::transaction_digest,
+                        object_events::event_sequence,
+                    ))
+                    .do_nothing()
+                    .execute(conn)?;
+            }
+            for event_batch in new_publish_events(&new_events).chunks(EVENT_BATCH_SIZE) {
+                diesel::insert_into(publish_events::table)
+                    .values(event_batch)
+                    .on_conflict((
+                        publish_events::transaction_digest,
+                        publish_events::event_sequence,
+                    ))
+                    .do_nothing()
+                    .execute(conn)?;
+            }
+            for event_batch in new_move_events(&new_events).chunks(EVENT_BATCH_SIZE) {
+                diesel::insert_into(move_events::table)
+                    .values(event_batch)
+                    .on_conflict((move_events::transaction_digest, move_events::event_sequence))
+                    .do_nothing()
+                    .execute(conn)?;
+            }
+            commit_object_changes(conn, &object_changes)?;
+            diesel::insert_into(checkpoints_table)
+                .values(&checkpoint)
+                .on_conflict(sequence_number)
+                .do_nothing()
+                .execute(conn)?;
+            process_committed_checkpoint(
+                conn,
+                custom_handlers,
+                checkpoint.sequence_number as u64,
+                handler_events,
+            )
+        });
+    commit_result.map_err(|e| {
+        IndexerError::PostgresWriteError(format!(
+            "Failed writing checkpoint {} with {} transactions, {} events and {} object changes to PostgresDB with error: {:?}",
+            checkpoint.sequence_number,
+            new_transactions.len(),
+            new_events.len(),
+            object_changes.len(),
+            e
+        ))
+    })
+}
+
 pub fn get_checkpoint(
     pg_pool_conn: &mut PgPoolConnection,
     checkpoint_sequence_number: i64,
@@ -242,3 +415,45 @@ pub fn get_latest_checkpoint_sequence_number(
         ))
     })
 }
+
+pub fn get_checkpoint_by_sequence_number(
+    pg_pool_conn: &mut PgPoolConnection,
+    checkpoint_sequence_number: i64,
+) -> Result<Option<Checkpoint>, IndexerError> {
+    let checkpoint_read_result = pg_pool_conn
+        .build_transaction()
+        .read_only()
+        .run::<_, Error, _>(|conn| {
+            checkpoints_table
+                .filter(sequence_number.eq(checkpoint_sequence_number))
+                .first::<Checkpoint>(conn)
+                .optional()
+        });
+    checkpoint_read_result.map_err(|e| {
+        IndexerError::PostgresReadError(format!(
+            "Failed reading checkpoint {} in PostgresDB with error {:?}",
+            checkpoint_sequence_number, e
+        ))
+    })
+}
+
+pub fn get_checkpoint_by_digest(
+    pg_pool_conn: &mut PgPoolConnection,
+    digest: String,
+) -> Result<Option<Checkpoint>, IndexerError> {
+    let checkpoint_read_result = pg_pool_conn
+        .build_transaction()
+        .read_only()
+        .run::<_, Error, _>(|conn| {
+            checkpoints_table
+                .filter(checkpoint_digest.eq(&digest))
+                .first::<Checkpoint>(conn)
+                .optional()
+        });
+    checkpoint_read_result.map_err(|e| {
+        IndexerError::PostgresReadError(format!(
+            "Failed reading checkpoint with digest {} in PostgresDB with error {:?}",
+            digest, e
+        ))
+    })
+}
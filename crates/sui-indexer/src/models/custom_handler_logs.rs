@@ -0,0 +1,106 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+use diesel::result::Error;
+
+use crate::errors::IndexerError;
+use crate::schema::custom_handler_logs;
+use crate::schema::custom_handler_logs::dsl::{
+    caught_up, custom_handler_logs as custom_handler_logs_table, handler_name,
+    next_checkpoint_sequence_number,
+};
+use crate::PgPoolConnection;
+
+#[derive(Queryable, Insertable, Debug, Clone)]
+#[diesel(table_name = custom_handler_logs)]
+pub struct CustomHandlerLog {
+    pub handler_name: String,
+    /// The next checkpoint to backfill the handler with.
+    pub next_checkpoint_sequence_number: i64,
+    /// Whether the backfill caught up with the checkpoint handler, which calls the handler from
+    /// then on.
+    pub caught_up: bool,
+}
+
+/// Register the custom handler `name`, to be backfilled from `start_checkpoint`. A handler that is
+/// already registered keeps its log.
+pub fn register_custom_handler(
+    pg_pool_conn: &mut PgPoolConnection,
+    name: &str,
+    start_checkpoint: i64,
+) -> Result<(), IndexerError> {
+    let log = CustomHandlerLog {
+        handler_name: name.to_string(),
+        next_checkpoint_sequence_number: start_checkpoint,
+        caught_up: false,
+    };
+    let log_commit_result: Result<usize, Error> = pg_pool_conn
+        .build_transaction()
+        .read_write()
+        .run::<_, Error, _>(|conn| {
+        diesel::insert_into(custom_handler_logs_table)
+            .values(&log)
+            .on_conflict(handler_name)
+            .do_nothing()
+            .execute(conn)
+    });
+
+    log_commit_result.map(|_| ()).map_err(|e| {
+        IndexerError::PostgresWriteError(format!(
+            "Failed registering custom handler {} in PostgresDB with error {:?}",
+            name, e
+        ))
+    })
+}
+
+/// Read the log of the custom handler `name`, or `None` if the handler is not registered.
+pub fn read_custom_handler_log(
+    pg_pool_conn: &mut PgPoolConnection,
+    name: &str,
+) -> Result<Option<CustomHandlerLog>, IndexerError> {
+    let log_read_result: Result<Option<CustomHandlerLog>, Error> = pg_pool_conn
+        .build_transaction()
+        .read_only()
+        .run::<_, Error, _>(|conn| {
+            custom_handler_logs_table
+                .filter(handler_name.eq(name))
+                .first::<CustomHandlerLog>(conn)
+                .optional()
+        });
+
+    log_read_result.map_err(|e| {
+        IndexerError::PostgresReadError(format!(
+            "Failed reading custom handler log of {} in PostgresDB with error {:?}",
+            name, e
+        ))
+    })
+}
+
+/// Read the log of the custom handler `name` and lock it until the end of the Postgres
+/// transaction of `conn`, so that the backfill and the checkpoint handler take turns calling the
+/// handler.
+pub fn lock_custom_handler_log(
+    conn: &mut PgConnection,
+    name: &str,
+) -> Result<CustomHandlerLog, Error> {
+    custom_handler_logs_table
+        .filter(handler_name.eq(name))
+        .for_update()
+        .first::<CustomHandlerLog>(conn)
+}
+
+/// Update the log of a custom handler. Meant to be called from within the Postgres transaction
+/// that commits the handler's writes, so that both land together.
+pub fn update_custom_handler_log(
+    conn: &mut PgConnection,
+    log: &CustomHandlerLog,
+) -> Result<usize, Error> {
+    diesel::update(custom_handler_logs_table.filter(handler_name.eq(&log.handler_name)))
+        .set((
+            next_checkpoint_sequence_number.eq(log.next_checkpoint_sequence_number),
+            caught_up.eq(log.caught_up),
+        ))
+        .execute(conn)
+}
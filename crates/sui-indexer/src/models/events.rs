@@ -16,7 +16,7 @@ use sui_types::base_types::TransactionDigest;
 // NOTE: EVENT_BATCH_SIZE * number of columns in events table
 // should less than 65535, which is the max "parameters" Postgres
 // can take in one query.
-const EVENT_BATCH_SIZE: usize = 1000;
+pub(crate) const EVENT_BATCH_SIZE: usize = 1000;
 
 #[derive(Queryable, Debug)]
 pub struct Event {
@@ -26,7 +26,6 @@ pub struct Event {
     pub event_time: Option<NaiveDateTime>,
     pub event_type: String,
     pub event_content: String,
-    pub next_cursor_transaction_digest: Option<String>,
 }
 
 #[derive(Debug, Insertable)]
@@ -37,15 +36,6 @@ pub struct NewEvent {
     pub event_time: Option<NaiveDateTime>,
     pub event_type: String,
     pub event_content: String,
-    pub next_cursor_transaction_digest: Option<String>,
-}
-
-#[derive(Clone, Debug)]
-pub struct IndexerEventEnvelope {
-    pub transaction_digest: TransactionDigest,
-    pub timestamp: Option<u64>,
-    pub events: Vec<SuiEvent>,
-    pub next_cursor: Option<TransactionDigest>,
 }
 
 pub fn read_events(
@@ -71,65 +61,34 @@ pub fn read_events(
     })
 }
 
-pub fn read_last_event(pg_pool_conn: &mut PgPoolConnection) -> Result<Option<Event>, IndexerError> {
-    let event_read_result: Result<Option<Event>, Error> = pg_pool_conn
+/// Read the events emitted by the transactions with digests `digests`.
+pub fn read_transaction_events(
+    pg_pool_conn: &mut PgPoolConnection,
+    digests: &[String],
+) -> Result<Vec<Event>, IndexerError> {
+    let event_read_result: Result<Vec<Event>, Error> = pg_pool_conn
         .build_transaction()
         .read_only()
         .run::<_, Error, _>(|conn| {
             events_table
-                .order(id.desc())
-                .limit(1)
+                .filter(events::transaction_digest.eq_any(digests))
                 .load::<Event>(conn)
-                .map(|mut events| events.pop())
         });
 
     event_read_result.map_err(|e| {
-        IndexerError::PostgresReadError(format!("Failed reading last event with error: {:?}", e))
+        IndexerError::PostgresReadError(format!(
+            "Failed reading events of {} transactions with error: {:?}",
+            digests.len(),
+            e
+        ))
     })
 }
 
-pub fn commit_events(
-    pg_pool_conn: &mut PgPoolConnection,
-    mut event_envelopes: Vec<IndexerEventEnvelope>,
-    txn_page_next_cursor: Option<TransactionDigest>,
-) -> Result<Option<(usize, TransactionDigest)>, IndexerError> {
-    // No op when there is no more than 1 event envelope,
-    // which could have  been left as next cursor from last iteration.
-    if event_envelopes.len() <= 1 {
-        return Ok(None);
-    }
-    let next_cursor: TransactionDigest;
-    if let Some(next_cursor_val) = txn_page_next_cursor {
-        // unwrap is safe because we already checked the length of new_events
-        let mut last_event_envelope = event_envelopes.pop().unwrap();
-        last_event_envelope.next_cursor = Some(next_cursor_val);
-        event_envelopes.push(last_event_envelope);
-        next_cursor = next_cursor_val;
-    } else {
-        // unwrap here are safe because we already checked the length of new_events
-        let next_cursor_event_envelope = event_envelopes.pop().unwrap();
-        let mut last_event_envelope = event_envelopes.pop().unwrap();
-        last_event_envelope.next_cursor = Some(next_cursor_event_envelope.transaction_digest);
-        event_envelopes.push(last_event_envelope);
-        next_cursor = next_cursor_event_envelope.transaction_digest;
-    }
-    let new_events: Vec<NewEvent> = event_envelopes
-        .into_iter()
-        .flat_map(|e| event_envelope_to_events(pg_pool_conn, e))
-        .collect();
-
-    // NOTE: Postgres can take at most 65535 parameters in a single query, one single txn on private testnet
-    // can have thousands of events, thus we will need to batch write here.
-    let event_batch_commit_result = commit_events_impl(pg_pool_conn, new_events);
-    Ok(Some((event_batch_commit_result, next_cursor)))
-}
-
 fn sui_event_to_new_event(
     sui_event: SuiEvent,
     sequence_number: i64,
     transaction_digest: TransactionDigest,
     timestamp_opt: Option<u64>,
-    next_cursor: Option<TransactionDigest>,
 ) -> Result<NewEvent, IndexerError> {
     let event_json = serde_json::to_string(&sui_event).map_err(|err| {
         IndexerError::InsertableParsingError(format!(
@@ -154,72 +113,20 @@ fn sui_event_to_new_event(
         event_time: naive_date_time_opt,
         event_type: sui_event.get_event_type(),
         event_content: event_json,
-        next_cursor_transaction_digest: next_cursor.map(|d| d.base58_encode()),
     })
 }
 
-fn event_envelope_to_events(
-    pg_pool_conn: &mut PgPoolConnection,
-    event_envelope: IndexerEventEnvelope,
-) -> Vec<NewEvent> {
-    let mut errors = vec![];
-    let new_events: Vec<NewEvent> = event_envelope
-        .events
+/// Convert the events emitted by a transaction, in emission order, to rows of the events table.
+pub fn transaction_events_to_new_events(
+    transaction_digest: TransactionDigest,
+    timestamp_opt: Option<u64>,
+    events: Vec<SuiEvent>,
+) -> Result<Vec<NewEvent>, IndexerError> {
+    events
         .into_iter()
         .enumerate()
-        .map(|(i, e)| {
-            sui_event_to_new_event(
-                e,
-                i as i64,
-                event_envelope.transaction_digest,
-                event_envelope.timestamp,
-                event_envelope.next_cursor,
-            )
-        })
-        .filter_map(|r| r.map_err(|e| errors.push(e)).ok())
-        .collect();
-    log_errors_to_pg(pg_pool_conn, errors);
-    new_events
-}
-
-fn commit_events_impl(pg_pool_conn: &mut PgPoolConnection, new_events: Vec<NewEvent>) -> usize {
-    let mut errors = vec![];
-    let mut new_events_to_process = new_events;
-    let mut total_event_committed = 0;
-    while !new_events_to_process.is_empty() {
-        let new_events_to_process_batch = new_events_to_process
-            .drain(..std::cmp::min(new_events_to_process.len(), EVENT_BATCH_SIZE))
-            .collect::<Vec<_>>();
-        let new_events_to_process_batch_len = new_events_to_process_batch.len();
-
-        let event_batch_commit_result: Result<usize, Error> = pg_pool_conn
-            .build_transaction()
-            .read_write()
-            .run::<_, Error, _>(|conn| {
-                diesel::insert_into(events::table)
-                    .values(&new_events_to_process_batch)
-                    .execute(conn)
-            });
-        match event_batch_commit_result {
-            Ok(inserted_count) => {
-                total_event_committed += inserted_count;
-                if inserted_count != new_events_to_process_batch_len {
-                    errors.push(IndexerError::PostgresWriteError(format!(
-                        "Inserted {} events, but expected to insert {}",
-                        inserted_count, new_events_to_process_batch_len
-                    )));
-                }
-            }
-            Err(e) => {
-                errors.push(IndexerError::PostgresWriteError(format!(
-                    "Failed inserting events with error: {:?}",
-                    e
-                )));
-            }
-        }
-    }
-    log_errors_to_pg(pg_pool_conn, errors);
-    total_event_committed
+        .map(|(i, e)| sui_event_to_new_event(e, i as i64, transaction_digest, timestamp_opt))
+        .collect()
 }
 
 pub fn events_to_sui_events(
@@ -248,3 +155,71 @@ pub fn events_to_sui_events(
     log_errors_to_pg(pg_pool_conn, errors);
     sui_events_to_process
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::move_events::new_move_events;
+    use crate::models::object_events::new_object_events;
+    use crate::models::publish_events::new_publish_events;
+    use sui_types::base_types::{ObjectID, SequenceNumber, SuiAddress};
+    use sui_types::digests::ObjectDigest;
+    use sui_types::object::Owner;
+
+    #[test]
+    fn test_transaction_events_to_event_tables() {
+        let sender = SuiAddress::random_for_testing_only();
+        let package_id = ObjectID::from_single_byte(2);
+        let transaction_digest = TransactionDigest::random();
+        let events = vec![
+            SuiEvent::Publish {
+                sender,
+                package_id,
+                version: SequenceNumber::from_u64(1),
+                digest: ObjectDigest::new([1; 32]),
+            },
+            SuiEvent::NewObject {
+                package_id,
+                transaction_module: "devnet_nft".to_string(),
+                sender,
+                recipient: Owner::AddressOwner(sender),
+                object_type: "0x2::devnet_nft::DevNetNFT".to_string(),
+                object_id: ObjectID::from_single_byte(3),
+                version: SequenceNumber::from_u64(1),
+            },
+            SuiEvent::MoveEvent {
+                package_id,
+                transaction_module: "devnet_nft".to_string(),
+                sender,
+                type_: "0x2::devnet_nft::MintNFTEvent".to_string(),
+                fields: None,
+                bcs: vec![],
+            },
+        ];
+        let new_events =
+            transaction_events_to_new_events(transaction_digest, Some(1000), events).unwrap();
+        // Events are keyed by their transaction and their position in it.
+        assert_eq!(
+            new_events
+                .iter()
+                .map(|e| (e.transaction_digest.clone(), e.event_sequence))
+                .collect::<Vec<_>>(),
+            (0..3)
+                .map(|i| (transaction_digest.base58_encode(), i))
+                .collect::<Vec<_>>()
+        );
+
+        // Each event table gets its events with the same keys.
+        let publish_events = new_publish_events(&new_events);
+        assert_eq!(publish_events.len(), 1);
+        assert_eq!(publish_events[0].event_sequence, 0);
+        let object_events = new_object_events(&new_events);
+        assert_eq!(object_events.len(), 1);
+        assert_eq!(object_events[0].event_sequence, 1);
+        let move_events = new_move_events(&new_events);
+        assert_eq!(move_events.len(), 1);
+        assert_eq!(move_events[0].event_sequence, 2);
+        assert_eq!(move_events[0].event_content, new_events[2].event_content);
+        assert_eq!(move_events[0].event_time, new_events[2].event_time);
+    }
+}
@@ -4,17 +4,14 @@
 pub mod address_logs;
 pub mod addresses;
 pub mod checkpoints;
+pub mod custom_handler_logs;
 pub mod error_logs;
 pub mod events;
-pub mod object_logs;
 pub mod objects;
 pub mod package_logs;
 pub mod packages;
 pub mod transactions;
 // TODO: remove below after wave 2
-pub mod move_event_logs;
 pub mod move_events;
-pub mod object_event_logs;
 pub mod object_events;
-pub mod publish_event_logs;
 pub mod publish_events;
@@ -4,7 +4,9 @@
 use crate::errors::IndexerError;
 use crate::schema::objects;
 use crate::schema::objects::dsl::{
+    object_content as object_content_column, object_digest as object_digest_column,
     object_id as object_id_column, object_status as object_status_column, objects as objects_table,
+    owner_address as owner_address_column, owner_type as owner_type_column,
     version as version_column,
 };
 use crate::PgPoolConnection;
@@ -12,10 +14,18 @@ use crate::PgPoolConnection;
 use diesel::pg::upsert::excluded;
 use diesel::prelude::*;
 use diesel::result::Error;
+use fastcrypto::encoding::{Base58, Encoding};
 use std::collections::BTreeMap;
-use sui_json_rpc_types::SuiEvent;
+use sui_json_rpc_types::{
+    GetObjectDataResponse, GetPastObjectDataResponse, OwnedObjectRef, SuiData, SuiEvent, SuiObject,
+    SuiObjectInfo, SuiObjectRef, SuiParsedData, SuiTransactionEffects,
+};
+use sui_types::base_types::{ObjectID, SequenceNumber, SuiAddress};
+use sui_types::digests::ObjectDigest;
 use sui_types::object::Owner;
 
+const OBJECT_BATCH_SIZE: usize = 1000;
+
 #[derive(Queryable, Debug, Identifiable)]
 #[diesel(primary_key(object_id))]
 pub struct Object {
@@ -29,9 +39,12 @@ pub struct Object {
     pub transaction_module: String,
     pub object_type: Option<String>,
     pub object_status: String,
+    pub object_digest: Option<String>,
+    pub previous_transaction: Option<String>,
+    pub object_content: Option<String>,
 }
 
-#[derive(Insertable, Debug)]
+#[derive(Insertable, Debug, Clone)]
 #[diesel(table_name = objects)]
 pub struct NewObject {
     pub object_id: String,
@@ -43,162 +56,314 @@ pub struct NewObject {
     pub transaction_module: String,
     pub object_type: Option<String>,
     pub object_status: String,
+    pub object_digest: Option<String>,
+    pub previous_transaction: Option<String>,
+    pub object_content: Option<String>,
 }
 
-pub fn commit_new_objects(
-    pg_pool_conn: &mut PgPoolConnection,
-    new_objects: Vec<NewObject>,
-) -> Result<usize, IndexerError> {
-    if new_objects.is_empty() {
-        return Ok(0);
+/// The reference of an object deleted or wrapped by a transaction.
+#[derive(Debug, Clone)]
+pub struct DeletedObject {
+    pub object_id: String,
+    pub version: i64,
+    pub object_digest: String,
+    pub previous_transaction: String,
+}
+
+/// The latest state of the objects changed by the transactions of a checkpoint.
+#[derive(Debug, Default)]
+pub struct ObjectChanges {
+    written: BTreeMap<String, NewObject>,
+    deleted: BTreeMap<String, DeletedObject>,
+}
+
+impl ObjectChanges {
+    /// Fold the objects written and deleted by a transaction into the changes. `contents` holds
+    /// the contents of the written objects as of this transaction, the row of an object without
+    /// contents is kept without them and reads of it fall back to the fullnode.
+    pub fn add_transaction(
+        &mut self,
+        effects: &SuiTransactionEffects,
+        contents: &BTreeMap<ObjectID, SuiObject<SuiParsedData>>,
+    ) -> Result<(), IndexerError> {
+        let origins = object_origins(&effects.events);
+        let previous_transaction = effects.transaction_digest.base58_encode();
+        let written = effects
+            .created
+            .iter()
+            .map(|o| (o, CREATED_STATUS))
+            .chain(effects.mutated.iter().map(|o| (o, MUTATED_STATUS)))
+            .chain(effects.unwrapped.iter().map(|o| (o, MUTATED_STATUS)));
+        for (object_ref, status) in written {
+            let object_id = object_ref.reference.object_id;
+            let origin = origins.get(&object_id);
+            let content = contents.get(&object_id);
+            let (owner_type, owner_address, initial_shared_version) =
+                owner_to_owner_info(&object_ref.owner);
+            let object_status = match origin {
+                Some(origin) if origin.transferred && status != CREATED_STATUS => {
+                    TRANSFERRED_STATUS
+                }
+                _ => status,
+            };
+            let object_content = content
+                .map(|c| {
+                    serde_json::to_string(c).map_err(|e| {
+                        IndexerError::InsertableParsingError(format!(
+                            "Failed converting object {} to JSON with error: {:?}",
+                            object_id, e
+                        ))
+                    })
+                })
+                .transpose()?;
+            self.write(NewObject {
+                object_id: object_id.to_string(),
+                version: object_ref.reference.version.value() as i64,
+                owner_type,
+                owner_address,
+                initial_shared_version,
+                // Every object written by a successful transaction has an event, only the gas
+                // object of a failed transaction does not.
+                package_id: origin
+                    .map(|origin| origin.package_id.to_string())
+                    .unwrap_or_default(),
+                transaction_module: origin
+                    .map(|origin| origin.transaction_module.clone())
+                    .unwrap_or_default(),
+                object_type: origin
+                    .and_then(|origin| origin.object_type.clone())
+                    .or_else(|| content.and_then(|c| c.data.type_().map(str::to_string))),
+                object_status: object_status.to_string(),
+                object_digest: Some(object_ref.reference.digest.base58_encode()),
+                previous_transaction: Some(previous_transaction.clone()),
+                object_content,
+            });
+        }
+        let deleted = effects
+            .deleted
+            .iter()
+            .chain(effects.wrapped.iter())
+            .chain(effects.unwrapped_then_deleted.iter());
+        for object_ref in deleted {
+            self.delete(DeletedObject {
+                object_id: object_ref.object_id.to_string(),
+                version: object_ref.version.value() as i64,
+                object_digest: object_ref.digest.base58_encode(),
+                previous_transaction: previous_transaction.clone(),
+            });
+        }
+        Ok(())
     }
-    let new_obj_commit_result = pg_pool_conn
-        .build_transaction()
-        .read_write()
-        .run::<_, Error, _>(|conn| {
-            diesel::insert_into(objects::table)
-                .values(&new_objects)
-                .on_conflict(object_id_column)
-                .do_nothing()
-                .execute(conn)
-        });
 
-    new_obj_commit_result.map_err(|e| {
-        IndexerError::PostgresWriteError(format!(
-            "Failed inserting new objects into database with objects {:?} and error: {:?}",
-            new_objects, e
-        ))
-    })
+    fn write(&mut self, object: NewObject) {
+        self.deleted.remove(&object.object_id);
+        self.written.insert(object.object_id.clone(), object);
+    }
+
+    fn delete(&mut self, deleted: DeletedObject) {
+        // An object written earlier in the checkpoint may not have a row to update yet.
+        match self.written.get_mut(&deleted.object_id) {
+            Some(object) => {
+                object.version = deleted.version;
+                object.object_status = DELETED_STATUS.to_string();
+                object.object_digest = Some(deleted.object_digest);
+                object.previous_transaction = Some(deleted.previous_transaction);
+                object.object_content = None;
+            }
+            None => {
+                self.deleted.insert(deleted.object_id.clone(), deleted);
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.written.len() + self.deleted.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
 }
 
-pub fn commit_object_transfers(
-    pg_pool_conn: &mut PgPoolConnection,
-    object_transfers: Vec<NewObject>,
-) -> Result<usize, IndexerError> {
-    if object_transfers.is_empty() {
-        return Ok(0);
+/// The objects created, mutated or unwrapped by a transaction, whose contents are stored.
+pub fn written_objects(effects: &SuiTransactionEffects) -> impl Iterator<Item = &OwnedObjectRef> {
+    effects
+        .created
+        .iter()
+        .chain(effects.mutated.iter())
+        .chain(effects.unwrapped.iter())
+}
+
+/// Write `changes` with the connection of an open Postgres transaction.
+pub fn commit_object_changes(
+    conn: &mut PgConnection,
+    changes: &ObjectChanges,
+) -> Result<(), Error> {
+    let written: Vec<NewObject> = changes.written.values().cloned().collect();
+    for object_batch in written.chunks(OBJECT_BATCH_SIZE) {
+        // The package and module an object was created by never change.
+        diesel::insert_into(objects::table)
+            .values(object_batch)
+            .on_conflict(object_id_column)
+            .do_update()
+            .set((
+                version_column.eq(excluded(version_column)),
+                owner_type_column.eq(excluded(owner_type_column)),
+                owner_address_column.eq(excluded(owner_address_column)),
+                objects::initial_shared_version.eq(excluded(objects::initial_shared_version)),
+                object_status_column.eq(excluded(object_status_column)),
+                object_digest_column.eq(excluded(object_digest_column)),
+                objects::previous_transaction.eq(excluded(objects::previous_transaction)),
+                object_content_column.eq(excluded(object_content_column)),
+            ))
+            .execute(conn)?;
     }
+    // Multi-row update is not supported by Diesel, see https://github.com/diesel-rs/diesel/discussions/2879
+    for deleted in changes.deleted.values() {
+        diesel::update(objects_table.filter(object_id_column.eq(&deleted.object_id)))
+            .set((
+                version_column.eq(deleted.version),
+                object_status_column.eq(DELETED_STATUS),
+                object_digest_column.eq(&deleted.object_digest),
+                objects::previous_transaction.eq(&deleted.previous_transaction),
+                object_content_column.eq(None::<String>),
+            ))
+            .execute(conn)?;
+    }
+    Ok(())
+}
 
-    let obj_transfer_commit_result = pg_pool_conn
+pub fn get_object(
+    pg_pool_conn: &mut PgPoolConnection,
+    object_id: ObjectID,
+) -> Result<Option<Object>, IndexerError> {
+    let obj_read_result = pg_pool_conn
         .build_transaction()
-        .read_write()
+        .read_only()
         .run::<_, Error, _>(|conn| {
-            diesel::insert_into(objects::table)
-                .values(&object_transfers)
-                .on_conflict(object_id_column)
-                .do_nothing()
-                .execute(conn)
+            objects_table
+                .filter(object_id_column.eq(object_id.to_string()))
+                .first::<Object>(conn)
+                .optional()
         });
-
-    obj_transfer_commit_result.map_err(|e| {
-        IndexerError::PostgresWriteError(format!(
-            "Failed inserting object transfers into database with objects {:?} and error: {:?}",
-            object_transfers, e
+    obj_read_result.map_err(|e| {
+        IndexerError::PostgresReadError(format!(
+            "Failed reading object {} from PostgresDB with error: {:?}",
+            object_id, e
         ))
     })
 }
 
-// Multi-row update is not supported by Diesel, see https://github.com/diesel-rs/diesel/discussions/2879
-// even though Postgres can do it via UPDATE ... FROM.
-// As a work-around, this function will read all related rows into memory, update the columns and upsert back to DB.
-pub fn commit_object_mutations(
+pub fn get_objects_owned_by_address(
     pg_pool_conn: &mut PgPoolConnection,
-    object_mutations: Vec<(String, (i64, String))>,
-) -> Result<usize, IndexerError> {
-    if object_mutations.is_empty() {
-        return Ok(0);
-    }
-    let object_map = BTreeMap::from_iter(object_mutations.into_iter());
-    let object_ids: Vec<String> = object_map.keys().cloned().collect();
-
-    let obj_read_result: Result<Vec<Object>, Error> = pg_pool_conn
+    address: SuiAddress,
+) -> Result<Vec<Object>, IndexerError> {
+    let obj_read_result = pg_pool_conn
         .build_transaction()
         .read_only()
         .run::<_, Error, _>(|conn| {
-        objects_table
-            .filter(object_id_column.eq_any(object_ids))
-            .load::<Object>(conn)
-    });
-
-    let objs = obj_read_result.map_err(|e| {
+            objects_table
+                .filter(owner_type_column.eq(ADDRESS_OWNER_TYPE))
+                .filter(owner_address_column.eq(address.to_string()))
+                .filter(object_status_column.ne(DELETED_STATUS))
+                .order(object_id_column.asc())
+                .load::<Object>(conn)
+        });
+    obj_read_result.map_err(|e| {
         IndexerError::PostgresReadError(format!(
-            "Failed reading selected objects from database for object mutations with error: {:?}",
-            e
+            "Failed reading objects owned by {} from PostgresDB with error: {:?}",
+            address, e
         ))
-    })?;
-    let updated_new_objs: Vec<NewObject> = objs
-        .into_iter()
-        .map(|obj| {
-            let updates = object_map.get(&obj.object_id);
-            let (mut version, mut status) = (obj.version, obj.object_status);
-            if let Some((new_version, new_status)) = updates {
-                version = *new_version;
-                status = new_status.clone();
-            }
-            NewObject {
-                object_id: obj.object_id,
-                version,
-                owner_type: obj.owner_type,
-                owner_address: obj.owner_address,
-                initial_shared_version: obj.initial_shared_version,
-                package_id: obj.package_id,
-                transaction_module: obj.transaction_module,
-                object_type: obj.object_type,
-                object_status: status,
-            }
-        })
-        .collect();
+    })
+}
 
-    let obj_mutation_commit_result = pg_pool_conn
-        .build_transaction()
-        .read_write()
-        .run::<_, Error, _>(|conn| {
-            diesel::insert_into(objects::table)
-                .values(&updated_new_objs)
-                .on_conflict(object_id_column)
-                .do_update()
-                .set((
-                    version_column.eq(excluded(version_column)),
-                    object_status_column.eq(excluded(object_status_column)),
-                ))
-                .execute(conn)
-        });
+impl Object {
+    /// The fullnode's read of the latest version of this object, or `None` if the object was
+    /// indexed before its contents were stored.
+    pub fn to_object_read(&self) -> Result<Option<GetObjectDataResponse>, IndexerError> {
+        if self.object_status == DELETED_STATUS {
+            return Ok(self.object_ref()?.map(GetObjectDataResponse::Deleted));
+        }
+        Ok(self.content()?.map(GetObjectDataResponse::Exists))
+    }
 
-    obj_mutation_commit_result.map_err(|e| IndexerError::PostgresWriteError(
-        format!("Failed writing object mutations to Postgres DB with mutations updated_new_objs {:?} and error: {:?} ", updated_new_objs, e) ))
-}
+    /// The fullnode's read of this object at `version`, or `None` if it cannot be answered from
+    /// the latest version of the object.
+    pub fn to_past_object_read(
+        &self,
+        version: SequenceNumber,
+    ) -> Result<Option<GetPastObjectDataResponse>, IndexerError> {
+        if version.value() as i64 != self.version {
+            return Ok(None);
+        }
+        if self.object_status == DELETED_STATUS {
+            return Ok(self
+                .object_ref()?
+                .map(GetPastObjectDataResponse::ObjectDeleted));
+        }
+        Ok(self.content()?.map(GetPastObjectDataResponse::VersionFound))
+    }
 
-pub fn commit_object_deletions(
-    pg_pool_conn: &mut PgPoolConnection,
-    object_deletions: Vec<String>,
-) -> Result<usize, IndexerError> {
-    if object_deletions.is_empty() {
-        return Ok(0);
+    pub fn to_object_info(&self) -> Result<Option<SuiObjectInfo>, IndexerError> {
+        Ok(self.content()?.map(|object| SuiObjectInfo {
+            object_id: object.reference.object_id,
+            version: object.reference.version,
+            digest: object.reference.digest,
+            type_: object.data.type_().unwrap_or("package").to_string(),
+            owner: object.owner,
+            previous_transaction: object.previous_transaction,
+        }))
     }
 
-    let obj_deletion_commit_result = pg_pool_conn
-        .build_transaction()
-        .read_write()
-        .run::<_, Error, _>(|conn| {
-            diesel::update(objects::table.filter(object_id_column.eq_any(object_deletions.clone())))
-                .set(object_status_column.eq(DELETED_STATUS.to_string()))
-                .execute(conn)
-        });
+    fn content(&self) -> Result<Option<SuiObject<SuiParsedData>>, IndexerError> {
+        self.object_content
+            .as_deref()
+            .map(|content| {
+                serde_json::from_str(content).map_err(|e| {
+                    IndexerError::PostgresReadError(format!(
+                        "Failed deserializing object {} with error: {:?}",
+                        self.object_id, e
+                    ))
+                })
+            })
+            .transpose()
+    }
 
-    obj_deletion_commit_result.map_err(|e| {
-        IndexerError::PostgresWriteError(format!(
-            "Failed writing object deletions to Postgres DB with IDs {:?} and error: {:?} ",
-            object_deletions, e
-        ))
-    })
+    fn object_ref(&self) -> Result<Option<SuiObjectRef>, IndexerError> {
+        let Some(digest) = &self.object_digest else {
+            return Ok(None);
+        };
+        let object_id = self.object_id.parse().map_err(|e| {
+            IndexerError::PostgresReadError(format!(
+                "Failed parsing object ID {} with error: {:?}",
+                self.object_id, e
+            ))
+        })?;
+        let digest: [u8; 32] = Base58::decode(digest)
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| {
+                IndexerError::PostgresReadError(format!(
+                    "Failed parsing digest {:?} of object {}",
+                    digest, self.object_id
+                ))
+            })?;
+        Ok(Some(SuiObjectRef {
+            object_id,
+            version: SequenceNumber::from_u64(self.version as u64),
+            digest: ObjectDigest::new(digest),
+        }))
+    }
 }
 
 // return owner_type, owner_address and initial_shared_version
 fn owner_to_owner_info(owner: &Owner) -> (String, Option<String>, Option<i64>) {
     match owner {
-        Owner::AddressOwner(address) => {
-            ("AddressOwner".to_string(), Some(address.to_string()), None)
-        }
+        Owner::AddressOwner(address) => (
+            ADDRESS_OWNER_TYPE.to_string(),
+            Some(address.to_string()),
+            None,
+        ),
         Owner::ObjectOwner(address) => ("ObjectOwner".to_string(), Some(address.to_string()), None),
         Owner::Shared {
             initial_shared_version,
@@ -211,86 +376,226 @@ fn owner_to_owner_info(owner: &Owner) -> (String, Option<String>, Option<i64>) {
     }
 }
 
+const ADDRESS_OWNER_TYPE: &str = "AddressOwner";
+
 const CREATED_STATUS: &str = "CREATED";
 const MUTATED_STATUS: &str = "MUTATED";
 const TRANSFERRED_STATUS: &str = "TRANSFERRED";
 const DELETED_STATUS: &str = "DELETED";
 
-pub fn commit_objects_from_events(
-    pg_pool_conn: &mut PgPoolConnection,
-    events: Vec<SuiEvent>,
-) -> Result<(), IndexerError> {
-    let mut new_objects = vec![];
-    let mut object_transfers = vec![];
-    let mut object_mutations = vec![];
-    let mut object_deletions = vec![];
-
-    for e in events.into_iter() {
-        match e {
+/// The package and module that wrote an object, and its type, as reported by its event.
+struct ObjectOrigin {
+    package_id: ObjectID,
+    transaction_module: String,
+    object_type: Option<String>,
+    transferred: bool,
+}
+
+fn object_origins(events: &[SuiEvent]) -> BTreeMap<ObjectID, ObjectOrigin> {
+    let mut origins = BTreeMap::new();
+    for event in events {
+        let (package_id, transaction_module, object_type, object_id, transferred) = match event {
             SuiEvent::NewObject {
                 package_id,
                 transaction_module,
-                sender: _,
-                recipient,
                 object_type,
                 object_id,
-                version,
-            } => {
-                let (owner_type, owner_address, initial_shared_version) =
-                    owner_to_owner_info(&recipient);
-                new_objects.push(NewObject {
-                    object_id: object_id.to_string(),
-                    version: version.value() as i64,
-                    owner_type,
-                    owner_address,
-                    initial_shared_version,
-                    package_id: package_id.to_string(),
-                    transaction_module,
-                    object_type: Some(object_type),
-                    object_status: CREATED_STATUS.to_string(),
-                });
+                ..
             }
+            | SuiEvent::MutateObject {
+                package_id,
+                transaction_module,
+                object_type,
+                object_id,
+                ..
+            } => (
+                package_id,
+                transaction_module,
+                Some(object_type),
+                object_id,
+                false,
+            ),
             SuiEvent::TransferObject {
                 package_id,
                 transaction_module,
-                sender: _,
-                recipient,
                 object_type,
                 object_id,
-                version,
-            } => {
-                let (owner_type, owner_address, initial_shared_version) =
-                    owner_to_owner_info(&recipient);
-                object_transfers.push(NewObject {
-                    object_id: object_id.to_string(),
-                    version: version.value() as i64,
-                    owner_type,
-                    owner_address,
-                    initial_shared_version,
-                    package_id: package_id.to_string(),
-                    transaction_module,
-                    object_type: Some(object_type),
-                    object_status: TRANSFERRED_STATUS.to_string(),
-                });
-            }
-            SuiEvent::MutateObject {
-                object_id, version, ..
-            } => {
-                object_mutations.push((
-                    object_id.to_string(),
-                    (version.value() as i64, MUTATED_STATUS.to_string()),
-                ));
-            }
-            SuiEvent::DeleteObject { object_id, .. } => {
-                object_deletions.push(object_id.to_string());
-            }
-            _ => {}
+                ..
+            } => (
+                package_id,
+                transaction_module,
+                Some(object_type),
+                object_id,
+                true,
+            ),
+            _ => continue,
+        };
+        let origin = origins.entry(*object_id).or_insert_with(|| ObjectOrigin {
+            package_id: *package_id,
+            transaction_module: transaction_module.clone(),
+            object_type: object_type.cloned(),
+            transferred: false,
+        });
+        origin.transferred |= transferred;
+    }
+    origins
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sui_json_rpc_types::{SuiExecutionStatus, SuiGasCostSummary};
+    use sui_types::base_types::{random_object_ref, TransactionDigest};
+
+    fn owned_ref(object_id: ObjectID, version: u64, owner: SuiAddress) -> OwnedObjectRef {
+        OwnedObjectRef {
+            owner: Owner::AddressOwner(owner),
+            reference: SuiObjectRef {
+                object_id,
+                version: SequenceNumber::from_u64(version),
+                digest: ObjectDigest::new([version as u8; 32]),
+            },
         }
     }
-    commit_new_objects(pg_pool_conn, new_objects)?;
-    commit_object_transfers(pg_pool_conn, object_transfers)?;
-    commit_object_mutations(pg_pool_conn, object_mutations)?;
-    commit_object_deletions(pg_pool_conn, object_deletions)?;
 
-    Ok(())
+    fn effects(gas_object: OwnedObjectRef) -> SuiTransactionEffects {
+        SuiTransactionEffects {
+            status: SuiExecutionStatus::Success,
+            executed_epoch: 0,
+            gas_used: SuiGasCostSummary {
+                computation_cost: 0,
+                storage_cost: 0,
+                storage_rebate: 0,
+            },
+            shared_objects: vec![],
+            transaction_digest: TransactionDigest::random(),
+            created: vec![],
+            mutated: vec![gas_object.clone()],
+            unwrapped: vec![],
+            deleted: vec![],
+            unwrapped_then_deleted: vec![],
+            wrapped: vec![],
+            gas_object,
+            events: vec![],
+            dependencies: vec![],
+        }
+    }
+
+    #[test]
+    fn test_object_changes_keep_latest_state() {
+        let owner = SuiAddress::random_for_testing_only();
+        let gas_id = random_object_ref().0;
+        let object_id = random_object_ref().0;
+
+        let mut changes = ObjectChanges::default();
+        let mut first = effects(owned_ref(gas_id, 1, owner));
+        first.created.push(owned_ref(object_id, 1, owner));
+        first.events.push(SuiEvent::NewObject {
+            package_id: ObjectID::from_single_byte(2),
+            transaction_module: "devnet_nft".to_string(),
+            sender: owner,
+            recipient: Owner::AddressOwner(owner),
+            object_type: "0x2::devnet_nft::DevNetNFT".to_string(),
+            object_id,
+            version: SequenceNumber::from_u64(1),
+        });
+        changes.add_transaction(&first, &BTreeMap::new()).unwrap();
+
+        let mut second = effects(owned_ref(gas_id, 2, owner));
+        second.mutated.push(owned_ref(object_id, 2, owner));
+        changes.add_transaction(&second, &BTreeMap::new()).unwrap();
+        assert_eq!(changes.len(), 2);
+
+        let object = &changes.written[&object_id.to_string()];
+        assert_eq!(object.version, 2);
+        assert_eq!(object.object_status, MUTATED_STATUS);
+        assert_eq!(object.package_id, ObjectID::from_single_byte(2).to_string());
+        assert_eq!(object.transaction_module, "devnet_nft");
+        assert_eq!(
+            object.previous_transaction,
+            Some(second.transaction_digest.base58_encode())
+        );
+        assert_eq!(
+            changes.written[&gas_id.to_string()].object_digest,
+            Some(ObjectDigest::new([2; 32]).base58_encode())
+        );
+
+        // An object deleted in the same checkpoint it was written in keeps its row.
+        let mut third = effects(owned_ref(gas_id, 3, owner));
+        third.deleted.push(SuiObjectRef {
+            object_id,
+            version: SequenceNumber::from_u64(3),
+            digest: ObjectDigest::OBJECT_DIGEST_DELETED,
+        });
+        changes.add_transaction(&third, &BTreeMap::new()).unwrap();
+        let object = &changes.written[&object_id.to_string()];
+        assert_eq!(object.version, 3);
+        assert_eq!(object.object_status, DELETED_STATUS);
+        assert!(object.object_content.is_none());
+        assert!(changes.deleted.is_empty());
+
+        // Objects written in earlier checkpoints are only updated.
+        let other_id = random_object_ref().0;
+        let mut fourth = effects(owned_ref(gas_id, 4, owner));
+        fourth.wrapped.push(SuiObjectRef {
+            object_id: other_id,
+            version: SequenceNumber::from_u64(7),
+            digest: ObjectDigest::OBJECT_DIGEST_WRAPPED,
+        });
+        changes.add_transaction(&fourth, &BTreeMap::new()).unwrap();
+        assert_eq!(changes.deleted[&other_id.to_string()].version, 7);
+
+        // And get a row again once unwrapped.
+        let mut fifth = effects(owned_ref(gas_id, 5, owner));
+        fifth.unwrapped.push(owned_ref(other_id, 8, owner));
+        changes.add_transaction(&fifth, &BTreeMap::new()).unwrap();
+        assert!(changes.deleted.is_empty());
+        assert_eq!(changes.written[&other_id.to_string()].version, 8);
+    }
+
+    #[test]
+    fn test_deleted_object_read() {
+        let object_id = random_object_ref().0;
+        let digest = ObjectDigest::OBJECT_DIGEST_DELETED;
+        let object = Object {
+            id: 1,
+            object_id: object_id.to_string(),
+            version: 5,
+            owner_type: ADDRESS_OWNER_TYPE.to_string(),
+            owner_address: Some(SuiAddress::random_for_testing_only().to_string()),
+            initial_shared_version: None,
+            package_id: ObjectID::from_single_byte(2).to_string(),
+            transaction_module: "devnet_nft".to_string(),
+            object_type: None,
+            object_status: DELETED_STATUS.to_string(),
+            object_digest: Some(digest.base58_encode()),
+            previous_transaction: Some(TransactionDigest::random().base58_encode()),
+            object_content: None,
+        };
+        let expected_ref = SuiObjectRef {
+            object_id,
+            version: SequenceNumber::from_u64(5),
+            digest,
+        };
+        assert!(matches!(
+            object.to_object_read().unwrap(),
+            Some(GetObjectDataResponse::Deleted(object_ref)) if object_ref == expected_ref
+        ));
+        assert!(matches!(
+            object.to_past_object_read(SequenceNumber::from_u64(5)).unwrap(),
+            Some(GetPastObjectDataResponse::ObjectDeleted(object_ref)) if object_ref == expected_ref
+        ));
+        // Older versions are only known to the fullnode.
+        assert!(object
+            .to_past_object_read(SequenceNumber::from_u64(4))
+            .unwrap()
+            .is_none());
+        // So are objects indexed before contents were stored.
+        let object = Object {
+            object_status: MUTATED_STATUS.to_string(),
+            ..object
+        };
+        assert!(object.to_object_read().unwrap().is_none());
+        assert!(object.to_object_info().unwrap().is_none());
+    }
 }
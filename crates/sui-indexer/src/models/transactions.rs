@@ -3,15 +3,19 @@
 
 use crate::schema::transactions;
 use crate::schema::transactions::dsl::{
-    checkpoint_sequence_number, id, transactions as transactions_table,
+    checkpoint_sequence_number, created, id, mutated, sender, transactions as transactions_table,
+    unwrapped,
 };
 use crate::utils::log_errors_to_pg;
 
 use chrono::NaiveDateTime;
-use diesel::dsl::max;
+use diesel::dsl::{max, sql};
+use diesel::pg::Pg;
 use diesel::prelude::*;
 use diesel::result::Error;
+use diesel::sql_types::Bool;
 use sui_json_rpc_types::{OwnedObjectRef, SuiObjectRef, SuiTransactionResponse};
+use sui_types::query::TransactionQuery;
 
 use crate::errors::IndexerError;
 use crate::schema::transactions::transaction_digest;
@@ -40,6 +44,7 @@ pub struct Transaction {
     pub storage_rebate: i64,
     pub gas_price: i64,
     pub transaction_content: String,
+    pub transaction_effects_content: Option<String>,
 }
 
 #[derive(Clone, Debug, Insertable)]
@@ -65,6 +70,7 @@ pub struct NewTransaction {
     pub storage_rebate: i64,
     pub gas_price: i64,
     pub transaction_content: String,
+    pub transaction_effects_content: Option<String>,
 }
 
 pub fn commit_transactions(
@@ -109,6 +115,12 @@ pub fn transaction_response_to_new_transaction(
             tx_resp.transaction, err
         ))
     })?;
+    let txn_effects_json = serde_json::to_string(&tx_resp.effects).map_err(|err| {
+        IndexerError::InsertableParsingError(format!(
+            "Failed converting transaction effects {:?} to JSON with error: {:?}",
+            tx_resp.effects, err
+        ))
+    })?;
     // canonical txn digest string is Base58 encoded
     let tx_digest = tx_resp.effects.transaction_digest.base58_encode();
     let gas_budget = tx_resp.transaction.data.gas_data.budget;
@@ -201,6 +213,7 @@ pub fn transaction_response_to_new_transaction(
         storage_cost: storage_cost as i64,
         storage_rebate: storage_rebate as i64,
         transaction_content: txn_json,
+        transaction_effects_content: Some(txn_effects_json),
     })
 }
 
@@ -260,3 +273,236 @@ pub fn read_latest_processed_checkpoint(
         ))
     })
 }
+
+impl Transaction {
+    /// Rebuild the fullnode's response for this transaction, or `None` if the transaction was
+    /// indexed without its effects.
+    pub fn to_transaction_response(&self) -> Result<Option<SuiTransactionResponse>, IndexerError> {
+        let Some(effects_content) = &self.transaction_effects_content else {
+            return Ok(None);
+        };
+        let transaction = serde_json::from_str(&self.transaction_content).map_err(|e| {
+            IndexerError::PostgresReadError(format!(
+                "Failed deserializing transaction {} with error: {:?}",
+                self.transaction_digest, e
+            ))
+        })?;
+        let effects = serde_json::from_str(effects_content).map_err(|e| {
+            IndexerError::PostgresReadError(format!(
+                "Failed deserializing effects of transaction {} with error: {:?}",
+                self.transaction_digest, e
+            ))
+        })?;
+        Ok(Some(SuiTransactionResponse {
+            transaction,
+            effects,
+            timestamp_ms: self
+                .transaction_time
+                .map(|time| time.timestamp_millis() as u64),
+            confirmed_local_execution: None,
+            checkpoint: Some(self.checkpoint_sequence_number as u64),
+        }))
+    }
+}
+
+pub fn get_transaction_by_digest(
+    pg_pool_conn: &mut PgPoolConnection,
+    txn_digest: String,
+) -> Result<Option<Transaction>, IndexerError> {
+    let txn_read_result: Result<Option<Transaction>, Error> = pg_pool_conn
+        .build_transaction()
+        .read_only()
+        .run::<_, Error, _>(|conn| {
+            transactions_table
+                .filter(transaction_digest.eq(&txn_digest))
+                .first::<Transaction>(conn)
+                .optional()
+        });
+
+    txn_read_result.map_err(|e| {
+        IndexerError::PostgresReadError(format!(
+            "Failed reading transaction with digest {} and err: {:?}",
+            txn_digest, e
+        ))
+    })
+}
+
+type TransactionFilter = Box<dyn BoxableExpression<transactions::table, Pg, SqlType = Bool>>;
+
+/// The filter selecting the transactions matching `query`, or `None` if the transactions table
+/// does not have the columns to answer it.
+fn transaction_query_filter(query: &TransactionQuery) -> Option<TransactionFilter> {
+    Some(match query {
+        TransactionQuery::All => Box::new(sql::<Bool>("TRUE")),
+        TransactionQuery::FromAddress(address) => Box::new(sender.eq(address.to_string())),
+        // Same as the fullnode's index, which counts created and unwrapped objects as mutated.
+        TransactionQuery::MutatedObject(object_id) => {
+            let object_id = vec![Some(object_id.to_string())];
+            Box::new(
+                mutated
+                    .contains(object_id.clone())
+                    .or(created.contains(object_id.clone()))
+                    .or(unwrapped.contains(object_id)),
+            )
+        }
+        // Sequence numbers are stored as BIGINT, none of them is above i64::MAX.
+        TransactionQuery::Checkpoint { start, end } => match i64::try_from(*start) {
+            Ok(start) => Box::new(
+                checkpoint_sequence_number.between(start, i64::try_from(*end).unwrap_or(i64::MAX)),
+            ),
+            Err(_) => Box::new(sql::<Bool>("FALSE")),
+        },
+        TransactionQuery::MatchAll(queries) => {
+            let mut filter: TransactionFilter = Box::new(sql::<Bool>("TRUE"));
+            for query in queries {
+                filter = Box::new(filter.and(transaction_query_filter(query)?));
+            }
+            filter
+        }
+        TransactionQuery::MatchAny(queries) => {
+            let mut filter: TransactionFilter = Box::new(sql::<Bool>("FALSE"));
+            for query in queries {
+                filter = Box::new(filter.or(transaction_query_filter(query)?));
+            }
+            filter
+        }
+        TransactionQuery::MoveFunction { .. }
+        | TransactionQuery::InputObject(_)
+        | TransactionQuery::ToAddress(_) => return None,
+    })
+}
+
+/// Read up to `limit` digests of transactions matching `query`, in the order they were indexed,
+/// starting from and including the `cursor` transaction, like the fullnode does. Returns `None` if
+/// the query cannot be answered from the transactions table, or the cursor transaction has not
+/// been indexed. `query` is expected to be validated like the fullnode does, see
+/// `IndexStore::validate_transaction_query`.
+pub fn read_transaction_digests(
+    pg_pool_conn: &mut PgPoolConnection,
+    query: &TransactionQuery,
+    cursor: Option<String>,
+    limit: usize,
+    descending_order: bool,
+) -> Result<Option<Vec<String>>, IndexerError> {
+    let Some(filter) = transaction_query_filter(query) else {
+        return Ok(None);
+    };
+
+    let txn_read_result: Result<Option<Vec<String>>, Error> = pg_pool_conn
+        .build_transaction()
+        .read_only()
+        .run::<_, Error, _>(|conn| {
+            let mut boxed_query = transactions_table
+                .filter(filter)
+                .select(transaction_digest)
+                .into_boxed();
+            if let Some(cursor) = &cursor {
+                let cursor_id = transactions_table
+                    .filter(transaction_digest.eq(cursor))
+                    .select(id)
+                    .first::<i64>(conn)
+                    .optional()?;
+                let Some(cursor_id) = cursor_id else {
+                    return Ok(None);
+                };
+                boxed_query = if descending_order {
+                    boxed_query.filter(id.le(cursor_id))
+                } else {
+                    boxed_query.filter(id.ge(cursor_id))
+                };
+            }
+            boxed_query = if descending_order {
+                boxed_query.order(id.desc())
+            } else {
+                boxed_query.order(id.asc())
+            };
+            boxed_query
+                .limit(limit as i64)
+                .load::<String>(conn)
+                .map(Some)
+        });
+
+    txn_read_result.map_err(|e| {
+        IndexerError::PostgresReadError(format!(
+            "Failed reading transactions for query {:?} with cursor {:?} and err: {:?}",
+            query, cursor, e
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use diesel::debug_query;
+    use sui_storage::IndexStore;
+    use sui_types::base_types::ObjectID;
+
+    fn filter_sql(query: &TransactionQuery) -> Option<String> {
+        let filter = transaction_query_filter(query)?;
+        Some(debug_query::<Pg, _>(&transactions_table.filter(filter).select(id)).to_string())
+    }
+
+    #[test]
+    fn test_checkpoint_range_filter() {
+        let sql = filter_sql(&TransactionQuery::Checkpoint { start: 3, end: 7 }).unwrap();
+        assert!(sql.ends_with("binds: [3, 7]"), "{sql}");
+
+        // Open ended ranges are capped to the largest sequence number that can be stored.
+        let sql = filter_sql(&TransactionQuery::Checkpoint {
+            start: 3,
+            end: u64::MAX,
+        })
+        .unwrap();
+        assert!(sql.ends_with(&format!("binds: [3, {}]", i64::MAX)), "{sql}");
+
+        let sql = filter_sql(&TransactionQuery::Checkpoint {
+            start: u64::MAX,
+            end: u64::MAX,
+        })
+        .unwrap();
+        assert!(sql.contains("FALSE"), "{sql}");
+        assert!(sql.ends_with("binds: []"), "{sql}");
+    }
+
+    #[test]
+    fn test_unsupported_query_filter() {
+        let object_id = ObjectID::from_single_byte(2);
+        assert!(filter_sql(&TransactionQuery::InputObject(object_id)).is_none());
+        assert!(filter_sql(&TransactionQuery::MatchAll(vec![
+            TransactionQuery::All,
+            TransactionQuery::MatchAny(vec![TransactionQuery::InputObject(object_id)]),
+        ]))
+        .is_none());
+        assert!(filter_sql(&TransactionQuery::MatchAny(vec![
+            TransactionQuery::MutatedObject(object_id),
+            TransactionQuery::Checkpoint { start: 0, end: 1 },
+        ]))
+        .is_some());
+    }
+
+    #[test]
+    fn test_transaction_query_validation() {
+        // The queries answered from Postgres are validated like the fullnode does.
+        assert!(
+            IndexStore::validate_transaction_query(&TransactionQuery::Checkpoint {
+                start: 7,
+                end: 3
+            })
+            .is_err()
+        );
+        assert!(
+            IndexStore::validate_transaction_query(&TransactionQuery::MatchAll(vec![
+            TransactionQuery::All;
+            sui_storage::indexes::TX_QUERY_MAX_CRITERIA
+        ]))
+            .is_err()
+        );
+        assert!(
+            IndexStore::validate_transaction_query(&TransactionQuery::MatchAll(vec![
+            TransactionQuery::All;
+            sui_storage::indexes::TX_QUERY_MAX_CRITERIA - 1
+        ]))
+            .is_ok()
+        );
+    }
+}
@@ -2,6 +2,5 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod address_processor;
-pub mod object_processor;
 pub mod package_processor;
 pub mod processor_orchestrator;
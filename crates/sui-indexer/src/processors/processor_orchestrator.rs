@@ -11,7 +11,6 @@ use sui_sdk::SuiClient;
 use tracing::{error, info, warn};
 
 use crate::processors::address_processor::AddressProcessor;
-use crate::processors::object_processor::ObjectProcessor;
 use crate::processors::package_processor::PackageProcessor;
 
 pub struct ProcessorOrchestrator {
@@ -37,8 +36,6 @@ impl ProcessorOrchestrator {
         info!("Processor orchestrator started...");
         let address_processor =
             AddressProcessor::new(self.conn_pool.clone(), &self.prometheus_registry);
-        let object_processor =
-            ObjectProcessor::new(self.conn_pool.clone(), &self.prometheus_registry);
         let package_processor = PackageProcessor::new(
             self.rpc_client.clone(),
             self.conn_pool.clone(),
@@ -68,29 +65,6 @@ impl ProcessorOrchestrator {
                 );
             }
         });
-        let obj_handle = tokio::task::spawn(async move {
-            let obj_result = retry(ExponentialBackoff::default(), || async {
-                let obj_processor_exec_res = object_processor.start().await;
-                if let Err(e) = &obj_processor_exec_res {
-                    object_processor
-                        .object_processor_metrics
-                        .total_object_processor_error
-                        .inc();
-                    warn!(
-                        "Indexer object processor failed with error: {:?}, retrying...",
-                        e
-                    );
-                }
-                Ok(obj_processor_exec_res?)
-            })
-            .await;
-            if let Err(e) = obj_result {
-                error!(
-                    "Indexer object processor failed after retrials with error {:?}",
-                    e
-                );
-            }
-        });
         let pkg_handle = tokio::task::spawn(async move {
             let pkg_result = retry(ExponentialBackoff::default(), || async {
                 let pkg_processor_exec_res = package_processor.start().await;
@@ -114,7 +88,7 @@ impl ProcessorOrchestrator {
                 );
             }
         });
-        try_join_all(vec![addr_handle, pkg_handle, obj_handle])
+        try_join_all(vec![addr_handle, pkg_handle])
             .await
             .expect("Processor orchestrator should not run into errors.");
     }
@@ -39,6 +39,14 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    custom_handler_logs (handler_name) {
+        handler_name -> Varchar,
+        next_checkpoint_sequence_number -> Int8,
+        caught_up -> Bool,
+    }
+}
+
 diesel::table! {
     error_logs (id) {
         id -> Int8,
@@ -56,15 +64,6 @@ diesel::table! {
         event_time -> Nullable<Timestamp>,
         event_type -> Varchar,
         event_content -> Varchar,
-        next_cursor_transaction_digest -> Nullable<Varchar>,
-    }
-}
-
-diesel::table! {
-    move_event_logs (id) {
-        id -> Int4,
-        next_cursor_tx_dig -> Nullable<Text>,
-        next_cursor_event_seq -> Nullable<Int8>,
     }
 }
 
@@ -79,14 +78,6 @@ diesel::table! {
     }
 }
 
-diesel::table! {
-    object_event_logs (id) {
-        id -> Int4,
-        next_cursor_tx_dig -> Nullable<Text>,
-        next_cursor_event_seq -> Nullable<Int8>,
-    }
-}
-
 diesel::table! {
     object_events (id) {
         id -> Int8,
@@ -98,12 +89,6 @@ diesel::table! {
     }
 }
 
-diesel::table! {
-    object_logs (last_processed_id) {
-        last_processed_id -> Int8,
-    }
-}
-
 diesel::table! {
     objects (id) {
         id -> Int8,
@@ -116,6 +101,9 @@ diesel::table! {
         transaction_module -> Text,
         object_type -> Nullable<Text>,
         object_status -> Varchar,
+        object_digest -> Nullable<Varchar>,
+        previous_transaction -> Nullable<Varchar>,
+        object_content -> Nullable<Text>,
     }
 }
 
@@ -135,14 +123,6 @@ diesel::table! {
     }
 }
 
-diesel::table! {
-    publish_event_logs (id) {
-        id -> Int4,
-        next_cursor_tx_dig -> Nullable<Text>,
-        next_cursor_event_seq -> Nullable<Int8>,
-    }
-}
-
 diesel::table! {
     publish_events (id) {
         id -> Int8,
@@ -177,6 +157,7 @@ diesel::table! {
         storage_rebate -> Int8,
         gas_price -> Int8,
         transaction_content -> Text,
+        transaction_effects_content -> Nullable<Text>,
     }
 }
 
@@ -184,17 +165,14 @@ diesel::allow_tables_to_appear_in_same_query!(
     address_logs,
     addresses,
     checkpoints,
+    custom_handler_logs,
     error_logs,
     events,
-    move_event_logs,
     move_events,
-    object_event_logs,
     object_events,
-    object_logs,
     objects,
     package_logs,
     packages,
-    publish_event_logs,
     publish_events,
     transactions,
 );
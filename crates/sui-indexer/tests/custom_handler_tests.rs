@@ -0,0 +1,103 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use diesel::pg::PgConnection;
+use diesel::result::Error;
+use prometheus::Registry;
+use sui_indexer::custom_handlers::{CustomEventHandler, MoveEventFilter};
+use sui_indexer::handlers::handler_orchestrator::HandlerOrchestrator;
+use sui_indexer::new_pg_connection_pool;
+use sui_json_rpc_types::{SuiEvent, SuiEventEnvelope};
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use test_utils::network::TestClusterBuilder;
+use test_utils::transaction::create_devnet_nft;
+
+struct RecordingHandler {
+    name: String,
+    checkpoints: Mutex<Vec<(CheckpointSequenceNumber, Vec<SuiEventEnvelope>)>>,
+}
+
+impl CustomEventHandler for RecordingHandler {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn filters(&self) -> Vec<MoveEventFilter> {
+        vec![MoveEventFilter::event_type("0x2::devnet_nft::MintNFTEvent").unwrap()]
+    }
+
+    fn process_checkpoint(
+        &self,
+        _conn: &mut PgConnection,
+        checkpoint: CheckpointSequenceNumber,
+        events: &[SuiEventEnvelope],
+    ) -> Result<(), Error> {
+        self.checkpoints
+            .lock()
+            .unwrap()
+            .push((checkpoint, events.to_vec()));
+        Ok(())
+    }
+}
+
+/// Needs a Postgres DB freshly set up with the indexer migrations, see the README, at
+/// `DATABASE_URL`.
+#[tokio::test]
+#[ignore = "requires a Postgres DB at DATABASE_URL"]
+async fn test_registered_custom_handler_receives_checkpoints() -> Result<(), anyhow::Error> {
+    let db_url = std::env::var("DATABASE_URL")?;
+    let mut cluster = TestClusterBuilder::new()
+        .enable_fullnode_events()
+        .build()
+        .await?;
+    let (_, _, tx_digest) = create_devnet_nft(cluster.wallet_mut()).await?;
+
+    let handler = Arc::new(RecordingHandler {
+        name: "recording_handler".to_string(),
+        checkpoints: Mutex::new(vec![]),
+    });
+    let orchestrator = HandlerOrchestrator::new(
+        cluster.sui_client().clone(),
+        new_pg_connection_pool(db_url).await?,
+        Registry::new(),
+    )
+    .with_custom_handler(handler.clone());
+    tokio::task::spawn(async move { orchestrator.run_forever().await });
+
+    tokio::time::timeout(Duration::from_secs(60), async {
+        loop {
+            let received = handler
+                .checkpoints
+                .lock()
+                .unwrap()
+                .iter()
+                .any(|(_, events)| events.iter().any(|e| e.tx_digest == tx_digest));
+            if received {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(500)).await;
+        }
+    })
+    .await?;
+
+    let checkpoints = handler.checkpoints.lock().unwrap();
+    // Every checkpoint is delivered, in order, from the handler's start checkpoint.
+    for (expected, (checkpoint, _)) in checkpoints.iter().enumerate() {
+        assert_eq!(*checkpoint, expected as u64);
+    }
+    // Only the events matching the handler's filters are delivered.
+    let events: Vec<_> = checkpoints
+        .iter()
+        .flat_map(|(_, events)| events.iter())
+        .collect();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].tx_digest, tx_digest);
+    let SuiEvent::MoveEvent { type_, .. } = &events[0].event else {
+        panic!("Expected a Move event, got {:?}", events[0].event);
+    };
+    assert_eq!(type_, "0x2::devnet_nft::MintNFTEvent");
+    Ok(())
+}
@@ -29,7 +29,7 @@ use serde::Serialize;
 use serde_json::{json, Value};
 use serde_with::serde_as;
 use sui_json::SuiJsonValue;
-use sui_protocol_config::ProtocolConfig;
+use sui_protocol_config::{ProtocolConfig, ProtocolVersion, SupportedProtocolVersions};
 use sui_types::base_types::{
     ObjectDigest, ObjectID, ObjectInfo, ObjectRef, SequenceNumber, SuiAddress, TransactionDigest,
     TransactionEffectsDigest,
@@ -41,7 +41,7 @@ use sui_types::dynamic_field::DynamicFieldInfo;
 use sui_types::error::{ExecutionError, SuiError, UserInputError, UserInputResult};
 use sui_types::event::{BalanceChangeType, Event, EventID};
 use sui_types::event::{EventEnvelope, EventType};
-use sui_types::filter::EventFilter;
+use sui_types::filter::{EventFieldPredicate, EventFilter};
 use sui_types::gas::GasCostSummary;
 use sui_types::gas_coin::GasCoin;
 use sui_types::messages::{
@@ -2864,6 +2864,12 @@ impl From<TypeTag> for SuiTypeTag {
     }
 }
 
+impl Display for SuiTypeTag {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 #[derive(Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub enum RPCTransactionRequestParams {
@@ -2900,6 +2906,10 @@ pub enum SuiEventFilter {
         path: String,
         value: Value,
     },
+    MoveEventFieldPredicate {
+        path: String,
+        predicate: EventFieldPredicate,
+    },
     SenderAddress(SuiAddress),
     EventType(EventType),
     ObjectId(ObjectID),
@@ -2907,6 +2917,7 @@ pub enum SuiEventFilter {
     Any(Vec<SuiEventFilter>),
     And(Box<SuiEventFilter>, Box<SuiEventFilter>),
     Or(Box<SuiEventFilter>, Box<SuiEventFilter>),
+    Not(Box<SuiEventFilter>),
 }
 
 impl TryInto<EventFilter> for SuiEventFilter {
@@ -2922,6 +2933,9 @@ impl TryInto<EventFilter> for SuiEventFilter {
                 EventFilter::MoveEventType(parse_sui_struct_tag(&event_type)?)
             }
             MoveEventField { path, value } => EventFilter::MoveEventField { path, value },
+            MoveEventFieldPredicate { path, predicate } => {
+                EventFilter::MoveEventFieldPredicate { path, predicate }
+            }
             SenderAddress(address) => EventFilter::SenderAddress(address),
             ObjectId(id) => EventFilter::ObjectId(id),
             All(filters) => EventFilter::MatchAll(
@@ -2938,6 +2952,7 @@ impl TryInto<EventFilter> for SuiEventFilter {
             ),
             And(filter_a, filter_b) => All(vec![*filter_a, *filter_b]).try_into()?,
             Or(filter_a, filter_b) => Any(vec![*filter_a, *filter_b]).try_into()?,
+            Not(filter) => EventFilter::Not(Box::new((*filter).try_into()?)),
             EventType(type_) => EventFilter::EventType(type_),
         })
     }
@@ -3054,3 +3069,36 @@ impl From<CheckpointDigest> for CheckpointId {
         Self::Digest(digest)
     }
 }
+
+#[derive(Clone, Debug, JsonSchema, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProtocolConfigResponse {
+    /// The lowest protocol version supported by the node
+    pub min_supported_protocol_version: ProtocolVersion,
+    /// The highest protocol version supported by the node
+    pub max_supported_protocol_version: ProtocolVersion,
+    /// The protocol version of the returned config
+    pub protocol_version: ProtocolVersion,
+    /// Every protocol constant by name. Constants that are not defined in this protocol version
+    /// are null.
+    pub attributes: BTreeMap<String, Value>,
+}
+
+impl ProtocolConfigResponse {
+    pub fn new(
+        config: &ProtocolConfig,
+        protocol_version: ProtocolVersion,
+        supported_protocol_versions: SupportedProtocolVersions,
+    ) -> Result<Self, anyhow::Error> {
+        let attributes = match serde_json::to_value(config)? {
+            Value::Object(attributes) => attributes.into_iter().collect(),
+            value => anyhow::bail!("Unexpected protocol config encoding: {value}"),
+        };
+        Ok(Self {
+            min_supported_protocol_version: supported_protocol_versions.min(),
+            max_supported_protocol_version: supported_protocol_versions.max(),
+            protocol_version,
+            attributes,
+        })
+    }
+}
@@ -6,11 +6,12 @@ use jsonrpsee_proc_macros::rpc;
 use std::collections::BTreeMap;
 use sui_json_rpc_types::{
     Checkpoint, CheckpointId, DynamicFieldPage, GetObjectDataResponse, GetPastObjectDataResponse,
-    GetRawObjectDataResponse, MoveFunctionArgType, SuiMoveNormalizedFunction,
-    SuiMoveNormalizedModule, SuiMoveNormalizedStruct, SuiObjectInfo, SuiTransactionResponse,
-    TransactionsPage,
+    GetRawObjectDataResponse, MoveFunctionArgType, ProtocolConfigResponse,
+    SuiMoveNormalizedFunction, SuiMoveNormalizedModule, SuiMoveNormalizedStruct, SuiObjectInfo,
+    SuiTransactionResponse, TransactionsPage,
 };
 use sui_open_rpc_macros::open_rpc;
+use sui_protocol_config::ProtocolVersion;
 use sui_types::base_types::{
     ObjectID, SequenceNumber, SuiAddress, TransactionDigest, TxSequenceNumber,
 };
@@ -210,4 +211,14 @@ pub trait ReadApi {
         /// the id of the object
         object_id: ObjectID,
     ) -> RpcResult<BTreeMap<String, String>>;
+
+    /// Return the protocol config of a protocol version, along with the range of protocol
+    /// versions supported by the node
+    #[method(name = "getProtocolConfig")]
+    async fn get_protocol_config(
+        &self,
+        /// the protocol version of the returned config. If None, default to the protocol version
+        /// of the current epoch
+        version: Option<ProtocolVersion>,
+    ) -> RpcResult<ProtocolConfigResponse>;
 }
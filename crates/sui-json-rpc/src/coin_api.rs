@@ -16,7 +16,7 @@ use sui_json_rpc_types::{Balance, Coin as SuiCoin};
 use sui_json_rpc_types::{CoinPage, SuiCoinMetadata};
 use sui_open_rpc::Module;
 use sui_types::balance::Supply;
-use sui_types::base_types::{ObjectID, ObjectType, SuiAddress};
+use sui_types::base_types::{ObjectID, ObjectInfo, ObjectType, SuiAddress};
 use sui_types::coin::{Coin, CoinMetadata, LockedCoin, TreasuryCap};
 use sui_types::error::SuiError;
 use sui_types::event::Event;
@@ -37,50 +37,28 @@ impl CoinReadApi {
         Self { state }
     }
 
-    async fn get_object(&self, object_id: &ObjectID) -> Result<Object, Error> {
-        Ok(self
-            .state
-            .get_object_read(object_id)
-            .await?
-            .into_object()
-            .map_err(SuiError::from)?)
-    }
-
-    async fn get_coin(&self, coin_id: &ObjectID) -> Result<SuiCoin, Error> {
-        let o = self.get_object(coin_id).await?;
-        if let Some(move_object) = o.data.try_as_move() {
-            let (balance, locked_until_epoch) = if Coin::is_coin(&move_object.type_) {
-                let coin: Coin = bcs::from_bytes(move_object.contents())?;
-                (coin.balance.value(), None)
-            } else if LockedCoin::is_locked_coin(&move_object.type_) {
-                let locked_coin: LockedCoin = bcs::from_bytes(move_object.contents())?;
-                (
-                    locked_coin.balance.value(),
-                    Some(locked_coin.locked_until_epoch),
-                )
-            } else {
-                return Err(Error::SuiError(SuiError::ObjectDeserializationError {
-                    error: format!("{:?} is not a supported coin type", move_object.type_),
-                }));
-            };
-
-            Ok(SuiCoin {
-                coin_type: move_object.type_.type_params.first().unwrap().to_string(),
-                coin_object_id: o.id(),
-                version: o.version(),
-                digest: o.digest(),
-                balance,
-                locked_until_epoch,
-                previous_transaction: o.previous_transaction,
+    /// Reads the coins listed by the owner index from a single snapshot of the store, taken after
+    /// the index was read. Coins that changed since they were listed are read at their latest
+    /// version, as the listed one may have been pruned, and coins that changed owner are left out.
+    fn read_listed_coins(
+        &self,
+        coins: impl IntoIterator<Item = ObjectInfo>,
+    ) -> Result<Vec<SuiCoin>, Error> {
+        let snapshot = self.state.read_snapshot()?;
+        snapshot
+            .reconcile_owned_objects(coins)?
+            .into_iter()
+            .map(|info| {
+                let o = snapshot
+                    .get_object_read_at_version(&info.object_id, info.version)?
+                    .into_object()
+                    .map_err(SuiError::from)?;
+                to_sui_coin(o)
             })
-        } else {
-            Err(Error::UnexpectedError(format!(
-                "Provided object : [{coin_id}] is not a Move object."
-            )))
-        }
+            .collect()
     }
 
-    async fn get_coins_internal(
+    fn get_coins_internal(
         &self,
         owner: SuiAddress,
         coin_type: Option<StructTag>,
@@ -91,17 +69,14 @@ impl CoinReadApi {
         let limit = cap_page_limit(limit);
         let mut coins = self
             .get_owner_coin_iterator(owner, &coin_type)?
-            .skip_while(|o| matches!(&cursor, Some(cursor) if cursor != o))
+            .skip_while(|o| matches!(&cursor, Some(cursor) if cursor != &o.object_id))
             .take(limit + 1)
             .collect::<Vec<_>>();
 
-        let next_cursor = coins.get(limit).cloned();
+        let next_cursor = coins.get(limit).map(|o| o.object_id);
         coins.truncate(limit);
 
-        let mut data = vec![];
-        for coin in coins {
-            data.push(self.get_coin(&coin).await?)
-        }
+        let data = self.read_listed_coins(coins)?;
         Ok(CoinPage { data, next_cursor })
     }
 
@@ -109,24 +84,26 @@ impl CoinReadApi {
         &'a self,
         owner: SuiAddress,
         coin_type: &'a Option<StructTag>,
-    ) -> Result<impl Iterator<Item = ObjectID> + '_, Error> {
+    ) -> Result<impl Iterator<Item = ObjectInfo> + '_, Error> {
         Ok(self
             .state
             .get_owner_objects_iterator(owner)?
-            .filter(move |o| matches!(&o.type_, ObjectType::Struct(type_) if is_coin_type(type_, coin_type)))
-            .map(|info|info.object_id))
+            .filter(move |o| matches!(&o.type_, ObjectType::Struct(type_) if is_coin_type(type_, coin_type))))
     }
 
-    async fn find_package_object(
+    fn find_package_object(
         &self,
         package_id: &ObjectID,
         object_struct_tag: StructTag,
     ) -> Result<Object, Error> {
-        let publish_txn_digest = self.get_object(package_id).await?.previous_transaction;
-        let (_, effects) = self
-            .state
-            .get_executed_transaction_and_effects(publish_txn_digest)
-            .await?;
+        // The package, its publish transaction and the object are read from the same snapshot
+        let snapshot = self.state.read_snapshot()?;
+        let publish_txn_digest = snapshot
+            .get_object_read(package_id)?
+            .into_object()
+            .map_err(SuiError::from)?
+            .previous_transaction;
+        let (_, effects) = snapshot.get_executed_transaction_and_effects(publish_txn_digest)?;
 
         let object_id = effects
             .events
@@ -147,7 +124,44 @@ impl CoinReadApi {
                 )
             })?;
 
-        self.get_object(&object_id).await
+        Ok(snapshot
+            .get_object_read(&object_id)?
+            .into_object()
+            .map_err(SuiError::from)?)
+    }
+}
+
+fn to_sui_coin(o: Object) -> Result<SuiCoin, Error> {
+    if let Some(move_object) = o.data.try_as_move() {
+        let (balance, locked_until_epoch) = if Coin::is_coin(&move_object.type_) {
+            let coin: Coin = bcs::from_bytes(move_object.contents())?;
+            (coin.balance.value(), None)
+        } else if LockedCoin::is_locked_coin(&move_object.type_) {
+            let locked_coin: LockedCoin = bcs::from_bytes(move_object.contents())?;
+            (
+                locked_coin.balance.value(),
+                Some(locked_coin.locked_until_epoch),
+            )
+        } else {
+            return Err(Error::SuiError(SuiError::ObjectDeserializationError {
+                error: format!("{:?} is not a supported coin type", move_object.type_),
+            }));
+        };
+
+        Ok(SuiCoin {
+            coin_type: move_object.type_.type_params.first().unwrap().to_string(),
+            coin_object_id: o.id(),
+            version: o.version(),
+            digest: o.digest(),
+            balance,
+            locked_until_epoch,
+            previous_transaction: o.previous_transaction,
+        })
+    } else {
+        Err(Error::UnexpectedError(format!(
+            "Provided object : [{}] is not a Move object.",
+            o.id()
+        )))
     }
 }
 
@@ -174,9 +188,7 @@ impl CoinReadApiServer for CoinReadApi {
             Some(c) => parse_sui_struct_tag(&c)?,
             None => GAS::type_(),
         });
-        Ok(self
-            .get_coins_internal(owner, coin_type, cursor, limit)
-            .await?)
+        Ok(self.get_coins_internal(owner, coin_type, cursor, limit)?)
     }
 
     async fn get_all_coins(
@@ -185,7 +197,7 @@ impl CoinReadApiServer for CoinReadApi {
         cursor: Option<ObjectID>,
         limit: Option<usize>,
     ) -> RpcResult<CoinPage> {
-        Ok(self.get_coins_internal(owner, None, cursor, limit).await?)
+        Ok(self.get_coins_internal(owner, None, cursor, limit)?)
     }
 
     async fn get_balance(
@@ -199,13 +211,14 @@ impl CoinReadApiServer for CoinReadApi {
         });
 
         // TODO: Add index to improve performance?
-        let coins = self.get_owner_coin_iterator(owner, &coin_type)?;
+        let coins = self
+            .get_owner_coin_iterator(owner, &coin_type)?
+            .collect::<Vec<_>>();
         let mut total_balance = 0u128;
         let mut locked_balance = HashMap::new();
         let mut coin_object_count = 0;
 
-        for coin in coins {
-            let coin = self.get_coin(&coin).await?;
+        for coin in self.read_listed_coins(coins)? {
             if let Some(lock) = coin.locked_until_epoch {
                 *locked_balance.entry(lock).or_default() += coin.balance as u128
             } else {
@@ -224,11 +237,12 @@ impl CoinReadApiServer for CoinReadApi {
 
     async fn get_all_balances(&self, owner: SuiAddress) -> RpcResult<Vec<Balance>> {
         // TODO: Add index to improve performance?
-        let coins = self.get_owner_coin_iterator(owner, &None)?;
+        let coins = self
+            .get_owner_coin_iterator(owner, &None)?
+            .collect::<Vec<_>>();
         let mut balances: HashMap<String, Balance> = HashMap::new();
 
-        for coin in coins {
-            let coin = self.get_coin(&coin).await?;
+        for coin in self.read_listed_coins(coins)? {
             let balance = balances.entry(coin.coin_type.clone()).or_insert(Balance {
                 coin_type: coin.coin_type,
                 coin_object_count: 0,
@@ -262,12 +276,10 @@ impl CoinReadApiServer for CoinReadApi {
             });
         }
 
-        let metadata_object = self
-            .find_package_object(
-                &coin_struct.address.into(),
-                CoinMetadata::type_(coin_struct),
-            )
-            .await?;
+        let metadata_object = self.find_package_object(
+            &coin_struct.address.into(),
+            CoinMetadata::type_(coin_struct),
+        )?;
         let metadata_object_id = metadata_object.id();
         Ok(metadata_object.try_into().map_err(|e: SuiError| {
             debug!(
@@ -284,9 +296,10 @@ impl CoinReadApiServer for CoinReadApi {
         Ok(if GAS::is_gas(&coin_struct) {
             Supply { value: 0 }
         } else {
-            let treasury_cap_object = self
-                .find_package_object(&coin_struct.address.into(), TreasuryCap::type_(coin_struct))
-                .await?;
+            let treasury_cap_object = self.find_package_object(
+                &coin_struct.address.into(),
+                TreasuryCap::type_(coin_struct),
+            )?;
 
             let treasury_cap = TreasuryCap::from_bcs_bytes(
                 treasury_cap_object.data.try_as_move().unwrap().contents(),
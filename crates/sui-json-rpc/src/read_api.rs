@@ -21,11 +21,13 @@ use jsonrpsee::RpcModule;
 use sui_core::authority::AuthorityState;
 use sui_json_rpc_types::{
     Checkpoint, CheckpointId, DynamicFieldPage, GetObjectDataResponse, GetPastObjectDataResponse,
-    GetRawObjectDataResponse, MoveFunctionArgType, ObjectValueKind, Page, SuiEvent,
-    SuiMoveNormalizedFunction, SuiMoveNormalizedModule, SuiMoveNormalizedStruct, SuiMoveStruct,
-    SuiMoveValue, SuiObjectInfo, SuiTransactionEffects, SuiTransactionResponse, TransactionsPage,
+    GetRawObjectDataResponse, MoveFunctionArgType, ObjectValueKind, Page, ProtocolConfigResponse,
+    SuiEvent, SuiMoveNormalizedFunction, SuiMoveNormalizedModule, SuiMoveNormalizedStruct,
+    SuiMoveStruct, SuiMoveValue, SuiObjectInfo, SuiTransactionEffects, SuiTransactionResponse,
+    TransactionsPage,
 };
 use sui_open_rpc::Module;
+use sui_protocol_config::{ProtocolConfig, ProtocolVersion};
 use sui_types::base_types::SequenceNumber;
 use sui_types::base_types::{ObjectID, SuiAddress, TransactionDigest, TxSequenceNumber};
 use sui_types::crypto::sha3_hash;
@@ -80,9 +82,16 @@ impl ReadApiServer for ReadApi {
         &self,
         address: SuiAddress,
     ) -> RpcResult<Vec<SuiObjectInfo>> {
-        Ok(self
+        let objects = self
             .state
             .get_owner_objects(address)
+            .map_err(|e| anyhow!("{e}"))?;
+        // The owner index is a separate DB, so the objects it lists are reconciled with a
+        // snapshot taken after reading it.
+        Ok(self
+            .state
+            .read_snapshot()
+            .and_then(|snapshot| snapshot.reconcile_owned_objects(objects))
             .map_err(|e| anyhow!("{e}"))?
             .into_iter()
             .map(SuiObjectInfo::from)
@@ -122,14 +131,25 @@ impl ReadApiServer for ReadApi {
         parent_object_id: ObjectID,
         name: DynamicFieldName,
     ) -> RpcResult<GetObjectDataResponse> {
+        let not_found =
+            || anyhow!("Cannot find dynamic field [{name:?}] for object [{parent_object_id}].");
         let id = self
             .state
             .get_dynamic_field_object_id(parent_object_id, &name)
             .map_err(|e| anyhow!("{e}"))?
-            .ok_or_else(|| {
-                anyhow!("Cannot find dynamic field [{name:?}] for object [{parent_object_id}].")
-            })?;
-        self.get_object(id).await
+            .ok_or_else(not_found)?;
+        // The dynamic field index is a separate DB, so the field it lists may have been removed
+        // since it was read.
+        Ok(self
+            .state
+            .read_snapshot()
+            .and_then(|snapshot| snapshot.get_dynamic_field_object_read(parent_object_id, &id))
+            .map_err(|e| {
+                debug!(object_id=?id, "Failed to get object: {:?}", e);
+                anyhow!("{e}")
+            })?
+            .ok_or_else(not_found)?
+            .try_into()?)
     }
 
     async fn get_total_transaction_number(&self) -> RpcResult<u64> {
@@ -153,13 +173,13 @@ impl ReadApiServer for ReadApi {
         &self,
         digest: TransactionDigest,
     ) -> RpcResult<SuiTransactionResponse> {
-        let (transaction, effects) = self
-            .state
+        // Read the transaction, its effects and its checkpoint from the same snapshot, so that they
+        // are consistent with each other even while the transaction is being checkpointed.
+        let snapshot = self.state.read_snapshot().map_err(|e| anyhow!("{e}"))?;
+        let (transaction, effects) = snapshot
             .get_executed_transaction_and_effects(digest)
-            .await
             .tap_err(|err| debug!(tx_digest=?digest, "Failed to get transaction: {:?}", err))?;
-        let checkpoint = self
-            .state
+        let checkpoint = snapshot
             .get_transaction_checkpoint(&digest)
             .map_err(|e| anyhow!("{e}"))?;
         let checkpoint_timestamp = checkpoint.as_ref().map(|c| c.summary.timestamp_ms);
@@ -387,6 +407,39 @@ impl ReadApiServer for ReadApi {
         let display_object = get_display_object(self, object_type).await?;
         Ok(get_rendered_fields(display_object.fields, &move_struct).map_err(|e| anyhow!("{e}"))?)
     }
+
+    async fn get_protocol_config(
+        &self,
+        version: Option<ProtocolVersion>,
+    ) -> RpcResult<ProtocolConfigResponse> {
+        let supported_protocol_versions = self.state.supported_protocol_versions();
+        let (version, config) = match version {
+            Some(version) => {
+                if !supported_protocol_versions.is_version_supported(version) {
+                    return Err(anyhow!(
+                        "Protocol version {} is not supported by this node, supported versions are {}..={}",
+                        version.as_u64(),
+                        supported_protocol_versions.min().as_u64(),
+                        supported_protocol_versions.max().as_u64(),
+                    )
+                    .into());
+                }
+                (version, ProtocolConfig::get_for_version(version))
+            }
+            None => {
+                let epoch_store = self.state.load_epoch_store_one_call_per_task();
+                (
+                    epoch_store.committee().protocol_version,
+                    epoch_store.protocol_config().clone(),
+                )
+            }
+        };
+        Ok(ProtocolConfigResponse::new(
+            &config,
+            version,
+            supported_protocol_versions,
+        )?)
+    }
 }
 
 impl SuiRpcModule for ReadApi {
@@ -19,6 +19,7 @@ use sui_json_rpc_types::{
     SuiTBlsSignObjectCommitmentType, SuiTransactionResponse, TransactionBytes,
 };
 use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, Keystore};
+use sui_protocol_config::ProtocolConfig;
 use sui_types::balance::Supply;
 use sui_types::base_types::ObjectID;
 use sui_types::base_types::TransactionDigest;
@@ -1176,3 +1177,29 @@ async fn test_delegation_with_locked_sui() -> Result<(), anyhow::Error> {
 
     Ok(())
 }
+
+#[sim_test]
+async fn test_get_protocol_config() -> Result<(), anyhow::Error> {
+    let cluster = TestClusterBuilder::new().build().await?;
+    let http_client = cluster.rpc_client();
+
+    let current = http_client.get_protocol_config(None).await?;
+    let expected = ProtocolConfig::get_for_version(current.protocol_version);
+    assert_eq!(current.attributes["max_tx_gas"], expected.max_tx_gas());
+    assert_eq!(
+        current.attributes["max_num_event_emit"],
+        expected.max_num_event_emit()
+    );
+
+    let min_version = current.min_supported_protocol_version;
+    let oldest = http_client.get_protocol_config(Some(min_version)).await?;
+    assert_eq!(oldest.protocol_version, min_version);
+    assert_eq!(oldest.attributes.len(), current.attributes.len());
+
+    // Versions the node does not support are rejected.
+    assert!(http_client
+        .get_protocol_config(Some(current.max_supported_protocol_version + 1))
+        .await
+        .is_err());
+    Ok(())
+}
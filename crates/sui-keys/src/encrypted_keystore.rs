@@ -0,0 +1,424 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Nonce};
+use anyhow::{anyhow, bail, ensure};
+use fastcrypto::encoding::{Base64, Encoding};
+use hmac::Hmac;
+use rand::rngs::OsRng;
+use rand::RngCore;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use sha2::Sha256;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use sui_types::base_types::SuiAddress;
+use sui_types::crypto::{EncodeDecodeBase64, PublicKey, Signature, Signer, SuiKeyPair};
+use sui_types::intent::{Intent, IntentMessage};
+use zeroize::{Zeroize, Zeroizing};
+
+use crate::keystore::{AccountKeystore, FileBasedKeystore};
+
+/// Environment variable holding the passphrase used to unlock an encrypted keystore when it is
+/// loaded from a client config.
+pub const SUI_KEYSTORE_PASSPHRASE_ENV: &str = "SUI_KEYSTORE_PASSPHRASE";
+
+/// Environment variable holding the new passphrase when re-encrypting a keystore.
+pub const SUI_KEYSTORE_NEW_PASSPHRASE_ENV: &str = "SUI_KEYSTORE_NEW_PASSPHRASE";
+
+/// PBKDF2-HMAC-SHA256 iterations used for new encrypted keystores.
+pub const DEFAULT_KDF_ITERATIONS: u32 = 600_000;
+
+const ENCRYPTED_KEYSTORE_VERSION: u8 = 1;
+const KEY_LENGTH: usize = 32;
+const SALT_LENGTH: usize = 16;
+const NONCE_LENGTH: usize = 12;
+
+/// On-disk format of an encrypted keystore. Public keys are stored in the clear so that the
+/// managed addresses can be listed without the passphrase.
+#[derive(Serialize, Deserialize)]
+struct EncryptedKeystoreFile {
+    version: u8,
+    kdf: KdfParams,
+    nonce: String,
+    ciphertext: String,
+    public_keys: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+struct KdfParams {
+    salt: String,
+    iterations: u32,
+}
+
+impl KdfParams {
+    fn generate(iterations: u32) -> Self {
+        let mut salt = [0u8; SALT_LENGTH];
+        OsRng.fill_bytes(&mut salt);
+        Self {
+            salt: Base64::encode(salt),
+            iterations,
+        }
+    }
+
+    fn derive_key(&self, passphrase: &str) -> Result<Zeroizing<[u8; KEY_LENGTH]>, anyhow::Error> {
+        let salt = Base64::decode(&self.salt).map_err(|e| anyhow!("Invalid KDF salt: {e}"))?;
+        let mut key = Zeroizing::new([0u8; KEY_LENGTH]);
+        pbkdf2::pbkdf2::<Hmac<Sha256>>(passphrase.as_bytes(), &salt, self.iterations, key.as_mut());
+        Ok(key)
+    }
+}
+
+/// A file based keystore whose private keys are encrypted at rest with AES-256-GCM, using a key
+/// derived from a passphrase. The keystore can be locked, in which case only its public keys
+/// are available and signing fails until it is unlocked again.
+#[derive(Default)]
+pub struct EncryptedFileKeystore {
+    public_keys: BTreeMap<SuiAddress, PublicKey>,
+    /// The decrypted keys, `None` while the keystore is locked.
+    keys: Option<BTreeMap<SuiAddress, SuiKeyPair>>,
+    /// The key derived from the passphrase, kept while unlocked so new keys can be saved.
+    encryption_key: Option<Zeroizing<[u8; KEY_LENGTH]>>,
+    /// The encrypted content last read from or written to disk, `(nonce, ciphertext)`.
+    sealed: Option<(Vec<u8>, Vec<u8>)>,
+    kdf: Option<KdfParams>,
+    path: Option<PathBuf>,
+}
+
+impl Serialize for EncryptedFileKeystore {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(
+            self.path
+                .as_ref()
+                .unwrap_or(&PathBuf::default())
+                .to_str()
+                .unwrap_or(""),
+        )
+    }
+}
+
+impl<'de> Deserialize<'de> for EncryptedFileKeystore {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use serde::de::Error;
+        EncryptedFileKeystore::load(&PathBuf::from(String::deserialize(deserializer)?))
+            .map_err(D::Error::custom)
+    }
+}
+
+impl AccountKeystore for EncryptedFileKeystore {
+    #[warn(deprecated)]
+    fn sign(&self, address: &SuiAddress, msg: &[u8]) -> Result<Signature, signature::Error> {
+        Ok(self.signing_key(address)?.sign(msg))
+    }
+
+    fn sign_secure<T>(
+        &self,
+        address: &SuiAddress,
+        msg: &T,
+        intent: Intent,
+    ) -> Result<Signature, signature::Error>
+    where
+        T: Serialize,
+    {
+        Ok(Signature::new_secure(
+            &IntentMessage::new(intent, msg),
+            self.signing_key(address)?,
+        ))
+    }
+
+    fn add_key(&mut self, keypair: SuiKeyPair) -> Result<(), anyhow::Error> {
+        let keys = self.keys.as_mut().ok_or_else(locked_error)?;
+        let public_key = keypair.public();
+        let address: SuiAddress = (&public_key).into();
+        keys.insert(address, keypair);
+        self.public_keys.insert(address, public_key);
+        self.save()?;
+        Ok(())
+    }
+
+    fn keys(&self) -> Vec<PublicKey> {
+        self.public_keys.values().cloned().collect()
+    }
+
+    fn get_key(&self, address: &SuiAddress) -> Result<&SuiKeyPair, anyhow::Error> {
+        match self.keys.as_ref().ok_or_else(locked_error)?.get(address) {
+            Some(key) => Ok(key),
+            None => Err(anyhow!("Cannot find key for address: [{address}]")),
+        }
+    }
+}
+
+impl EncryptedFileKeystore {
+    /// Opens and unlocks the encrypted keystore at `path`, or creates an empty one protected by
+    /// `passphrase` if the file does not exist.
+    pub fn new(path: &PathBuf, passphrase: &str) -> Result<Self, anyhow::Error> {
+        Self::new_with_kdf_iterations(path, passphrase, DEFAULT_KDF_ITERATIONS)
+    }
+
+    pub fn new_with_kdf_iterations(
+        path: &PathBuf,
+        passphrase: &str,
+        kdf_iterations: u32,
+    ) -> Result<Self, anyhow::Error> {
+        if path.exists() {
+            let mut keystore = Self::open(path)?;
+            keystore.unlock(passphrase)?;
+            Ok(keystore)
+        } else {
+            Self::create(path, passphrase, kdf_iterations)
+        }
+    }
+
+    /// Creates an empty, unlocked keystore protected by `passphrase`. Any existing file at
+    /// `path` is overwritten on the next save.
+    pub fn create(
+        path: &Path,
+        passphrase: &str,
+        kdf_iterations: u32,
+    ) -> Result<Self, anyhow::Error> {
+        let kdf = KdfParams::generate(kdf_iterations);
+        let encryption_key = kdf.derive_key(passphrase)?;
+        Ok(Self {
+            public_keys: BTreeMap::new(),
+            keys: Some(BTreeMap::new()),
+            encryption_key: Some(encryption_key),
+            sealed: None,
+            kdf: Some(kdf),
+            path: Some(path.to_path_buf()),
+        })
+    }
+
+    /// Reads the encrypted keystore at `path` without decrypting it. The returned keystore is
+    /// locked.
+    pub fn open(path: &PathBuf) -> Result<Self, anyhow::Error> {
+        let file: EncryptedKeystoreFile = serde_json::from_slice(&fs::read(path)?)
+            .map_err(|e| anyhow!("Invalid encrypted keystore file {:?}: {e}", path))?;
+        ensure!(
+            file.version == ENCRYPTED_KEYSTORE_VERSION,
+            "Unsupported encrypted keystore version {} in {:?}",
+            file.version,
+            path
+        );
+        let public_keys = file
+            .public_keys
+            .iter()
+            .map(|pk| {
+                PublicKey::decode_base64(pk)
+                    .map(|pk| (SuiAddress::from(&pk), pk))
+                    .map_err(|e| anyhow!("Invalid public key in {:?}: {e}", path))
+            })
+            .collect::<Result<BTreeMap<_, _>, _>>()?;
+        let nonce = Base64::decode(&file.nonce).map_err(|e| anyhow!("Invalid nonce: {e}"))?;
+        ensure!(
+            nonce.len() == NONCE_LENGTH,
+            "Invalid nonce length in {:?}",
+            path
+        );
+        let ciphertext =
+            Base64::decode(&file.ciphertext).map_err(|e| anyhow!("Invalid ciphertext: {e}"))?;
+        Ok(Self {
+            public_keys,
+            keys: None,
+            encryption_key: None,
+            sealed: Some((nonce, ciphertext)),
+            kdf: Some(file.kdf),
+            path: Some(path.to_path_buf()),
+        })
+    }
+
+    /// Reads the encrypted keystore at `path`, unlocking it if the passphrase is set in the
+    /// `SUI_KEYSTORE_PASSPHRASE` environment variable.
+    pub fn load(path: &PathBuf) -> Result<Self, anyhow::Error> {
+        let mut keystore = Self::open(path)?;
+        if let Ok(passphrase) = std::env::var(SUI_KEYSTORE_PASSPHRASE_ENV) {
+            keystore.unlock(&passphrase)?;
+        }
+        Ok(keystore)
+    }
+
+    /// Encrypts the keys of a plaintext keystore into a new encrypted keystore at `path`.
+    pub fn from_file_keystore(
+        keystore: &FileBasedKeystore,
+        path: &Path,
+        passphrase: &str,
+        kdf_iterations: u32,
+    ) -> Result<Self, anyhow::Error> {
+        let mut encrypted = Self::create(path, passphrase, kdf_iterations)?;
+        let keys = encrypted.keys.as_mut().ok_or_else(locked_error)?;
+        for keypair in keystore.key_pairs() {
+            let keypair = SuiKeyPair::decode_base64(&keypair.encode_base64())
+                .map_err(|e| anyhow!("Invalid keypair: {e}"))?;
+            let public_key = keypair.public();
+            let address = SuiAddress::from(&public_key);
+            keys.insert(address, keypair);
+            encrypted.public_keys.insert(address, public_key);
+        }
+        encrypted.save()?;
+        Ok(encrypted)
+    }
+
+    /// Returns true if the file at `path` is an encrypted keystore rather than a plaintext one.
+    pub fn is_encrypted_keystore_file(path: &Path) -> bool {
+        fs::read(path)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice::<EncryptedKeystoreFile>(&bytes).ok())
+            .is_some()
+    }
+
+    pub fn is_locked(&self) -> bool {
+        self.keys.is_none()
+    }
+
+    /// Decrypts the private keys with `passphrase`.
+    pub fn unlock(&mut self, passphrase: &str) -> Result<(), anyhow::Error> {
+        if !self.is_locked() {
+            return Ok(());
+        }
+        let kdf = self
+            .kdf
+            .as_ref()
+            .ok_or_else(|| anyhow!("Missing KDF parameters"))?;
+        let (nonce, ciphertext) = self
+            .sealed
+            .as_ref()
+            .ok_or_else(|| anyhow!("Encrypted keystore has no content"))?;
+        let encryption_key = kdf.derive_key(passphrase)?;
+        let cipher = Aes256Gcm::new_from_slice(encryption_key.as_ref())
+            .map_err(|e| anyhow!("Invalid encryption key: {e}"))?;
+        let plaintext = Zeroizing::new(
+            cipher
+                .decrypt(Nonce::from_slice(nonce), ciphertext.as_ref())
+                .map_err(|_| anyhow!("Incorrect passphrase for encrypted keystore"))?,
+        );
+        let kp_strings: Zeroizing<Vec<String>> =
+            Zeroizing::new(serde_json::from_slice(&plaintext)?);
+        let keys = kp_strings
+            .iter()
+            .map(|kpstr| {
+                SuiKeyPair::decode_base64(kpstr)
+                    .map(|k| (SuiAddress::from(&k.public()), k))
+                    .map_err(|e| anyhow!("Invalid keypair in encrypted keystore: {e}"))
+            })
+            .collect::<Result<BTreeMap<_, _>, _>>()?;
+        if keys.keys().ne(self.public_keys.keys()) {
+            bail!("Public keys of the encrypted keystore do not match its private keys");
+        }
+        self.keys = Some(keys);
+        self.encryption_key = Some(encryption_key);
+        Ok(())
+    }
+
+    /// Drops the decrypted private keys and the derived encryption key from memory.
+    pub fn lock(&mut self) {
+        self.keys = None;
+        self.encryption_key = None;
+    }
+
+    /// Re-encrypts the keystore with a key derived from `new_passphrase` and a fresh salt. The
+    /// keystore must be unlocked.
+    pub fn change_passphrase(&mut self, new_passphrase: &str) -> Result<(), anyhow::Error> {
+        if self.is_locked() {
+            return Err(locked_error());
+        }
+        let iterations = self
+            .kdf
+            .as_ref()
+            .map_or(DEFAULT_KDF_ITERATIONS, |kdf| kdf.iterations);
+        let kdf = KdfParams::generate(iterations);
+        self.encryption_key = Some(kdf.derive_key(new_passphrase)?);
+        self.kdf = Some(kdf);
+        self.save()
+    }
+
+    pub fn set_path(&mut self, path: &Path) {
+        self.path = Some(path.to_path_buf());
+    }
+
+    pub fn path(&self) -> Option<&PathBuf> {
+        self.path.as_ref()
+    }
+
+    pub fn save(&mut self) -> Result<(), anyhow::Error> {
+        let keys = self.keys.as_ref().ok_or_else(locked_error)?;
+        let (Some(encryption_key), Some(kdf)) = (&self.encryption_key, &self.kdf) else {
+            return Err(locked_error());
+        };
+        let mut kp_strings = keys
+            .values()
+            .map(EncodeDecodeBase64::encode_base64)
+            .collect::<Vec<_>>();
+        let plaintext = Zeroizing::new(serde_json::to_vec(&kp_strings)?);
+        kp_strings.zeroize();
+        let mut nonce = [0u8; NONCE_LENGTH];
+        OsRng.fill_bytes(&mut nonce);
+        let cipher = Aes256Gcm::new_from_slice(encryption_key.as_ref())
+            .map_err(|e| anyhow!("Invalid encryption key: {e}"))?;
+        let ciphertext = cipher
+            .encrypt(Nonce::from_slice(&nonce), plaintext.as_ref())
+            .map_err(|e| anyhow!("Failed to encrypt keystore: {e}"))?;
+
+        if let Some(path) = &self.path {
+            let file = EncryptedKeystoreFile {
+                version: ENCRYPTED_KEYSTORE_VERSION,
+                kdf: kdf.clone(),
+                nonce: Base64::encode(nonce),
+                ciphertext: Base64::encode(&ciphertext),
+                public_keys: self
+                    .public_keys
+                    .values()
+                    .map(EncodeDecodeBase64::encode_base64)
+                    .collect(),
+            };
+            write_atomically(path, serde_json::to_string_pretty(&file)?.as_bytes())?;
+        }
+        self.sealed = Some((nonce.to_vec(), ciphertext));
+        Ok(())
+    }
+
+    fn signing_key(&self, address: &SuiAddress) -> Result<&SuiKeyPair, signature::Error> {
+        self.keys
+            .as_ref()
+            .ok_or_else(|| signature::Error::from_source(locked_error().to_string()))?
+            .get(address)
+            .ok_or_else(|| {
+                signature::Error::from_source(format!("Cannot find key for address: [{address}]"))
+            })
+    }
+}
+
+/// Replaces the file at `path` without ever leaving it partially written: the contents are
+/// written and synced to a temporary file first, which is then renamed over `path`. On Unix the
+/// file is only readable and writable by its owner.
+fn write_atomically(path: &Path, contents: &[u8]) -> Result<(), anyhow::Error> {
+    let tmp_path = path.with_extension("tmp");
+    // A temporary file left behind by an interrupted write may have other permissions, so it is
+    // never reused.
+    match fs::remove_file(&tmp_path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.into()),
+        _ => {}
+    }
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let mut file = options.open(&tmp_path)?;
+    file.write_all(contents)?;
+    file.sync_all()?;
+    fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+fn locked_error() -> anyhow::Error {
+    anyhow!(
+        "Keystore is locked, unlock it with its passphrase (e.g. by setting {}).",
+        SUI_KEYSTORE_PASSPHRASE_ENV
+    )
+}
@@ -0,0 +1,273 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A keystore backed by an external signer process listening on a Unix socket, so that
+//! private keys never live in the process building transactions.
+//!
+//! Each request opens a new connection and exchanges a single line of JSON in each
+//! direction:
+//! * `{"method":"keys"}` is answered with `{"keys":["<Base64 flag || pk>", ...]}`
+//! * `{"method":"sign","params":{"address":"0x..","intent_message":"<Base64 BCS>"}}` is
+//!   answered with `{"signature":"<Base64 flag || sig || pk>"}`
+//!
+//! Either request may instead be answered with `{"error":"<reason>"}`. The signer receives
+//! the full BCS serialized `IntentMessage`, so it can inspect the intent and the transaction
+//! before signing it.
+
+use anyhow::{anyhow, bail};
+use fastcrypto::encoding::{Base64, Encoding};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::BTreeMap;
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::thread::JoinHandle;
+use std::time::Duration;
+use sui_types::base_types::SuiAddress;
+use sui_types::crypto::{
+    EncodeDecodeBase64, PublicKey, Signature, Signer, SuiKeyPair, SuiSignature,
+};
+use sui_types::intent::{Intent, IntentMessage};
+
+use crate::keystore::{AccountKeystore, InMemKeystore};
+
+/// How long to wait for the external signer, which may require a user confirmation.
+const SIGNER_TIMEOUT: Duration = Duration::from_secs(120);
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "method", content = "params", rename_all = "snake_case")]
+pub enum SignerRequest {
+    Keys,
+    Sign {
+        address: SuiAddress,
+        /// Base64 encoded BCS bytes of the `IntentMessage` to sign.
+        intent_message: String,
+    },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SignerResponse {
+    /// Base64 encoded `flag || pk` of every key held by the signer.
+    Keys(Vec<String>),
+    /// Base64 encoded `flag || sig || pk`.
+    Signature(String),
+    Error(String),
+}
+
+pub struct ExternalKeystore {
+    socket_path: PathBuf,
+    public_keys: BTreeMap<SuiAddress, PublicKey>,
+}
+
+impl Serialize for ExternalKeystore {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.socket_path.to_str().unwrap_or(""))
+    }
+}
+
+impl<'de> Deserialize<'de> for ExternalKeystore {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use serde::de::Error;
+        ExternalKeystore::new(&PathBuf::from(String::deserialize(deserializer)?))
+            .map_err(D::Error::custom)
+    }
+}
+
+impl AccountKeystore for ExternalKeystore {
+    #[warn(deprecated)]
+    fn sign(&self, _address: &SuiAddress, _msg: &[u8]) -> Result<Signature, signature::Error> {
+        Err(signature::Error::from_source(
+            "The external signer only signs intent messages, use sign_secure instead",
+        ))
+    }
+
+    fn sign_secure<T>(
+        &self,
+        address: &SuiAddress,
+        msg: &T,
+        intent: Intent,
+    ) -> Result<Signature, signature::Error>
+    where
+        T: Serialize,
+    {
+        let intent_msg = IntentMessage::new(intent, msg);
+        let bytes = bcs::to_bytes(&intent_msg).map_err(signature::Error::from_source)?;
+        let signature = self
+            .sign_intent_message_bytes(address, &bytes)
+            .map_err(|e| signature::Error::from_source(e.to_string()))?;
+        // Never hand out a signature the network would reject, e.g. if the signer answered
+        // for the wrong key or message.
+        signature
+            .verify_secure(&intent_msg, *address)
+            .map_err(|e| {
+                signature::Error::from_source(format!(
+                    "External signer returned an invalid signature for [{address}]: {e}"
+                ))
+            })?;
+        Ok(signature)
+    }
+
+    fn add_key(&mut self, _keypair: SuiKeyPair) -> Result<(), anyhow::Error> {
+        bail!(
+            "Keys of an external signer are managed by the signer at {:?}",
+            self.socket_path
+        )
+    }
+
+    fn keys(&self) -> Vec<PublicKey> {
+        self.public_keys.values().cloned().collect()
+    }
+
+    fn get_key(&self, address: &SuiAddress) -> Result<&SuiKeyPair, anyhow::Error> {
+        Err(anyhow!(
+            "The private key for address [{address}] is held by the external signer at {:?}",
+            self.socket_path
+        ))
+    }
+}
+
+impl ExternalKeystore {
+    /// Connects to the signer listening on `socket_path` and fetches the keys it holds.
+    pub fn new(socket_path: &Path) -> Result<Self, anyhow::Error> {
+        let mut keystore = Self {
+            socket_path: socket_path.to_path_buf(),
+            public_keys: BTreeMap::new(),
+        };
+        keystore.refresh_keys()?;
+        Ok(keystore)
+    }
+
+    pub fn socket_path(&self) -> &Path {
+        &self.socket_path
+    }
+
+    /// Re-fetches the list of keys held by the signer.
+    pub fn refresh_keys(&mut self) -> Result<(), anyhow::Error> {
+        let keys = match self.request(&SignerRequest::Keys)? {
+            SignerResponse::Keys(keys) => keys,
+            response => bail!("Unexpected response to keys request: {response:?}"),
+        };
+        self.public_keys = keys
+            .iter()
+            .map(|pk| {
+                PublicKey::decode_base64(pk)
+                    .map(|pk| (SuiAddress::from(&pk), pk))
+                    .map_err(|e| anyhow!("Invalid public key [{pk}] from external signer: {e}"))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(())
+    }
+
+    fn sign_intent_message_bytes(
+        &self,
+        address: &SuiAddress,
+        intent_message: &[u8],
+    ) -> Result<Signature, anyhow::Error> {
+        if !self.public_keys.contains_key(address) {
+            bail!("Cannot find key for address: [{address}]");
+        }
+        let request = SignerRequest::Sign {
+            address: *address,
+            intent_message: Base64::encode(intent_message),
+        };
+        match self.request(&request)? {
+            SignerResponse::Signature(signature) => Signature::decode_base64(&signature)
+                .map_err(|e| anyhow!("Invalid signature from external signer: {e}")),
+            response => bail!("Unexpected response to sign request: {response:?}"),
+        }
+    }
+
+    fn request(&self, request: &SignerRequest) -> Result<SignerResponse, anyhow::Error> {
+        let stream = UnixStream::connect(&self.socket_path).map_err(|e| {
+            anyhow!(
+                "Cannot connect to external signer at {:?}: {e}",
+                self.socket_path
+            )
+        })?;
+        stream.set_read_timeout(Some(SIGNER_TIMEOUT))?;
+        stream.set_write_timeout(Some(SIGNER_TIMEOUT))?;
+        write_message(&stream, request)?;
+        match read_message(&stream)? {
+            SignerResponse::Error(e) => bail!("External signer error: {e}"),
+            response => Ok(response),
+        }
+    }
+}
+
+fn write_message<T: Serialize>(mut stream: &UnixStream, message: &T) -> Result<(), anyhow::Error> {
+    let mut line = serde_json::to_vec(message)?;
+    line.push(b'\n');
+    stream.write_all(&line)?;
+    stream.flush()?;
+    Ok(())
+}
+
+fn read_message<T: DeserializeOwned>(stream: &UnixStream) -> Result<T, anyhow::Error> {
+    let mut line = String::new();
+    BufReader::new(stream).read_line(&mut line)?;
+    if line.is_empty() {
+        bail!("Connection closed before a message was received");
+    }
+    Ok(serde_json::from_str(&line)?)
+}
+
+/// A reference implementation of the external signer protocol, serving the keys of an
+/// in-memory keystore. Meant for tests and as an example for real signer daemons.
+pub struct MockExternalSigner {
+    keystore: InMemKeystore,
+}
+
+impl MockExternalSigner {
+    pub fn new(keystore: InMemKeystore) -> Self {
+        Self { keystore }
+    }
+
+    /// Binds `socket_path` and serves requests on a background thread, one connection at a
+    /// time.
+    pub fn spawn(self, socket_path: &Path) -> Result<JoinHandle<()>, anyhow::Error> {
+        let listener = UnixListener::bind(socket_path)?;
+        Ok(std::thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let response = match read_message(&stream) {
+                    Ok(request) => self.handle(request),
+                    Err(e) => SignerResponse::Error(e.to_string()),
+                };
+                // The client may have gone away, there is nobody to report the error to.
+                let _ = write_message(&stream, &response);
+            }
+        }))
+    }
+
+    pub fn handle(&self, request: SignerRequest) -> SignerResponse {
+        match request {
+            SignerRequest::Keys => SignerResponse::Keys(
+                self.keystore
+                    .keys()
+                    .iter()
+                    .map(EncodeDecodeBase64::encode_base64)
+                    .collect(),
+            ),
+            SignerRequest::Sign {
+                address,
+                intent_message,
+            } => {
+                let bytes = match Base64::decode(&intent_message) {
+                    Ok(bytes) => bytes,
+                    Err(e) => return SignerResponse::Error(format!("Invalid intent message: {e}")),
+                };
+                match self.keystore.get_key(&address) {
+                    Ok(key) => SignerResponse::Signature(key.sign(&bytes).encode_base64()),
+                    Err(e) => SignerResponse::Error(e.to_string()),
+                }
+            }
+        }
+    }
+}
@@ -21,6 +21,8 @@ use sui_types::crypto::{
     SignatureScheme, Signer, SuiKeyPair,
 };
 
+use crate::encrypted_keystore::EncryptedFileKeystore;
+use crate::external_keystore::ExternalKeystore;
 use crate::key_derive::{derive_key_pair_from_path, generate_new_key};
 
 #[derive(Serialize, Deserialize)]
@@ -28,6 +30,8 @@ use crate::key_derive::{derive_key_pair_from_path, generate_new_key};
 pub enum Keystore {
     File(FileBasedKeystore),
     InMem(InMemKeystore),
+    Encrypted(EncryptedFileKeystore),
+    External(ExternalKeystore),
 }
 #[enum_dispatch]
 pub trait AccountKeystore: Send + Sync {
@@ -91,6 +95,17 @@ impl Display for Keystore {
                 writeln!(writer, "Keystore Type : InMem")?;
                 write!(f, "{}", writer)
             }
+            Keystore::Encrypted(encrypted) => {
+                writeln!(writer, "Keystore Type : Encrypted")?;
+                writeln!(writer, "Keystore Path : {:?}", encrypted.path())?;
+                write!(writer, "Locked        : {}", encrypted.is_locked())?;
+                write!(f, "{}", writer)
+            }
+            Keystore::External(external) => {
+                writeln!(writer, "Keystore Type : External")?;
+                write!(writer, "Signer Socket : {:?}", external.socket_path())?;
+                write!(f, "{}", writer)
+            }
         }
     }
 }
@@ -198,6 +213,10 @@ impl FileBasedKeystore {
         })
     }
 
+    pub fn path(&self) -> Option<&PathBuf> {
+        self.path.as_ref()
+    }
+
     pub fn set_path(&mut self, path: &Path) {
         self.path = Some(path.to_path_buf());
     }
@@ -5,8 +5,13 @@ use std::str::FromStr;
 use fastcrypto::hash::{HashFunction, Sha3_256};
 use tempfile::TempDir;
 
-use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, Keystore};
-use sui_types::crypto::{SignatureScheme, SuiSignatureInner};
+use sui_keys::encrypted_keystore::EncryptedFileKeystore;
+use sui_keys::external_keystore::{ExternalKeystore, MockExternalSigner};
+use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, InMemKeystore, Keystore};
+use sui_types::crypto::{
+    get_key_pair, EncodeDecodeBase64, SignatureScheme, SuiKeyPair, SuiSignature, SuiSignatureInner,
+};
+use sui_types::intent::{Intent, IntentMessage};
 use sui_types::{
     base_types::{SuiAddress, SUI_ADDRESS_LENGTH},
     crypto::Ed25519SuiSignature,
@@ -69,3 +74,125 @@ fn keystore_display_test() -> Result<(), anyhow::Error> {
     assert!(!keystore.to_string().contains("keys:"));
     Ok(())
 }
+
+#[test]
+fn encrypted_keystore_test() -> Result<(), anyhow::Error> {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let mut keystore = Keystore::from(EncryptedFileKeystore::new_with_kdf_iterations(
+        &keystore_path,
+        "passphrase",
+        10,
+    )?);
+    let (address, _, _) = keystore.generate_and_add_new_key(SignatureScheme::ED25519, None)?;
+
+    // Private keys are not stored in plaintext
+    let content = std::fs::read_to_string(&keystore_path)?;
+    let key = keystore.get_key(&address)?;
+    assert!(!content.contains(&key.encode_base64()));
+    assert!(EncryptedFileKeystore::is_encrypted_keystore_file(
+        &keystore_path
+    ));
+
+    // Addresses are listed while locked, but signing requires the passphrase
+    let mut locked = EncryptedFileKeystore::open(&keystore_path)?;
+    assert!(locked.is_locked());
+    assert_eq!(locked.addresses(), vec![address]);
+    assert!(locked
+        .sign_secure(&address, b"hello", Intent::default())
+        .is_err());
+    assert!(locked.unlock("wrong passphrase").is_err());
+    locked.unlock("passphrase")?;
+    locked.sign_secure(&address, b"hello", Intent::default())?;
+
+    locked.change_passphrase("new passphrase")?;
+    let mut reopened = EncryptedFileKeystore::open(&keystore_path)?;
+    assert!(reopened.unlock("passphrase").is_err());
+    reopened.unlock("new passphrase")?;
+    assert_eq!(reopened.addresses(), vec![address]);
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn encrypted_keystore_file_permissions_test() -> Result<(), anyhow::Error> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    // A stale temporary file with permissive permissions is not reused
+    std::fs::write(keystore_path.with_extension("tmp"), "")?;
+    std::fs::set_permissions(
+        keystore_path.with_extension("tmp"),
+        std::fs::Permissions::from_mode(0o644),
+    )?;
+    let mut keystore =
+        EncryptedFileKeystore::new_with_kdf_iterations(&keystore_path, "passphrase", 10)?;
+    keystore.generate_and_add_new_key(SignatureScheme::ED25519, None)?;
+
+    let mode = std::fs::metadata(&keystore_path)?.permissions().mode();
+    assert_eq!(mode & 0o777, 0o600);
+    Ok(())
+}
+
+#[test]
+fn encrypt_file_keystore_test() -> Result<(), anyhow::Error> {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let mut keystore = FileBasedKeystore::new(&keystore_path)?;
+    keystore.generate_and_add_new_key(SignatureScheme::ED25519, None)?;
+    keystore.generate_and_add_new_key(SignatureScheme::Secp256k1, None)?;
+    assert!(!EncryptedFileKeystore::is_encrypted_keystore_file(
+        &keystore_path
+    ));
+
+    let encrypted =
+        EncryptedFileKeystore::from_file_keystore(&keystore, &keystore_path, "passphrase", 10)?;
+    assert_eq!(encrypted.addresses(), keystore.addresses());
+    assert!(FileBasedKeystore::new(&keystore_path).is_err());
+
+    let mut reopened = EncryptedFileKeystore::open(&keystore_path)?;
+    reopened.unlock("passphrase")?;
+    for address in keystore.addresses() {
+        assert_eq!(
+            reopened.get_key(&address)?.public(),
+            keystore.get_key(&address)?.public()
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn external_keystore_test() -> Result<(), anyhow::Error> {
+    let temp_dir = TempDir::new().unwrap();
+    let socket_path = temp_dir.path().join("signer.sock");
+    let signer_keys = InMemKeystore::new(2);
+    let addresses = signer_keys.addresses();
+    MockExternalSigner::new(signer_keys).spawn(&socket_path)?;
+
+    let mut keystore = Keystore::from(ExternalKeystore::new(&socket_path)?);
+    assert_eq!(keystore.addresses(), addresses);
+    assert!(keystore.to_string().contains("signer.sock"));
+
+    let intent = Intent::default();
+    for address in &addresses {
+        let signature = keystore.sign_secure(address, b"hello", intent.clone())?;
+        signature.verify_secure(&IntentMessage::new(intent.clone(), b"hello"), *address)?;
+        // Private keys never leave the signer
+        assert!(keystore.get_key(address).is_err());
+    }
+
+    assert!(keystore
+        .sign_secure(&SuiAddress::random_for_testing_only(), b"hello", intent)
+        .is_err());
+    assert!(keystore
+        .add_key(SuiKeyPair::Ed25519(get_key_pair().1))
+        .is_err());
+    Ok(())
+}
+
+#[test]
+fn external_keystore_without_signer_test() {
+    let temp_dir = TempDir::new().unwrap();
+    assert!(ExternalKeystore::new(&temp_dir.path().join("signer.sock")).is_err());
+}
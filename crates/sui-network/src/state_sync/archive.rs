@@ -0,0 +1,403 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Archive of checkpoints, used as a source of checkpoints which is independent of the history
+//! retained by peers.
+//!
+//! The archive is a sequence of bundles, each holding a fixed range of consecutive checkpoints
+//! along with their contents and the transactions and effects they include, and a `MANIFEST`
+//! listing the bundles. Bundles are immutable once written, and the manifest is only updated
+//! after the bundle it adds has been written, so an archive can be read while it is written.
+//!
+//! An archive starts at the first checkpoint the node archiving it still had when it started
+//! archiving, or at a configured checkpoint. Once a bundle has been written, the node publishes
+//! its last checkpoint as its highest archived checkpoint, and never prunes checkpoints past it.
+//!
+//! Nothing read from the archive is trusted: checkpoints read by StateSync are verified against
+//! the committee of their epoch, and their contents, transactions and effects against the
+//! digests in the checkpoint, exactly as if they had been received from a peer.
+//!
+//! The archive is written to and read from an [ArchiveStore], which is a key-value store of
+//! immutable blobs. [LocalArchiveStore] keeps them in a local directory; remote object stores,
+//! such as S3-compatible ones, can be used by implementing [ArchiveStore].
+
+use anyhow::{anyhow, ensure, Result};
+use prometheus::{
+    register_int_counter_with_registry, register_int_gauge_with_registry, IntCounter, IntGauge,
+    Registry,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    io::ErrorKind,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use sui_types::{
+    messages::{Transaction, TransactionEffects},
+    messages_checkpoint::{
+        CertifiedCheckpointSummary as Checkpoint, CheckpointContents, CheckpointSequenceNumber,
+    },
+    storage::{ReadStore, WriteStore},
+};
+use tracing::{debug, info, warn};
+
+pub const MANIFEST_KEY: &str = "MANIFEST";
+
+/// A store of immutable blobs, identified by `/` separated keys, that an archive is kept in.
+#[async_trait::async_trait]
+pub trait ArchiveStore: Send + Sync + 'static {
+    async fn put(&self, key: &str, bytes: Vec<u8>) -> Result<()>;
+
+    /// Returns `None` if there is no blob with this key.
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>>;
+}
+
+/// An [ArchiveStore] backed by a directory of the local filesystem.
+#[derive(Clone, Debug)]
+pub struct LocalArchiveStore {
+    root: PathBuf,
+}
+
+impl LocalArchiveStore {
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+}
+
+#[async_trait::async_trait]
+impl ArchiveStore for LocalArchiveStore {
+    async fn put(&self, key: &str, bytes: Vec<u8>) -> Result<()> {
+        let path = self.root.join(key);
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        // Write to a temporary file first so that readers never observe a partial blob.
+        let tmp_path = path.with_extension("tmp");
+        tokio::fs::write(&tmp_path, bytes).await?;
+        tokio::fs::rename(&tmp_path, &path).await?;
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        match tokio::fs::read(self.root.join(key)).await {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ArchiveManifest {
+    /// Bundles of the archive, in order. Bundles cover consecutive ranges of checkpoints
+    /// starting at the first archived checkpoint.
+    pub bundles: Vec<BundleInfo>,
+}
+
+impl ArchiveManifest {
+    /// The checkpoint following the last one in the archive, or `None` if the archive is empty.
+    pub fn next_checkpoint(&self) -> Option<CheckpointSequenceNumber> {
+        self.bundles.last().map(|bundle| bundle.last_checkpoint + 1)
+    }
+
+    pub fn find_bundle(&self, sequence_number: CheckpointSequenceNumber) -> Option<BundleInfo> {
+        let index = self
+            .bundles
+            .partition_point(|bundle| bundle.last_checkpoint < sequence_number);
+        self.bundles
+            .get(index)
+            .filter(|bundle| bundle.contains(sequence_number))
+            .copied()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BundleInfo {
+    pub first_checkpoint: CheckpointSequenceNumber,
+    pub last_checkpoint: CheckpointSequenceNumber,
+}
+
+impl BundleInfo {
+    pub fn key(&self) -> String {
+        format!("bundles/{}-{}", self.first_checkpoint, self.last_checkpoint)
+    }
+
+    pub fn contains(&self, sequence_number: CheckpointSequenceNumber) -> bool {
+        (self.first_checkpoint..=self.last_checkpoint).contains(&sequence_number)
+    }
+
+    fn len(&self) -> usize {
+        (self.last_checkpoint - self.first_checkpoint + 1) as usize
+    }
+}
+
+/// A checkpoint with everything needed to sync it.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ArchivedCheckpoint {
+    pub checkpoint: Checkpoint,
+    pub contents: CheckpointContents,
+    /// The transactions of the checkpoint and their effects, in the order of `contents`.
+    pub transactions: Vec<(Transaction, TransactionEffects)>,
+}
+
+async fn read_manifest(archive: &dyn ArchiveStore) -> Result<ArchiveManifest> {
+    match archive.get(MANIFEST_KEY).await? {
+        Some(bytes) => Ok(bcs::from_bytes(&bytes)?),
+        None => Ok(ArchiveManifest::default()),
+    }
+}
+
+/// Continuously appends the checkpoints synced to a store to an archive, one bundle at a time,
+/// and records the highest archived checkpoint in the store.
+pub struct ArchiveWriter<S> {
+    store: S,
+    archive: Arc<dyn ArchiveStore>,
+    checkpoints_per_bundle: u64,
+    start_checkpoint: Option<CheckpointSequenceNumber>,
+    metrics: Option<ArchiveWriterMetrics>,
+}
+
+struct ArchiveWriterMetrics {
+    highest_archived_checkpoint: IntGauge,
+    archived_bundles: IntCounter,
+}
+
+impl ArchiveWriterMetrics {
+    fn new(registry: &Registry) -> Self {
+        Self {
+            highest_archived_checkpoint: register_int_gauge_with_registry!(
+                "highest_archived_checkpoint",
+                "Highest checkpoint written to the checkpoint archive",
+                registry
+            )
+            .unwrap(),
+            archived_bundles: register_int_counter_with_registry!(
+                "archived_bundles",
+                "Number of bundles written to the checkpoint archive",
+                registry
+            )
+            .unwrap(),
+        }
+    }
+}
+
+impl<S> ArchiveWriter<S>
+where
+    S: WriteStore + Send + Sync + 'static,
+    <S as ReadStore>::Error: std::error::Error,
+{
+    /// If the archive is empty, it starts at `start_checkpoint`, or at the lowest checkpoint
+    /// available in the store if unspecified.
+    pub fn new(
+        store: S,
+        archive: Arc<dyn ArchiveStore>,
+        checkpoints_per_bundle: u64,
+        start_checkpoint: Option<CheckpointSequenceNumber>,
+    ) -> Self {
+        assert!(checkpoints_per_bundle > 0);
+        Self {
+            store,
+            archive,
+            checkpoints_per_bundle,
+            start_checkpoint,
+            metrics: None,
+        }
+    }
+
+    pub fn with_metrics(mut self, registry: &Registry) -> Self {
+        self.metrics = Some(ArchiveWriterMetrics::new(registry));
+        self
+    }
+
+    /// Writes bundles as soon as enough checkpoints have been synced to fill them. Checkpoints
+    /// must be archived before they are pruned from the store.
+    pub async fn run(self, interval: Duration) {
+        info!("Checkpoint archive writer started");
+        let mut interval = tokio::time::interval(interval);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.write_available_bundles().await {
+                warn!("Failed to write checkpoint archive bundle: {e:?}");
+            }
+        }
+    }
+
+    /// Writes every bundle whose checkpoints have all been synced, and returns the number of
+    /// bundles written.
+    pub async fn write_available_bundles(&self) -> Result<usize> {
+        let mut manifest = read_manifest(self.archive.as_ref()).await?;
+        if let Some(bundle) = manifest.bundles.last() {
+            // In case we stopped after updating the manifest but before updating the watermark
+            self.update_highest_archived_checkpoint(bundle.last_checkpoint)?;
+        }
+        let highest_synced = self
+            .store
+            .get_highest_synced_checkpoint()
+            .expect("store operation should not fail")
+            .sequence_number();
+        let lowest_available = self
+            .store
+            .get_lowest_available_checkpoint()
+            .expect("store operation should not fail");
+        let mut written = 0;
+        loop {
+            let first_checkpoint = manifest
+                .next_checkpoint()
+                .or(self.start_checkpoint)
+                .unwrap_or(lowest_available);
+            let bundle = BundleInfo {
+                first_checkpoint,
+                last_checkpoint: first_checkpoint + self.checkpoints_per_bundle - 1,
+            };
+            if bundle.last_checkpoint > highest_synced {
+                return Ok(written);
+            }
+            ensure!(
+                first_checkpoint >= lowest_available,
+                "checkpoint {first_checkpoint} has been pruned before it was archived, the lowest \
+                 available checkpoint is {lowest_available}"
+            );
+
+            let checkpoints = (bundle.first_checkpoint..=bundle.last_checkpoint)
+                .map(|sequence_number| self.archived_checkpoint(sequence_number))
+                .collect::<Result<Vec<_>>>()?;
+            self.archive
+                .put(&bundle.key(), bcs::to_bytes(&checkpoints)?)
+                .await?;
+            manifest.bundles.push(bundle);
+            self.archive
+                .put(MANIFEST_KEY, bcs::to_bytes(&manifest)?)
+                .await?;
+            self.update_highest_archived_checkpoint(bundle.last_checkpoint)?;
+            if let Some(metrics) = &self.metrics {
+                metrics.archived_bundles.inc();
+            }
+            debug!(
+                first_checkpoint = bundle.first_checkpoint,
+                last_checkpoint = bundle.last_checkpoint,
+                "Wrote checkpoint archive bundle"
+            );
+            written += 1;
+        }
+    }
+
+    fn update_highest_archived_checkpoint(
+        &self,
+        sequence_number: CheckpointSequenceNumber,
+    ) -> Result<()> {
+        let checkpoint = self
+            .store
+            .get_checkpoint_by_sequence_number(sequence_number)
+            .expect("store operation should not fail")
+            .ok_or_else(|| anyhow!("checkpoint {sequence_number} not found"))?;
+        self.store
+            .update_highest_archived_checkpoint(&checkpoint)
+            .expect("store operation should not fail");
+        if let Some(metrics) = &self.metrics {
+            metrics
+                .highest_archived_checkpoint
+                .set(sequence_number as i64);
+        }
+        Ok(())
+    }
+
+    fn archived_checkpoint(
+        &self,
+        sequence_number: CheckpointSequenceNumber,
+    ) -> Result<ArchivedCheckpoint> {
+        let checkpoint = self
+            .store
+            .get_checkpoint_by_sequence_number(sequence_number)
+            .expect("store operation should not fail")
+            .ok_or_else(|| anyhow!("checkpoint {sequence_number} not found"))?;
+        let contents = self
+            .store
+            .get_checkpoint_contents(&checkpoint.content_digest())
+            .expect("store operation should not fail")
+            .ok_or_else(|| anyhow!("contents of checkpoint {sequence_number} not found"))?;
+        let transactions = contents
+            .iter()
+            .map(|digests| {
+                let transaction = self
+                    .store
+                    .get_transaction(&digests.transaction)
+                    .expect("store operation should not fail")
+                    .ok_or_else(|| anyhow!("transaction {:?} not found", digests.transaction))?;
+                let effects = self
+                    .store
+                    .get_transaction_effects(&digests.effects)
+                    .expect("store operation should not fail")
+                    .ok_or_else(|| anyhow!("effects {:?} not found", digests.effects))?;
+                Ok((transaction.into_inner(), effects))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(ArchivedCheckpoint {
+            checkpoint: checkpoint.into_inner(),
+            contents,
+            transactions,
+        })
+    }
+}
+
+/// Reads checkpoints from an archive. The last bundle read is cached, as checkpoints are
+/// usually read in order.
+pub struct ArchiveReader {
+    archive: Arc<dyn ArchiveStore>,
+    manifest: Mutex<ArchiveManifest>,
+    cached_bundle: Mutex<Option<(BundleInfo, Arc<Vec<ArchivedCheckpoint>>)>>,
+}
+
+impl ArchiveReader {
+    pub fn new(archive: Arc<dyn ArchiveStore>) -> Self {
+        Self {
+            archive,
+            manifest: Mutex::new(ArchiveManifest::default()),
+            cached_bundle: Mutex::new(None),
+        }
+    }
+
+    /// Returns `None` if the checkpoint is not in the archive.
+    pub async fn get_checkpoint(
+        &self,
+        sequence_number: CheckpointSequenceNumber,
+    ) -> Result<Option<ArchivedCheckpoint>> {
+        if let Some((bundle, checkpoints)) = &*self.cached_bundle.lock().unwrap() {
+            if bundle.contains(sequence_number) {
+                return Ok(Some(
+                    checkpoints[(sequence_number - bundle.first_checkpoint) as usize].clone(),
+                ));
+            }
+        }
+
+        let mut bundle = self.manifest.lock().unwrap().find_bundle(sequence_number);
+        if bundle.is_none() {
+            // The archive may have grown since we last read its manifest.
+            let manifest = read_manifest(self.archive.as_ref()).await?;
+            bundle = manifest.find_bundle(sequence_number);
+            *self.manifest.lock().unwrap() = manifest;
+        }
+        let Some(bundle) = bundle else {
+            return Ok(None);
+        };
+
+        let bytes = self
+            .archive
+            .get(&bundle.key())
+            .await?
+            .ok_or_else(|| anyhow!("archive bundle {} not found", bundle.key()))?;
+        let checkpoints: Vec<ArchivedCheckpoint> = bcs::from_bytes(&bytes)?;
+        ensure!(
+            checkpoints.len() == bundle.len()
+                && checkpoints.iter().zip(bundle.first_checkpoint..).all(
+                    |(archived, sequence_number)| archived.checkpoint.sequence_number()
+                        == sequence_number
+                ),
+            "archive bundle {} does not hold the expected checkpoints",
+            bundle.key()
+        );
+        let checkpoint = checkpoints[(sequence_number - bundle.first_checkpoint) as usize].clone();
+        *self.cached_bundle.lock().unwrap() = Some((bundle, Arc::new(checkpoints)));
+        Ok(Some(checkpoint))
+    }
+}
@@ -16,8 +16,10 @@ use tokio::{
 };
 
 use super::{
-    metrics::Metrics, server::Server, Handle, PeerHeights, StateSync, StateSyncEventLoop,
-    StateSyncMessage, StateSyncServer,
+    archive::{ArchiveReader, ArchiveStore, LocalArchiveStore},
+    metrics::Metrics,
+    server::Server,
+    Handle, PeerHeights, StateSync, StateSyncEventLoop, StateSyncMessage, StateSyncServer,
 };
 use sui_types::storage::WriteStore;
 
@@ -25,6 +27,7 @@ pub struct Builder<S> {
     store: Option<S>,
     config: Option<StateSyncConfig>,
     metrics: Option<Metrics>,
+    archive: Option<Arc<dyn ArchiveStore>>,
 }
 
 impl Builder<()> {
@@ -34,6 +37,7 @@ impl Builder<()> {
             store: None,
             config: None,
             metrics: None,
+            archive: None,
         }
     }
 }
@@ -44,6 +48,7 @@ impl<S> Builder<S> {
             store: Some(store),
             config: self.config,
             metrics: self.metrics,
+            archive: self.archive,
         }
     }
 
@@ -56,6 +61,13 @@ impl<S> Builder<S> {
         self.metrics = Some(Metrics::enabled(registry));
         self
     }
+
+    /// Read checkpoints that no peer can provide from this archive. Takes precedence over the
+    /// `archive_path` of the config.
+    pub fn archive(mut self, archive: Arc<dyn ArchiveStore>) -> Self {
+        self.archive = Some(archive);
+        self
+    }
 }
 
 impl<S> Builder<S>
@@ -110,19 +122,24 @@ where
             store,
             config,
             metrics,
+            archive,
         } = self;
         let store = store.unwrap();
         let config = config.unwrap_or_default();
         let metrics = metrics.unwrap_or_else(Metrics::disabled);
+        let archive = archive
+            .or_else(|| {
+                config
+                    .archive_path
+                    .clone()
+                    .map(|path| Arc::new(LocalArchiveStore::new(path)) as Arc<dyn ArchiveStore>)
+            })
+            .map(|archive| Arc::new(ArchiveReader::new(archive)));
 
         let (sender, mailbox) = mpsc::channel(config.mailbox_capacity());
         let (checkpoint_event_sender, _receiver) =
             broadcast::channel(config.synced_checkpoint_broadcast_channel_capacity());
         let weak_sender = sender.downgrade();
-        let handle = Handle {
-            sender,
-            checkpoint_event_sender: checkpoint_event_sender.clone(),
-        };
         let peer_heights = PeerHeights {
             peers: HashMap::new(),
             unprocessed_checkpoints: HashMap::new(),
@@ -130,6 +147,11 @@ where
         }
         .pipe(RwLock::new)
         .pipe(Arc::new);
+        let handle = Handle {
+            sender,
+            checkpoint_event_sender: checkpoint_event_sender.clone(),
+            peer_heights: peer_heights.clone(),
+        };
 
         let server = Server {
             store: store.clone(),
@@ -146,6 +168,7 @@ where
                 peer_heights,
                 checkpoint_event_sender,
                 metrics,
+                archive,
             },
             server,
         )
@@ -160,6 +183,7 @@ pub struct UnstartedStateSync<S> {
     pub(super) peer_heights: Arc<RwLock<PeerHeights>>,
     pub(super) checkpoint_event_sender: broadcast::Sender<VerifiedCheckpoint>,
     pub(super) metrics: Metrics,
+    pub(super) archive: Option<Arc<ArchiveReader>>,
 }
 
 impl<S> UnstartedStateSync<S>
@@ -176,6 +200,7 @@ where
             peer_heights,
             checkpoint_event_sender,
             metrics,
+            archive,
         } = self;
 
         (
@@ -191,6 +216,7 @@ where
                 checkpoint_event_sender,
                 network,
                 metrics,
+                archive,
             },
             handle,
         )
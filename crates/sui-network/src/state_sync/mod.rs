@@ -46,10 +46,15 @@
 //! indicating that a new checkpoint has been fully downloaded. Notifications on this broadcast
 //! channel will always be made in order. StateSync will also send out a notification to its peers
 //! of the newly synchronized checkpoint so that it can help other peers synchronize.
+//!
+//! If a checkpoint archive is configured, checkpoints and their contents that none of our peers
+//! are able to provide, e.g. because they have pruned them, are read from the archive instead.
+//! See the [archive] module.
 
 use anemo::{types::PeerEvent, PeerId, Request, Response, Result};
 use anyhow::anyhow;
 use futures::{FutureExt, StreamExt};
+use serde::Serialize;
 use std::{
     collections::HashMap,
     sync::{Arc, RwLock},
@@ -77,6 +82,7 @@ use tracing::{debug, info, trace, warn};
 mod generated {
     include!(concat!(env!("OUT_DIR"), "/sui.StateSync.rs"));
 }
+pub mod archive;
 mod builder;
 mod metrics;
 mod server;
@@ -91,6 +97,7 @@ pub use generated::{
 };
 pub use server::GetCheckpointSummaryRequest;
 
+use self::archive::ArchiveReader;
 use self::metrics::Metrics;
 
 /// A handle to the StateSync subsystem.
@@ -101,6 +108,7 @@ use self::metrics::Metrics;
 pub struct Handle {
     sender: mpsc::Sender<StateSyncMessage>,
     checkpoint_event_sender: broadcast::Sender<VerifiedCheckpoint>,
+    peer_heights: Arc<RwLock<PeerHeights>>,
 }
 
 impl Handle {
@@ -123,8 +131,34 @@ impl Handle {
     pub fn subscribe_to_synced_checkpoints(&self) -> broadcast::Receiver<VerifiedCheckpoint> {
         self.checkpoint_event_sender.subscribe()
     }
+
+    /// Returns what StateSync currently knows about each of its connected peers.
+    pub fn peer_sync_status(&self) -> Vec<PeerSyncStatus> {
+        self.peer_heights
+            .read()
+            .unwrap()
+            .peers
+            .iter()
+            .map(|(peer_id, info)| PeerSyncStatus {
+                peer_id: *peer_id,
+                on_same_chain_as_us: info.on_same_chain_as_us,
+                height: info.height,
+            })
+            .collect()
+    }
 }
 
+/// The sync status of a single peer, as tracked by StateSync.
+#[derive(Clone, Debug, Serialize)]
+pub struct PeerSyncStatus {
+    pub peer_id: PeerId,
+    /// Indicates if this Peer is on the same chain as us.
+    pub on_same_chain_as_us: bool,
+    /// Highest checkpoint sequence number we know of for this Peer.
+    pub height: CheckpointSequenceNumber,
+}
+
+#[derive(Debug)]
 struct PeerHeights {
     /// Table used to track the highest checkpoint for each of our peers.
     peers: HashMap<PeerId, PeerStateSyncInfo>,
@@ -268,6 +302,7 @@ struct StateSyncEventLoop<S> {
     checkpoint_event_sender: broadcast::Sender<VerifiedCheckpoint>,
     network: anemo::Network,
     metrics: Metrics,
+    archive: Option<Arc<ArchiveReader>>,
 }
 
 impl<S> StateSyncEventLoop<S>
@@ -537,6 +572,7 @@ where
                 self.config.timeout(),
                 // The if condition should ensure that this is Some
                 highest_known_checkpoint.unwrap(),
+                self.archive.clone(),
             )
             .map(|result| match result {
                 Ok(()) => {}
@@ -566,13 +602,15 @@ where
 
         if highest_verified_checkpoint.sequence_number()
             > highest_synced_checkpoint.sequence_number()
-            // skip if we aren't connected to any peers that can help
-            && self
-                .peer_heights
-                .read()
-                .unwrap()
-                .highest_known_checkpoint_sequence_number()
-                > Some(highest_synced_checkpoint.sequence_number())
+            // skip if we aren't connected to any peers that can help, unless we can read from
+            // the archive
+            && (self.archive.is_some()
+                || self
+                    .peer_heights
+                    .read()
+                    .unwrap()
+                    .highest_known_checkpoint_sequence_number()
+                    > Some(highest_synced_checkpoint.sequence_number()))
         {
             let task = sync_checkpoint_contents(
                 self.network.clone(),
@@ -585,6 +623,7 @@ where
                 self.config.checkpoint_content_download_concurrency(),
                 self.config.timeout(),
                 highest_verified_checkpoint,
+                self.archive.clone(),
             );
 
             let task_handle = self.tasks.spawn(task);
@@ -777,6 +816,7 @@ async fn sync_to_checkpoint<S>(
     checkpoint_header_download_concurrency: usize,
     timeout: Duration,
     checkpoint: Checkpoint,
+    archive: Option<Arc<ArchiveReader>>,
 ) -> Result<()>
 where
     S: WriteStore,
@@ -820,6 +860,7 @@ where
                 .collect::<Vec<_>>();
             rand::seq::SliceRandom::shuffle(peers.as_mut_slice(), &mut rng);
             let peer_heights = peer_heights.clone();
+            let archive = archive.clone();
             async move {
                 if let Some(checkpoint) = peer_heights
                     .read()
@@ -856,6 +897,19 @@ where
                     }
                 }
 
+                // Fall back to the archive if none of our peers were able to help
+                if let Some(archive) = archive {
+                    if let Some(archived) = archive
+                        .get_checkpoint(next)
+                        .await
+                        .tap_err(|e| debug!("unable to read checkpoint from archive: {e}"))
+                        .ok()
+                        .flatten()
+                    {
+                        return (Some(archived.checkpoint), next, None);
+                    }
+                }
+
                 (None, next, None)
             }
         })
@@ -979,6 +1033,7 @@ async fn sync_checkpoint_contents<S>(
     checkpoint_content_download_concurrency: usize,
     timeout: Duration,
     target_checkpoint: VerifiedCheckpoint,
+    archive: Option<Arc<ArchiveReader>>,
 ) where
     S: WriteStore + Clone,
     <S as ReadStore>::Error: std::error::Error,
@@ -1006,6 +1061,7 @@ async fn sync_checkpoint_contents<S>(
                 transaction_download_concurrency,
                 timeout,
                 checkpoint,
+                archive.clone(),
             )
         })
         .pipe(futures::stream::iter)
@@ -1050,7 +1106,39 @@ async fn sync_one_checkpoint_contents<S>(
     transaction_download_concurrency: usize,
     timeout: Duration,
     checkpoint: VerifiedCheckpoint,
+    archive: Option<Arc<ArchiveReader>>,
 ) -> Result<(VerifiedCheckpoint, u64)>
+where
+    S: WriteStore + Clone,
+    <S as ReadStore>::Error: std::error::Error,
+{
+    let result = sync_one_checkpoint_contents_from_peers(
+        network,
+        store.clone(),
+        peer_heights,
+        transaction_download_concurrency,
+        timeout,
+        &checkpoint,
+    )
+    .await;
+    let num_txns = match (result, archive) {
+        (Err(e), Some(archive)) => {
+            debug!("{e}, falling back to the archive");
+            sync_one_checkpoint_contents_from_archive(&archive, &store, &checkpoint).await?
+        }
+        (result, _) => result?,
+    };
+    Ok((checkpoint, num_txns))
+}
+
+async fn sync_one_checkpoint_contents_from_peers<S>(
+    network: anemo::Network,
+    store: S,
+    peer_heights: Arc<RwLock<PeerHeights>>,
+    transaction_download_concurrency: usize,
+    timeout: Duration,
+    checkpoint: &VerifiedCheckpoint,
+) -> Result<u64>
 where
     S: WriteStore + Clone,
     <S as ReadStore>::Error: std::error::Error,
@@ -1087,7 +1175,64 @@ where
         result?;
     }
 
-    Ok((checkpoint, num_txns))
+    Ok(num_txns)
+}
+
+async fn sync_one_checkpoint_contents_from_archive<S>(
+    archive: &ArchiveReader,
+    store: S,
+    checkpoint: &VerifiedCheckpoint,
+) -> Result<u64>
+where
+    S: WriteStore,
+    <S as ReadStore>::Error: std::error::Error,
+{
+    let archived = archive
+        .get_checkpoint(checkpoint.sequence_number())
+        .await?
+        .ok_or_else(|| {
+            anyhow!(
+                "checkpoint {} is not in the archive",
+                checkpoint.sequence_number()
+            )
+        })?;
+
+    // Verify everything against the checkpoint before inserting anything in our store
+    if archived.contents.digest() != checkpoint.content_digest()
+        || archived.contents.size() != archived.transactions.len()
+    {
+        return Err(anyhow!(
+            "archived contents of checkpoint {} do not match the checkpoint",
+            checkpoint.sequence_number()
+        ));
+    }
+    for (digests, (transaction, effects)) in archived.contents.iter().zip(&archived.transactions) {
+        if !(transaction.digest() == &digests.transaction
+            && effects.digest() == digests.effects
+            && effects.transaction_digest == digests.transaction)
+        {
+            return Err(anyhow!(
+                "archived transaction {:?} does not match checkpoint {}",
+                digests.transaction,
+                checkpoint.sequence_number()
+            ));
+        }
+    }
+
+    let num_txns = archived.contents.size() as u64;
+    store
+        .insert_checkpoint_contents(archived.contents)
+        .expect("store operation should not fail");
+    for (transaction, effects) in archived.transactions {
+        store
+            .insert_transaction_and_effects(
+                sui_types::messages::VerifiedTransaction::new_unchecked(transaction),
+                effects,
+            )
+            .expect("store operation should not fail");
+    }
+
+    Ok(num_txns)
 }
 
 async fn get_checkpoint_contents<S>(
@@ -153,6 +153,28 @@ impl CommitteeFixture {
         (ordered_checkpoints, sequence_number_to_digest, checkpoints)
     }
 
+    /// Makes the checkpoint following `previous_checkpoint`, holding `contents`.
+    pub fn make_checkpoint_with_contents(
+        &self,
+        previous_checkpoint: &VerifiedCheckpoint,
+        contents: &CheckpointContents,
+    ) -> VerifiedCheckpoint {
+        let summary = CheckpointSummary {
+            epoch: self.epoch,
+            sequence_number: previous_checkpoint.summary.sequence_number + 1,
+            network_total_transactions: previous_checkpoint.summary.network_total_transactions
+                + contents.size() as u64,
+            content_digest: contents.digest(),
+            previous_digest: Some(previous_checkpoint.summary.digest()),
+            epoch_rolling_gas_cost_summary: Default::default(),
+            end_of_epoch_data: None,
+            timestamp_ms: 0,
+            version_specific_data: Vec::new(),
+        };
+
+        self.create_certified_checkpoint(summary)
+    }
+
     pub fn make_end_of_epoch_checkpoint(
         &self,
         previous_checkpoint: VerifiedCheckpoint,
@@ -3,6 +3,11 @@
 
 use crate::{
     state_sync::{
+        archive::{
+            ArchiveReader, ArchiveStore, ArchiveWriter, ArchivedCheckpoint, BundleInfo,
+            LocalArchiveStore,
+        },
+        sync_one_checkpoint_contents_from_archive,
         test_utils::{empty_contents, CommitteeFixture},
         Builder, GetCheckpointSummaryRequest, PeerStateSyncInfo, StateSync, StateSyncMessage,
         UnstartedStateSync,
@@ -10,9 +15,12 @@ use crate::{
     utils::build_network,
 };
 use anemo::{PeerId, Request};
-use std::{collections::HashMap, time::Duration};
+use std::{collections::HashMap, sync::Arc, time::Duration};
+use sui_config::p2p::StateSyncConfig;
 use sui_types::{
-    messages_checkpoint::CheckpointDigest,
+    base_types::ExecutionDigests,
+    messages::{TransactionEffects, VerifiedTransaction},
+    messages_checkpoint::{CheckpointContents, CheckpointDigest, VerifiedCheckpoint},
     storage::{ReadStore, SharedInMemoryStore, WriteStore},
 };
 use tokio::time::timeout;
@@ -398,3 +406,244 @@ async fn sync_with_checkpoints_being_inserted() {
         &sequence_number_to_digest
     );
 }
+
+#[tokio::test]
+async fn sync_from_archive() {
+    let committee = CommitteeFixture::generate(rand::rngs::OsRng, 0, 4);
+    let (ordered_checkpoints, _sequence_number_to_digest, checkpoints) =
+        committee.make_checkpoints(25, None);
+    let archive_dir = tempfile::tempdir().unwrap();
+
+    // Archive the checkpoints of a store that has all of them
+    let store = SharedInMemoryStore::default();
+    store.inner_mut().insert_genesis_state(
+        ordered_checkpoints.first().cloned().unwrap(),
+        empty_contents(),
+        vec![],
+        vec![],
+        committee.committee().to_owned(),
+    );
+    for checkpoint in ordered_checkpoints.clone() {
+        store.inner_mut().insert_checkpoint(checkpoint);
+    }
+    store
+        .inner_mut()
+        .update_highest_synced_checkpoint(ordered_checkpoints.last().unwrap());
+    let archive = Arc::new(LocalArchiveStore::new(archive_dir.path().to_path_buf()));
+    let writer = ArchiveWriter::new(store, archive.clone(), 10, None);
+    // Only full bundles are written
+    assert_eq!(writer.write_available_bundles().await.unwrap(), 2);
+    assert_eq!(writer.write_available_bundles().await.unwrap(), 0);
+
+    let reader = ArchiveReader::new(archive);
+    for checkpoint in &ordered_checkpoints[..20] {
+        let archived = reader
+            .get_checkpoint(checkpoint.sequence_number())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(archived.checkpoint.summary, checkpoint.summary);
+        assert_eq!(archived.contents, empty_contents());
+    }
+    assert!(reader.get_checkpoint(20).await.unwrap().is_none());
+
+    // Build a node reading from the archive, which knows of a peer it isn't connected to
+    let config = StateSyncConfig {
+        archive_path: Some(archive_dir.path().to_path_buf()),
+        ..Default::default()
+    };
+    let (builder, server) = Builder::new()
+        .store(SharedInMemoryStore::default())
+        .config(config)
+        .build();
+    let network = build_network(|router| router.add_rpc_service(server));
+    let (mut event_loop, _handle) = builder.build(network);
+    event_loop.store.inner_mut().insert_genesis_state(
+        ordered_checkpoints.first().cloned().unwrap(),
+        empty_contents(),
+        vec![],
+        vec![],
+        committee.committee().to_owned(),
+    );
+    let last_archived = ordered_checkpoints[19].clone();
+    event_loop.peer_heights.write().unwrap().peers.insert(
+        PeerId([9; 32]),
+        PeerStateSyncInfo {
+            genesis_checkpoint_digest: ordered_checkpoints[0].digest(),
+            on_same_chain_as_us: true,
+            height: last_archived.sequence_number(),
+        },
+    );
+    event_loop
+        .peer_heights
+        .write()
+        .unwrap()
+        .insert_checkpoint(last_archived.clone().into_inner());
+
+    // Sync the checkpoint summaries, then their contents
+    event_loop.maybe_start_checkpoint_summary_sync_task();
+    event_loop.tasks.join_next().await.unwrap().unwrap();
+    assert_eq!(
+        event_loop
+            .store
+            .get_highest_verified_checkpoint()
+            .unwrap()
+            .summary,
+        last_archived.summary
+    );
+    event_loop.maybe_start_checkpoint_contents_sync_task();
+    event_loop.tasks.join_next().await.unwrap().unwrap();
+    assert_eq!(
+        event_loop
+            .store
+            .get_highest_synced_checkpoint()
+            .unwrap()
+            .summary,
+        last_archived.summary
+    );
+
+    let store = event_loop.store.inner();
+    for checkpoint in &ordered_checkpoints[..20] {
+        assert_eq!(
+            store
+                .get_checkpoint_by_sequence_number(checkpoint.sequence_number())
+                .unwrap()
+                .summary,
+            checkpoints[&checkpoint.digest()].summary
+        );
+    }
+}
+
+/// Returns a store holding the summaries of `checkpoints`, of which the first is genesis, but
+/// none of their contents.
+fn store_with_checkpoint_summaries(
+    committee: &CommitteeFixture,
+    checkpoints: &[VerifiedCheckpoint],
+) -> SharedInMemoryStore {
+    let store = SharedInMemoryStore::default();
+    store.inner_mut().insert_genesis_state(
+        checkpoints[0].clone(),
+        empty_contents(),
+        vec![],
+        vec![],
+        committee.committee().to_owned(),
+    );
+    for checkpoint in &checkpoints[1..] {
+        store.inner_mut().insert_checkpoint(checkpoint.clone());
+    }
+    store
+}
+
+#[tokio::test]
+async fn archive_checkpoints_with_transactions() {
+    let committee = CommitteeFixture::generate(rand::rngs::OsRng, 0, 4);
+    let (mut checkpoints, _sequence_number_to_digest, _checkpoints) =
+        committee.make_checkpoints(1, None);
+    let store = store_with_checkpoint_summaries(&committee, &checkpoints);
+
+    // Each checkpoint after genesis holds one transaction
+    for round in 1..=10 {
+        let transaction = VerifiedTransaction::new_consensus_commit_prologue(0, round, 0);
+        let effects = TransactionEffects {
+            transaction_digest: *transaction.digest(),
+            ..Default::default()
+        };
+        let contents =
+            CheckpointContents::new_with_causally_ordered_transactions([ExecutionDigests::new(
+                *transaction.digest(),
+                effects.digest(),
+            )]);
+        let checkpoint =
+            committee.make_checkpoint_with_contents(checkpoints.last().unwrap(), &contents);
+        let mut inner = store.inner_mut();
+        inner.insert_checkpoint(checkpoint.clone());
+        inner.insert_checkpoint_contents(contents);
+        inner.insert_transaction_and_effects(transaction, effects);
+        inner.update_highest_synced_checkpoint(&checkpoint);
+        checkpoints.push(checkpoint);
+    }
+
+    // Archive everything after genesis
+    let archive_dir = tempfile::tempdir().unwrap();
+    let archive = Arc::new(LocalArchiveStore::new(archive_dir.path().to_path_buf()));
+    let writer = ArchiveWriter::new(store.clone(), archive.clone(), 5, Some(1));
+    assert_eq!(writer.write_available_bundles().await.unwrap(), 2);
+    assert_eq!(
+        store
+            .inner()
+            .get_highest_archived_checkpoint()
+            .unwrap()
+            .sequence_number(),
+        10
+    );
+    let reader = ArchiveReader::new(archive.clone());
+    assert!(reader.get_checkpoint(0).await.unwrap().is_none());
+
+    // The transactions and effects of every archived checkpoint can be synced
+    let synced_store = store_with_checkpoint_summaries(&committee, &checkpoints);
+    for checkpoint in &checkpoints[1..] {
+        assert_eq!(
+            sync_one_checkpoint_contents_from_archive(&reader, &synced_store, checkpoint)
+                .await
+                .unwrap(),
+            1
+        );
+        let contents = synced_store
+            .get_checkpoint_contents(&checkpoint.content_digest())
+            .unwrap()
+            .unwrap();
+        let digests = contents.iter().next().unwrap();
+        assert_eq!(
+            synced_store
+                .get_transaction(&digests.transaction)
+                .unwrap()
+                .unwrap(),
+            store
+                .get_transaction(&digests.transaction)
+                .unwrap()
+                .unwrap()
+        );
+        assert_eq!(
+            synced_store
+                .get_transaction_effects(&digests.effects)
+                .unwrap()
+                .unwrap(),
+            store
+                .get_transaction_effects(&digests.effects)
+                .unwrap()
+                .unwrap()
+        );
+    }
+
+    // Tamper with the effects of checkpoint 8, in the second bundle
+    let bundle_key = BundleInfo {
+        first_checkpoint: 6,
+        last_checkpoint: 10,
+    }
+    .key();
+    let mut bundle: Vec<ArchivedCheckpoint> =
+        bcs::from_bytes(&archive.get(&bundle_key).await.unwrap().unwrap()).unwrap();
+    bundle[2].transactions[0].1.gas_used.computation_cost += 1;
+    archive
+        .put(&bundle_key, bcs::to_bytes(&bundle).unwrap())
+        .await
+        .unwrap();
+
+    let reader = ArchiveReader::new(archive);
+    let tampered_store = store_with_checkpoint_summaries(&committee, &checkpoints);
+    let err = sync_one_checkpoint_contents_from_archive(&reader, &tampered_store, &checkpoints[8])
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("does not match"), "{err}");
+    assert!(tampered_store
+        .get_checkpoint_contents(&checkpoints[8].content_digest())
+        .unwrap()
+        .is_none());
+    // The other checkpoints of the bundle are still synced
+    assert_eq!(
+        sync_one_checkpoint_contents_from_archive(&reader, &tampered_store, &checkpoints[7])
+            .await
+            .unwrap(),
+        1
+    );
+}
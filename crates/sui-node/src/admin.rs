@@ -1,26 +1,97 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::SuiNode;
+use arc_swap::ArcSwapOption;
 use axum::{
-    extract::Extension,
-    http::StatusCode,
+    async_trait,
+    extract::{Extension, FromRequestParts},
+    http::{request::Parts, StatusCode},
     routing::{get, post},
-    Router,
+    Json, Router,
 };
 use mysten_metrics::spawn_monitored_task;
+use serde::Serialize;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::path::PathBuf;
+use std::sync::Arc;
+use sui_network::state_sync::PeerSyncStatus;
+use sui_types::committee::EpochId;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use telemetry_subscribers::FilterHandle;
 use tracing::info;
 
+// Routes of the admin server. The server only listens on localhost. It is started before the
+// node, so that the logging filter can be changed while the node starts; the other routes
+// return 503 until the node has started.
+//
+// GET  /logging                 current tracing filter
+// POST /logging                 set the tracing filter to the request body
+// GET  /epoch                   current epoch and protocol version
+// GET  /checkpoints             checkpoint sync, execution and pruning watermarks
+// GET  /peers                   state-sync status of the connected peers
+// GET  /transaction-manager     number of certificates queued for execution
+// POST /db-checkpoint           checkpoint the node's databases to the path in the request body
+// GET  /pruner                  whether object pruning is paused
+// POST /pruner                  `pause` or `resume` object pruning
 const LOGGING_ROUTE: &str = "/logging";
+const EPOCH_ROUTE: &str = "/epoch";
+const CHECKPOINTS_ROUTE: &str = "/checkpoints";
+const PEERS_ROUTE: &str = "/peers";
+const TRANSACTION_MANAGER_ROUTE: &str = "/transaction-manager";
+const DB_CHECKPOINT_ROUTE: &str = "/db-checkpoint";
+const PRUNER_ROUTE: &str = "/pruner";
 
-pub fn start_admin_server(port: u16, filter_handle: FilterHandle) {
+type AdminResult<T> = Result<Json<T>, (StatusCode, String)>;
+
+/// Handle to the admin server, used to hand it the node once started.
+#[derive(Clone, Default)]
+pub struct AdminServerHandle {
+    node: Arc<ArcSwapOption<SuiNode>>,
+}
+
+impl AdminServerHandle {
+    pub fn set_node(&self, node: Arc<SuiNode>) {
+        self.node.store(Some(node));
+    }
+}
+
+/// Extracts the node from the request, rejecting it while the node is starting.
+struct StartedNode(Arc<SuiNode>);
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for StartedNode {
+    type Rejection = (StatusCode, String);
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Extension(handle) = Extension::<AdminServerHandle>::from_request_parts(parts, state)
+            .await
+            .map_err(internal_error)?;
+        handle.node.load_full().map(StartedNode).ok_or_else(|| {
+            (
+                StatusCode::SERVICE_UNAVAILABLE,
+                "the node is starting".into(),
+            )
+        })
+    }
+}
+
+pub fn start_admin_server(port: u16, filter_handle: FilterHandle) -> AdminServerHandle {
     let filter = filter_handle.get().unwrap();
+    let handle = AdminServerHandle::default();
 
     let app = Router::new()
         .route(LOGGING_ROUTE, get(get_filter))
         .route(LOGGING_ROUTE, post(set_filter))
-        .layer(Extension(filter_handle));
+        .route(EPOCH_ROUTE, get(get_epoch))
+        .route(CHECKPOINTS_ROUTE, get(get_checkpoints))
+        .route(PEERS_ROUTE, get(get_peers))
+        .route(TRANSACTION_MANAGER_ROUTE, get(get_transaction_manager))
+        .route(DB_CHECKPOINT_ROUTE, post(create_db_checkpoint))
+        .route(PRUNER_ROUTE, get(get_pruner))
+        .route(PRUNER_ROUTE, post(set_pruner))
+        .layer(Extension(filter_handle))
+        .layer(Extension(handle.clone()));
 
     let socket_address = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), port);
     info!(
@@ -35,6 +106,12 @@ pub fn start_admin_server(port: u16, filter_handle: FilterHandle) {
             .await
             .unwrap();
     });
+
+    handle
+}
+
+fn internal_error(err: impl ToString) -> (StatusCode, String) {
+    (StatusCode::INTERNAL_SERVER_ERROR, err.to_string())
 }
 
 async fn get_filter(Extension(filter_handle): Extension<FilterHandle>) -> (StatusCode, String) {
@@ -56,3 +133,176 @@ async fn set_filter(
         Err(err) => (StatusCode::BAD_REQUEST, err.to_string()),
     }
 }
+
+#[derive(Serialize)]
+struct EpochInfo {
+    epoch: EpochId,
+    protocol_version: u64,
+    is_validator: bool,
+}
+
+async fn get_epoch(StartedNode(node): StartedNode) -> Json<EpochInfo> {
+    let state = node.state();
+    let epoch_store = state.load_epoch_store_one_call_per_task();
+    Json(EpochInfo {
+        epoch: epoch_store.epoch(),
+        protocol_version: epoch_store.committee().protocol_version.as_u64(),
+        is_validator: state.is_validator(&epoch_store),
+    })
+}
+
+#[derive(Serialize)]
+struct CheckpointWatermarks {
+    highest_verified: Option<CheckpointSequenceNumber>,
+    highest_synced: Option<CheckpointSequenceNumber>,
+    highest_executed: Option<CheckpointSequenceNumber>,
+    highest_pruned: Option<CheckpointSequenceNumber>,
+    /// The highest checkpoint any peer on our chain claims to have synced.
+    highest_known_by_peers: Option<CheckpointSequenceNumber>,
+}
+
+async fn get_checkpoints(StartedNode(node): StartedNode) -> AdminResult<CheckpointWatermarks> {
+    let store = &node.checkpoint_store;
+    Ok(Json(CheckpointWatermarks {
+        highest_verified: store
+            .get_highest_verified_checkpoint()
+            .map_err(internal_error)?
+            .map(|c| c.sequence_number()),
+        highest_synced: store
+            .get_highest_synced_checkpoint_seq_number()
+            .map_err(internal_error)?,
+        highest_executed: store
+            .get_highest_executed_checkpoint_seq_number()
+            .map_err(internal_error)?,
+        highest_pruned: store
+            .get_highest_pruned_checkpoint_seq_number()
+            .map_err(internal_error)?,
+        highest_known_by_peers: node
+            .state_sync
+            .peer_sync_status()
+            .iter()
+            .filter(|peer| peer.on_same_chain_as_us)
+            .map(|peer| peer.height)
+            .max(),
+    }))
+}
+
+async fn get_peers(StartedNode(node): StartedNode) -> Json<Vec<PeerSyncStatus>> {
+    Json(node.state_sync.peer_sync_status())
+}
+
+#[derive(Serialize)]
+struct TransactionManagerQueues {
+    /// Certificates waiting for missing input objects.
+    pending: usize,
+    /// Certificates ready for, or in, execution.
+    executing: usize,
+}
+
+async fn get_transaction_manager(StartedNode(node): StartedNode) -> Json<TransactionManagerQueues> {
+    let transaction_manager = node.state().transaction_manager().clone();
+    Json(TransactionManagerQueues {
+        pending: transaction_manager.pending_certificates_len(),
+        executing: transaction_manager.executing_certificates_len(),
+    })
+}
+
+async fn create_db_checkpoint(
+    StartedNode(node): StartedNode,
+    path: String,
+) -> (StatusCode, String) {
+    let path = PathBuf::from(path.trim());
+    if path.as_os_str().is_empty() {
+        return (
+            StatusCode::BAD_REQUEST,
+            "expected the checkpoint path in the request body".into(),
+        );
+    }
+    let state = node.state();
+    let checkpoint_path = path.clone();
+    match tokio::task::spawn_blocking(move || state.checkpoint_db(&checkpoint_path)).await {
+        Ok(Ok(())) => {
+            info!(path =? path, "Created DB checkpoint");
+            (StatusCode::OK, "".into())
+        }
+        Ok(Err(err)) => internal_error(err),
+        Err(err) => internal_error(err),
+    }
+}
+
+#[derive(Serialize)]
+struct PrunerStatus {
+    paused: bool,
+}
+
+async fn get_pruner(StartedNode(node): StartedNode) -> Json<PrunerStatus> {
+    Json(PrunerStatus {
+        paused: node.state().objects_pruner().is_paused(),
+    })
+}
+
+async fn set_pruner(StartedNode(node): StartedNode, command: String) -> (StatusCode, String) {
+    let paused = match command.trim() {
+        "pause" => true,
+        "resume" => false,
+        _ => {
+            return (
+                StatusCode::BAD_REQUEST,
+                "expected `pause` or `resume`".into(),
+            )
+        }
+    };
+    node.state().objects_pruner().set_paused(paused);
+    (StatusCode::OK, "".into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::start_admin_server;
+    use reqwest::StatusCode;
+    use std::time::Duration;
+    use sui_config::utils::get_available_port;
+    use telemetry_subscribers::FilterHandle;
+
+    #[tokio::test]
+    async fn test_admin_server_serves_logging_before_node_starts() {
+        let port = get_available_port("127.0.0.1");
+        let (_subscriber, filter_handle) = FilterHandle::new_for_testing("info");
+        let _handle = start_admin_server(port, filter_handle);
+
+        let client = reqwest::Client::new();
+        let url = |route: &str| format!("http://127.0.0.1:{port}{route}");
+
+        // Wait for the server to accept connections
+        tokio::time::timeout(Duration::from_secs(10), async {
+            while client.get(url("/logging")).send().await.is_err() {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+            }
+        })
+        .await
+        .expect("the admin server should start");
+
+        let response = client
+            .post(url("/logging"))
+            .body("debug")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let response = client.get(url("/logging")).send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.text().await.unwrap(), "debug");
+
+        let response = client
+            .post(url("/logging"))
+            .body("sui=not-a-level")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        // The node's routes are unavailable until it has started.
+        let response = client.get(url("/epoch")).send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+}
@@ -25,6 +25,7 @@ use sui_core::authority_server::ValidatorService;
 use sui_core::checkpoints::checkpoint_executor;
 use sui_core::epoch::committee_store::CommitteeStore;
 use sui_core::state_accumulator::StateAccumulator;
+use sui_core::state_snapshot::accumulate_and_write_state_snapshot;
 use sui_core::storage::RocksDbStore;
 use sui_core::transaction_orchestrator::TransactiondOrchestrator;
 use sui_core::{
@@ -38,6 +39,7 @@ use sui_json_rpc::transaction_execution_api::TransactionExecutionApi;
 use sui_json_rpc::{JsonRpcServerBuilder, ServerHandle};
 use sui_network::api::ValidatorServer;
 use sui_network::discovery;
+use sui_network::state_sync::archive::{ArchiveWriter, LocalArchiveStore};
 use sui_network::{state_sync, DEFAULT_CONNECT_TIMEOUT_SEC, DEFAULT_HTTP2_KEEPALIVE_SEC};
 use tracing::debug;
 
@@ -54,7 +56,7 @@ use tokio::sync::broadcast;
 use tokio::sync::{watch, Mutex};
 use tokio::task::JoinHandle;
 use tower::ServiceBuilder;
-use tracing::{error_span, info, Instrument};
+use tracing::{error, error_span, info, Instrument};
 use typed_store::DBMetrics;
 pub mod admin;
 mod handle;
@@ -195,6 +197,17 @@ impl SuiNode {
             checkpoint_store.clone(),
         );
 
+        if let Some(archive_config) = &config.checkpoint_archive_config {
+            let archive_writer = ArchiveWriter::new(
+                state_sync_store.clone(),
+                Arc::new(LocalArchiveStore::new(archive_config.archive_path.clone())),
+                archive_config.checkpoints_per_bundle(),
+                archive_config.start_checkpoint,
+            )
+            .with_metrics(&prometheus_registry);
+            spawn_monitored_task!(archive_writer.run(archive_config.interval_period()));
+        }
+
         let index_store = if is_validator {
             None
         } else {
@@ -220,6 +233,9 @@ impl SuiNode {
         let (p2p_network, discovery_handle, state_sync_handle) =
             Self::create_p2p_network(&config, state_sync_store, &prometheus_registry)?;
 
+        let mut pruning_config = config.authority_store_pruning_config.clone();
+        // Checkpoints are only pruned once they are archived
+        pruning_config.retain_unarchived_checkpoints = config.checkpoint_archive_config.is_some();
         let state = AuthorityState::new(
             config.protocol_public_key(),
             secret,
@@ -231,7 +247,7 @@ impl SuiNode {
             event_store,
             checkpoint_store.clone(),
             &prometheus_registry,
-            &config.authority_store_pruning_config,
+            &pruning_config,
             genesis.objects(),
             config.epoch_duration_ms,
         )
@@ -802,6 +818,29 @@ impl SuiNode {
             cur_epoch_store.record_epoch_reconfig_start_time_metric();
             let _ = self.end_of_epoch_channel.send(next_epoch_committee.clone());
 
+            if let Some(snapshot_config) = &self.config.state_snapshot_config {
+                let epoch = cur_epoch_store.epoch();
+                let last_checkpoint = self
+                    .checkpoint_store
+                    .get_epoch_last_checkpoint(epoch)?
+                    .expect("Last checkpoint of a finished epoch must exist")
+                    .sequence_number();
+                let snapshot_dir = snapshot_config.epoch_snapshot_path(epoch);
+                let task = accumulate_and_write_state_snapshot(
+                    self.accumulator.clone(),
+                    self.state.database.clone(),
+                    self.checkpoint_store.clone(),
+                    cur_epoch_store.clone(),
+                    last_checkpoint,
+                    snapshot_dir,
+                );
+                spawn_monitored_task!(async move {
+                    if let Err(err) = task.await {
+                        error!(epoch, "Failed to write state snapshot: {err:?}");
+                    }
+                });
+            }
+
             // The following code handles 4 different cases, depending on whether the node
             // was a validator in the previous epoch, and whether the node is a validator
             // in the new epoch.
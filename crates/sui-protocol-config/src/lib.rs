@@ -83,6 +83,14 @@ impl SupportedProtocolVersions {
     pub fn is_version_supported(&self, v: ProtocolVersion) -> bool {
         v.0 >= self.min.0 && v.0 <= self.max.0
     }
+
+    pub fn min(&self) -> ProtocolVersion {
+        self.min
+    }
+
+    pub fn max(&self) -> ProtocolVersion {
+        self.max
+    }
 }
 
 /// Constants that change the behavior of the protocol.
@@ -102,7 +110,10 @@ impl SupportedProtocolVersions {
 /// This way, if the constant is accessed in a protocol version in which it is not defined, the
 /// validator will crash. (Crashing is necessary because this type of error would almost always
 /// result in forking if not prevented here).
-#[derive(Clone)]
+///
+/// The config serializes to a map from each field name to its value, or null if the constant is
+/// not defined in the config's protocol version.
+#[derive(Clone, Debug, Serialize)]
 pub struct ProtocolConfig {
     // ==== Move VM, Move bytecode verifier, and execution limits ===
     /// Maximum Move bytecode version the VM understands. All older versions are accepted.
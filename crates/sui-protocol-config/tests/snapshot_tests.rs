@@ -0,0 +1,24 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// This file contains tests that detect changes in the protocol config of released protocol
+// versions. The values of a protocol version must never change once it has been released, since
+// validators running different binaries would fork. To change a constant, add a new protocol
+// version instead.
+//
+// When a new protocol version is added, record its snapshot with
+// 0. Install cargo-insta
+// 1. Run `cargo insta test --review` under `./sui-protocol-config`.
+// 2. Review, and accept the new snapshot.
+
+use insta::assert_yaml_snapshot;
+use sui_protocol_config::{ProtocolConfig, ProtocolVersion};
+
+#[test]
+#[cfg_attr(msim, ignore)]
+fn protocol_config_snapshots_match() {
+    for version in ProtocolVersion::MIN.as_u64()..=ProtocolVersion::MAX.as_u64() {
+        let config = ProtocolConfig::get_for_version(ProtocolVersion::new(version));
+        assert_yaml_snapshot!(format!("version_{version}"), config);
+    }
+}
@@ -8,7 +8,7 @@ use axum_extra::extract::WithRejection;
 use futures::StreamExt;
 use std::collections::HashMap;
 
-use sui_sdk::{SuiClient, SUI_COIN_TYPE};
+use sui_sdk::SuiClient;
 use sui_types::base_types::SuiAddress;
 use sui_types::governance::DelegationStatus;
 
@@ -17,7 +17,7 @@ use crate::types::{
     AccountBalanceRequest, AccountBalanceResponse, AccountCoinsRequest, AccountCoinsResponse,
     Amount, Coin, SubAccount, SubAccountType,
 };
-use crate::{OnlineServerContext, SuiEnv};
+use crate::{OnlineServerContext, SuiEnv, SUI};
 
 /// Get an array of all AccountBalances for an AccountIdentifier and the BlockIdentifier
 /// at which the balance lookup was performed.
@@ -46,13 +46,31 @@ pub async fn balance(
             ctx.blocks().current_block_identifier().await?
         };
 
-        ctx.blocks()
-            .get_balance_at_block(address, block_identifier.index)
-            .await
-            .map(|balance| AccountBalanceResponse {
-                block_identifier,
-                balances: vec![Amount::new(balance)],
-            })
+        let mut currencies = request.currencies;
+        if currencies.is_empty() {
+            currencies.push(SUI.clone())
+        }
+        let mut balances = vec![];
+        for currency in currencies {
+            let balance = if currency.is_sui() {
+                ctx.blocks()
+                    .get_balance_at_block(address, block_identifier.index)
+                    .await?
+            } else {
+                ctx.blocks()
+                    .get_coin_balance_at_block(
+                        address,
+                        &currency.coin_type()?,
+                        block_identifier.index,
+                    )
+                    .await?
+            };
+            balances.push(Amount::new_with_currency(balance, currency));
+        }
+        Ok(AccountBalanceResponse {
+            block_identifier,
+            balances,
+        })
     }
 }
 
@@ -132,16 +150,22 @@ pub async fn coins(
     WithRejection(Json(request), _): WithRejection<Json<AccountCoinsRequest>, Error>,
 ) -> Result<AccountCoinsResponse, Error> {
     env.check_network_identifier(&request.network_identifier)?;
-    let coins = context
-        .client
-        .coin_read_api()
-        .get_coins_stream(
-            request.account_identifier.address,
-            Some(SUI_COIN_TYPE.to_string()),
-        )
-        .map(Coin::from)
-        .collect()
-        .await;
+    let mut currencies = request.currencies;
+    if currencies.is_empty() {
+        currencies.push(SUI.clone())
+    }
+    let mut coins = vec![];
+    for currency in currencies {
+        let coin_type = currency.coin_type()?;
+        let currency_coins: Vec<_> = context
+            .client
+            .coin_read_api()
+            .get_coins_stream(request.account_identifier.address, Some(coin_type))
+            .map(|coin| Coin::new(coin, currency.clone()))
+            .collect()
+            .await;
+        coins.extend(currency_coins);
+    }
 
     Ok(AccountCoinsResponse {
         block_identifier: context.blocks().current_block_identifier().await?,
@@ -9,14 +9,15 @@ use sui_types::messages::{ExecuteTransactionRequestType, Transaction, Transactio
 use sui_types::signature::GenericSignature;
 
 use crate::errors::Error;
+use crate::operations::Operations;
 use crate::types::{
     Amount, ConstructionCombineRequest, ConstructionCombineResponse, ConstructionDeriveRequest,
     ConstructionDeriveResponse, ConstructionHashRequest, ConstructionMetadata,
     ConstructionMetadataRequest, ConstructionMetadataResponse, ConstructionParseRequest,
     ConstructionParseResponse, ConstructionPayloadsRequest, ConstructionPayloadsResponse,
     ConstructionPreprocessRequest, ConstructionPreprocessResponse, ConstructionSubmitRequest,
-    InternalOperation, MetadataOptions, SignatureType, SigningPayload, TransactionIdentifier,
-    TransactionIdentifierResponse, TransactionMetadata,
+    ConstructionTransaction, Currency, InternalOperation, MetadataOptions, SignatureType,
+    SigningPayload, TransactionIdentifier, TransactionIdentifierResponse, TransactionMetadata,
 };
 use crate::{OnlineServerContext, SuiEnv};
 use axum::extract::State;
@@ -53,18 +54,21 @@ pub async fn payloads(
     let metadata = request.metadata.ok_or(Error::MissingMetadata)?;
     let address = metadata.sender;
 
-    let data = request
+    let internal_operation = request
         .operations
-        .into_internal(Some(metadata.tx_metadata.clone().into()))?
-        .try_into_data(metadata)?;
-    let intent_msg = IntentMessage::new(Intent::default(), data);
-    let intent_msg_bytes = bcs::to_bytes(&intent_msg)?;
+        .into_internal(Some(metadata.tx_metadata.clone().into()))?;
+    let pay_currency = internal_operation.pay_currency();
+    let data = internal_operation.try_into_data(metadata)?;
+    let unsigned_tx = ConstructionTransaction {
+        tx: IntentMessage::new(Intent::default(), data),
+        pay_currency,
+    };
 
     Ok(ConstructionPayloadsResponse {
-        unsigned_transaction: Hex::from_bytes(&intent_msg_bytes),
+        unsigned_transaction: Hex::from_bytes(&bcs::to_bytes(&unsigned_tx)?),
         payloads: vec![SigningPayload {
             account_identifier: address.into(),
-            hex_bytes: Hex::encode(bcs::to_bytes(&intent_msg)?),
+            hex_bytes: Hex::encode(bcs::to_bytes(&unsigned_tx.tx)?),
             signature_type: Some(SignatureType::Ed25519),
         }],
     })
@@ -79,8 +83,9 @@ pub async fn combine(
     WithRejection(Json(request), _): WithRejection<Json<ConstructionCombineRequest>, Error>,
 ) -> Result<ConstructionCombineResponse, Error> {
     env.check_network_identifier(&request.network_identifier)?;
-    let unsigned_tx = request.unsigned_transaction.to_vec()?;
-    let intent_msg: IntentMessage<TransactionData> = bcs::from_bytes(&unsigned_tx)?;
+    let unsigned_tx: ConstructionTransaction<IntentMessage<TransactionData>> =
+        bcs::from_bytes(&request.unsigned_transaction.to_vec()?)?;
+    let intent_msg = unsigned_tx.tx;
     let sig = request
         .signatures
         .first()
@@ -101,7 +106,10 @@ pub async fn combine(
         )?],
     );
     signed_tx.verify_signature()?;
-    let signed_tx_bytes = bcs::to_bytes(&signed_tx)?;
+    let signed_tx_bytes = bcs::to_bytes(&ConstructionTransaction {
+        tx: signed_tx,
+        pay_currency: unsigned_tx.pay_currency,
+    })?;
 
     Ok(ConstructionCombineResponse {
         signed_transaction: Hex::from_bytes(&signed_tx_bytes),
@@ -117,8 +125,9 @@ pub async fn submit(
     WithRejection(Json(request), _): WithRejection<Json<ConstructionSubmitRequest>, Error>,
 ) -> Result<TransactionIdentifierResponse, Error> {
     env.check_network_identifier(&request.network_identifier)?;
-    let signed_tx: Transaction = bcs::from_bytes(&request.signed_transaction.to_vec()?)?;
-    let signed_tx = signed_tx.verify()?;
+    let signed_tx: ConstructionTransaction<Transaction> =
+        bcs::from_bytes(&request.signed_transaction.to_vec()?)?;
+    let signed_tx = signed_tx.tx.verify()?;
 
     let response = context
         .client
@@ -169,10 +178,12 @@ pub async fn hash(
 ) -> Result<TransactionIdentifierResponse, Error> {
     env.check_network_identifier(&request.network_identifier)?;
     let tx_bytes = request.signed_transaction.to_vec()?;
-    let tx: Transaction = bcs::from_bytes(&tx_bytes)?;
+    let tx: ConstructionTransaction<Transaction> = bcs::from_bytes(&tx_bytes)?;
 
     Ok(TransactionIdentifierResponse {
-        transaction_identifier: TransactionIdentifier { hash: *tx.digest() },
+        transaction_identifier: TransactionIdentifier {
+            hash: *tx.tx.digest(),
+        },
         metadata: None,
     })
 }
@@ -220,6 +231,32 @@ pub async fn metadata(
             let gas = sender_coins[0];
             (TransactionMetadata::PaySui(sender_coins), gas, 1000)
         }
+        InternalOperation::Pay {
+            sender,
+            amounts,
+            currency,
+            ..
+        } => {
+            let amount = amounts.iter().sum::<u64>() as u128;
+            let sender_coins = context
+                .client
+                .coin_read_api()
+                .select_coins(*sender, Some(currency.coin_type()?), amount, None, vec![])
+                .await?
+                .into_iter()
+                .map(|coin| coin.object_ref())
+                .collect::<Vec<_>>();
+            // gas is paid with a separate SUI coin
+            let gas = context
+                .client
+                .coin_read_api()
+                .select_coins(*sender, None, 1000 * gas_price as u128, None, vec![])
+                .await?
+                .first()
+                .ok_or_else(|| Error::DataError(format!("No SUI coin for gas in [{sender}]")))?
+                .object_ref();
+            (TransactionMetadata::Pay(sender_coins), gas, 1000)
+        }
         InternalOperation::Delegation {
             sender,
             validator,
@@ -295,20 +332,21 @@ pub async fn parse(
 ) -> Result<ConstructionParseResponse, Error> {
     env.check_network_identifier(&request.network_identifier)?;
 
-    let data = if request.signed {
-        let tx: Transaction = bcs::from_bytes(&request.transaction.to_vec()?)?;
-        tx.into_data().intent_message.value
+    let (data, pay_currency) = if request.signed {
+        let tx: ConstructionTransaction<Transaction> =
+            bcs::from_bytes(&request.transaction.to_vec()?)?;
+        (tx.tx.into_data().intent_message.value, tx.pay_currency)
     } else {
-        let intent: IntentMessage<TransactionData> =
+        let intent: ConstructionTransaction<IntentMessage<TransactionData>> =
             bcs::from_bytes(&request.transaction.to_vec()?)?;
-        intent.value
+        (intent.tx.value, intent.pay_currency)
     };
     let account_identifier_signers = if request.signed {
         vec![data.sender().into()]
     } else {
         vec![]
     };
-    let operations = data.try_into()?;
+    let operations = Operations::try_from_data(data, pay_currency.map(Currency::from))?;
     Ok(ConstructionParseResponse {
         operations,
         account_identifier_signers,
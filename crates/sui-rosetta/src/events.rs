@@ -0,0 +1,60 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use axum::extract::State;
+use axum::{Extension, Json};
+use axum_extra::extract::WithRejection;
+
+use crate::types::{
+    BlockEvent, BlockEventType, BlockIdentifier, EventsBlocksRequest, EventsBlocksResponse,
+};
+use crate::{Error, OnlineServerContext, SuiEnv};
+
+/// This module implements the [Rosetta Events API](https://www.rosetta-api.org/docs/EventsApi.html)
+
+const DEFAULT_EVENTS_LIMIT: u64 = 100;
+
+/// Get the blocks added to the chain, starting at the event sequence `offset`. Checkpoints are
+/// final, so there is exactly one `block_added` event per block and its sequence is the block
+/// index.
+/// [Rosetta API Spec](https://www.rosetta-api.org/docs/EventsApi.html#eventsblocks)
+pub async fn blocks(
+    State(context): State<OnlineServerContext>,
+    Extension(env): Extension<SuiEnv>,
+    WithRejection(Json(request), _): WithRejection<Json<EventsBlocksRequest>, Error>,
+) -> Result<EventsBlocksResponse, Error> {
+    env.check_network_identifier(&request.network_identifier)?;
+    let max_sequence = context.blocks().current_block_identifier().await?.index;
+    let offset = request.offset.unwrap_or_default();
+    let limit = request
+        .limit
+        .filter(|limit| *limit > 0)
+        .unwrap_or(DEFAULT_EVENTS_LIMIT)
+        .min(DEFAULT_EVENTS_LIMIT);
+
+    let mut events = vec![];
+    for sequence in offset
+        ..offset
+            .saturating_add(limit)
+            .min(max_sequence.saturating_add(1))
+    {
+        let checkpoint = context
+            .client
+            .read_api()
+            .get_checkpoint(sequence.into())
+            .await?;
+        events.push(BlockEvent {
+            sequence,
+            block_identifier: BlockIdentifier {
+                index: checkpoint.sequence_number,
+                hash: checkpoint.digest,
+            },
+            type_: BlockEventType::BlockAdded,
+        });
+    }
+
+    Ok(EventsBlocksResponse {
+        max_sequence,
+        events,
+    })
+}
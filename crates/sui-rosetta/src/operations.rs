@@ -9,7 +9,7 @@ use std::vec;
 use serde::Deserialize;
 use serde::Serialize;
 use sui_sdk::rpc_types::{
-    SuiEvent, SuiMoveCall, SuiPaySui, SuiTransactionData, SuiTransactionKind,
+    SuiEvent, SuiMoveCall, SuiPay, SuiPaySui, SuiTransactionData, SuiTransactionKind,
     SuiTransactionResponse,
 };
 
@@ -25,9 +25,10 @@ use sui_types::object::Owner;
 use sui_types::sui_system_state::SUI_SYSTEM_MODULE_NAME;
 use sui_types::SUI_FRAMEWORK_OBJECT_ID;
 
+use crate::state::CoinMetadataCache;
 use crate::types::{
-    AccountIdentifier, Amount, CoinAction, CoinChange, CoinID, CoinIdentifier, InternalOperation,
-    OperationIdentifier, OperationStatus, OperationType, PreprocessMetadata,
+    AccountIdentifier, Amount, CoinAction, CoinChange, CoinID, CoinIdentifier, Currency,
+    InternalOperation, OperationIdentifier, OperationStatus, OperationType, PreprocessMetadata,
 };
 use crate::Error;
 
@@ -88,6 +89,7 @@ impl Operations {
             metadata,
         ) {
             (OperationType::PaySui, _) => self.pay_sui_ops_to_internal(),
+            (OperationType::Pay, _) => self.pay_ops_to_internal(),
             (
                 OperationType::Delegation,
                 Some(PreprocessMetadata::Delegation { locked_until_epoch }),
@@ -125,6 +127,53 @@ impl Operations {
         })
     }
 
+    fn pay_ops_to_internal(self) -> Result<InternalOperation, Error> {
+        let mut recipients = vec![];
+        let mut amounts = vec![];
+        let mut sender = None;
+        let mut currency = None;
+        for op in self {
+            if let (Some(amount), Some(account)) = (op.amount, op.account) {
+                match &currency {
+                    None => currency = Some(amount.currency.clone()),
+                    Some(currency) if currency != &amount.currency => {
+                        return Err(Error::InvalidInput(
+                            "All Pay operations must use the same currency".to_string(),
+                        ))
+                    }
+                    _ => {}
+                }
+                if amount.value.is_negative() {
+                    sender = Some(account.address)
+                } else {
+                    recipients.push(account.address);
+                    let amount = amount.value.abs();
+                    if amount > u64::MAX as i128 {
+                        return Err(Error::InvalidInput(
+                            "Input amount exceed u64::MAX".to_string(),
+                        ));
+                    }
+                    amounts.push(amount as u64)
+                }
+            }
+        }
+        let sender = sender.ok_or_else(|| Error::MissingInput("Sender address".to_string()))?;
+        let currency = currency.ok_or_else(|| Error::MissingInput("Amount".to_string()))?;
+        if currency.is_sui() {
+            return Err(Error::InvalidInput(
+                "Use PaySui operations to transfer SUI".to_string(),
+            ));
+        }
+        let currency =
+            Currency::new_coin(currency.coin_type()?, currency.symbol, currency.decimals);
+        Ok(InternalOperation::Pay {
+            sender,
+            recipients,
+            amounts,
+            currency,
+        })
+    }
+
     fn delegation_ops_to_internal(
         self,
         locked_until_epoch: Option<EpochId>,
@@ -247,25 +296,63 @@ impl Operations {
         pay_operations
     }
 
+    fn parse_pay_operations(sender: SuiAddress, tx: SuiPay, currency: Currency) -> Vec<Operation> {
+        let recipients = tx.recipients.iter().zip(&tx.amounts);
+        let mut aggregated_recipients: HashMap<SuiAddress, u64> = HashMap::new();
+
+        for (recipient, amount) in recipients {
+            *aggregated_recipients.entry(*recipient).or_default() += *amount
+        }
+
+        let mut pay_operations = aggregated_recipients
+            .into_iter()
+            .map(|(recipient, amount)| Operation::pay(recipient, amount.into(), currency.clone()))
+            .collect::<Vec<_>>();
+        let total_paid = tx.amounts.iter().sum::<u64>();
+        pay_operations.push(Operation::pay(sender, -(total_paid as i128), currency));
+        pay_operations
+    }
+
+    /// Operations of a constructed transaction. The coin type of a `Pay` transaction is not
+    /// part of the transaction, it is parsed into `Pay` operations when its currency is known.
+    pub fn try_from_data(
+        data: TransactionData,
+        pay_currency: Option<Currency>,
+    ) -> Result<Self, Error> {
+        let data = SuiTransactionData::try_from(data)?;
+        let sender = data.sender;
+        data.transactions
+            .into_iter()
+            .map(|tx| match (tx, &pay_currency) {
+                (SuiTransactionKind::Pay(tx), Some(currency)) => {
+                    Ok(Self::parse_pay_operations(sender, tx, currency.clone()))
+                }
+                (tx, _) => Self::from_transaction(tx, sender, None),
+            })
+            .collect()
+    }
+
+    /// Balance changing operations extracted from the coin balance change events of a
+    /// transaction. SUI balance changes are returned as operations, while balance changes of
+    /// other coins are returned keyed by coin type and account, to be resolved to a currency.
     fn get_balance_operation_from_events(
         events: &[SuiEvent],
         status: Option<OperationStatus>,
-        balances: HashMap<SuiAddress, i128>,
-    ) -> impl Iterator<Item = Operation> {
-        let (balances, gas) = events
-            .iter()
-            .flat_map(Self::get_balance_change_from_event)
-            .fold(
-                (balances, HashMap::<SuiAddress, i128>::new()),
-                |(mut balances, mut gas), (type_, address, amount)| {
-                    if type_ == BalanceChangeType::Gas {
-                        *gas.entry(address).or_default() += amount;
-                    } else {
-                        *balances.entry(address).or_default() += amount;
-                    }
-                    (balances, gas)
-                },
-            );
+        mut balances: HashMap<SuiAddress, i128>,
+    ) -> (Vec<Operation>, HashMap<(String, SuiAddress), i128>) {
+        let mut gas = HashMap::<SuiAddress, i128>::new();
+        let mut coin_balances = HashMap::<(String, SuiAddress), i128>::new();
+        for (type_, coin_type, address, amount) in
+            events.iter().flat_map(Self::get_balance_change_from_event)
+        {
+            if coin_type != GAS::type_().to_string() {
+                *coin_balances.entry((coin_type, address)).or_default() += amount;
+            } else if type_ == BalanceChangeType::Gas {
+                *gas.entry(address).or_default() += amount;
+            } else {
+                *balances.entry(address).or_default() += amount;
+            }
+        }
 
         let balance_change = balances
             .into_iter()
@@ -275,12 +362,12 @@ impl Operations {
             .into_iter()
             .map(|(addr, amount)| Operation::gas(addr, amount));
 
-        balance_change.chain(gas)
+        (balance_change.chain(gas).collect(), coin_balances)
     }
 
     fn get_balance_change_from_event(
         event: &SuiEvent,
-    ) -> Option<(BalanceChangeType, SuiAddress, i128)> {
+    ) -> Option<(BalanceChangeType, String, SuiAddress, i128)> {
         if let SuiEvent::CoinBalanceChange {
             owner: Owner::AddressOwner(owner),
             coin_type,
@@ -289,29 +376,18 @@ impl Operations {
             ..
         } = event
         {
-            // We only interested in SUI coins and account addresses
-            if coin_type == &GAS::type_().to_string() {
-                return Some((*change_type, *owner, *amount));
-            }
+            // We only interested in account addresses
+            return Some((*change_type, coin_type.clone(), *owner, *amount));
         }
         None
     }
-}
 
-impl TryFrom<SuiTransactionData> for Operations {
-    type Error = Error;
-    fn try_from(data: SuiTransactionData) -> Result<Self, Self::Error> {
-        let sender = data.sender;
-        data.transactions
-            .into_iter()
-            .map(|tx| Self::from_transaction(tx, sender, None))
-            .collect()
-    }
-}
-
-impl TryFrom<SuiTransactionResponse> for Operations {
-    type Error = Error;
-    fn try_from(response: SuiTransactionResponse) -> Result<Self, Self::Error> {
+    /// Operations of an executed transaction, including the balance changes of every coin type
+    /// extracted from its events.
+    pub async fn try_from_response(
+        response: SuiTransactionResponse,
+        cache: &CoinMetadataCache,
+    ) -> Result<Self, Error> {
         let status = Some(response.effects.status.into());
         let ops: Operations = response.transaction.data.try_into()?;
         let ops = ops.set_status(status).into_iter();
@@ -333,12 +409,38 @@ impl TryFrom<SuiTransactionResponse> for Operations {
             });
 
         // Extract coin change operations from events
-        let coin_change_operations = Self::get_balance_operation_from_events(
+        let (balance_operations, coin_balances) = Self::get_balance_operation_from_events(
             &response.effects.events,
             status,
             accounted_balances,
         );
-        Ok(ops.into_iter().chain(coin_change_operations).collect())
+
+        let mut coin_operations = vec![];
+        for ((coin_type, addr), amount) in coin_balances {
+            if amount != 0 {
+                let currency = cache.get_currency(&coin_type).await?;
+                coin_operations.push(Operation::coin_balance_change(
+                    status, addr, amount, currency,
+                ));
+            }
+        }
+
+        Ok(ops
+            .into_iter()
+            .chain(balance_operations)
+            .chain(coin_operations)
+            .collect())
+    }
+}
+
+impl TryFrom<SuiTransactionData> for Operations {
+    type Error = Error;
+    fn try_from(data: SuiTransactionData) -> Result<Self, Self::Error> {
+        let sender = data.sender;
+        data.transactions
+            .into_iter()
+            .map(|tx| Self::from_transaction(tx, sender, None))
+            .collect()
     }
 }
 
@@ -421,6 +523,18 @@ impl Operation {
         }
     }
 
+    fn pay(address: SuiAddress, amount: i128, currency: Currency) -> Self {
+        Operation {
+            operation_identifier: Default::default(),
+            type_: OperationType::Pay,
+            status: None,
+            account: Some(address.into()),
+            amount: Some(Amount::new_with_currency(amount, currency)),
+            coin_change: None,
+            metadata: None,
+        }
+    }
+
     fn balance_change(status: Option<OperationStatus>, addr: SuiAddress, amount: i128) -> Self {
         Self {
             operation_identifier: Default::default(),
@@ -432,6 +546,22 @@ impl Operation {
             metadata: None,
         }
     }
+    fn coin_balance_change(
+        status: Option<OperationStatus>,
+        addr: SuiAddress,
+        amount: i128,
+        currency: Currency,
+    ) -> Self {
+        Self {
+            operation_identifier: Default::default(),
+            type_: OperationType::CoinBalanceChange,
+            status,
+            account: Some(addr.into()),
+            amount: Some(Amount::new_with_currency(amount, currency)),
+            coin_change: None,
+            metadata: None,
+        }
+    }
     fn gas(addr: SuiAddress, amount: i128) -> Self {
         Self {
             operation_identifier: Default::default(),
@@ -0,0 +1,229 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use axum::extract::State;
+use axum::{Extension, Json};
+use axum_extra::extract::WithRejection;
+use tracing::debug;
+
+use sui_storage::indexes::MAX_CHECKPOINT_RANGE_SIZE;
+use sui_types::base_types::TransactionDigest;
+use sui_types::query::TransactionQuery;
+
+use crate::operations::Operations;
+use crate::types::{
+    BlockIdentifier, BlockTransaction, SearchOperator, SearchTransactionsRequest,
+    SearchTransactionsResponse, Transaction, TransactionIdentifier,
+};
+use crate::{Error, OnlineServerContext, SuiEnv};
+
+#[cfg(test)]
+#[path = "unit_tests/search_tests.rs"]
+mod search_tests;
+
+/// This module implements the [Rosetta Search API](https://www.rosetta-api.org/docs/SearchApi.html)
+
+const DEFAULT_SEARCH_LIMIT: u64 = 25;
+const MAX_SEARCH_LIMIT: u64 = 100;
+const DIGEST_PAGE_SIZE: usize = 1000;
+
+/// Search for transactions matching a set of conditions, most recent first.
+/// [Rosetta API Spec](https://www.rosetta-api.org/docs/SearchApi.html#searchtransactions)
+pub async fn transactions(
+    State(context): State<OnlineServerContext>,
+    Extension(env): Extension<SuiEnv>,
+    WithRejection(Json(request), _): WithRejection<Json<SearchTransactionsRequest>, Error>,
+) -> Result<SearchTransactionsResponse, Error> {
+    env.check_network_identifier(&request.network_identifier)?;
+    let offset = request.offset.unwrap_or_default();
+    let limit = request
+        .limit
+        .filter(|limit| *limit > 0)
+        .unwrap_or(DEFAULT_SEARCH_LIMIT)
+        .min(MAX_SEARCH_LIMIT);
+
+    let (digests, total_count) = match &request.transaction_identifier {
+        Some(TransactionIdentifier { hash }) => {
+            if request.account_identifier.is_some()
+                || request.address.is_some()
+                || request.max_block.is_some()
+            {
+                return Err(Error::InvalidInput(
+                    "transaction_identifier cannot be combined with other conditions".into(),
+                ));
+            }
+            let digests = if offset == 0 { vec![*hash] } else { vec![] };
+            (digests, 1)
+        }
+        None => {
+            let query = search_query(&request)?;
+            debug!("Searching transactions with query {query:?}");
+            search_digests(&context, query, offset, limit).await?
+        }
+    };
+
+    let mut block_identifiers = HashMap::new();
+    let mut transactions = vec![];
+    for digest in digests {
+        let response = context.client.read_api().get_transaction(digest).await?;
+        let checkpoint = response.checkpoint.ok_or_else(|| {
+            Error::DataError(format!(
+                "Transaction [{digest:?}] is not in a checkpoint yet"
+            ))
+        })?;
+        let block_identifier = match block_identifiers.get(&checkpoint) {
+            Some(block_identifier) => *block_identifier,
+            None => {
+                let checkpoint = context
+                    .client
+                    .read_api()
+                    .get_checkpoint(checkpoint.into())
+                    .await?;
+                let block_identifier = BlockIdentifier {
+                    index: checkpoint.sequence_number,
+                    hash: checkpoint.digest,
+                };
+                block_identifiers.insert(checkpoint.sequence_number, block_identifier);
+                block_identifier
+            }
+        };
+        transactions.push(BlockTransaction {
+            block_identifier,
+            transaction: Transaction {
+                transaction_identifier: TransactionIdentifier {
+                    hash: response.effects.transaction_digest,
+                },
+                operations: Operations::try_from_response(response, &context.coin_metadata_cache)
+                    .await?,
+                related_transactions: vec![],
+                metadata: None,
+            },
+        })
+    }
+
+    let next_offset =
+        Some(offset.saturating_add(limit)).filter(|next_offset| *next_offset < total_count);
+    Ok(SearchTransactionsResponse {
+        transactions,
+        total_count,
+        next_offset,
+    })
+}
+
+/// Translate the conditions of the request to a fullnode transaction query.
+fn search_query(request: &SearchTransactionsRequest) -> Result<TransactionQuery, Error> {
+    if request.coin_identifier.is_some()
+        || request.currency.is_some()
+        || request.status.is_some()
+        || request.type_.is_some()
+        || request.success.is_some()
+    {
+        return Err(Error::InvalidInput(
+            "only transaction_identifier, account_identifier, address and max_block conditions are supported"
+                .into(),
+        ));
+    }
+
+    let mut addresses = vec![];
+    if let Some(account) = &request.account_identifier {
+        if account.sub_account.is_some() {
+            return Err(Error::InvalidInput(
+                "sub accounts are not supported in transaction search".into(),
+            ));
+        }
+        addresses.push(account.address);
+    }
+    addresses.extend(request.address);
+
+    // A transaction involves an address if it was sent by, or sent objects to, the address.
+    let mut conditions: Vec<TransactionQuery> = addresses
+        .into_iter()
+        .map(|address| {
+            TransactionQuery::MatchAny(vec![
+                TransactionQuery::FromAddress(address),
+                TransactionQuery::ToAddress(address),
+            ])
+        })
+        .collect();
+    let query = match conditions.len() {
+        0 => None,
+        1 => Some(conditions.remove(0)),
+        _ => Some(match request.operator.unwrap_or_default() {
+            SearchOperator::And => TransactionQuery::MatchAll(conditions),
+            SearchOperator::Or => TransactionQuery::MatchAny(conditions),
+        }),
+    };
+    // max_block is an upper bound on the results, whatever the operator of the other conditions.
+    let max_block = request
+        .max_block
+        .map(|max_block| TransactionQuery::Checkpoint {
+            start: 0,
+            end: max_block,
+        });
+
+    match (query, max_block) {
+        (Some(query), Some(max_block)) => Ok(TransactionQuery::MatchAll(vec![query, max_block])),
+        (Some(query), None) | (None, Some(query)) => Ok(query),
+        (None, None) => Err(Error::MissingInput("search condition".into())),
+    }
+}
+
+/// Digests of the transactions matching `query` from `offset` to `offset + limit`, most recent
+/// first, and the number of transactions matching `query`.
+async fn search_digests(
+    context: &OnlineServerContext,
+    query: TransactionQuery,
+    offset: u64,
+    limit: u64,
+) -> Result<(Vec<TransactionDigest>, u64), Error> {
+    // The fullnode only serves checkpoint ranges on their own up to a size, a range beyond the
+    // latest block is searched up to it.
+    let queries: Vec<_> = match query {
+        TransactionQuery::Checkpoint { start, end } => {
+            let current_block = context.blocks().current_block_identifier().await?.index;
+            checkpoint_windows(start, end.min(current_block)).collect()
+        }
+        query => vec![query],
+    };
+
+    let mut digests = vec![];
+    let mut total_count = 0;
+    for query in queries {
+        let mut cursor = None;
+        loop {
+            let page = context
+                .client
+                .read_api()
+                .get_transactions(query.clone(), cursor, Some(DIGEST_PAGE_SIZE), true)
+                .await?;
+            for digest in page.data {
+                if total_count >= offset && (digests.len() as u64) < limit {
+                    digests.push(digest);
+                }
+                total_count += 1;
+            }
+            if page.next_cursor.is_none() {
+                break;
+            }
+            cursor = page.next_cursor;
+        }
+    }
+    Ok((digests, total_count))
+}
+
+/// Splits the checkpoints from `start` to `end` into ranges the fullnode serves on their own, the
+/// latest first.
+fn checkpoint_windows(start: u64, end: u64) -> impl Iterator<Item = TransactionQuery> {
+    let mut next_end = Some(end);
+    std::iter::from_fn(move || {
+        let end = next_end.filter(|end| *end >= start)?;
+        let window_start = end.saturating_sub(MAX_CHECKPOINT_RANGE_SIZE - 1).max(start);
+        next_end = window_start.checked_sub(1);
+        Some(TransactionQuery::Checkpoint {
+            start: window_start,
+            end,
+        })
+    })
+}
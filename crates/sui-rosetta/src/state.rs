@@ -3,10 +3,10 @@
 
 use crate::operations::Operations;
 use crate::types::{
-    Block, BlockHash, BlockIdentifier, BlockResponse, OperationStatus, OperationType, Transaction,
-    TransactionIdentifier,
+    Block, BlockHash, BlockIdentifier, BlockResponse, Currency, OperationStatus, OperationType,
+    Transaction, TransactionIdentifier,
 };
-use crate::Error;
+use crate::{Error, SUI};
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use mysten_metrics::spawn_monitored_task;
@@ -20,7 +20,9 @@ use sui_sdk::rpc_types::Checkpoint;
 use sui_sdk::SuiClient;
 use sui_storage::default_db_options;
 use sui_types::base_types::{EpochId, SuiAddress};
+use sui_types::gas_coin::GAS;
 use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use tokio::sync::Mutex;
 use tracing::{debug, error, info, warn};
 use typed_store::rocks::{DBMap, DBOptions, MetricConf};
 use typed_store::traits::TableSummary;
@@ -35,13 +37,19 @@ mod balance_changing_tx_tests;
 #[derive(Clone)]
 pub struct OnlineServerContext {
     pub client: SuiClient,
+    pub coin_metadata_cache: CoinMetadataCache,
     block_provider: Arc<dyn BlockProvider + Send + Sync>,
 }
 
 impl OnlineServerContext {
-    pub fn new(client: SuiClient, block_provider: Arc<dyn BlockProvider + Send + Sync>) -> Self {
+    pub fn new(
+        client: SuiClient,
+        coin_metadata_cache: CoinMetadataCache,
+        block_provider: Arc<dyn BlockProvider + Send + Sync>,
+    ) -> Self {
         Self {
             client,
+            coin_metadata_cache,
             block_provider,
         }
     }
@@ -51,6 +59,56 @@ impl OnlineServerContext {
     }
 }
 
+/// Resolves coin types to Rosetta currencies using the `CoinMetadata` of the coin. Coins whose
+/// package published no `CoinMetadata` are named after their type, without decimals.
+/// Coin metadata is immutable once published, so it is cached for the lifetime of the server.
+#[derive(Clone)]
+pub struct CoinMetadataCache {
+    client: SuiClient,
+    currencies: Arc<Mutex<HashMap<String, Currency>>>,
+}
+
+impl CoinMetadataCache {
+    pub fn new(client: SuiClient) -> Self {
+        Self {
+            client,
+            currencies: Default::default(),
+        }
+    }
+
+    pub async fn get_currency(&self, coin_type: &str) -> Result<Currency, Error> {
+        if coin_type == GAS::type_().to_string() {
+            return Ok(SUI.clone());
+        }
+        if let Some(currency) = self.currencies.lock().await.get(coin_type) {
+            return Ok(currency.clone());
+        }
+        let currency = match self
+            .client
+            .coin_read_api()
+            .get_coin_metadata(coin_type.to_string())
+            .await
+        {
+            Ok(metadata) => Currency::new_coin(
+                coin_type.to_string(),
+                metadata.symbol,
+                metadata.decimals as u64,
+            ),
+            // The full node answered, but found no `CoinMetadata` for the coin type.
+            Err(sui_sdk::error::Error::RpcError(jsonrpsee::core::Error::Call(e))) => {
+                warn!("No coin metadata for {coin_type}, naming the currency after it: {e}");
+                Currency::new_coin(coin_type.to_string(), coin_type.to_string(), 0)
+            }
+            Err(e) => return Err(e.into()),
+        };
+        self.currencies
+            .lock()
+            .await
+            .insert(coin_type.to_string(), currency.clone());
+        Ok(currency)
+    }
+}
+
 #[async_trait]
 pub trait BlockProvider {
     async fn get_block_by_index(&self, index: u64) -> Result<BlockResponse, Error>;
@@ -64,12 +122,19 @@ pub trait BlockProvider {
         addr: SuiAddress,
         block_height: u64,
     ) -> Result<i128, Error>;
+    async fn get_coin_balance_at_block(
+        &self,
+        addr: SuiAddress,
+        coin_type: &str,
+        block_height: u64,
+    ) -> Result<i128, Error>;
 }
 
 #[derive(Clone)]
 pub struct CheckpointBlockProvider {
     index_store: Arc<CheckpointIndexStore>,
     client: SuiClient,
+    coin_metadata_cache: CoinMetadataCache,
 }
 
 #[async_trait]
@@ -124,14 +189,33 @@ impl BlockProvider for CheckpointBlockProvider {
             )
             .unwrap_or_default())
     }
+
+    async fn get_coin_balance_at_block(
+        &self,
+        addr: SuiAddress,
+        coin_type: &str,
+        block_height: u64,
+    ) -> Result<i128, Error> {
+        self.index_store
+            .get_coin_balance_at_block(addr, coin_type, block_height)
+    }
 }
 
 impl CheckpointBlockProvider {
-    pub fn spawn(client: SuiClient, db_path: &Path) -> Self {
+    pub fn spawn(
+        client: SuiClient,
+        coin_metadata_cache: CoinMetadataCache,
+        db_path: &Path,
+    ) -> Self {
         let blocks = Self {
             index_store: Arc::new(CheckpointIndexStore::open(db_path, None)),
             client,
+            coin_metadata_cache,
         };
+        blocks
+            .index_store
+            .init_first_coin_balance_checkpoint()
+            .unwrap();
 
         let update_interval = option_env!("CHECKPOINT_UPDATE_INTERVAL")
             .map(|i| u64::from_str(i).ok())
@@ -201,16 +285,16 @@ impl CheckpointBlockProvider {
         } else {
             block_height - 1
         };
-        let balances: HashMap<SuiAddress, i128> =
-            block
-                .transactions
-                .into_iter()
-                .fold(HashMap::new(), |mut changes, tx| {
-                    for (address, balance) in extract_balance_changes_from_ops(tx.operations) {
-                        *changes.entry(address).or_default() += balance;
-                    }
-                    changes
-                });
+        let mut balances: HashMap<SuiAddress, i128> = HashMap::new();
+        let mut coin_balances: HashMap<(SuiAddress, String), i128> = HashMap::new();
+        for tx in block.transactions {
+            for (address, balance) in extract_balance_changes_from_ops(tx.operations.clone()) {
+                *balances.entry(address).or_default() += balance;
+            }
+            for (key, balance) in extract_coin_balance_changes_from_ops(tx.operations)? {
+                *coin_balances.entry(key).or_default() += balance;
+            }
+        }
 
         for (addr, value) in balances {
             let current_balance = self.get_balance_at_block(addr, last_block_height).await?;
@@ -224,6 +308,19 @@ impl CheckpointBlockProvider {
                 .balances
                 .insert(&(addr, block_height), &new_balance)?;
         }
+
+        for ((addr, coin_type), value) in coin_balances {
+            let current_balance =
+                self.index_store
+                    .coin_balance_at_block(addr, &coin_type, last_block_height)?;
+            let new_balance = current_balance + value;
+            if new_balance < 0 {
+                warn!("Account {coin_type} balance fall below 0 at block {block_height}, address: [{addr}], current balance = {current_balance}, balance change = {value}.");
+            }
+            self.index_store
+                .coin_balances
+                .insert(&(addr, coin_type, block_height), &new_balance)?;
+        }
         Ok(())
     }
 
@@ -237,7 +334,7 @@ impl CheckpointBlockProvider {
                 transaction_identifier: TransactionIdentifier {
                     hash: tx.effects.transaction_digest,
                 },
-                operations: Operations::try_from(tx)?,
+                operations: Operations::try_from_response(tx, &self.coin_metadata_cache).await?,
                 related_transactions: vec![],
                 metadata: None,
             })
@@ -314,10 +411,38 @@ fn extract_balance_changes_from_ops(ops: Operations) -> HashMap<SuiAddress, i128
         })
 }
 
+/// Balance changes of coins other than SUI, keyed by account and coin type.
+fn extract_coin_balance_changes_from_ops(
+    ops: Operations,
+) -> Result<HashMap<(SuiAddress, String), i128>, Error> {
+    let mut changes = HashMap::new();
+    for op in ops {
+        if let (
+            Some(OperationStatus::Success),
+            OperationType::CoinBalanceChange,
+            Some(addr),
+            Some(amount),
+        ) = (op.status, op.type_, op.account, op.amount)
+        {
+            *changes
+                .entry((addr.address, amount.currency.coin_type()?))
+                .or_default() += amount.value
+        }
+    }
+    Ok(changes)
+}
+
 #[derive(DBMapUtils)]
 pub struct CheckpointIndexStore {
     #[default_options_override_fn = "default_config"]
     balances: DBMap<(SuiAddress, EpochId), i128>,
+    /// Balances of coins other than SUI, keyed by account, coin type and block height.
+    #[default_options_override_fn = "default_config"]
+    coin_balances: DBMap<(SuiAddress, String, CheckpointSequenceNumber), i128>,
+    /// First block with indexed balances of coins other than SUI, stores created before they
+    /// were indexed only have them from the block following the upgrade.
+    #[default_options_override_fn = "default_config"]
+    first_coin_balance_checkpoint: DBMap<bool, CheckpointSequenceNumber>,
     #[default_options_override_fn = "default_config"]
     last_checkpoint: DBMap<bool, CheckpointSequenceNumber>,
 }
@@ -333,7 +458,62 @@ impl CheckpointIndexStore {
     }
 
     pub fn is_empty(&self) -> bool {
-        self.last_checkpoint.is_empty() && self.balances.is_empty()
+        self.last_checkpoint.is_empty() && self.balances.is_empty() && self.coin_balances.is_empty()
+    }
+
+    fn init_first_coin_balance_checkpoint(&self) -> Result<(), Error> {
+        if self.first_coin_balance_checkpoint.is_empty() {
+            let first_checkpoint = match self.last_checkpoint.get(&true)? {
+                Some(last_checkpoint) => last_checkpoint + 1,
+                None if self.balances.is_empty() => 0,
+                // Only the genesis block has been indexed.
+                None => 1,
+            };
+            self.first_coin_balance_checkpoint
+                .insert(&true, &first_checkpoint)?;
+        }
+        Ok(())
+    }
+
+    /// Balance of a coin other than SUI at `block_height`. Blocks from before the coin balances
+    /// were indexed are rejected instead of reporting a balance of 0.
+    fn get_coin_balance_at_block(
+        &self,
+        addr: SuiAddress,
+        coin_type: &str,
+        block_height: u64,
+    ) -> Result<i128, Error> {
+        let first_checkpoint = self
+            .first_coin_balance_checkpoint
+            .get(&true)?
+            .unwrap_or_default();
+        if block_height < first_checkpoint {
+            return Err(Error::DataError(format!(
+                "Balances of {coin_type} are only available from block {first_checkpoint}, got block {block_height}"
+            )));
+        }
+        self.coin_balance_at_block(addr, coin_type, block_height)
+    }
+
+    fn coin_balance_at_block(
+        &self,
+        addr: SuiAddress,
+        coin_type: &str,
+        block_height: u64,
+    ) -> Result<i128, Error> {
+        Ok(self
+            .coin_balances
+            .iter()
+            .skip_prior_to(&(addr, coin_type.to_string(), block_height))?
+            .next()
+            .and_then(|((address, type_, _), balance)| {
+                if address == addr && type_ == coin_type {
+                    Some(balance)
+                } else {
+                    None
+                }
+            })
+            .unwrap_or_default())
     }
 }
 
@@ -19,6 +19,7 @@ use serde_json::Value;
 use strum_macros::EnumIter;
 use strum_macros::EnumString;
 use sui_sdk::rpc_types::{SuiExecutionStatus, SuiTransactionKind};
+use sui_sdk::SUI_COIN_TYPE;
 use sui_types::base_types::{ObjectID, ObjectRef, SequenceNumber, SuiAddress, TransactionDigest};
 use sui_types::committee::EpochId;
 use sui_types::crypto::PublicKey as SuiPublicKey;
@@ -27,12 +28,14 @@ use sui_types::governance::{
     ADD_DELEGATION_LOCKED_COIN_FUN_NAME, ADD_DELEGATION_MUL_COIN_FUN_NAME,
 };
 use sui_types::messages::{
-    CallArg, MoveCall, ObjectArg, PaySui, SingleTransactionKind, TransactionData, TransactionKind,
+    CallArg, MoveCall, ObjectArg, Pay, PaySui, SingleTransactionKind, TransactionData,
+    TransactionKind,
 };
 use sui_types::messages_checkpoint::CheckpointDigest;
 use sui_types::sui_system_state::SUI_SYSTEM_MODULE_NAME;
 use sui_types::{
-    SUI_FRAMEWORK_OBJECT_ID, SUI_SYSTEM_STATE_OBJECT_ID, SUI_SYSTEM_STATE_OBJECT_SHARED_VERSION,
+    parse_sui_type_tag, SUI_FRAMEWORK_OBJECT_ID, SUI_SYSTEM_STATE_OBJECT_ID,
+    SUI_SYSTEM_STATE_OBJECT_SHARED_VERSION,
 };
 
 pub type BlockHeight = u64;
@@ -101,10 +104,43 @@ impl From<SuiAddress> for AccountIdentifier {
     }
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Hash)]
 pub struct Currency {
     pub symbol: String,
     pub decimals: u64,
+    /// None for SUI, every other coin is identified by its Move type.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<CurrencyMetadata>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct CurrencyMetadata {
+    pub coin_type: String,
+}
+
+impl Currency {
+    /// Currency of the `Coin<T>` type `coin_type`, with symbol and decimals from its `CoinMetadata`.
+    pub fn new_coin(coin_type: String, symbol: String, decimals: u64) -> Self {
+        Self {
+            symbol,
+            decimals,
+            metadata: Some(CurrencyMetadata { coin_type }),
+        }
+    }
+
+    /// The coin type in its canonical form, the form coin balances are indexed with.
+    pub fn coin_type(&self) -> Result<String, Error> {
+        match &self.metadata {
+            Some(CurrencyMetadata { coin_type }) => Ok(parse_sui_type_tag(coin_type)
+                .map_err(|e| Error::InvalidInput(format!("Invalid coin type {coin_type}: {e}")))?
+                .to_string()),
+            None => Ok(SUI_COIN_TYPE.to_string()),
+        }
+    }
+
+    pub fn is_sui(&self) -> bool {
+        self.metadata.is_none()
+    }
 }
 #[derive(Serialize, Deserialize)]
 pub struct AccountBalanceRequest {
@@ -157,6 +193,13 @@ impl Amount {
             metadata: None,
         }
     }
+    pub fn new_with_currency(value: i128, currency: Currency) -> Self {
+        Self {
+            value,
+            currency,
+            metadata: None,
+        }
+    }
     pub fn new_locked(epoch: EpochId, value: i128) -> Self {
         Self {
             value,
@@ -194,6 +237,8 @@ pub struct AccountCoinsRequest {
     pub network_identifier: NetworkIdentifier,
     pub account_identifier: AccountIdentifier,
     pub include_mempool: bool,
+    #[serde(default)]
+    pub currencies: Vec<Currency>,
 }
 #[derive(Serialize)]
 pub struct AccountCoinsResponse {
@@ -211,8 +256,8 @@ pub struct Coin {
     pub amount: Amount,
 }
 
-impl From<sui_sdk::rpc_types::Coin> for Coin {
-    fn from(coin: sui_sdk::rpc_types::Coin) -> Self {
+impl Coin {
+    pub fn new(coin: sui_sdk::rpc_types::Coin, currency: Currency) -> Self {
         Self {
             coin_identifier: CoinIdentifier {
                 identifier: CoinID {
@@ -222,7 +267,7 @@ impl From<sui_sdk::rpc_types::Coin> for Coin {
             },
             amount: Amount {
                 value: coin.balance as i128,
-                currency: SUI.clone(),
+                currency,
                 metadata: coin.locked_until_epoch.map(|epoch| AmountMetadata {
                     lock_until_epoch: epoch,
                 }),
@@ -394,14 +439,15 @@ pub enum OperationType {
     // Balance changing operations from TransactionEffect
     Gas,
     SuiBalanceChange,
+    CoinBalanceChange,
     // sui-rosetta supported operation type
     PaySui,
+    Pay,
     Delegation,
     WithdrawDelegation,
     SwitchDelegation,
     // All other Sui transaction types, readonly
     TransferSUI,
-    Pay,
     PayAllSui,
     TransferObject,
     Publish,
@@ -547,6 +593,7 @@ pub struct ConstructionPreprocessRequest {
 #[derive(Serialize, Deserialize)]
 pub enum PreprocessMetadata {
     PaySui,
+    Pay,
     Delegation { locked_until_epoch: Option<EpochId> },
 }
 
@@ -554,6 +601,7 @@ impl From<TransactionMetadata> for PreprocessMetadata {
     fn from(tx_metadata: TransactionMetadata) -> Self {
         match tx_metadata {
             TransactionMetadata::PaySui(_) => Self::PaySui,
+            TransactionMetadata::Pay(_) => Self::Pay,
             TransactionMetadata::Delegation {
                 locked_until_epoch, ..
             } => Self::Delegation { locked_until_epoch },
@@ -618,12 +666,36 @@ impl IntoResponse for ConstructionMetadataResponse {
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub enum TransactionMetadata {
     PaySui(Vec<ObjectRef>),
+    Pay(Vec<ObjectRef>),
     Delegation {
         coins: Vec<ObjectRef>,
         locked_until_epoch: Option<EpochId>,
     },
 }
 
+/// The transaction exchanged by the construction endpoints as unsigned and signed transaction.
+/// The coin objects of a `Pay` transaction don't tell which coin is paid, so its currency is
+/// passed along with the transaction for `/construction/parse` to return the `Pay` operations.
+#[derive(Serialize, Deserialize)]
+pub struct ConstructionTransaction<T> {
+    pub tx: T,
+    pub pay_currency: Option<PayCurrency>,
+}
+
+/// A currency other than SUI, in a form that can be BCS serialized.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PayCurrency {
+    pub coin_type: String,
+    pub symbol: String,
+    pub decimals: u64,
+}
+
+impl From<PayCurrency> for Currency {
+    fn from(currency: PayCurrency) -> Self {
+        Currency::new_coin(currency.coin_type, currency.symbol, currency.decimals)
+    }
+}
+
 #[derive(Deserialize)]
 pub struct ConstructionParseRequest {
     pub network_identifier: NetworkIdentifier,
@@ -851,6 +923,100 @@ impl IntoResponse for BlockTransactionResponse {
     }
 }
 
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchOperator {
+    Or,
+    #[default]
+    And,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SearchTransactionsRequest {
+    pub network_identifier: NetworkIdentifier,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub operator: Option<SearchOperator>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_block: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub offset: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transaction_identifier: Option<TransactionIdentifier>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account_identifier: Option<AccountIdentifier>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<SuiAddress>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coin_identifier: Option<CoinIdentifier>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub currency: Option<Currency>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<OperationStatus>,
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub type_: Option<OperationType>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub success: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct BlockTransaction {
+    pub block_identifier: BlockIdentifier,
+    pub transaction: Transaction,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SearchTransactionsResponse {
+    pub transactions: Vec<BlockTransaction>,
+    pub total_count: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_offset: Option<u64>,
+}
+
+impl IntoResponse for SearchTransactionsResponse {
+    fn into_response(self) -> Response {
+        Json(self).into_response()
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct EventsBlocksRequest {
+    pub network_identifier: NetworkIdentifier,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub offset: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BlockEventType {
+    BlockAdded,
+    /// Never emitted, checkpoints are final.
+    BlockRemoved,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct BlockEvent {
+    pub sequence: u64,
+    pub block_identifier: BlockIdentifier,
+    #[serde(rename = "type")]
+    pub type_: BlockEventType,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct EventsBlocksResponse {
+    pub max_sequence: u64,
+    pub events: Vec<BlockEvent>,
+}
+
+impl IntoResponse for EventsBlocksResponse {
+    fn into_response(self) -> Response {
+        Json(self).into_response()
+    }
+}
+
 #[derive(Serialize, Clone)]
 pub struct PrefundedAccount {
     pub privkey: String,
@@ -866,6 +1032,12 @@ pub enum InternalOperation {
         recipients: Vec<SuiAddress>,
         amounts: Vec<u64>,
     },
+    Pay {
+        sender: SuiAddress,
+        recipients: Vec<SuiAddress>,
+        amounts: Vec<u64>,
+        currency: Currency,
+    },
     Delegation {
         sender: SuiAddress,
         validator: SuiAddress,
@@ -878,9 +1050,29 @@ impl InternalOperation {
     pub fn sender(&self) -> SuiAddress {
         match self {
             InternalOperation::PaySui { sender, .. }
+            | InternalOperation::Pay { sender, .. }
             | InternalOperation::Delegation { sender, .. } => *sender,
         }
     }
+
+    pub fn pay_currency(&self) -> Option<PayCurrency> {
+        match self {
+            InternalOperation::Pay {
+                currency:
+                    Currency {
+                        symbol,
+                        decimals,
+                        metadata: Some(CurrencyMetadata { coin_type }),
+                    },
+                ..
+            } => Some(PayCurrency {
+                coin_type: coin_type.clone(),
+                symbol: symbol.clone(),
+                decimals: *decimals,
+            }),
+            _ => None,
+        }
+    }
     /// Combine with ConstructionMetadata to form the TransactionData
     pub fn try_into_data(self, metadata: ConstructionMetadata) -> Result<TransactionData, Error> {
         let single_tx = match (self, metadata.tx_metadata) {
@@ -896,6 +1088,18 @@ impl InternalOperation {
                 recipients,
                 amounts,
             }),
+            (
+                Self::Pay {
+                    recipients,
+                    amounts,
+                    ..
+                },
+                TransactionMetadata::Pay(coins),
+            ) => SingleTransactionKind::Pay(Pay {
+                coins,
+                recipients,
+                amounts,
+            }),
             (
                 InternalOperation::Delegation {
                     validator,
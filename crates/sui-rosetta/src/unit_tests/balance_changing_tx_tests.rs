@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::collections::{BTreeMap, HashMap};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
 use anyhow::anyhow;
@@ -29,8 +29,42 @@ use sui_types::messages::{
 };
 use test_utils::network::TestClusterBuilder;
 
-use crate::state::extract_balance_changes_from_ops;
-use crate::types::{ConstructionMetadata, TransactionMetadata};
+use crate::state::{extract_balance_changes_from_ops, CheckpointIndexStore, CoinMetadataCache};
+use crate::types::{Amount, ConstructionMetadata, Currency, TransactionMetadata};
+
+#[test]
+fn test_coin_balances_before_upgrade() -> Result<(), anyhow::Error> {
+    let coin_type = "0x2::test::TEST";
+    let addr = SuiAddress::random_for_testing_only();
+
+    // Store which indexed blocks 0 to 10 before coin balances were indexed
+    let dir = tempfile::tempdir()?;
+    let store = CheckpointIndexStore::open(dir.path(), None);
+    store.balances.insert(&(addr, 0), &1000)?;
+    store.last_checkpoint.insert(&true, &10)?;
+    store.init_first_coin_balance_checkpoint()?;
+    store
+        .coin_balances
+        .insert(&(addr, coin_type.to_string(), 11), &100)?;
+
+    assert!(store
+        .get_coin_balance_at_block(addr, coin_type, 10)
+        .is_err());
+    assert_eq!(100, store.get_coin_balance_at_block(addr, coin_type, 11)?);
+    assert_eq!(100, store.get_coin_balance_at_block(addr, coin_type, 12)?);
+
+    // Restarting doesn't move the first block with coin balances
+    store.last_checkpoint.insert(&true, &20)?;
+    store.init_first_coin_balance_checkpoint()?;
+    assert_eq!(100, store.get_coin_balance_at_block(addr, coin_type, 11)?);
+
+    // Stores indexed from genesis have coin balances of every block
+    let dir = tempfile::tempdir()?;
+    let store = CheckpointIndexStore::open(dir.path(), None);
+    store.init_first_coin_balance_checkpoint()?;
+    assert_eq!(0, store.get_coin_balance_at_block(addr, coin_type, 0)?);
+    Ok(())
+}
 
 #[tokio::test]
 async fn test_transfer_sui() {
@@ -121,19 +155,7 @@ async fn test_publish_and_move_call() {
     let sender = get_random_address(&network.accounts, vec![]);
     let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
         .join("../../sui_programmability/examples/fungible_tokens");
-    let package = sui_framework::build_move_package(&path, BuildConfig::new_for_testing()).unwrap();
-    let compiled_module = package
-        .get_modules()
-        .map(|m| {
-            let mut module_bytes = Vec::new();
-            m.serialize(&mut module_bytes).unwrap();
-            module_bytes
-        })
-        .collect::<Vec<_>>();
-
-    let tx = SingleTransactionKind::Publish(MoveModulePublish {
-        modules: compiled_module,
-    });
+    let tx = publish_package(&path);
     let response =
         test_transaction(&client, keystore, vec![], sender, tx, None, 10000, false).await;
 
@@ -169,6 +191,58 @@ async fn test_publish_and_move_call() {
     test_transaction(&client, keystore, vec![], sender, tx, None, 10000, false).await;
 }
 
+#[tokio::test]
+async fn test_coin_without_metadata() {
+    let network = TestClusterBuilder::new().build().await.unwrap();
+    let client = network.wallet.get_client().await.unwrap();
+    let keystore = &network.wallet.config.keystore;
+
+    // The package mints a coin when it is published, but publishes no `CoinMetadata` for it.
+    let sender = get_random_address(&network.accounts, vec![]);
+    let path =
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("src/unit_tests/data/coin_without_metadata");
+    let response = test_transaction(
+        &client,
+        keystore,
+        vec![],
+        sender,
+        publish_package(&path),
+        None,
+        10000,
+        false,
+    )
+    .await;
+    let coin_type = response
+        .effects
+        .events
+        .iter()
+        .find_map(|event| match event {
+            SuiEvent::CoinBalanceChange { coin_type, .. }
+                if *coin_type != GasCoin::type_().to_string() =>
+            {
+                Some(coin_type.clone())
+            }
+            _ => None,
+        })
+        .unwrap();
+
+    // The currency is named after the coin type, and cached.
+    let cache = CoinMetadataCache::new(client.clone());
+    let currency = Currency::new_coin(coin_type.clone(), coin_type.clone(), 0);
+    let ops = Operations::try_from_response(response, &cache)
+        .await
+        .unwrap();
+    assert!(ops.into_iter().any(|op| matches!(
+        op.amount,
+        Some(Amount { value: 1000, currency: ref c, .. }) if *c == currency
+    )));
+    assert_eq!(
+        Some(&currency),
+        cache.currencies.lock().await.get(&coin_type)
+    );
+    assert_eq!(currency, cache.get_currency(&coin_type).await.unwrap());
+}
+
 #[tokio::test]
 async fn test_split_coin() {
     let network = TestClusterBuilder::new().build().await.unwrap();
@@ -480,6 +554,20 @@ async fn test_delegation_parsing() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+/// Transaction publishing the Move package at `path`.
+fn publish_package(path: &Path) -> SingleTransactionKind {
+    let package = sui_framework::build_move_package(path, BuildConfig::new_for_testing()).unwrap();
+    let modules = package
+        .get_modules()
+        .map(|m| {
+            let mut module_bytes = Vec::new();
+            m.serialize(&mut module_bytes).unwrap();
+            module_bytes
+        })
+        .collect::<Vec<_>>();
+    SingleTransactionKind::Publish(MoveModulePublish { modules })
+}
+
 fn find_module_object(
     effects: &SuiTransactionEffects,
     module: &str,
@@ -586,7 +674,10 @@ async fn test_transaction(
         assert!(matches!(effects.status, SuiExecutionStatus::Failure { .. }));
     }
 
-    let ops = response.clone().try_into().unwrap();
+    let ops =
+        Operations::try_from_response(response.clone(), &CoinMetadataCache::new(client.clone()))
+            .await
+            .unwrap();
     let balances_from_ops = extract_balance_changes_from_ops(ops);
 
     // get actual balance changed after transaction
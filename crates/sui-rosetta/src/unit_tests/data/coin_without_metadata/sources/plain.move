@@ -0,0 +1,25 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+/// A coin minted from a bare `Supply`, without `CoinMetadata`.
+module coin_without_metadata::plain {
+    use sui::balance::{Self, Supply};
+    use sui::coin;
+    use sui::object::{Self, UID};
+    use sui::transfer;
+    use sui::tx_context::{Self, TxContext};
+
+    struct PLAIN has drop {}
+
+    struct Treasury has key {
+        id: UID,
+        supply: Supply<PLAIN>,
+    }
+
+    fun init(witness: PLAIN, ctx: &mut TxContext) {
+        let supply = balance::create_supply(witness);
+        let minted = balance::increase_supply(&mut supply, 1000);
+        transfer::transfer(coin::from_balance(minted, ctx), tx_context::sender(ctx));
+        transfer::freeze_object(Treasury { id: object::new(ctx), supply });
+    }
+}
@@ -4,8 +4,10 @@
 use sui_types::base_types::{ObjectDigest, ObjectID, SequenceNumber, SuiAddress};
 use sui_types::messages::TransactionData;
 
-use crate::operations::Operations;
-use crate::types::{ConstructionMetadata, TransactionMetadata};
+use crate::operations::{Operation, Operations};
+use crate::types::{
+    Amount, ConstructionMetadata, Currency, InternalOperation, OperationType, TransactionMetadata,
+};
 
 #[tokio::test]
 async fn test_operation_data_parsing() -> Result<(), anyhow::Error> {
@@ -41,3 +43,103 @@ async fn test_operation_data_parsing() -> Result<(), anyhow::Error> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_pay_operation_data_parsing() -> Result<(), anyhow::Error> {
+    let coin = (
+        ObjectID::random(),
+        SequenceNumber::new(),
+        ObjectDigest::random(),
+    );
+    let gas = (
+        ObjectID::random(),
+        SequenceNumber::new(),
+        ObjectDigest::random(),
+    );
+    let sender = SuiAddress::random_for_testing_only();
+    let recipient = SuiAddress::random_for_testing_only();
+    let currency = Currency::new_coin("0x2::test::TEST".to_string(), "TEST".to_string(), 6);
+
+    let pay = |address: SuiAddress, amount: i128| Operation {
+        operation_identifier: Default::default(),
+        type_: OperationType::Pay,
+        status: None,
+        account: Some(address.into()),
+        amount: Some(Amount::new_with_currency(amount, currency.clone())),
+        coin_change: None,
+        metadata: None,
+    };
+    let ops = Operations::new(vec![pay(recipient, 10000), pay(sender, -10000)]);
+
+    let metadata = ConstructionMetadata {
+        tx_metadata: TransactionMetadata::Pay(vec![coin]),
+        sender,
+        gas,
+        gas_price: 1,
+        budget: 1000,
+    };
+    let internal_operation = ops
+        .clone()
+        .into_internal(Some(metadata.tx_metadata.clone().into()))?;
+    let pay_currency = internal_operation.pay_currency();
+    let data = internal_operation.try_into_data(metadata)?;
+
+    let expected = TransactionData::new_pay_with_dummy_gas_price(
+        sender,
+        vec![coin],
+        vec![recipient],
+        vec![10000],
+        gas,
+        1000,
+    );
+    assert_eq!(expected, data);
+
+    // The Pay operations are parsed back from the transaction and its currency
+    let parsed_ops = Operations::try_from_data(data, pay_currency.map(Currency::from))?;
+    assert_eq!(
+        serde_json::to_value(ops)?,
+        serde_json::to_value(parsed_ops)?
+    );
+
+    // SUI must be transferred with PaySui
+    let ops = Operations::new(vec![
+        Operation {
+            amount: Some(Amount::new(10000)),
+            ..pay(recipient, 0)
+        },
+        Operation {
+            amount: Some(Amount::new(-10000)),
+            ..pay(sender, 0)
+        },
+    ]);
+    assert!(ops.into_internal(None).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_pay_currency_coin_type_is_normalized() -> Result<(), anyhow::Error> {
+    let currency = Currency::new_coin("0x02::test::TEST".to_string(), "TEST".to_string(), 6);
+    assert_eq!("0x2::test::TEST", currency.coin_type()?);
+
+    let sender = SuiAddress::random_for_testing_only();
+    let recipient = SuiAddress::random_for_testing_only();
+    let pay = |address: SuiAddress, amount: i128| Operation {
+        operation_identifier: Default::default(),
+        type_: OperationType::Pay,
+        status: None,
+        account: Some(address.into()),
+        amount: Some(Amount::new_with_currency(amount, currency.clone())),
+        coin_change: None,
+        metadata: None,
+    };
+    let ops = Operations::new(vec![pay(recipient, 10000), pay(sender, -10000)]);
+    let InternalOperation::Pay { currency, .. } = ops.into_internal(None)? else {
+        panic!("Expecting a Pay operation")
+    };
+    assert_eq!("0x2::test::TEST", currency.coin_type()?);
+
+    let invalid = Currency::new_coin("not a type".to_string(), "TEST".to_string(), 6);
+    assert!(invalid.coin_type().is_err());
+    Ok(())
+}
@@ -0,0 +1,139 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use sui_storage::indexes::{TransactionCheckpointReader, MAX_CHECKPOINT_RANGE_SIZE};
+use sui_storage::IndexStore;
+use sui_types::base_types::{SuiAddress, TransactionDigest};
+use sui_types::error::SuiResult;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use sui_types::query::TransactionQuery;
+
+use crate::search::{checkpoint_windows, search_query};
+use crate::types::{
+    AccountIdentifier, NetworkIdentifier, SearchOperator, SearchTransactionsRequest,
+};
+use crate::SuiEnv;
+
+fn request(operator: Option<SearchOperator>, max_block: Option<u64>) -> SearchTransactionsRequest {
+    SearchTransactionsRequest {
+        network_identifier: NetworkIdentifier {
+            blockchain: "sui".to_string(),
+            network: SuiEnv::LocalNet,
+        },
+        operator,
+        max_block,
+        offset: None,
+        limit: None,
+        transaction_identifier: None,
+        account_identifier: Some(AccountIdentifier::from(
+            SuiAddress::random_for_testing_only(),
+        )),
+        address: Some(SuiAddress::random_for_testing_only()),
+        coin_identifier: None,
+        currency: None,
+        status: None,
+        type_: None,
+        success: None,
+    }
+}
+
+#[test]
+fn test_max_block_bounds_any_operator() {
+    for operator in [SearchOperator::And, SearchOperator::Or] {
+        let query = search_query(&request(Some(operator), Some(10))).unwrap();
+        let TransactionQuery::MatchAll(conditions) = query else {
+            panic!("max_block must be ANDed with the other conditions, got {query:?}");
+        };
+        assert_eq!(2, conditions.len());
+        match (&conditions[0], operator) {
+            (TransactionQuery::MatchAll(addresses), SearchOperator::And)
+            | (TransactionQuery::MatchAny(addresses), SearchOperator::Or) => {
+                assert_eq!(2, addresses.len())
+            }
+            (condition, _) => panic!("Unexpected address conditions {condition:?}"),
+        }
+        assert!(matches!(
+            conditions[1],
+            TransactionQuery::Checkpoint { start: 0, end: 10 }
+        ));
+    }
+}
+
+#[test]
+fn test_max_block_only() {
+    let request = SearchTransactionsRequest {
+        account_identifier: None,
+        address: None,
+        ..request(Some(SearchOperator::Or), Some(10))
+    };
+    assert!(matches!(
+        search_query(&request).unwrap(),
+        TransactionQuery::Checkpoint { start: 0, end: 10 }
+    ));
+
+    let request = SearchTransactionsRequest {
+        max_block: None,
+        ..request
+    };
+    assert!(search_query(&request).is_err());
+}
+
+/// A fullnode that has not executed any checkpoint.
+struct NoCheckpoints;
+
+impl TransactionCheckpointReader for NoCheckpoints {
+    fn get_checkpoint_of_transaction(
+        &self,
+        _digest: &TransactionDigest,
+    ) -> SuiResult<Option<CheckpointSequenceNumber>> {
+        Ok(None)
+    }
+
+    fn get_transactions_of_checkpoint(
+        &self,
+        _sequence_number: CheckpointSequenceNumber,
+    ) -> SuiResult<Vec<TransactionDigest>> {
+        Ok(vec![])
+    }
+}
+
+#[test]
+fn test_max_block_only_is_searched_in_windows() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = IndexStore::new(dir.path().to_path_buf());
+    let max_block = 3 * MAX_CHECKPOINT_RANGE_SIZE;
+
+    // The fullnode rejects the whole checkpoint range on its own.
+    let request = SearchTransactionsRequest {
+        account_identifier: None,
+        address: None,
+        ..request(None, Some(max_block))
+    };
+    let query = search_query(&request).unwrap();
+    assert!(store
+        .get_transactions(query.clone(), &NoCheckpoints, None, None, true)
+        .is_err());
+
+    // But serves each window, which together cover the range from the latest checkpoint on.
+    let TransactionQuery::Checkpoint { start, end } = query else {
+        panic!("Unexpected max_block query {query:?}");
+    };
+    let mut next_end = Some(end);
+    for window in checkpoint_windows(start, end) {
+        let TransactionQuery::Checkpoint { start, end } = window else {
+            panic!("Unexpected window {window:?}");
+        };
+        assert_eq!(next_end, Some(end));
+        next_end = start.checked_sub(1);
+        store
+            .get_transactions(window, &NoCheckpoints, None, None, true)
+            .unwrap();
+    }
+    assert_eq!(next_end, None);
+
+    // Combined with other conditions, the range is served as is.
+    let query = search_query(&request(None, Some(max_block))).unwrap();
+    store
+        .get_transactions(query, &NoCheckpoints, None, None, true)
+        .unwrap();
+}
@@ -6,11 +6,13 @@ mod rosetta_client;
 use crate::rosetta_client::RosettaEndpoint;
 use rosetta_client::{get_random_sui, start_rosetta_test_server};
 use serde_json::json;
+use std::time::Duration;
 use sui_keys::keystore::AccountKeystore;
 use sui_rosetta::operations::Operations;
 use sui_rosetta::types::{
-    AccountBalanceRequest, AccountBalanceResponse, AccountIdentifier, NetworkIdentifier,
-    SubAccount, SubAccountType, SuiEnv,
+    AccountBalanceRequest, AccountBalanceResponse, AccountIdentifier, BlockEventType,
+    EventsBlocksRequest, EventsBlocksResponse, NetworkIdentifier, SearchTransactionsRequest,
+    SearchTransactionsResponse, SubAccount, SubAccountType, SuiEnv,
 };
 use sui_sdk::json::SuiJsonValue;
 use sui_sdk::rpc_types::SuiExecutionStatus;
@@ -248,3 +250,139 @@ async fn test_delegation() {
 
     assert_eq!(SuiExecutionStatus::Success, tx.effects.status)
 }
+
+#[tokio::test]
+async fn test_search_transactions_and_block_events() {
+    let test_cluster = TestClusterBuilder::new().build().await.unwrap();
+    let sender = test_cluster.accounts[0];
+    let recipient = test_cluster.accounts[1];
+    let client = test_cluster.wallet.get_client().await.unwrap();
+    let keystore = &test_cluster.wallet.config.keystore;
+
+    let (rosetta_client, _handle) =
+        start_rosetta_test_server(client.clone(), test_cluster.swarm.dir()).await;
+
+    let network_identifier = NetworkIdentifier {
+        blockchain: "sui".to_string(),
+        network: SuiEnv::LocalNet,
+    };
+
+    let coins = client
+        .coin_read_api()
+        .get_coins(sender, None, None, None)
+        .await
+        .unwrap();
+    let tx = client
+        .transaction_builder()
+        .transfer_sui(
+            sender,
+            coins.data[0].coin_object_id,
+            2000,
+            recipient,
+            Some(100),
+        )
+        .await
+        .unwrap();
+    let tx = to_sender_signed_transaction(tx, keystore.get_key(&sender).unwrap());
+    let digest = client
+        .quorum_driver()
+        .execute_transaction(tx, None)
+        .await
+        .unwrap()
+        .effects
+        .transaction_digest;
+
+    let search = |address| SearchTransactionsRequest {
+        network_identifier: network_identifier.clone(),
+        operator: None,
+        max_block: None,
+        offset: None,
+        limit: Some(1),
+        transaction_identifier: None,
+        account_identifier: Some(AccountIdentifier::from(address)),
+        address: None,
+        coin_identifier: None,
+        currency: None,
+        status: None,
+        type_: None,
+        success: None,
+    };
+
+    // Wait for the transfer to be checkpointed, it is then the most recent transaction of both
+    // the sender and the recipient.
+    let mut response: SearchTransactionsResponse;
+    let mut attempts = 0;
+    loop {
+        response = rosetta_client
+            .call(RosettaEndpoint::SearchTransactions, &search(recipient))
+            .await;
+        if response
+            .transactions
+            .first()
+            .map(|tx| tx.transaction.transaction_identifier.hash)
+            == Some(digest)
+        {
+            break;
+        }
+        attempts += 1;
+        assert!(attempts < 30, "Transaction {digest:?} not found by search");
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+    assert_eq!(1, response.transactions.len());
+    assert_eq!(
+        (response.total_count > 1).then_some(1),
+        response.next_offset
+    );
+    let block = response.transactions[0].block_identifier;
+
+    let response: SearchTransactionsResponse = rosetta_client
+        .call(RosettaEndpoint::SearchTransactions, &search(sender))
+        .await;
+    assert_eq!(
+        digest,
+        response.transactions[0]
+            .transaction
+            .transaction_identifier
+            .hash
+    );
+
+    // Nothing matches both conditions before the transfer was checkpointed.
+    let request = SearchTransactionsRequest {
+        max_block: Some(block.index - 1),
+        offset: Some(0),
+        limit: Some(100),
+        ..search(recipient)
+    };
+    let response: SearchTransactionsResponse = rosetta_client
+        .call(RosettaEndpoint::SearchTransactions, &request)
+        .await;
+    assert!(response
+        .transactions
+        .iter()
+        .all(|tx| tx.transaction.transaction_identifier.hash != digest));
+
+    // The block of the transfer is reported once it has been indexed.
+    let mut attempts = 0;
+    loop {
+        let response: EventsBlocksResponse = rosetta_client
+            .call(
+                RosettaEndpoint::EventsBlocks,
+                &EventsBlocksRequest {
+                    network_identifier: network_identifier.clone(),
+                    offset: Some(block.index),
+                    limit: Some(1),
+                },
+            )
+            .await;
+        if let Some(event) = response.events.first() {
+            assert!(response.max_sequence >= block.index);
+            assert_eq!(block.index, event.sequence);
+            assert_eq!(block.hash, event.block_identifier.hash);
+            assert_eq!(BlockEventType::BlockAdded, event.type_);
+            break;
+        }
+        attempts += 1;
+        assert!(attempts < 30, "Block {} not reported", block.index);
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+}
@@ -215,6 +215,8 @@ pub enum RosettaEndpoint {
     Submit,
     Metadata,
     Status,
+    SearchTransactions,
+    EventsBlocks,
 }
 
 impl RosettaEndpoint {
@@ -235,6 +237,8 @@ impl RosettaEndpoint {
             RosettaEndpoint::Submit => "construction/submit",
             RosettaEndpoint::Metadata => "construction/metadata",
             RosettaEndpoint::Status => "network/status",
+            RosettaEndpoint::SearchTransactions => "search/transactions",
+            RosettaEndpoint::EventsBlocks => "events/blocks",
         }
     }
 
@@ -254,7 +258,9 @@ impl RosettaEndpoint {
             | RosettaEndpoint::Transaction
             | RosettaEndpoint::Submit
             | RosettaEndpoint::Metadata
-            | RosettaEndpoint::Status => true,
+            | RosettaEndpoint::Status
+            | RosettaEndpoint::SearchTransactions
+            | RosettaEndpoint::EventsBlocks => true,
         }
     }
 }
@@ -13,11 +13,13 @@ use std::sync::Arc;
 use std::time::{Duration, Instant};
 use sui_json_rpc::api::GovernanceReadApiClient;
 use sui_json_rpc_types::{
-    Balance, Checkpoint, CheckpointId, Coin, CoinPage, DynamicFieldPage, EventPage,
-    GetObjectDataResponse, GetPastObjectDataResponse, GetRawObjectDataResponse, SuiCoinMetadata,
-    SuiEventEnvelope, SuiEventFilter, SuiMoveNormalizedModule, SuiObjectInfo,
-    SuiTransactionEffects, SuiTransactionResponse, TransactionsPage,
+    Balance, Checkpoint, CheckpointId, Coin, CoinPage, DevInspectResults, DynamicFieldPage,
+    EventPage, GetObjectDataResponse, GetPastObjectDataResponse, GetRawObjectDataResponse,
+    ProtocolConfigResponse, SuiCoinMetadata, SuiEventEnvelope, SuiEventFilter,
+    SuiMoveNormalizedModule, SuiObjectInfo, SuiTransactionEffects, SuiTransactionResponse,
+    TransactionsPage,
 };
+use sui_protocol_config::ProtocolVersion;
 use sui_types::balance::Supply;
 use sui_types::base_types::{
     ObjectID, SequenceNumber, SuiAddress, TransactionDigest, TxSequenceNumber,
@@ -26,7 +28,8 @@ use sui_types::committee::EpochId;
 use sui_types::error::TRANSACTION_NOT_FOUND_MSG_PREFIX;
 use sui_types::event::EventID;
 use sui_types::messages::{
-    CommitteeInfoResponse, ExecuteTransactionRequestType, TransactionData, VerifiedTransaction,
+    CommitteeInfoResponse, ExecuteTransactionRequestType, TransactionData, TransactionKind,
+    VerifiedTransaction,
 };
 use sui_types::messages_checkpoint::{CheckpointSequenceNumber, CheckpointSummary};
 use sui_types::query::{EventQuery, TransactionQuery};
@@ -153,6 +156,15 @@ impl ReadApi {
             .await?)
     }
 
+    /// Return the protocol config of `version`, or of the current epoch if `version` is None,
+    /// along with the range of protocol versions supported by the node
+    pub async fn get_protocol_config(
+        &self,
+        version: Option<ProtocolVersion>,
+    ) -> SuiRpcResult<ProtocolConfigResponse> {
+        Ok(self.api.http.get_protocol_config(version).await?)
+    }
+
     pub fn get_transactions_stream(
         &self,
         query: TransactionQuery,
@@ -209,6 +221,25 @@ impl ReadApi {
             .dry_run_transaction(Base64::from_bytes(&bcs::to_bytes(&tx)?))
             .await?)
     }
+
+    pub async fn dev_inspect_transaction(
+        &self,
+        sender_address: SuiAddress,
+        tx: TransactionKind,
+        gas_price: Option<u64>,
+        epoch: Option<EpochId>,
+    ) -> SuiRpcResult<DevInspectResults> {
+        Ok(self
+            .api
+            .http
+            .dev_inspect_transaction(
+                sender_address,
+                Base64::from_bytes(&bcs::to_bytes(&tx)?),
+                gas_price,
+                epoch,
+            )
+            .await?)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -37,6 +37,7 @@ use sui_types::error::SuiError::{StorageCorruptedFieldError, StorageMissingField
 use sui_types::event::{BalanceChangeType, Event, EventID};
 use sui_types::event::{EventEnvelope, EventType};
 use sui_types::object::Owner;
+use sui_types::query::EventQuery;
 
 pub mod sql;
 pub mod test_utils;
@@ -44,6 +45,9 @@ pub mod test_utils;
 /// Maximum number of events one can ask for right now
 pub const EVENT_STORE_QUERY_MAX_LIMIT: usize = 1000;
 
+/// Maximum number of criteria, including combinators, in a single composite query
+pub const EVENT_STORE_QUERY_MAX_CRITERIA: usize = 100;
+
 pub const OBJECT_VERSION_KEY: &str = "obj_ver";
 pub const AMOUNT_KEY: &str = "amount";
 pub const BALANCE_CHANGE_TYPE_KEY: &str = "change_type";
@@ -429,6 +433,15 @@ pub trait EventStore {
     /// Returns Ok(rows_affected).
     async fn add_events(&self, events: &[EventEnvelope]) -> Result<u64, SuiError>;
 
+    /// Deletes all events emitted by the given transactions. Used to prune the events of
+    /// transactions which are no longer retained by the node.
+    ///
+    /// Returns Ok(rows_affected).
+    async fn delete_events_by_transactions(
+        &self,
+        digests: &[TransactionDigest],
+    ) -> Result<u64, SuiError>;
+
     /// Returns at most `limit` events emitted by all transaction, ordered .
     async fn all_events(
         &self,
@@ -527,6 +540,18 @@ pub trait EventStore {
         limit: usize,
         descending: bool,
     ) -> Result<Vec<StoredEvent>, SuiError>;
+
+    /// Returns at most `limit` events matching `query`, which may combine several criteria
+    /// with `MatchAll`, `MatchAny` and `Not`, sorted in time order defined by the descending
+    /// parameter.
+    async fn events_by_query(
+        &self,
+        query: &EventQuery,
+        tx_seq: i64,
+        event_seq: i64,
+        limit: usize,
+        descending: bool,
+    ) -> Result<Vec<StoredEvent>, SuiError>;
 }
 
 /// EventStoreType contains different implementations of EventStores, but implements the EventStore trait.
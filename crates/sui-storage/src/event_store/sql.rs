@@ -23,7 +23,9 @@ use tracing::{info, instrument, log, warn};
 use sui_types::base_types::SuiAddress;
 use sui_types::error::SuiError;
 use sui_types::event::Event;
+use sui_types::filter::{EventFieldPredicate, FieldOperand};
 use sui_types::object::Owner;
+use sui_types::parse_sui_struct_tag;
 
 use super::*;
 
@@ -427,6 +429,35 @@ impl EventStore for SqlEventStore {
         Ok(rows_affected)
     }
 
+    #[instrument(level = "debug", skip_all, err)]
+    async fn delete_events_by_transactions(
+        &self,
+        digests: &[TransactionDigest],
+    ) -> Result<u64, SuiError> {
+        let _guard = self.query_lock.read().await;
+        let mut rows_affected = 0;
+
+        for chunk in digests.chunks(MAX_INSERT_BATCH) {
+            let mut query_builder =
+                QueryBuilder::<Sqlite>::new("DELETE FROM events WHERE tx_digest IN (");
+            let mut separated = query_builder.separated(", ");
+            for digest in chunk {
+                separated.push_bind(digest.into_inner().to_vec());
+            }
+            separated.push_unseparated(")");
+
+            let res = query_builder
+                .build()
+                .execute(&self.pool)
+                .await
+                .map_err(convert_sqlx_err)?;
+
+            rows_affected += res.rows_affected();
+        }
+
+        Ok(rows_affected)
+    }
+
     #[instrument(level = "debug", skip_all, err)]
     async fn all_events(
         &self,
@@ -670,18 +701,44 @@ impl EventStore for SqlEventStore {
             .map_err(convert_sqlx_err)?;
         Ok(rows)
     }
+
+    #[instrument(level = "debug", skip_all, err)]
+    async fn events_by_query(
+        &self,
+        query: &EventQuery,
+        tx_seq: i64,
+        event_seq: i64,
+        limit: usize,
+        descending: bool,
+    ) -> Result<Vec<StoredEvent>, SuiError> {
+        let mut query_builder = build_event_query(query, tx_seq, event_seq, limit, descending)?;
+        let _guard = self.query_lock.read().await;
+        let rows = query_builder
+            .build()
+            .map(StoredEvent::from)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(convert_sqlx_err)?;
+        Ok(rows)
+    }
 }
 
 fn convert_sqlx_err(err: sqlx::Error) -> SuiError {
     SuiError::GenericStorageError(err.to_string())
 }
 
-fn get_event_query(causes: Vec<(&str, Comparator)>, descending: bool) -> String {
-    let (seq_num_cmp, event_num_cmp, order) = if descending {
+/// Comparators on seq_num and event_num selecting the events after the cursor, and the
+/// matching sort order.
+fn cursor_comparators(descending: bool) -> (Comparator, Comparator, &'static str) {
+    if descending {
         (Comparator::LessThan, Comparator::LessThanOrEq, "DESC")
     } else {
         (Comparator::MoreThan, Comparator::MoreThanOrEq, "ASC")
-    };
+    }
+}
+
+fn get_event_query(causes: Vec<(&str, Comparator)>, descending: bool) -> String {
+    let (seq_num_cmp, event_num_cmp, order) = cursor_comparators(descending);
     let mut query = format!(
         "SELECT * FROM events WHERE (seq_num {seq_num_cmp} ? OR (seq_num = ? AND event_num {event_num_cmp} ?))"
     );
@@ -700,6 +757,290 @@ fn get_event_query(causes: Vec<(&str, Comparator)>, descending: bool) -> String
     query
 }
 
+/// Builds the query for events matching a (possibly composite) `EventQuery`. The whole query
+/// is translated into a single SQL predicate, so filtering is done by SQLite using its indexes.
+fn build_event_query(
+    query: &EventQuery,
+    tx_seq: i64,
+    event_seq: i64,
+    limit: usize,
+    descending: bool,
+) -> Result<QueryBuilder<'static, Sqlite>, SuiError> {
+    let (seq_num_cmp, event_num_cmp, order) = cursor_comparators(descending);
+    let mut builder = QueryBuilder::new(format!(
+        "SELECT * FROM events WHERE (seq_num {seq_num_cmp} "
+    ));
+    builder
+        .push_bind(tx_seq)
+        .push(" OR (seq_num = ")
+        .push_bind(tx_seq)
+        .push(format!(" AND event_num {event_num_cmp} "))
+        .push_bind(event_seq)
+        .push(")) AND ");
+    push_event_query_predicate(&mut builder, query, &mut 0)?;
+    builder
+        .push(format!(
+            " ORDER BY seq_num {order}, event_num {order} LIMIT "
+        ))
+        .push_bind(limit as i64);
+    Ok(builder)
+}
+
+fn push_event_query_predicate(
+    builder: &mut QueryBuilder<'static, Sqlite>,
+    query: &EventQuery,
+    criteria: &mut usize,
+) -> Result<(), SuiError> {
+    *criteria += 1;
+    if *criteria > EVENT_STORE_QUERY_MAX_CRITERIA {
+        return Err(SuiError::InvalidEventQuery {
+            error: format!("A query can have at most {EVENT_STORE_QUERY_MAX_CRITERIA} criteria"),
+        });
+    }
+    match query {
+        EventQuery::All => {
+            builder.push("1");
+        }
+        EventQuery::Transaction(digest) => {
+            builder
+                .push("tx_digest = ")
+                .push_bind(digest.into_inner().to_vec());
+        }
+        EventQuery::MoveModule { package, module } => {
+            let module = Identifier::from_str(module).map_err(|e| SuiError::InvalidEventQuery {
+                error: format!("Invalid module name {module}: {e}"),
+            })?;
+            builder
+                .push("(package_id = ")
+                .push_bind(package.to_vec())
+                .push(" AND module_name = ")
+                .push_bind(module.to_string())
+                .push(")");
+        }
+        EventQuery::MoveEvent(struct_name) => {
+            let struct_name = parse_sui_struct_tag(struct_name)
+                .map_err(|e| SuiError::InvalidEventQuery {
+                    error: format!("Invalid move event struct name {struct_name}: {e}"),
+                })?
+                .to_string();
+            builder.push("move_event_name = ").push_bind(struct_name);
+        }
+        EventQuery::EventType(event_type) => {
+            builder.push("event_type = ").push_bind(*event_type as u16);
+        }
+        EventQuery::Sender(sender) => {
+            builder.push("sender = ").push_bind(sender.to_vec());
+        }
+        EventQuery::Recipient(recipient) => {
+            let recipient_str =
+                serde_json::to_string(recipient).map_err(|e| SuiError::OwnerFailedToSerialize {
+                    error: (e.to_string()),
+                })?;
+            builder.push("recipient = ").push_bind(recipient_str);
+        }
+        EventQuery::Object(object) => {
+            builder.push("object_id = ").push_bind(object.to_vec());
+        }
+        EventQuery::TimeRange {
+            start_time,
+            end_time,
+        } => {
+            builder
+                .push("(timestamp >= ")
+                .push_bind(*start_time as i64)
+                .push(" AND timestamp < ")
+                .push_bind(*end_time as i64)
+                .push(")");
+        }
+        EventQuery::MoveEventField { path, predicate } => {
+            push_event_field_predicate(builder, path, predicate)?
+        }
+        EventQuery::MatchAll(queries) => {
+            push_event_query_predicates(builder, queries, " AND ", "1", criteria)?
+        }
+        EventQuery::MatchAny(queries) => {
+            push_event_query_predicates(builder, queries, " OR ", "0", criteria)?
+        }
+        EventQuery::Not(query) => {
+            // Comparisons against a NULL column evaluate to NULL, which NOT leaves as NULL and
+            // thus excluded, so treat them as not matching before negating.
+            builder.push("NOT IFNULL(");
+            push_event_query_predicate(builder, query, criteria)?;
+            builder.push(", 0)");
+        }
+    }
+    Ok(())
+}
+
+fn push_event_query_predicates(
+    builder: &mut QueryBuilder<'static, Sqlite>,
+    queries: &[EventQuery],
+    separator: &str,
+    if_empty: &str,
+    criteria: &mut usize,
+) -> Result<(), SuiError> {
+    if queries.is_empty() {
+        builder.push(if_empty);
+        return Ok(());
+    }
+    builder.push("(");
+    for (i, query) in queries.iter().enumerate() {
+        if i > 0 {
+            builder.push(separator);
+        }
+        push_event_query_predicate(builder, query, criteria)?;
+    }
+    builder.push(")");
+    Ok(())
+}
+
+/// Appends a predicate on the Move event field at JSON pointer `pointer`, evaluated on the
+/// JSON `fields` column with the same semantics as `EventFieldPredicate::try_matches`.
+fn push_event_field_predicate(
+    builder: &mut QueryBuilder<'static, Sqlite>,
+    pointer: &str,
+    predicate: &EventFieldPredicate,
+) -> Result<(), SuiError> {
+    let path = json_pointer_to_sqlite_path(pointer)?;
+    // `t` is the JSON type of the field and `v` its value as text, e.g. `text` and `100` for
+    // a Move u64.
+    builder
+        .push("EXISTS (SELECT 1 FROM (SELECT json_type(fields, ")
+        .push_bind(path.clone())
+        .push(") AS t, CAST(json_extract(fields, ")
+        .push_bind(path)
+        .push(") AS TEXT) AS v) WHERE ");
+    match predicate {
+        EventFieldPredicate::Eq(value) => {
+            push_field_comparison(builder, FieldOperand::new(value), Comparator::Equal)?
+        }
+        EventFieldPredicate::Gt(value) => {
+            push_field_comparison(builder, ordered_operand(value)?, Comparator::MoreThan)?
+        }
+        EventFieldPredicate::Gte(value) => {
+            push_field_comparison(builder, ordered_operand(value)?, Comparator::MoreThanOrEq)?
+        }
+        EventFieldPredicate::Lt(value) => {
+            push_field_comparison(builder, ordered_operand(value)?, Comparator::LessThan)?
+        }
+        EventFieldPredicate::Lte(value) => {
+            push_field_comparison(builder, ordered_operand(value)?, Comparator::LessThanOrEq)?
+        }
+        EventFieldPredicate::Between { min, max } => {
+            builder.push("(");
+            push_field_comparison(builder, ordered_operand(min)?, Comparator::MoreThanOrEq)?;
+            builder.push(" AND ");
+            push_field_comparison(builder, ordered_operand(max)?, Comparator::LessThanOrEq)?;
+            builder.push(")");
+        }
+        EventFieldPredicate::Prefix(prefix) => {
+            builder
+                .push("(t = 'text' AND substr(v, 1, ")
+                .push_bind(prefix.chars().count() as i64)
+                .push(") = ")
+                .push_bind(prefix.clone())
+                .push(")");
+        }
+        EventFieldPredicate::In(values) => {
+            if values.is_empty() {
+                builder.push("0");
+            } else {
+                builder.push("(");
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        builder.push(" OR ");
+                    }
+                    push_field_comparison(builder, FieldOperand::new(value), Comparator::Equal)?;
+                }
+                builder.push(")");
+            }
+        }
+    }
+    builder.push(")");
+    Ok(())
+}
+
+fn ordered_operand(value: &Value) -> Result<FieldOperand<'_>, SuiError> {
+    FieldOperand::ordered(value).map_err(|e| SuiError::InvalidEventQuery {
+        error: e.to_string(),
+    })
+}
+
+fn push_field_comparison(
+    builder: &mut QueryBuilder<'static, Sqlite>,
+    operand: FieldOperand<'_>,
+    cmp: Comparator,
+) -> Result<(), SuiError> {
+    match operand {
+        // Unsigned integers are stored as JSON numbers or, for u64 and above, as strings of
+        // digits. Without leading zeros they compare as (length, digits).
+        FieldOperand::Numeric(digits) => {
+            builder
+                .push(
+                    "(t IN ('integer', 'text') AND v <> '' AND v NOT GLOB '*[^0-9]*' \
+                     AND (length(ltrim(v, '0')), ltrim(v, '0')) ",
+                )
+                .push(format!("{cmp} ("))
+                .push_bind(digits.len() as i64)
+                .push(", ")
+                .push_bind(digits)
+                .push("))");
+        }
+        FieldOperand::Text(s) => {
+            builder
+                .push(format!("(t = 'text' AND v {cmp} "))
+                .push_bind(s.to_string())
+                .push(")");
+        }
+        FieldOperand::Other(value) => match value {
+            Value::Bool(true) => {
+                builder.push("t = 'true'");
+            }
+            Value::Bool(false) => {
+                builder.push("t = 'false'");
+            }
+            Value::Null => {
+                builder.push("t = 'null'");
+            }
+            Value::Number(n) => {
+                builder
+                    .push("(t IN ('integer', 'real') AND CAST(v AS REAL) = ")
+                    .push_bind(n.as_f64())
+                    .push(")");
+            }
+            _ => {
+                return Err(SuiError::InvalidEventQuery {
+                    error: format!("Cannot compare event fields with {value} in a query"),
+                })
+            }
+        },
+    }
+    Ok(())
+}
+
+/// Converts a JSON pointer, e.g. `/pool/amount`, into an SQLite JSON path, e.g.
+/// `$."pool"."amount"`. Numeric segments are treated as array indexes.
+fn json_pointer_to_sqlite_path(pointer: &str) -> Result<String, SuiError> {
+    let invalid = || SuiError::InvalidEventQuery {
+        error: format!("Invalid JSON pointer to event field: {pointer}"),
+    };
+    if pointer.is_empty() {
+        return Ok("$".to_string());
+    }
+    let mut path = String::from("$");
+    for segment in pointer.strip_prefix('/').ok_or_else(invalid)?.split('/') {
+        let segment = segment.replace("~1", "/").replace("~0", "~");
+        if !segment.is_empty() && segment.bytes().all(|b| b.is_ascii_digit()) {
+            path.push_str(&format!("[{segment}]"));
+        } else if segment.contains('"') {
+            return Err(invalid());
+        } else {
+            path.push_str(&format!(".\"{segment}\""));
+        }
+    }
+    Ok(path)
+}
+
 enum Comparator {
     Equal,
     LessThanOrEq,
@@ -971,6 +1312,62 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_eventstore_delete_events_by_transactions() -> Result<(), SuiError> {
+        telemetry_subscribers::init_for_testing();
+
+        // Initialize store
+        let db = SqlEventStore::new_memory_only_not_prod().await?;
+        db.initialize().await?;
+
+        let pruned_digest = TransactionDigest::random();
+        let retained_digest = TransactionDigest::random();
+        let to_insert = vec![
+            test_utils::new_test_newobj_event(
+                1_000_000,
+                pruned_digest,
+                1,
+                0, // event_num
+                None,
+                None,
+                None,
+            ),
+            test_utils::new_test_deleteobj_event(
+                1_000_000,
+                pruned_digest,
+                1,
+                1, // event_num
+                None,
+                None,
+            ),
+            test_utils::new_test_publish_event(
+                1_001_000,
+                retained_digest,
+                2,
+                0, // event_num
+                None,
+            ),
+        ];
+        db.add_events(&to_insert).await?;
+
+        let deleted = db
+            .delete_events_by_transactions(&[pruned_digest, TransactionDigest::random()])
+            .await?;
+        assert_eq!(deleted, 2);
+
+        let events = db
+            .events_by_transaction(pruned_digest, 0, 0, 10, false)
+            .await?;
+        assert!(events.is_empty());
+        let events = db
+            .events_by_transaction(retained_digest, 0, 0, 10, false)
+            .await?;
+        assert_eq!(events.len(), 1);
+        test_queried_event_vs_test_envelope(&events[0], &to_insert[2]);
+
+        Ok(())
+    }
+
     // Test for reads by event type, plus returning events in desc timestamp and limit
     #[tokio::test]
     async fn test_eventstore_query_by_type() -> Result<(), SuiError> {
@@ -1405,6 +1802,244 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_eventstore_composite_queries() -> Result<(), SuiError> {
+        telemetry_subscribers::init_for_testing();
+
+        let db = SqlEventStore::new_memory_only_not_prod().await?;
+        db.initialize().await?;
+
+        let sender = SuiAddress::random_for_testing_only();
+        let recipient = Owner::AddressOwner(SuiAddress::random_for_testing_only());
+        let object_id = ObjectID::random();
+        let to_insert = vec![
+            test_utils::new_test_transfer_event(
+                // 0, transfer, object, sender, recipient
+                1_000_000,
+                TransactionDigest::random(),
+                1,
+                0,
+                1,
+                "0x2::test:Object",
+                Some(object_id),
+                Some(sender),
+                Some(recipient),
+            ),
+            test_utils::new_test_newobj_event(
+                // 1, new object, object, sender
+                1_001_000,
+                TransactionDigest::random(),
+                2,
+                0,
+                Some(object_id),
+                Some(sender),
+                None,
+            ),
+            test_utils::new_test_transfer_event(
+                // 2, transfer, recipient
+                1_002_000,
+                TransactionDigest::random(),
+                3,
+                0,
+                1,
+                "0x2::test:Object",
+                None,
+                None,
+                Some(recipient),
+            ),
+            test_utils::new_test_deleteobj_event(
+                // 3, delete object, sender
+                1_003_000,
+                TransactionDigest::random(),
+                4,
+                0,
+                None,
+                Some(sender),
+            ),
+        ];
+        assert_eq!(db.add_events(&to_insert).await?, 4);
+
+        let query_ids = |query: EventQuery, descending: bool| {
+            let db = &db;
+            let (tx_seq, event_seq) = if descending {
+                (i64::MAX, i64::MAX)
+            } else {
+                (0, 0)
+            };
+            async move {
+                Ok::<_, SuiError>(
+                    db.events_by_query(&query, tx_seq, event_seq, 10, descending)
+                        .await?
+                        .into_iter()
+                        .map(|e| e.seq_num)
+                        .collect::<Vec<_>>(),
+                )
+            }
+        };
+
+        // Sender AND time range
+        let query = EventQuery::MatchAll(vec![
+            EventQuery::Sender(sender),
+            EventQuery::TimeRange {
+                start_time: 1_001_000,
+                end_time: 1_010_000,
+            },
+        ]);
+        assert_eq!(query_ids(query.clone(), false).await?, vec![2, 4]);
+        assert_eq!(query_ids(query, true).await?, vec![4, 2]);
+
+        // Recipient OR object
+        let query = EventQuery::MatchAny(vec![
+            EventQuery::Recipient(recipient),
+            EventQuery::Object(object_id),
+        ]);
+        assert_eq!(query_ids(query, false).await?, vec![1, 2, 3]);
+
+        // Events from other senders
+        let query = EventQuery::Not(Box::new(EventQuery::Sender(sender)));
+        assert_eq!(query_ids(query, false).await?, vec![3]);
+
+        // Transfers not involving the object
+        let query = EventQuery::MatchAll(vec![
+            EventQuery::EventType(EventType::TransferObject),
+            EventQuery::Not(Box::new(EventQuery::Object(object_id))),
+        ]);
+        assert_eq!(query_ids(query, false).await?, vec![3]);
+
+        // Empty combinators
+        assert_eq!(
+            query_ids(EventQuery::MatchAll(vec![]), false).await?,
+            vec![1, 2, 3, 4]
+        );
+        assert!(query_ids(EventQuery::MatchAny(vec![]), false)
+            .await?
+            .is_empty());
+
+        // Too many criteria
+        let query = EventQuery::MatchAny(vec![EventQuery::All; EVENT_STORE_QUERY_MAX_CRITERIA]);
+        assert!(query_ids(query, false).await.is_err());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_eventstore_move_event_field_queries() -> Result<(), SuiError> {
+        telemetry_subscribers::init_for_testing();
+
+        let db = SqlEventStore::new_memory_only_not_prod().await?;
+        db.initialize().await?;
+
+        let fields = [
+            json!({ "amount": "5", "pool": { "name": "SUI/USDC" }, "active": true }),
+            json!({ "amount": "1000", "pool": { "name": "SUI/USDT" }, "active": false }),
+            json!({ "amount": "18446744073709551615", "pool": { "name": "ETH/USDC" } }),
+            json!({ "amount": 20, "pool": { "name": "SUI/ETH" } }),
+        ];
+        let to_insert = fields
+            .into_iter()
+            .enumerate()
+            .map(|(i, json)| {
+                let mut event = test_utils::new_test_move_event(
+                    1_000_000 + i as u64,
+                    TransactionDigest::random(),
+                    i as u64 + 1,
+                    0,
+                    ObjectID::from_hex_literal("0x3").unwrap(),
+                    "test_module",
+                    "SwapEvent",
+                );
+                event.move_struct_json_value = Some(json);
+                event
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(db.add_events(&to_insert).await?, 4);
+
+        let query_ids = |path: &str, predicate: EventFieldPredicate| {
+            let query = EventQuery::MoveEventField {
+                path: path.to_string(),
+                predicate,
+            };
+            let db = &db;
+            async move {
+                Ok::<_, SuiError>(
+                    db.events_by_query(&query, 0, 0, 10, false)
+                        .await?
+                        .into_iter()
+                        .map(|e| e.seq_num)
+                        .collect::<Vec<_>>(),
+                )
+            }
+        };
+
+        assert_eq!(
+            query_ids("/amount", EventFieldPredicate::Gt(json!(10))).await?,
+            vec![2, 3, 4]
+        );
+        assert_eq!(
+            query_ids("/amount", EventFieldPredicate::Gt(json!("1000"))).await?,
+            vec![3]
+        );
+        assert_eq!(
+            query_ids("/amount", EventFieldPredicate::Lte(json!(20))).await?,
+            vec![1, 4]
+        );
+        assert_eq!(
+            query_ids(
+                "/amount",
+                EventFieldPredicate::Between {
+                    min: json!(20),
+                    max: json!("1000"),
+                }
+            )
+            .await?,
+            vec![2, 4]
+        );
+        assert_eq!(
+            query_ids(
+                "/amount",
+                EventFieldPredicate::In(vec![json!(5), json!("20")])
+            )
+            .await?,
+            vec![1, 4]
+        );
+        assert_eq!(
+            query_ids("/pool/name", EventFieldPredicate::Prefix("SUI/".into())).await?,
+            vec![1, 2, 4]
+        );
+        assert_eq!(
+            query_ids("/pool/name", EventFieldPredicate::Gte(json!("SUI/USDC"))).await?,
+            vec![1, 2]
+        );
+        assert_eq!(
+            query_ids("/active", EventFieldPredicate::Eq(json!(true))).await?,
+            vec![1]
+        );
+        assert!(query_ids("/active", EventFieldPredicate::Gt(json!(true)))
+            .await
+            .is_err());
+        assert!(query_ids("amount", EventFieldPredicate::Gt(json!(10)))
+            .await
+            .is_err());
+
+        // Combined with other criteria
+        let query = EventQuery::MatchAll(vec![
+            EventQuery::MoveEvent(to_insert[0].event.move_event_name().unwrap()),
+            EventQuery::MoveEventField {
+                path: "/amount".to_string(),
+                predicate: EventFieldPredicate::Gt(json!(100)),
+            },
+            EventQuery::Not(Box::new(EventQuery::MoveEventField {
+                path: "/pool/name".to_string(),
+                predicate: EventFieldPredicate::Prefix("ETH/".into()),
+            })),
+        ]);
+        let events = db.events_by_query(&query, 0, 0, 10, false).await?;
+        assert_eq!(events.len(), 1);
+        test_queried_event_vs_test_envelope(&events[0], &to_insert[1]);
+
+        Ok(())
+    }
+
     // Test we can retrieve u64 object version (aka sequence number) values
     // stored as string in sqlite
     #[tokio::test]
@@ -8,6 +8,7 @@ use anyhow::anyhow;
 use move_core_types::identifier::Identifier;
 use serde::{de::DeserializeOwned, Serialize};
 use std::cmp::min;
+use std::iter::Peekable;
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicU64, Ordering};
 use tracing::debug;
@@ -22,6 +23,7 @@ use sui_types::base_types::{ObjectInfo, ObjectRef};
 use sui_types::dynamic_field::{DynamicFieldInfo, DynamicFieldName};
 use sui_types::error::{SuiError, SuiResult};
 use sui_types::fp_ensure;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use sui_types::object::Owner;
 use sui_types::query::TransactionQuery;
 
@@ -29,11 +31,22 @@ use crate::default_db_options;
 
 type OwnerIndexKey = (SuiAddress, ObjectID);
 type DynamicFieldKey = (ObjectID, ObjectID);
+type TransactionIter<'a> =
+    Box<dyn Iterator<Item = SuiResult<(TxSequenceNumber, TransactionDigest)>> + 'a>;
 
 pub const MAX_TX_RANGE_SIZE: u64 = 4096;
 
+/// Maximum number of criteria in a composite transaction query.
+pub const TX_QUERY_MAX_CRITERIA: usize = 100;
+
+/// Maximum number of checkpoints a `Checkpoint` criterion can span when it is not combined
+/// with a more selective criterion, as the transactions of those checkpoints are loaded in
+/// memory to be sorted.
+pub const MAX_CHECKPOINT_RANGE_SIZE: u64 = 1024;
+
 pub const MAX_GET_OWNED_OBJECT_SIZE: usize = 256;
 
+#[derive(Default)]
 pub struct ObjectIndexChanges {
     pub deleted_owners: Vec<OwnerIndexKey>,
     pub deleted_dynamic_fields: Vec<DynamicFieldKey>,
@@ -99,6 +112,21 @@ pub struct IndexStore {
     tables: IndexStoreTables,
 }
 
+/// Maps transactions to the checkpoints that include them. Checkpoints are tracked by the
+/// authority store rather than the IndexStore, and are needed to serve
+/// [TransactionQuery::Checkpoint] criteria.
+pub trait TransactionCheckpointReader {
+    fn get_checkpoint_of_transaction(
+        &self,
+        digest: &TransactionDigest,
+    ) -> SuiResult<Option<CheckpointSequenceNumber>>;
+
+    fn get_transactions_of_checkpoint(
+        &self,
+        sequence_number: CheckpointSequenceNumber,
+    ) -> SuiResult<Vec<TransactionDigest>>;
+}
+
 // These functions are used to initialize the DB tables
 fn transactions_order_table_default_config() -> DBOptions {
     default_db_options(None, Some(1_000_000)).0
@@ -239,6 +267,72 @@ impl IndexStore {
         Ok(sequence)
     }
 
+    /// Removes a transaction from every transaction index, given the same arguments it was
+    /// indexed with. The owner and dynamic field indexes are left untouched, as they track the
+    /// latest state of objects rather than transactions. Does nothing if the transaction isn't
+    /// indexed, e.g. because it has already been pruned.
+    pub fn prune_tx(
+        &self,
+        sender: SuiAddress,
+        active_inputs: impl Iterator<Item = ObjectID>,
+        mutated_objects: impl Iterator<Item = (ObjectRef, Owner)> + Clone,
+        move_functions: impl Iterator<Item = (ObjectID, Identifier, Identifier)>,
+        digest: &TransactionDigest,
+    ) -> SuiResult {
+        let Some(sequence) = self.tables.transactions_seq.get(digest)? else {
+            return Ok(());
+        };
+
+        let batch = self.tables.transactions_from_addr.batch();
+
+        let batch =
+            batch.delete_batch(&self.tables.transaction_order, std::iter::once(sequence))?;
+
+        let batch = batch.delete_batch(&self.tables.transactions_seq, std::iter::once(*digest))?;
+
+        let batch = batch.delete_batch(
+            &self.tables.transactions_from_addr,
+            std::iter::once((sender, sequence)),
+        )?;
+
+        let batch = batch.delete_batch(
+            &self.tables.transactions_by_input_object_id,
+            active_inputs.map(|id| (id, sequence)),
+        )?;
+
+        let batch = batch.delete_batch(
+            &self.tables.transactions_by_mutated_object_id,
+            mutated_objects
+                .clone()
+                .map(|(obj_ref, _)| (obj_ref.0, sequence)),
+        )?;
+
+        let batch = batch.delete_batch(
+            &self.tables.transactions_by_move_function,
+            move_functions.map(|(obj_id, module, function)| {
+                (obj_id, module.to_string(), function.to_string(), sequence)
+            }),
+        )?;
+
+        let batch = batch.delete_batch(
+            &self.tables.transactions_to_addr,
+            mutated_objects.filter_map(|(_, owner)| {
+                owner.get_owner_address().ok().map(|addr| (addr, sequence))
+            }),
+        )?;
+
+        let batch = batch.delete_batch(&self.tables.timestamps, std::iter::once(*digest))?;
+
+        batch.write()?;
+
+        Ok(())
+    }
+
+    /// Returns the most recently indexed transaction.
+    pub fn get_last_transaction(&self) -> SuiResult<Option<(TxSequenceNumber, TransactionDigest)>> {
+        Ok(self.tables.transaction_order.iter().skip_to_last().next())
+    }
+
     pub fn next_sequence_number(&self) -> TxSequenceNumber {
         self.next_sequence_number.load(Ordering::SeqCst) + 1
     }
@@ -246,6 +340,7 @@ impl IndexStore {
     pub fn get_transactions(
         &self,
         query: TransactionQuery,
+        checkpoints: &dyn TransactionCheckpointReader,
         cursor: Option<TransactionDigest>,
         limit: Option<usize>,
         reverse: bool,
@@ -281,6 +376,11 @@ impl IndexStore {
             TransactionQuery::ToAddress(address) => {
                 self.get_transactions_to_addr(address, cursor, limit, reverse)?
             }
+            TransactionQuery::Checkpoint { .. }
+            | TransactionQuery::MatchAll(_)
+            | TransactionQuery::MatchAny(_) => {
+                self.get_transactions_by_query(&query, checkpoints, cursor, limit, reverse)?
+            }
             TransactionQuery::All => {
                 let iter = self.tables.transaction_order.iter();
 
@@ -306,6 +406,365 @@ impl IndexStore {
         })
     }
 
+    /// Serves composite and checkpoint queries. Each criterion is streamed from its index in
+    /// transaction order, intersections are driven by their most selective criterion and
+    /// filtered with point lookups on the other ones, and unions are merged.
+    pub fn get_transactions_by_query(
+        &self,
+        query: &TransactionQuery,
+        checkpoints: &dyn TransactionCheckpointReader,
+        cursor: TxSequenceNumber,
+        limit: Option<usize>,
+        reverse: bool,
+    ) -> SuiResult<Vec<TransactionDigest>> {
+        Self::validate_transaction_query(query)?;
+        let query = self.resolve_query(query)?;
+        let iter = self
+            .query_iter(&query, checkpoints, cursor, reverse)?
+            .map(|res| res.map(|(_, digest)| digest));
+        if let Some(limit) = limit {
+            iter.take(limit).collect()
+        } else {
+            iter.collect()
+        }
+    }
+
+    /// Checks `query` against the limits of transaction queries, so that other stores answering
+    /// them reject the same queries.
+    pub fn validate_transaction_query(query: &TransactionQuery) -> SuiResult {
+        Self::validate_query(query, &mut 0)
+    }
+
+    fn validate_query(query: &TransactionQuery, criteria: &mut usize) -> SuiResult {
+        *criteria += 1;
+        fp_ensure!(
+            *criteria <= TX_QUERY_MAX_CRITERIA,
+            SuiError::InvalidTransactionQuery {
+                error: format!("A query can have at most {TX_QUERY_MAX_CRITERIA} criteria"),
+            }
+        );
+        match query {
+            TransactionQuery::Checkpoint { start, end } => fp_ensure!(
+                start <= end,
+                SuiError::InvalidTransactionQuery {
+                    error: format!(
+                        "Checkpoint range start must not exceed end, (start={start}, end={end}) given"
+                    ),
+                }
+            ),
+            TransactionQuery::MatchAll(queries) | TransactionQuery::MatchAny(queries) => {
+                for query in queries {
+                    Self::validate_query(query, criteria)?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Replaces Move function criteria without a module or function with the union of the
+    /// matching functions found in the index, whose transactions can be streamed in order.
+    fn resolve_query(&self, query: &TransactionQuery) -> SuiResult<TransactionQuery> {
+        Ok(match query {
+            TransactionQuery::MoveFunction {
+                package,
+                module,
+                function,
+            } if module.is_none() || function.is_none() => TransactionQuery::MatchAny(
+                self.get_move_functions(*package, module.as_deref(), function.as_deref())?
+                    .into_iter()
+                    .map(|(module, function)| TransactionQuery::MoveFunction {
+                        package: *package,
+                        module: Some(module),
+                        function: Some(function),
+                    })
+                    .collect(),
+            ),
+            TransactionQuery::MatchAll(queries) => TransactionQuery::MatchAll(
+                queries
+                    .iter()
+                    .map(|query| self.resolve_query(query))
+                    .collect::<SuiResult<_>>()?,
+            ),
+            TransactionQuery::MatchAny(queries) => TransactionQuery::MatchAny(
+                queries
+                    .iter()
+                    .map(|query| self.resolve_query(query))
+                    .collect::<SuiResult<_>>()?,
+            ),
+            query => query.clone(),
+        })
+    }
+
+    /// Returns the (module, function) pairs of `package` with indexed transactions.
+    fn get_move_functions(
+        &self,
+        package: ObjectID,
+        module: Option<&str>,
+        function: Option<&str>,
+    ) -> SuiResult<Vec<(String, String)>> {
+        let mut functions = vec![];
+        let mut key = (
+            package,
+            module.unwrap_or_default().to_string(),
+            String::new(),
+            TxSequenceNumber::MIN,
+        );
+        while let Some(((id, m, f, _), _)) = self
+            .tables
+            .transactions_by_move_function
+            .iter()
+            .skip_to(&key)?
+            .next()
+        {
+            if id != package || module.map_or(false, |module| module != m) {
+                break;
+            }
+            if function.map_or(true, |function| function == f) {
+                functions.push((m.clone(), f.clone()));
+            }
+            // Skip the remaining transactions of this function.
+            key = (package, m, f, TxSequenceNumber::MAX);
+        }
+        Ok(functions)
+    }
+
+    /// Whether a criterion of an intersection is expected to match few transactions, and
+    /// can therefore drive it.
+    fn is_selective(query: &TransactionQuery) -> bool {
+        match query {
+            TransactionQuery::All | TransactionQuery::Checkpoint { .. } => false,
+            TransactionQuery::MatchAll(queries) => queries.iter().any(Self::is_selective),
+            TransactionQuery::MatchAny(queries) => queries.iter().all(Self::is_selective),
+            _ => true,
+        }
+    }
+
+    fn query_iter<'a>(
+        &'a self,
+        query: &TransactionQuery,
+        checkpoints: &'a dyn TransactionCheckpointReader,
+        cursor: TxSequenceNumber,
+        reverse: bool,
+    ) -> SuiResult<TransactionIter<'a>> {
+        Ok(match query {
+            TransactionQuery::All => {
+                let iter = self.tables.transaction_order.iter();
+                if reverse {
+                    Box::new(iter.skip_prior_to(&cursor)?.reverse().map(Ok))
+                } else {
+                    Box::new(iter.skip_to(&cursor)?.map(Ok))
+                }
+            }
+            TransactionQuery::MoveFunction {
+                package,
+                module,
+                function,
+            } => {
+                let package = *package;
+                let module = module.clone().unwrap_or_default();
+                let function = function.clone().unwrap_or_default();
+                let key = (package, module.clone(), function.clone(), cursor);
+                let iter = self.tables.transactions_by_move_function.iter();
+                if reverse {
+                    Box::new(
+                        iter.skip_prior_to(&key)?
+                            .reverse()
+                            .take_while(move |((id, m, f, _), _)| {
+                                *id == package && *m == module && *f == function
+                            })
+                            .map(|((_, _, _, seq), digest)| Ok((seq, digest))),
+                    )
+                } else {
+                    Box::new(
+                        iter.skip_to(&key)?
+                            .take_while(move |((id, m, f, _), _)| {
+                                *id == package && *m == module && *f == function
+                            })
+                            .map(|((_, _, _, seq), digest)| Ok((seq, digest))),
+                    )
+                }
+            }
+            TransactionQuery::InputObject(object_id) => Self::index_iter(
+                &self.tables.transactions_by_input_object_id,
+                *object_id,
+                cursor,
+                reverse,
+            )?,
+            TransactionQuery::MutatedObject(object_id) => Self::index_iter(
+                &self.tables.transactions_by_mutated_object_id,
+                *object_id,
+                cursor,
+                reverse,
+            )?,
+            TransactionQuery::FromAddress(address) => Self::index_iter(
+                &self.tables.transactions_from_addr,
+                *address,
+                cursor,
+                reverse,
+            )?,
+            TransactionQuery::ToAddress(address) => {
+                Self::index_iter(&self.tables.transactions_to_addr, *address, cursor, reverse)?
+            }
+            TransactionQuery::Checkpoint { start, end } => {
+                fp_ensure!(
+                    end - start < MAX_CHECKPOINT_RANGE_SIZE,
+                    SuiError::InvalidTransactionQuery {
+                        error: format!(
+                            "Checkpoint ranges must not span more than {MAX_CHECKPOINT_RANGE_SIZE} checkpoints unless combined with another criterion, (start={start}, end={end}) given"
+                        ),
+                    }
+                );
+                let mut digests = vec![];
+                for checkpoint in *start..=*end {
+                    digests.extend(checkpoints.get_transactions_of_checkpoint(checkpoint)?);
+                }
+                let mut transactions = self
+                    .tables
+                    .transactions_seq
+                    .multi_get(&digests)?
+                    .into_iter()
+                    .zip(digests)
+                    // Transactions executed but not yet indexed are skipped.
+                    .filter_map(|(seq, digest)| seq.map(|seq| (seq, digest)))
+                    .filter(|(seq, _)| {
+                        if reverse {
+                            *seq <= cursor
+                        } else {
+                            *seq >= cursor
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                transactions.sort_by_key(|(seq, _)| *seq);
+                if reverse {
+                    transactions.reverse();
+                }
+                Box::new(transactions.into_iter().map(Ok))
+            }
+            TransactionQuery::MatchAll(queries) => {
+                let driver = queries.iter().position(Self::is_selective).or_else(|| {
+                    queries
+                        .iter()
+                        .position(|query| !matches!(query, TransactionQuery::All))
+                });
+                let Some(driver) = driver else {
+                    return self.query_iter(&TransactionQuery::All, checkpoints, cursor, reverse);
+                };
+                let mut filters = queries.clone();
+                let driver = filters.remove(driver);
+                Box::new(
+                    self.query_iter(&driver, checkpoints, cursor, reverse)?
+                        .filter_map(move |res| {
+                            res.and_then(|(seq, digest)| {
+                                for query in &filters {
+                                    if !self.matches(query, checkpoints, seq, &digest)? {
+                                        return Ok(None);
+                                    }
+                                }
+                                Ok(Some((seq, digest)))
+                            })
+                            .transpose()
+                        }),
+                )
+            }
+            TransactionQuery::MatchAny(queries) => Box::new(MergedTransactions {
+                iters: queries
+                    .iter()
+                    .map(|query| {
+                        Ok(self
+                            .query_iter(query, checkpoints, cursor, reverse)?
+                            .peekable())
+                    })
+                    .collect::<SuiResult<_>>()?,
+                reverse,
+            }),
+        })
+    }
+
+    fn index_iter<'a, KeyT: Clone + Serialize + DeserializeOwned + PartialEq + 'a>(
+        index: &'a DBMap<(KeyT, TxSequenceNumber), TransactionDigest>,
+        key: KeyT,
+        cursor: TxSequenceNumber,
+        reverse: bool,
+    ) -> SuiResult<TransactionIter<'a>> {
+        Ok(if reverse {
+            Box::new(
+                index
+                    .iter()
+                    .skip_prior_to(&(key.clone(), cursor))?
+                    .reverse()
+                    .take_while(move |((id, _), _)| *id == key)
+                    .map(|((_, seq), digest)| Ok((seq, digest))),
+            )
+        } else {
+            Box::new(
+                index
+                    .iter()
+                    .skip_to(&(key.clone(), cursor))?
+                    .take_while(move |((id, _), _)| *id == key)
+                    .map(|((_, seq), digest)| Ok((seq, digest))),
+            )
+        })
+    }
+
+    /// Whether the transaction with sequence number `seq` matches a resolved query.
+    fn matches(
+        &self,
+        query: &TransactionQuery,
+        checkpoints: &dyn TransactionCheckpointReader,
+        seq: TxSequenceNumber,
+        digest: &TransactionDigest,
+    ) -> SuiResult<bool> {
+        Ok(match query {
+            TransactionQuery::All => true,
+            TransactionQuery::MoveFunction {
+                package,
+                module,
+                function,
+            } => self.tables.transactions_by_move_function.contains_key(&(
+                *package,
+                module.clone().unwrap_or_default(),
+                function.clone().unwrap_or_default(),
+                seq,
+            ))?,
+            TransactionQuery::InputObject(object_id) => self
+                .tables
+                .transactions_by_input_object_id
+                .contains_key(&(*object_id, seq))?,
+            TransactionQuery::MutatedObject(object_id) => self
+                .tables
+                .transactions_by_mutated_object_id
+                .contains_key(&(*object_id, seq))?,
+            TransactionQuery::FromAddress(address) => self
+                .tables
+                .transactions_from_addr
+                .contains_key(&(*address, seq))?,
+            TransactionQuery::ToAddress(address) => self
+                .tables
+                .transactions_to_addr
+                .contains_key(&(*address, seq))?,
+            TransactionQuery::Checkpoint { start, end } => checkpoints
+                .get_checkpoint_of_transaction(digest)?
+                .map_or(false, |checkpoint| (*start..=*end).contains(&checkpoint)),
+            TransactionQuery::MatchAll(queries) => {
+                for query in queries {
+                    if !self.matches(query, checkpoints, seq, digest)? {
+                        return Ok(false);
+                    }
+                }
+                true
+            }
+            TransactionQuery::MatchAny(queries) => {
+                for query in queries {
+                    if self.matches(query, checkpoints, seq, digest)? {
+                        return Ok(true);
+                    }
+                }
+                false
+            }
+        })
+    }
+
     pub fn get_transactions_in_range(
         &self,
         start: TxSequenceNumber,
@@ -616,3 +1075,38 @@ impl IndexStore {
         self.tables.owner_index.is_empty()
     }
 }
+
+/// Merges streams of transactions ordered by sequence number into a single ordered stream,
+/// without duplicates.
+struct MergedTransactions<'a> {
+    iters: Vec<Peekable<TransactionIter<'a>>>,
+    reverse: bool,
+}
+
+impl Iterator for MergedTransactions<'_> {
+    type Item = SuiResult<(TxSequenceNumber, TransactionDigest)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let reverse = self.reverse;
+        let mut next: Option<TxSequenceNumber> = None;
+        for iter in &mut self.iters {
+            match iter.peek() {
+                Some(Err(_)) => return iter.next(),
+                Some(Ok((seq, _))) => {
+                    if next.map_or(true, |next| if reverse { *seq > next } else { *seq < next }) {
+                        next = Some(*seq);
+                    }
+                }
+                None => {}
+            }
+        }
+        let next = next?;
+        let mut item = None;
+        for iter in &mut self.iters {
+            if matches!(iter.peek(), Some(Ok((seq, _))) if *seq == next) {
+                item = iter.next();
+            }
+        }
+        item
+    }
+}
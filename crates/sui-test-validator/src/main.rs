@@ -15,7 +15,7 @@ use sui_cluster_test::{
     config::{ClusterTestOpt, Env},
     faucet::{FaucetClient, FaucetClientFactory},
 };
-use sui_faucet::{FaucetRequest, FixedAmountRequest};
+use sui_faucet::{CoinRequest, FaucetError, FaucetRequest, FaucetResponse, FixedAmountRequest};
 use tower::ServiceBuilder;
 use tower_http::cors::{Any, CorsLayer};
 
@@ -106,6 +106,9 @@ async fn faucet_request(
         FaucetRequest::FixedAmountRequest(FixedAmountRequest { recipient }) => {
             state.faucet.request_sui_coins(recipient).await
         }
+        FaucetRequest::CoinRequest(CoinRequest { coin_type, .. }) => {
+            FaucetResponse::from(FaucetError::UnsupportedCoinType(coin_type))
+        }
     };
 
     if !result.transferred_gas_objects.is_empty() {
@@ -3,13 +3,20 @@
 
 use crate::{
     db_tool::{execute_db_tool_command, print_db_all_tables, DbToolCommand},
-    get_object, get_transaction, make_clients, ConciseObjectOutput, GroupedObjectOutput,
-    VerboseObjectOutput,
+    get_object, get_transaction,
+    index_rebuild::rebuild_index,
+    make_clients,
+    protocol_config_diff::diff_protocol_configs,
+    ConciseObjectOutput, GroupedObjectOutput, VerboseObjectOutput,
 };
-use anyhow::Result;
+use anyhow::{anyhow, Result};
+use fastcrypto::encoding::{Encoding, Hex};
+use fastcrypto::hash::Digest;
 use std::path::PathBuf;
 use sui_config::genesis::Genesis;
+use sui_config::{Config, NodeConfig};
 use sui_core::authority_client::AuthorityAPI;
+use sui_core::state_snapshot::restore_state_snapshot;
 
 use sui_types::{base_types::*, object::Owner};
 
@@ -97,6 +104,60 @@ pub enum ToolCommand {
         cmd: Option<DbToolCommand>,
     },
 
+    /// Rebuild the index store of a fullnode from its authority store and checkpoints, e.g. to
+    /// enable indexing without resyncing. The node must be stopped. The index store is built in a
+    /// new directory, which replaces the node's `indexes` once complete. Rerunning the command
+    /// after an interruption resumes from the last indexed checkpoint.
+    #[clap(name = "rebuild-index")]
+    RebuildIndex {
+        /// Path of the node's DB, containing the `store`, `checkpoints` and `epochs` DBs
+        #[clap(long = "db-path")]
+        db_path: PathBuf,
+
+        #[clap(long = "genesis")]
+        genesis: PathBuf,
+
+        /// Path of the index store to build, which must not exist yet unless it is resumed.
+        /// Defaults to `indexes.new` in the node's DB
+        #[clap(long = "index-path")]
+        index_path: Option<PathBuf>,
+    },
+
+    /// Bootstrap the DB of a stopped node from a state snapshot written at the end of an epoch.
+    /// The node's DB must not exist yet. Run `rebuild-index` afterwards on fullnodes that serve
+    /// JSON-RPC.
+    #[clap(name = "restore-snapshot")]
+    RestoreSnapshot {
+        /// Path of the node's config, which provides its DB path and genesis
+        #[clap(long = "config-path")]
+        config_path: PathBuf,
+
+        /// Path of the snapshot directory, containing the `MANIFEST` and `chunks`
+        #[clap(long = "snapshot-path")]
+        snapshot_path: PathBuf,
+
+        /// Hex encoded digest of the root state hash of the snapshot epoch, obtained from a
+        /// trusted source. Required while checkpoints don't commit to the root state hash.
+        #[clap(long = "root-state-digest")]
+        root_state_digest: Option<String>,
+    },
+
+    /// Print the protocol constants that differ between two protocol versions
+    #[clap(name = "protocol-config-diff")]
+    ProtocolConfigDiff {
+        /// The older protocol version
+        #[clap(long)]
+        from: u64,
+
+        /// The newer protocol version
+        #[clap(long)]
+        to: u64,
+
+        /// Print the diff as JSON
+        #[clap(long)]
+        json: bool,
+    },
+
     #[clap(name = "dump-validators")]
     DumpValidators {
         #[clap(long = "genesis")]
@@ -225,6 +286,46 @@ impl ToolCommand {
                     None => print_db_all_tables(path)?,
                 }
             }
+            ToolCommand::RebuildIndex {
+                db_path,
+                genesis,
+                index_path,
+            } => {
+                let genesis = Genesis::load(genesis)?;
+                let index_path = index_path.unwrap_or_else(|| db_path.join("indexes.new"));
+                rebuild_index(&db_path, &genesis, &index_path).await?;
+            }
+            ToolCommand::RestoreSnapshot {
+                config_path,
+                snapshot_path,
+                root_state_digest,
+            } => {
+                let config = NodeConfig::load(&config_path)?;
+                let root_state_digest = root_state_digest
+                    .map(|digest| -> Result<Digest<32>> {
+                        let digest = Hex::decode(&digest)
+                            .map_err(|e| anyhow!("Invalid root state digest: {e}"))?
+                            .try_into()
+                            .map_err(|_| anyhow!("Root state digest must be 32 bytes long"))?;
+                        Ok(Digest { digest })
+                    })
+                    .transpose()?;
+                restore_state_snapshot(
+                    &snapshot_path,
+                    config.db_path(),
+                    config.genesis()?,
+                    config.protocol_public_key(),
+                    root_state_digest.as_ref(),
+                )?;
+            }
+            ToolCommand::ProtocolConfigDiff { from, to, json } => {
+                let diff = diff_protocol_configs(from, to)?;
+                if json {
+                    println!("{}", serde_json::to_string_pretty(&diff)?);
+                } else {
+                    println!("{diff}");
+                }
+            }
             ToolCommand::DumpValidators { genesis, concise } => {
                 let genesis = Genesis::load(genesis).unwrap();
                 if !concise {
@@ -0,0 +1,398 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Offline rebuild of the fullnode `IndexStore` from the authority store and checkpoints of a
+//! stopped node.
+//!
+//! Transactions are indexed in checkpoint order, timestamped with their checkpoint. The owner
+//! and dynamic field indexes are then built from the live object set rather than replayed, so
+//! that pruned object versions are not needed.
+
+use anyhow::{anyhow, bail};
+use itertools::Itertools;
+use move_bytecode_utils::module_cache::SyncModuleCache;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use sui_config::genesis::Genesis;
+use sui_core::authority::{live_object_index_changes, AuthorityStore, ResolverWrapper};
+use sui_core::checkpoints::CheckpointStore;
+use sui_core::epoch::committee_store::CommitteeStore;
+use sui_storage::indexes::ObjectIndexChanges;
+use sui_storage::IndexStore;
+use sui_types::base_types::TransactionDigest;
+
+/// How often progress is reported.
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Number of live objects written to the owner and dynamic field indexes in one batch.
+const OBJECT_BATCH_SIZE: usize = 10_000;
+
+/// Marks an index store that is being built by this command, and can be resumed. It is removed
+/// once the index store is complete.
+const REBUILD_MARKER: &str = "REBUILD_IN_PROGRESS";
+
+/// Rebuilds the index store at `index_path` from the node DB at `db_path`. The index store must
+/// not exist yet, so that no stale entries of another index store are kept, unless it was
+/// partially built by a previous run, in which case indexing resumes from its last checkpoint.
+pub async fn rebuild_index(
+    db_path: &Path,
+    genesis: &Genesis,
+    index_path: &Path,
+) -> anyhow::Result<()> {
+    let (store, checkpoint_store) = open_node_db(db_path, genesis).await?;
+    let marker = prepare_index_path(index_path)?;
+    let indexes = IndexStore::new(index_path.to_path_buf());
+
+    index_transactions(&store, &checkpoint_store, &indexes)?;
+    index_live_objects(&store, &indexes)?;
+    fs::remove_file(marker)?;
+    println!("Index store at {index_path:?} is complete");
+    Ok(())
+}
+
+/// Opens the authority store and the checkpoint store of the node DB at `db_path`.
+async fn open_node_db(
+    db_path: &Path,
+    genesis: &Genesis,
+) -> anyhow::Result<(Arc<AuthorityStore>, Arc<CheckpointStore>)> {
+    let store_path = db_path.join("store");
+    // Opening an empty authority store would initialize it from genesis.
+    if !store_path.exists() {
+        bail!("Authority store not found at {store_path:?}");
+    }
+    let committee_store = Arc::new(CommitteeStore::new(
+        db_path.join("epochs"),
+        &genesis.committee()?,
+        None,
+    ));
+    let store = Arc::new(AuthorityStore::open(&store_path, None, genesis, &committee_store).await?);
+    let checkpoint_store = CheckpointStore::new(&db_path.join("checkpoints"));
+    Ok((store, checkpoint_store))
+}
+
+/// Creates the directory of a new index store with the rebuild marker, or checks that an
+/// existing one is an index store left by an interrupted rebuild. Returns the marker's path.
+fn prepare_index_path(index_path: &Path) -> anyhow::Result<PathBuf> {
+    let marker = index_path.join(REBUILD_MARKER);
+    if marker.exists() {
+        println!("Resuming the rebuild of the index store at {index_path:?}");
+        return Ok(marker);
+    }
+    if index_path.exists() && fs::read_dir(index_path)?.next().is_some() {
+        bail!("{index_path:?} is not empty and is not an interrupted rebuild, pass the path of a new index store");
+    }
+    fs::create_dir_all(index_path)?;
+    fs::write(&marker, [])?;
+    Ok(marker)
+}
+
+fn index_transactions(
+    store: &AuthorityStore,
+    checkpoint_store: &CheckpointStore,
+    indexes: &IndexStore,
+) -> anyhow::Result<()> {
+    let highest = checkpoint_store
+        .get_highest_executed_checkpoint_seq_number()?
+        .ok_or_else(|| anyhow!("The node has not executed any checkpoint"))?;
+    // Resume from the checkpoint of the last indexed transaction, as the other transactions of
+    // that checkpoint may not have been indexed yet.
+    let start = match indexes.get_last_transaction()? {
+        Some((_, digest)) => store
+            .get_transaction_checkpoint(&digest)?
+            .map(|(_, checkpoint)| checkpoint)
+            .ok_or_else(|| {
+                anyhow!("Indexed transaction [{digest}] is not in any executed checkpoint, the index store at the given path was not built by this command")
+            })?,
+        // A node restored from a state snapshot only has the checkpoints after it.
+        None => checkpoint_store
+            .get_highest_pruned_checkpoint_seq_number()?
+            .map_or(0, |pruned| pruned + 1),
+    };
+
+    println!("Indexing transactions of checkpoints {start} to {highest}");
+    let mut indexed = 0;
+    let mut last_report = Instant::now();
+    for sequence_number in start..=highest {
+        let checkpoint = checkpoint_store
+            .get_checkpoint_by_sequence_number(sequence_number)?
+            .ok_or_else(|| anyhow!("Checkpoint {sequence_number} not found"))?;
+        let contents = checkpoint_store
+            .get_checkpoint_contents(&checkpoint.content_digest())?
+            .ok_or_else(|| anyhow!("Contents of checkpoint {sequence_number} not found"))?;
+        for digests in contents.iter() {
+            if indexes.get_transaction_seq(&digests.transaction)?.is_some() {
+                continue;
+            }
+            index_transaction(
+                store,
+                indexes,
+                &digests.transaction,
+                checkpoint.summary.timestamp_ms,
+            )?;
+            indexed += 1;
+        }
+        if last_report.elapsed() >= PROGRESS_INTERVAL || sequence_number == highest {
+            println!("Indexed checkpoint {sequence_number}/{highest}, {indexed} transactions");
+            last_report = Instant::now();
+        }
+    }
+    Ok(())
+}
+
+fn index_transaction(
+    store: &AuthorityStore,
+    indexes: &IndexStore,
+    digest: &TransactionDigest,
+    timestamp_ms: u64,
+) -> anyhow::Result<()> {
+    let transaction = store
+        .get_transaction(digest)?
+        .ok_or_else(|| anyhow!("Transaction [{digest}] not found"))?;
+    let effects = store
+        .get_executed_effects(digest)?
+        .ok_or_else(|| anyhow!("Effects of transaction [{digest}] not found"))?;
+    let data = &transaction.data().intent_message.value;
+    indexes.index_tx(
+        data.sender(),
+        data.input_objects()?.iter().map(|o| o.object_id()),
+        effects
+            .all_mutated()
+            .map(|(obj_ref, owner, _kind)| (*obj_ref, *owner)),
+        data.move_calls()
+            .iter()
+            .map(|mc| (mc.package, mc.module.clone(), mc.function.clone())),
+        // The owner and dynamic field indexes are built from the live object set instead.
+        ObjectIndexChanges::default(),
+        digest,
+        timestamp_ms,
+    )?;
+    Ok(())
+}
+
+fn index_live_objects(store: &Arc<AuthorityStore>, indexes: &IndexStore) -> anyhow::Result<()> {
+    println!("Indexing live objects");
+    let resolver = SyncModuleCache::new(ResolverWrapper(store.clone()));
+    let mut indexed = 0;
+    let mut last_report = Instant::now();
+    for object_refs in &store.iter_live_object_set().chunks(OBJECT_BATCH_SIZE) {
+        let objects = object_refs
+            .map(|(id, version, _)| {
+                store
+                    .get_object_by_key(&id, version)?
+                    .ok_or_else(|| anyhow!("Live object [{id}] at version [{version}] not found"))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        indexes.insert_genesis_objects(live_object_index_changes(store, &resolver, &objects)?)?;
+        indexed += objects.len();
+        if last_report.elapsed() >= PROGRESS_INTERVAL {
+            println!("Indexed {indexed} live objects");
+            last_report = Instant::now();
+        }
+    }
+    println!("Indexed {indexed} live objects");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        index_transaction, open_node_db, prepare_index_path, rebuild_index, REBUILD_MARKER,
+    };
+    use fastcrypto::traits::KeypairTraits;
+    use std::path::Path;
+    use sui_config::NetworkConfig;
+    use sui_core::authority::AuthorityState;
+    use sui_core::checkpoints::CheckpointStore;
+    use sui_storage::IndexStore;
+    use sui_types::base_types::{dbg_addr, ExecutionDigests, SuiAddress};
+    use sui_types::gas::GasCostSummary;
+    use sui_types::messages::{CertifiedTransaction, TransactionData};
+    use sui_types::messages_checkpoint::{
+        CertifiedCheckpointSummary, CheckpointContents, SignedCheckpointSummary, VerifiedCheckpoint,
+    };
+    use sui_types::object::Owner;
+    use sui_types::query::TransactionQuery;
+    use sui_types::utils::to_sender_signed_transaction;
+
+    /// The checkpoints written by `write_checkpoints`, with the range of the executed
+    /// transactions each of them holds.
+    const CHECKPOINTS: [(u64, std::ops::Range<usize>); 2] = [(1, 0..2), (2, 2..3)];
+
+    /// Executes transfers of the coins of the first account of the network, and returns the
+    /// digests of their transactions and effects, in execution order.
+    async fn execute_transfers(
+        network_config: &NetworkConfig,
+        state: &AuthorityState,
+        recipient: SuiAddress,
+    ) -> Vec<ExecutionDigests> {
+        let sender_key = &network_config.account_keys[0];
+        let sender: SuiAddress = sender_key.public().into();
+        let mut coins = network_config
+            .genesis
+            .objects()
+            .iter()
+            .filter(|o| o.owner == Owner::AddressOwner(sender))
+            .map(|o| o.compute_object_reference());
+        let mut gas = coins.next().unwrap();
+        let committee = state.clone_committee_for_testing();
+
+        let mut digests = vec![];
+        for coin in coins.take(3) {
+            let data = TransactionData::new_transfer_with_dummy_gas_price(
+                recipient, coin, sender, gas, 10000,
+            );
+            let transaction = to_sender_signed_transaction(data, sender_key);
+            let vote = state
+                .handle_transaction(transaction.clone())
+                .await
+                .unwrap()
+                .status
+                .into_signed_for_testing();
+            let certificate =
+                CertifiedTransaction::new(transaction.into_message(), vec![vote], &committee)
+                    .unwrap()
+                    .verify(&committee)
+                    .unwrap();
+            let effects = state
+                .try_execute_for_test(&certificate)
+                .await
+                .unwrap()
+                .into_message();
+            gas = effects.gas_object.0;
+            digests.push(ExecutionDigests::new(
+                effects.transaction_digest,
+                effects.digest(),
+            ));
+        }
+        digests
+    }
+
+    /// Writes checkpoints 1 and 2 to the checkpoint store of the node DB at `db_path`, holding
+    /// the first two and the last of `digests`, and marks them executed. The genesis checkpoint
+    /// is marked pruned, as its transaction is not executed by the test state.
+    fn write_checkpoints(
+        db_path: &Path,
+        network_config: &NetworkConfig,
+        digests: &[ExecutionDigests],
+    ) {
+        let genesis = &network_config.genesis;
+        let committee = genesis.committee().unwrap();
+        let key = network_config.validator_configs[0].protocol_key_pair();
+        let checkpoint_store = CheckpointStore::new(&db_path.join("checkpoints"));
+        checkpoint_store
+            .insert_verified_checkpoint(genesis.checkpoint())
+            .unwrap();
+        checkpoint_store
+            .update_highest_pruned_checkpoint(&genesis.checkpoint())
+            .unwrap();
+
+        let mut previous = genesis.checkpoint();
+        for (sequence_number, digests) in CHECKPOINTS.map(|(s, range)| (s, &digests[range])) {
+            let contents =
+                CheckpointContents::new_with_causally_ordered_transactions(digests.iter().cloned());
+            let signed = SignedCheckpointSummary::new(
+                0,
+                sequence_number,
+                previous.summary.network_total_transactions + digests.len() as u64,
+                key.public().into(),
+                key,
+                &contents,
+                Some(previous.digest()),
+                GasCostSummary::default(),
+                None,
+                sequence_number * 1000,
+            );
+            let checkpoint = VerifiedCheckpoint::new_unchecked(
+                CertifiedCheckpointSummary::aggregate(vec![signed], &committee).unwrap(),
+            );
+            checkpoint_store
+                .insert_checkpoint_contents(contents)
+                .unwrap();
+            checkpoint_store
+                .insert_verified_checkpoint(checkpoint.clone())
+                .unwrap();
+            checkpoint_store
+                .update_highest_executed_checkpoint(&checkpoint)
+                .unwrap();
+            previous = checkpoint;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rebuild_index_resumes_and_matches_live_index() {
+        let dir = tempfile::tempdir().unwrap();
+        let network_config = sui_config::builder::ConfigBuilder::new(&dir).build();
+        let genesis = &network_config.genesis;
+        let keypair = network_config.validator_configs[0].protocol_key_pair();
+
+        // A node which indexes the transactions it executes
+        let live_path = dir.path().join("live");
+        std::fs::create_dir_all(&live_path).unwrap();
+        let state = AuthorityState::new_for_testing(
+            genesis.committee().unwrap(),
+            keypair,
+            Some(live_path),
+            genesis,
+        )
+        .await;
+        let recipient = dbg_addr(2);
+        let digests = execute_transfers(&network_config, &state, recipient).await;
+        for (sequence_number, range) in CHECKPOINTS {
+            let transactions: Vec<_> = digests[range].iter().map(|d| d.transaction).collect();
+            state
+                .database
+                .insert_finalized_transactions(&transactions, 0, sequence_number)
+                .unwrap();
+        }
+
+        // The node is still running, so the rebuild reads a copy of its DB
+        let db_path = dir.path().join("db");
+        state.checkpoint_db(&db_path).unwrap();
+        write_checkpoints(&db_path, &network_config, &digests);
+
+        // A rebuild interrupted after indexing the first transaction of checkpoint 1
+        let index_path = db_path.join("indexes.new");
+        {
+            let (store, _checkpoint_store) = open_node_db(&db_path, genesis).await.unwrap();
+            prepare_index_path(&index_path).unwrap();
+            let indexes = IndexStore::new(index_path.clone());
+            index_transaction(&store, &indexes, &digests[0].transaction, 1000).unwrap();
+        }
+        assert!(index_path.join(REBUILD_MARKER).exists());
+
+        rebuild_index(&db_path, genesis, &index_path).await.unwrap();
+        assert!(!index_path.join(REBUILD_MARKER).exists());
+
+        let rebuilt = IndexStore::new(index_path);
+        let sender: SuiAddress = network_config.account_keys[0].public().into();
+        assert_eq!(
+            rebuilt.get_transactions_in_range(0, 10).unwrap(),
+            state.get_transactions_in_range(0, 10).unwrap()
+        );
+        assert_eq!(
+            rebuilt
+                .get_transactions_from_addr(sender, 0, None, false)
+                .unwrap(),
+            state
+                .get_transactions(TransactionQuery::FromAddress(sender), None, None, false)
+                .unwrap()
+        );
+        assert_eq!(
+            rebuilt
+                .get_transactions_to_addr(recipient, 0, None, false)
+                .unwrap(),
+            state
+                .get_transactions(TransactionQuery::ToAddress(recipient), None, None, false)
+                .unwrap()
+        );
+        for owner in [sender, recipient] {
+            assert_eq!(
+                rebuilt.get_owner_objects(owner).unwrap(),
+                state.get_owner_objects(owner).unwrap()
+            );
+        }
+        assert_eq!(rebuilt.get_transactions_in_range(0, 10).unwrap().len(), 3);
+    }
+}
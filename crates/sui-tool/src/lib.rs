@@ -20,6 +20,8 @@ use anyhow::anyhow;
 
 pub mod commands;
 pub mod db_tool;
+pub mod index_rebuild;
+pub mod protocol_config_diff;
 
 fn make_clients(
     genesis: PathBuf,
@@ -0,0 +1,198 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Structured diff of the protocol constants of two protocol versions, to review what a protocol
+//! upgrade changes.
+
+use anyhow::bail;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Display, Formatter};
+use sui_protocol_config::{ProtocolConfig, ProtocolVersion};
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    /// The constant is not defined in the older version.
+    Added,
+    /// The constant is not defined in the newer version.
+    Removed,
+    Modified,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct ProtocolConfigChange {
+    pub name: String,
+    pub kind: ChangeKind,
+    /// The value in the older version, null if the constant is not defined in it.
+    pub from: Value,
+    /// The value in the newer version, null if the constant is not defined in it.
+    pub to: Value,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct ProtocolConfigDiff {
+    pub from_version: u64,
+    pub to_version: u64,
+    /// Changed constants, sorted by name.
+    pub changes: Vec<ProtocolConfigChange>,
+    /// Number of constants with the same value in both versions.
+    pub unchanged: usize,
+}
+
+/// Compares every constant of the protocol configs of versions `from` and `to`.
+pub fn diff_protocol_configs(from: u64, to: u64) -> anyhow::Result<ProtocolConfigDiff> {
+    Ok(diff_attributes(
+        from,
+        to,
+        protocol_config_attributes(from)?,
+        protocol_config_attributes(to)?,
+    ))
+}
+
+/// Compares the constants of two protocol configs. A constant missing from a version, because
+/// the field is not in its encoding or is null, is not defined in it.
+fn diff_attributes(
+    from_version: u64,
+    to_version: u64,
+    mut from_attributes: BTreeMap<String, Value>,
+    mut to_attributes: BTreeMap<String, Value>,
+) -> ProtocolConfigDiff {
+    let names: BTreeSet<String> = from_attributes
+        .keys()
+        .chain(to_attributes.keys())
+        .cloned()
+        .collect();
+
+    let mut changes = vec![];
+    let mut unchanged = 0;
+    for name in names {
+        let from_value = from_attributes.remove(&name).unwrap_or(Value::Null);
+        let to_value = to_attributes.remove(&name).unwrap_or(Value::Null);
+        let kind = match (&from_value, &to_value) {
+            _ if from_value == to_value => {
+                unchanged += 1;
+                continue;
+            }
+            (Value::Null, _) => ChangeKind::Added,
+            (_, Value::Null) => ChangeKind::Removed,
+            _ => ChangeKind::Modified,
+        };
+        changes.push(ProtocolConfigChange {
+            name,
+            kind,
+            from: from_value,
+            to: to_value,
+        });
+    }
+
+    ProtocolConfigDiff {
+        from_version,
+        to_version,
+        changes,
+        unchanged,
+    }
+}
+
+fn protocol_config_attributes(version: u64) -> anyhow::Result<BTreeMap<String, Value>> {
+    let (min, max) = (ProtocolVersion::MIN.as_u64(), ProtocolVersion::MAX.as_u64());
+    if !(min..=max).contains(&version) {
+        bail!("Protocol version {version} is not supported by this binary, supported versions are {min}..={max}");
+    }
+    let config = ProtocolConfig::get_for_version(ProtocolVersion::new(version));
+    match serde_json::to_value(config)? {
+        Value::Object(attributes) => Ok(attributes.into_iter().collect()),
+        value => bail!("Unexpected protocol config encoding: {value}"),
+    }
+}
+
+impl Display for ProtocolConfigDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Protocol config changes from version {} to version {}:",
+            self.from_version, self.to_version
+        )?;
+        for change in &self.changes {
+            match change.kind {
+                ChangeKind::Added => writeln!(f, "+ {}: {}", change.name, change.to)?,
+                ChangeKind::Removed => writeln!(f, "- {}: {}", change.name, change.from)?,
+                ChangeKind::Modified => {
+                    writeln!(f, "~ {}: {} -> {}", change.name, change.from, change.to)?
+                }
+            }
+        }
+        write!(
+            f,
+            "{} changed, {} unchanged",
+            self.changes.len(),
+            self.unchanged
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{diff_attributes, diff_protocol_configs, ChangeKind, ProtocolConfigChange};
+    use serde_json::{json, Value};
+    use std::collections::BTreeMap;
+    use sui_protocol_config::ProtocolVersion;
+
+    fn attributes(values: &[(&str, Value)]) -> BTreeMap<String, Value> {
+        values
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_diff_added_removed_and_modified_constants() {
+        let from = attributes(&[
+            ("max_args", json!(512)),
+            ("max_gas", json!(1000)),
+            ("old_limit", json!(8)),
+            ("unset_in_both", Value::Null),
+        ]);
+        let to = attributes(&[
+            ("max_args", json!(512)),
+            ("max_gas", json!(2000)),
+            ("new_limit", json!(16)),
+            ("old_limit", Value::Null),
+        ]);
+        let diff = diff_attributes(1, 2, from, to);
+
+        let change = |name: &str, kind, from, to| ProtocolConfigChange {
+            name: name.to_string(),
+            kind,
+            from,
+            to,
+        };
+        assert_eq!(
+            diff.changes,
+            vec![
+                change("max_gas", ChangeKind::Modified, json!(1000), json!(2000)),
+                // Only present in the newer version
+                change("new_limit", ChangeKind::Added, Value::Null, json!(16)),
+                change("old_limit", ChangeKind::Removed, json!(8), Value::Null),
+            ]
+        );
+        assert_eq!(diff.unchanged, 2);
+        assert_eq!(
+            diff.to_string(),
+            "Protocol config changes from version 1 to version 2:\n\
+             ~ max_gas: 1000 -> 2000\n\
+             + new_limit: 16\n\
+             - old_limit: 8\n\
+             3 changed, 2 unchanged"
+        );
+    }
+
+    #[test]
+    fn test_diff_unsupported_version() {
+        let max = ProtocolVersion::MAX.as_u64();
+        assert!(diff_protocol_configs(max, max + 1).is_err());
+        let diff = diff_protocol_configs(max, max).unwrap();
+        assert!(diff.changes.is_empty());
+    }
+}
@@ -25,8 +25,8 @@ use sui_types::coin::{Coin, LockedCoin};
 use sui_types::error::UserInputError;
 use sui_types::gas_coin::GasCoin;
 use sui_types::messages::{
-    CallArg, InputObjectKind, MoveCall, ObjectArg, SingleTransactionKind, TransactionData,
-    TransactionKind, TransferObject,
+    CallArg, InputObjectKind, MoveCall, ObjectArg, ProgrammableTransaction, SingleTransactionKind,
+    TransactionData, TransactionKind, TransferObject,
 };
 
 use sui_types::governance::{
@@ -41,6 +41,8 @@ use sui_types::{
     SUI_SYSTEM_STATE_OBJECT_SHARED_VERSION,
 };
 
+pub mod programmable_transaction_builder;
+
 #[async_trait]
 pub trait DataReader {
     async fn get_objects_owned_by_address(
@@ -321,6 +323,11 @@ impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
         })
     }
 
+    /// Resolves `id` to an `ObjectArg`, using a mutable reference for shared objects.
+    pub async fn object_arg(&self, id: ObjectID) -> anyhow::Result<ObjectArg> {
+        self.get_object_arg(id, &mut BTreeMap::new()).await
+    }
+
     async fn get_object_arg(
         &self,
         id: ObjectID,
@@ -599,6 +606,33 @@ impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
         ))
     }
 
+    /// Wraps a `ProgrammableTransaction`, usually built with
+    /// [`programmable_transaction_builder::ProgrammableTransactionBuilder`], into
+    /// `TransactionData`, selecting a gas coin that is not one of its owned object inputs.
+    pub async fn programmable(
+        &self,
+        signer: SuiAddress,
+        pt: ProgrammableTransaction,
+        gas: Option<ObjectID>,
+        gas_budget: u64,
+    ) -> anyhow::Result<TransactionData> {
+        let input_objects = pt
+            .input_objects()?
+            .iter()
+            .flat_map(|obj| match obj {
+                InputObjectKind::ImmOrOwnedMoveObject((id, _, _)) => Some(*id),
+                _ => None,
+            })
+            .collect();
+        let gas_price = self.0.get_reference_gas_price().await?;
+        let gas = self
+            .select_gas(signer, gas, gas_budget, input_objects, gas_price)
+            .await?;
+        Ok(TransactionData::new_programmable(
+            signer, gas, pt, gas_budget, gas_price,
+        ))
+    }
+
     pub async fn request_add_delegation(
         &self,
         signer: SuiAddress,
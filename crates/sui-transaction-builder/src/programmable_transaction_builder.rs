@@ -0,0 +1,198 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A typed builder for `ProgrammableTransaction`s. Inputs are de-duplicated, and every
+//! `Argument` handed to a command is checked against the inputs and the commands added
+//! before it, so a finished transaction never references a value that does not exist.
+
+use std::collections::HashMap;
+
+use anyhow::{anyhow, bail, ensure};
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::TypeTag;
+use serde::Serialize;
+
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::messages::{
+    Argument, CallArg, Command, ObjectArg, ProgrammableMoveCall, ProgrammableTransaction,
+};
+
+#[cfg(test)]
+#[path = "unit_tests/programmable_transaction_builder_tests.rs"]
+mod programmable_transaction_builder_tests;
+
+#[derive(Default)]
+pub struct ProgrammableTransactionBuilder {
+    inputs: Vec<CallArg>,
+    input_indices: HashMap<CallArg, u16>,
+    commands: Vec<Command>,
+}
+
+impl ProgrammableTransactionBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `arg` to the transaction inputs, returning the existing `Argument::Input` if an
+    /// identical input has already been added.
+    pub fn input(&mut self, arg: CallArg) -> anyhow::Result<Argument> {
+        if let Some(idx) = self.input_indices.get(&arg) {
+            return Ok(Argument::Input(*idx));
+        }
+        if let CallArg::Object(obj_arg) = &arg {
+            let id = object_arg_id(obj_arg);
+            ensure!(
+                !self.inputs.iter().any(|input| matches!(
+                    input,
+                    CallArg::Object(existing) if object_arg_id(existing) == id
+                )),
+                "Object [{id}] is already an input of this transaction with a different reference"
+            );
+        }
+        let idx = u16::try_from(self.inputs.len())
+            .map_err(|_| anyhow!("Too many inputs in programmable transaction"))?;
+        self.input_indices.insert(arg.clone(), idx);
+        self.inputs.push(arg);
+        Ok(Argument::Input(idx))
+    }
+
+    /// Adds a BCS serialized pure value to the transaction inputs.
+    pub fn pure<T: Serialize>(&mut self, value: T) -> anyhow::Result<Argument> {
+        self.input(CallArg::Pure(bcs::to_bytes(&value)?))
+    }
+
+    pub fn obj(&mut self, obj_arg: ObjectArg) -> anyhow::Result<Argument> {
+        self.input(CallArg::Object(obj_arg))
+    }
+
+    /// Appends `command` after validating its arguments, returning an `Argument::Result`
+    /// referring to it.
+    pub fn command(&mut self, command: Command) -> anyhow::Result<Argument> {
+        match &command {
+            Command::MoveCall(call) => {
+                for arg in &call.arguments {
+                    self.check_argument(arg)?;
+                }
+            }
+            Command::TransferObjects(objects, recipient) => {
+                ensure!(
+                    !objects.is_empty(),
+                    "TransferObjects requires at least one object"
+                );
+                for arg in objects.iter().chain(std::iter::once(recipient)) {
+                    self.check_argument(arg)?;
+                }
+            }
+            Command::SplitCoin(coin, amount) => {
+                self.check_argument(coin)?;
+                self.check_argument(amount)?;
+            }
+            Command::MergeCoins(target, coins) => {
+                ensure!(!coins.is_empty(), "MergeCoins requires at least one coin");
+                for arg in std::iter::once(target).chain(coins) {
+                    self.check_argument(arg)?;
+                }
+            }
+            Command::Publish(modules) => {
+                ensure!(!modules.is_empty(), "Publish requires at least one module");
+            }
+        }
+        let idx = u16::try_from(self.commands.len())
+            .map_err(|_| anyhow!("Too many commands in programmable transaction"))?;
+        self.commands.push(command);
+        Ok(Argument::Result(idx))
+    }
+
+    pub fn move_call(
+        &mut self,
+        package: ObjectID,
+        module: Identifier,
+        function: Identifier,
+        type_arguments: Vec<TypeTag>,
+        arguments: Vec<Argument>,
+    ) -> anyhow::Result<Argument> {
+        self.command(Command::MoveCall(Box::new(ProgrammableMoveCall {
+            package,
+            module,
+            function,
+            type_arguments,
+            arguments,
+        })))
+    }
+
+    pub fn split_coin(&mut self, coin: Argument, amount: Argument) -> anyhow::Result<Argument> {
+        self.command(Command::SplitCoin(coin, amount))
+    }
+
+    pub fn merge_coins(
+        &mut self,
+        target: Argument,
+        coins: Vec<Argument>,
+    ) -> anyhow::Result<Argument> {
+        self.command(Command::MergeCoins(target, coins))
+    }
+
+    pub fn transfer_objects(
+        &mut self,
+        objects: Vec<Argument>,
+        recipient: Argument,
+    ) -> anyhow::Result<Argument> {
+        self.command(Command::TransferObjects(objects, recipient))
+    }
+
+    /// Transfers `objects` to `recipient`, adding the recipient address as a pure input.
+    pub fn transfer_objects_to(
+        &mut self,
+        objects: Vec<Argument>,
+        recipient: SuiAddress,
+    ) -> anyhow::Result<Argument> {
+        let recipient = self.pure(recipient)?;
+        self.transfer_objects(objects, recipient)
+    }
+
+    pub fn publish(&mut self, modules: Vec<Vec<u8>>) -> anyhow::Result<Argument> {
+        self.command(Command::Publish(modules))
+    }
+
+    pub fn finish(self) -> ProgrammableTransaction {
+        let Self {
+            inputs, commands, ..
+        } = self;
+        ProgrammableTransaction { inputs, commands }
+    }
+
+    fn check_argument(&self, arg: &Argument) -> anyhow::Result<()> {
+        match *arg {
+            Argument::GasCoin => (),
+            Argument::Input(i) => ensure!(
+                (i as usize) < self.inputs.len(),
+                "Input({i}) is out of bounds, the transaction has {} inputs",
+                self.inputs.len()
+            ),
+            Argument::Result(i) => match self.commands.get(i as usize) {
+                None => bail!("Result({i}) does not refer to a preceding command"),
+                Some(Command::MoveCall(_) | Command::SplitCoin(_, _)) => (),
+                Some(command) => bail!("Result({i}) refers to {command}, which has no result"),
+            },
+            Argument::NestedResult(i, j) => match self.commands.get(i as usize) {
+                None => bail!("NestedResult({i},{j}) does not refer to a preceding command"),
+                Some(Command::MoveCall(_)) => (),
+                Some(Command::SplitCoin(_, _)) => ensure!(
+                    j == 0,
+                    "NestedResult({i},{j}) is out of bounds, SplitCoin has a single result"
+                ),
+                Some(command) => {
+                    bail!("NestedResult({i},{j}) refers to {command}, which has no result")
+                }
+            },
+        }
+        Ok(())
+    }
+}
+
+fn object_arg_id(obj_arg: &ObjectArg) -> ObjectID {
+    match obj_arg {
+        ObjectArg::ImmOrOwnedObject((id, _, _)) => *id,
+        ObjectArg::SharedObject { id, .. } => *id,
+    }
+}
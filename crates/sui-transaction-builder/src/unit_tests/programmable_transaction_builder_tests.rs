@@ -0,0 +1,115 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use move_core_types::ident_str;
+use sui_types::base_types::{random_object_ref, SequenceNumber};
+use sui_types::SUI_FRAMEWORK_OBJECT_ID;
+
+use super::*;
+
+#[test]
+fn test_inputs_are_deduplicated() {
+    let mut builder = ProgrammableTransactionBuilder::new();
+    let coin = random_object_ref();
+    let a = builder.pure(100u64).unwrap();
+    let b = builder.obj(ObjectArg::ImmOrOwnedObject(coin)).unwrap();
+    assert_eq!(builder.pure(100u64).unwrap(), a);
+    assert_eq!(builder.obj(ObjectArg::ImmOrOwnedObject(coin)).unwrap(), b);
+    assert_eq!(a, Argument::Input(0));
+    assert_eq!(b, Argument::Input(1));
+    assert_eq!(builder.finish().inputs.len(), 2);
+}
+
+#[test]
+fn test_conflicting_object_inputs() {
+    let mut builder = ProgrammableTransactionBuilder::new();
+    let (id, version, digest) = random_object_ref();
+    builder
+        .obj(ObjectArg::ImmOrOwnedObject((id, version, digest)))
+        .unwrap();
+    assert!(builder
+        .obj(ObjectArg::SharedObject {
+            id,
+            initial_shared_version: SequenceNumber::from(1),
+            mutable: true,
+        })
+        .is_err());
+}
+
+#[test]
+fn test_split_and_transfer() {
+    let mut builder = ProgrammableTransactionBuilder::new();
+    let amount = builder.pure(1000u64).unwrap();
+    let coin = builder.split_coin(Argument::GasCoin, amount).unwrap();
+    assert_eq!(coin, Argument::Result(0));
+    builder
+        .transfer_objects_to(vec![coin], SuiAddress::random_for_testing_only())
+        .unwrap();
+    let pt = builder.finish();
+    assert_eq!(pt.inputs.len(), 2);
+    assert_eq!(pt.commands.len(), 2);
+    assert_eq!(
+        pt.commands[1],
+        Command::TransferObjects(vec![Argument::Result(0)], Argument::Input(1))
+    );
+}
+
+#[test]
+fn test_invalid_arguments() {
+    let mut builder = ProgrammableTransactionBuilder::new();
+    // no inputs yet
+    assert!(builder
+        .split_coin(Argument::GasCoin, Argument::Input(0))
+        .is_err());
+    // no commands yet
+    let amount = builder.pure(1u64).unwrap();
+    assert!(builder.split_coin(Argument::Result(0), amount).is_err());
+
+    let coin = builder.split_coin(Argument::GasCoin, amount).unwrap();
+    // SplitCoin has exactly one result
+    assert!(builder
+        .merge_coins(Argument::GasCoin, vec![Argument::NestedResult(0, 1)])
+        .is_err());
+    let merge = builder.merge_coins(Argument::GasCoin, vec![coin]).unwrap();
+    // MergeCoins has no result
+    assert!(builder
+        .transfer_objects(vec![merge], Argument::Input(0))
+        .is_err());
+    // Empty vectors are rejected
+    assert!(builder.merge_coins(Argument::GasCoin, vec![]).is_err());
+    assert!(builder
+        .transfer_objects(vec![], Argument::Input(0))
+        .is_err());
+    assert!(builder.publish(vec![]).is_err());
+
+    // Failed commands are not added to the transaction
+    assert_eq!(builder.finish().commands.len(), 2);
+}
+
+#[test]
+fn test_move_call_nested_results() {
+    let mut builder = ProgrammableTransactionBuilder::new();
+    let call = builder
+        .move_call(
+            SUI_FRAMEWORK_OBJECT_ID,
+            ident_str!("m").to_owned(),
+            ident_str!("f").to_owned(),
+            vec![],
+            vec![],
+        )
+        .unwrap();
+    let Argument::Result(idx) = call else {
+        panic!("expected a result argument")
+    };
+    // The number of Move return values is not known statically
+    builder
+        .transfer_objects_to(
+            vec![
+                Argument::NestedResult(idx, 0),
+                Argument::NestedResult(idx, 1),
+            ],
+            SuiAddress::random_for_testing_only(),
+        )
+        .unwrap();
+    assert_eq!(builder.finish().commands.len(), 2);
+}
@@ -344,6 +344,8 @@ pub enum SuiError {
     ObjectDeserializationError { error: String },
     #[error("Event store component is not active on this node")]
     NoEventStore,
+    #[error("Invalid event query: {error:?}")]
+    InvalidEventQuery { error: String },
 
     // Client side error
     #[error("Too many authority errors were detected for {}: {:?}", action, errors)]
@@ -353,6 +355,8 @@ pub enum SuiError {
     },
     #[error("Invalid transaction range query to the fullnode: {:?}", error)]
     FullNodeInvalidTxRangeQuery { error: String },
+    #[error("Invalid transaction query: {error:?}")]
+    InvalidTransactionQuery { error: String },
 
     // Errors related to the authority-consensus interface.
     #[error("Failed to connect with consensus node: {0}")]
@@ -1,8 +1,13 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::cmp::Ordering;
+
+use anyhow::bail;
 use move_core_types::identifier::Identifier;
 use move_core_types::language_storage::StructTag;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 use crate::base_types::SuiAddress;
@@ -21,12 +26,20 @@ pub enum EventFilter {
     Module(Identifier),
     MoveEventType(StructTag),
     EventType(EventType),
-    MoveEventField { path: String, value: Value },
+    MoveEventField {
+        path: String,
+        value: Value,
+    },
+    MoveEventFieldPredicate {
+        path: String,
+        predicate: EventFieldPredicate,
+    },
     SenderAddress(SuiAddress),
     Recipient(Owner),
     ObjectId(ObjectID),
     MatchAll(Vec<EventFilter>),
     MatchAny(Vec<EventFilter>),
+    Not(Box<EventFilter>),
 }
 
 impl EventFilter {
@@ -42,6 +55,15 @@ impl EventFilter {
                 }
                 _ => false,
             },
+            EventFilter::MoveEventFieldPredicate { path, predicate } => {
+                match &item.move_struct_json_value {
+                    Some(json) => match json.pointer(path) {
+                        Some(field) => predicate.try_matches(field)?,
+                        None => false,
+                    },
+                    _ => false,
+                }
+            }
             EventFilter::SenderAddress(sender) => {
                 matches!(&item.event.sender(), Some(addr) if addr == sender)
             }
@@ -55,8 +77,24 @@ impl EventFilter {
                 matches!(item.event.object_id(), Some(id) if &id == object_id)
             }
             EventFilter::EventType(type_) => &item.event.event_type() == type_,
-            EventFilter::MatchAll(filters) => filters.iter().all(|f| f.matches(item)),
-            EventFilter::MatchAny(filters) => filters.iter().any(|f| f.matches(item)),
+            EventFilter::MatchAll(filters) => {
+                for filter in filters {
+                    if !filter.try_matches(item)? {
+                        return Ok(false);
+                    }
+                }
+                true
+            }
+            EventFilter::MatchAny(filters) => {
+                for filter in filters {
+                    if filter.try_matches(item)? {
+                        return Ok(true);
+                    }
+                }
+                false
+            }
+            // An invalid filter must not match when negated either
+            EventFilter::Not(filter) => !filter.try_matches(item)?,
             EventFilter::Recipient(recipient) => {
                 matches!(item.event.recipient(), Some(event_recipient) if event_recipient == recipient)
             }
@@ -66,6 +104,14 @@ impl EventFilter {
     pub fn and(self, other_filter: EventFilter) -> Self {
         Self::MatchAll(vec![self, other_filter])
     }
+
+    pub fn or(self, other_filter: EventFilter) -> Self {
+        Self::MatchAny(vec![self, other_filter])
+    }
+
+    pub fn negate(self) -> Self {
+        Self::Not(Box::new(self))
+    }
 }
 
 impl Filter<EventEnvelope> for EventFilter {
@@ -77,3 +123,122 @@ impl Filter<EventEnvelope> for EventFilter {
 pub trait Filter<T> {
     fn matches(&self, item: &T) -> bool;
 }
+
+/// A predicate on a field of a Move event's JSON value.
+///
+/// Operands that are unsigned integers, either as JSON numbers or as strings of decimal digits
+/// (the JSON form of Move `u64`, `u128` and `u256`), are compared numerically with fields of
+/// either form. Other string operands are compared lexicographically with string fields. Any
+/// other operand can only be used for equality.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum EventFieldPredicate {
+    Eq(Value),
+    Gt(Value),
+    Gte(Value),
+    Lt(Value),
+    Lte(Value),
+    /// Inclusive on both ends
+    Between {
+        min: Value,
+        max: Value,
+    },
+    Prefix(String),
+    In(Vec<Value>),
+}
+
+/// How a predicate operand compares to event fields, see [EventFieldPredicate].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FieldOperand<'a> {
+    /// Decimal digits without leading zeros, so that numbers compare by (length, digits)
+    Numeric(String),
+    Text(&'a str),
+    Other(&'a Value),
+}
+
+impl<'a> FieldOperand<'a> {
+    pub fn new(value: &'a Value) -> Self {
+        match (normalized_unsigned(value), value) {
+            (Some(digits), _) => FieldOperand::Numeric(digits),
+            (None, Value::String(s)) => FieldOperand::Text(s),
+            (None, other) => FieldOperand::Other(other),
+        }
+    }
+
+    /// Returns an error if the operand cannot be used with an ordering comparison.
+    pub fn ordered(value: &'a Value) -> Result<Self, anyhow::Error> {
+        match Self::new(value) {
+            FieldOperand::Other(other) => {
+                bail!("Cannot compare event fields with {other}, expected an integer or a string")
+            }
+            operand => Ok(operand),
+        }
+    }
+
+    fn compare(&self, field: &Value) -> Option<Ordering> {
+        match self {
+            FieldOperand::Numeric(digits) => normalized_unsigned(field).map(|field| {
+                field
+                    .len()
+                    .cmp(&digits.len())
+                    .then_with(|| field.as_str().cmp(digits.as_str()))
+            }),
+            FieldOperand::Text(s) => field.as_str().map(|field| field.cmp(*s)),
+            FieldOperand::Other(value) => (field == *value).then_some(Ordering::Equal),
+        }
+    }
+}
+
+/// Returns the decimal digits of `value` without leading zeros if it is an unsigned integer,
+/// either as a JSON number or as a string of digits.
+pub fn normalized_unsigned(value: &Value) -> Option<String> {
+    let digits = match value {
+        Value::Number(n) => n.as_u64()?.to_string(),
+        Value::String(s) if !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit()) => s.clone(),
+        _ => return None,
+    };
+    Some(digits.trim_start_matches('0').to_string())
+}
+
+impl EventFieldPredicate {
+    pub fn try_matches(&self, field: &Value) -> Result<bool, anyhow::Error> {
+        let compare = |operand: &Value, ordered: bool| {
+            let operand = if ordered {
+                FieldOperand::ordered(operand)?
+            } else {
+                FieldOperand::new(operand)
+            };
+            Ok::<_, anyhow::Error>(operand.compare(field))
+        };
+        Ok(match self {
+            EventFieldPredicate::Eq(value) => compare(value, false)? == Some(Ordering::Equal),
+            EventFieldPredicate::Gt(value) => compare(value, true)? == Some(Ordering::Greater),
+            EventFieldPredicate::Gte(value) => matches!(
+                compare(value, true)?,
+                Some(Ordering::Greater | Ordering::Equal)
+            ),
+            EventFieldPredicate::Lt(value) => compare(value, true)? == Some(Ordering::Less),
+            EventFieldPredicate::Lte(value) => matches!(
+                compare(value, true)?,
+                Some(Ordering::Less | Ordering::Equal)
+            ),
+            EventFieldPredicate::Between { min, max } => {
+                matches!(
+                    compare(min, true)?,
+                    Some(Ordering::Greater | Ordering::Equal)
+                ) && matches!(compare(max, true)?, Some(Ordering::Less | Ordering::Equal))
+            }
+            EventFieldPredicate::Prefix(prefix) => {
+                matches!(field, Value::String(s) if s.starts_with(prefix.as_str()))
+            }
+            EventFieldPredicate::In(values) => {
+                for value in values {
+                    if compare(value, false)? == Some(Ordering::Equal) {
+                        return Ok(true);
+                    }
+                }
+                false
+            }
+        })
+    }
+}
@@ -1265,6 +1265,17 @@ impl TransactionData {
         Self::new(kind, sender, gas_payment, gas_budget, gas_price)
     }
 
+    pub fn new_programmable(
+        sender: SuiAddress,
+        gas_payment: ObjectRef,
+        pt: ProgrammableTransaction,
+        gas_budget: u64,
+        gas_price: u64,
+    ) -> Self {
+        let kind = TransactionKind::Single(SingleTransactionKind::ProgrammableTransaction(pt));
+        Self::new(kind, sender, gas_payment, gas_budget, gas_price)
+    }
+
     pub fn sender(&self) -> SuiAddress {
         self.sender
     }
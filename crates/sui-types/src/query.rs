@@ -7,6 +7,8 @@ use serde::Serialize;
 
 use crate::base_types::{SuiAddress, TransactionDigest};
 use crate::event::EventType;
+use crate::filter::EventFieldPredicate;
+use crate::messages_checkpoint::CheckpointSequenceNumber;
 use crate::object::Owner;
 use crate::ObjectID;
 
@@ -28,6 +30,15 @@ pub enum TransactionQuery {
     FromAddress(SuiAddress),
     /// Query by recipient address.
     ToAddress(SuiAddress),
+    /// Query by the checkpoints that include the transactions, inclusive on both ends.
+    Checkpoint {
+        start: CheckpointSequenceNumber,
+        end: CheckpointSequenceNumber,
+    },
+    /// Return transactions matching all of the given queries
+    MatchAll(Vec<TransactionQuery>),
+    /// Return transactions matching any of the given queries
+    MatchAny(Vec<TransactionQuery>),
 }
 
 #[derive(Clone, Debug, JsonSchema, Serialize, Deserialize)]
@@ -66,4 +77,16 @@ pub enum EventQuery {
         /// right endpoint of time interval, milliseconds since epoch, exclusive
         end_time: u64,
     },
+    /// Return Move events with a field matching the predicate
+    MoveEventField {
+        /// JSON pointer to the field in the event's JSON value, e.g. `/amount`
+        path: String,
+        predicate: EventFieldPredicate,
+    },
+    /// Return events matching all of the given queries
+    MatchAll(Vec<EventQuery>),
+    /// Return events matching any of the given queries
+    MatchAny(Vec<EventQuery>),
+    /// Return events not matching the given query
+    Not(Box<EventQuery>),
 }
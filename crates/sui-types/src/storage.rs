@@ -200,6 +200,10 @@ pub trait ReadStore {
 
     fn get_highest_synced_checkpoint(&self) -> Result<VerifiedCheckpoint, Self::Error>;
 
+    /// The lowest checkpoint whose contents, transactions and effects are still stored. Those of
+    /// older checkpoints have been pruned.
+    fn get_lowest_available_checkpoint(&self) -> Result<CheckpointSequenceNumber, Self::Error>;
+
     fn get_checkpoint_contents(
         &self,
         digest: &CheckpointContentsDigest,
@@ -243,6 +247,10 @@ impl<T: ReadStore> ReadStore for &T {
         ReadStore::get_highest_synced_checkpoint(*self)
     }
 
+    fn get_lowest_available_checkpoint(&self) -> Result<CheckpointSequenceNumber, Self::Error> {
+        ReadStore::get_lowest_available_checkpoint(*self)
+    }
+
     fn get_checkpoint_contents(
         &self,
         digest: &CheckpointContentsDigest,
@@ -275,6 +283,12 @@ pub trait WriteStore: ReadStore {
         &self,
         checkpoint: &VerifiedCheckpoint,
     ) -> Result<(), Self::Error>;
+    /// Records that every checkpoint up to and including `checkpoint` has been written to the
+    /// checkpoint archive, and so may be pruned.
+    fn update_highest_archived_checkpoint(
+        &self,
+        checkpoint: &VerifiedCheckpoint,
+    ) -> Result<(), Self::Error>;
     fn insert_checkpoint_contents(&self, contents: CheckpointContents) -> Result<(), Self::Error>;
 
     fn insert_committee(&self, new_committee: Committee) -> Result<(), Self::Error>;
@@ -298,6 +312,13 @@ impl<T: WriteStore> WriteStore for &T {
         WriteStore::update_highest_synced_checkpoint(*self, checkpoint)
     }
 
+    fn update_highest_archived_checkpoint(
+        &self,
+        checkpoint: &VerifiedCheckpoint,
+    ) -> Result<(), Self::Error> {
+        WriteStore::update_highest_archived_checkpoint(*self, checkpoint)
+    }
+
     fn insert_checkpoint_contents(&self, contents: CheckpointContents) -> Result<(), Self::Error> {
         WriteStore::insert_checkpoint_contents(*self, contents)
     }
@@ -319,6 +340,7 @@ impl<T: WriteStore> WriteStore for &T {
 pub struct InMemoryStore {
     highest_verified_checkpoint: Option<(CheckpointSequenceNumber, CheckpointDigest)>,
     highest_synced_checkpoint: Option<(CheckpointSequenceNumber, CheckpointDigest)>,
+    highest_archived_checkpoint: Option<(CheckpointSequenceNumber, CheckpointDigest)>,
     checkpoints: HashMap<CheckpointDigest, VerifiedCheckpoint>,
     sequence_number_to_digest: HashMap<CheckpointSequenceNumber, CheckpointDigest>,
     checkpoint_contents: HashMap<CheckpointContentsDigest, CheckpointContents>,
@@ -375,6 +397,21 @@ impl InMemoryStore {
             .and_then(|(_, digest)| self.get_checkpoint_by_digest(digest))
     }
 
+    pub fn get_highest_archived_checkpoint(&self) -> Option<&VerifiedCheckpoint> {
+        self.highest_archived_checkpoint
+            .as_ref()
+            .and_then(|(_, digest)| self.get_checkpoint_by_digest(digest))
+    }
+
+    /// Nothing is ever pruned from an in-memory store, so this is its lowest checkpoint.
+    pub fn get_lowest_available_checkpoint(&self) -> CheckpointSequenceNumber {
+        self.sequence_number_to_digest
+            .keys()
+            .min()
+            .copied()
+            .unwrap_or_default()
+    }
+
     pub fn get_checkpoint_contents(
         &self,
         digest: &CheckpointContentsDigest,
@@ -423,6 +460,15 @@ impl InMemoryStore {
         self.highest_synced_checkpoint = Some((checkpoint.sequence_number(), checkpoint.digest()));
     }
 
+    pub fn update_highest_archived_checkpoint(&mut self, checkpoint: &VerifiedCheckpoint) {
+        if !self.checkpoints.contains_key(&checkpoint.digest()) {
+            panic!("store should already contain checkpoint");
+        }
+
+        self.highest_archived_checkpoint =
+            Some((checkpoint.sequence_number(), checkpoint.digest()));
+    }
+
     pub fn checkpoints(&self) -> &HashMap<CheckpointDigest, VerifiedCheckpoint> {
         &self.checkpoints
     }
@@ -525,6 +571,10 @@ impl ReadStore for SharedInMemoryStore {
             .pipe(Ok)
     }
 
+    fn get_lowest_available_checkpoint(&self) -> Result<CheckpointSequenceNumber, Self::Error> {
+        Ok(self.inner().get_lowest_available_checkpoint())
+    }
+
     fn get_checkpoint_contents(
         &self,
         digest: &CheckpointContentsDigest,
@@ -572,6 +622,15 @@ impl WriteStore for SharedInMemoryStore {
         Ok(())
     }
 
+    fn update_highest_archived_checkpoint(
+        &self,
+        checkpoint: &VerifiedCheckpoint,
+    ) -> Result<(), Self::Error> {
+        self.inner_mut()
+            .update_highest_archived_checkpoint(checkpoint);
+        Ok(())
+    }
+
     fn insert_checkpoint_contents(&self, contents: CheckpointContents) -> Result<(), Self::Error> {
         self.inner_mut().insert_checkpoint_contents(contents);
         Ok(())
@@ -10,7 +10,7 @@ use serde_json::json;
 use crate::base_types::{ObjectDigest, SuiAddress, TransactionDigest};
 use crate::event::EventType;
 use crate::event::{Event, EventEnvelope};
-use crate::filter::{EventFilter, Filter};
+use crate::filter::{EventFieldPredicate, EventFilter, Filter};
 use crate::gas_coin::GasCoin;
 use crate::object::{Owner, OBJECT_START_VERSION};
 use crate::{ObjectID, MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS};
@@ -67,6 +67,113 @@ fn test_move_event_filter() {
     // Match all should == false and Match Any should still eq true.
     assert!(!EventFilter::MatchAll(filters.clone()).matches(&envelope));
     assert!(EventFilter::MatchAny(filters.clone()).matches(&envelope));
+
+    // Negating flips the result of the inner filter.
+    assert!(EventFilter::MatchAll(filters.clone())
+        .negate()
+        .matches(&envelope));
+    assert!(!EventFilter::MatchAny(filters.clone())
+        .negate()
+        .matches(&envelope));
+    let false_filter = EventFilter::Package(ObjectID::from(MOVE_STDLIB_ADDRESS));
+    assert!(EventFilter::EventType(EventType::MoveEvent)
+        .and(false_filter.negate())
+        .matches(&envelope));
+}
+
+#[test]
+fn test_move_event_field_predicates() {
+    let amount = |value: serde_json::Value| json!({ "amount": value, "pool": "SUI/USDC" });
+    let matches = |predicate: EventFieldPredicate, field: serde_json::Value| {
+        predicate.try_matches(&field).unwrap()
+    };
+
+    // u64 and larger are strings of digits, smaller integers are numbers
+    assert!(matches(EventFieldPredicate::Gt(json!(999)), json!("1000")));
+    assert!(matches(EventFieldPredicate::Gt(json!("999")), json!(1000)));
+    assert!(!matches(
+        EventFieldPredicate::Gt(json!("18446744073709551616")),
+        json!("18446744073709551615")
+    ));
+    assert!(matches(EventFieldPredicate::Eq(json!(7)), json!("007")));
+    assert!(matches(EventFieldPredicate::Lte(json!(7)), json!(7)));
+    assert!(matches(
+        EventFieldPredicate::Between {
+            min: json!(10),
+            max: json!("20"),
+        },
+        json!("20")
+    ));
+    assert!(!matches(
+        EventFieldPredicate::Between {
+            min: json!(10),
+            max: json!(20),
+        },
+        json!("21")
+    ));
+
+    // Strings compare lexicographically, and never match numbers
+    assert!(matches(EventFieldPredicate::Lt(json!("b")), json!("abc")));
+    assert!(!matches(EventFieldPredicate::Lt(json!("b")), json!(1)));
+    assert!(matches(
+        EventFieldPredicate::Prefix("SUI/".into()),
+        json!("SUI/USDC")
+    ));
+    assert!(matches(
+        EventFieldPredicate::In(vec![json!("a"), json!(true), json!(5)]),
+        json!("5")
+    ));
+    assert!(!matches(EventFieldPredicate::In(vec![]), json!("5")));
+
+    // Booleans can only be compared for equality
+    assert!(matches(EventFieldPredicate::Eq(json!(true)), json!(true)));
+    assert!(EventFieldPredicate::Gt(json!(true))
+        .try_matches(&json!(true))
+        .is_err());
+
+    let envelope = EventEnvelope {
+        timestamp: 0,
+        tx_digest: TransactionDigest::random(),
+        seq_num: 0,
+        event_num: 0,
+        event: Event::MoveEvent {
+            package_id: ObjectID::from(SUI_FRAMEWORK_ADDRESS),
+            transaction_module: Identifier::from(ident_str!("test_module")),
+            sender: SuiAddress::random_for_testing_only(),
+            type_: GasCoin::type_(),
+            contents: vec![],
+        },
+        move_struct_json_value: Some(amount(json!("5000"))),
+    };
+    let filter = |path: &str, predicate| EventFilter::MoveEventFieldPredicate {
+        path: path.to_string(),
+        predicate,
+    };
+    assert!(filter("/amount", EventFieldPredicate::Gt(json!(1000))).matches(&envelope));
+    assert!(!filter("/amount", EventFieldPredicate::Lt(json!(1000))).matches(&envelope));
+    assert!(!filter("/missing", EventFieldPredicate::Gt(json!(1000))).matches(&envelope));
+    // Invalid predicates do not match
+    assert!(!filter("/amount", EventFieldPredicate::Gt(json!(null))).matches(&envelope));
+
+    // Nor do filters combining them, whether negated, required or optional
+    let invalid = || filter("/amount", EventFieldPredicate::Gt(json!(null)));
+    assert!(!invalid().negate().matches(&envelope));
+    assert!(!invalid().negate().negate().matches(&envelope));
+    assert!(!invalid()
+        .or(filter("/amount", EventFieldPredicate::Gt(json!(1000))))
+        .negate()
+        .matches(&envelope));
+    assert!(!EventFilter::MatchAll(vec![
+        filter("/amount", EventFieldPredicate::Gt(json!(1000))),
+        invalid()
+    ])
+    .negate()
+    .matches(&envelope));
+    assert!(!EventFilter::MatchAny(vec![
+        filter("/amount", EventFieldPredicate::Lt(json!(1000))),
+        invalid()
+    ])
+    .matches(&envelope));
 }
 
 #[test]
@@ -10,6 +10,7 @@ use std::{
     time::Instant,
 };
 
+use crate::client_ptb::{build_programmable_transaction, PtbCommand};
 use crate::config::{Config, PersistedConfig, SuiClientConfig, SuiEnv};
 use anyhow::{anyhow, ensure};
 use bip32::DerivationPath;
@@ -34,11 +35,12 @@ use sui_framework_build::compiled_package::BuildConfig;
 use sui_json::SuiJsonValue;
 use sui_json_rpc_types::SuiExecutionStatus;
 use sui_json_rpc_types::{
-    DynamicFieldPage, GetObjectDataResponse, SuiObjectInfo, SuiParsedObject, SuiRawData,
-    SuiTransactionResponse,
+    DevInspectResults, DynamicFieldPage, GetObjectDataResponse, ProtocolConfigResponse,
+    SuiObjectInfo, SuiParsedObject, SuiRawData, SuiTransactionEffects, SuiTransactionResponse,
 };
 use sui_json_rpc_types::{GetRawObjectDataResponse, SuiData};
 use sui_keys::keystore::AccountKeystore;
+use sui_protocol_config::ProtocolVersion;
 use sui_sdk::SuiClient;
 use sui_types::crypto::SignatureScheme;
 use sui_types::dynamic_field::DynamicFieldType;
@@ -47,7 +49,7 @@ use sui_types::signature::GenericSignature;
 use sui_types::{
     base_types::{ObjectID, SuiAddress},
     gas_coin::GasCoin,
-    messages::{Transaction, VerifiedTransaction},
+    messages::{Transaction, TransactionData, VerifiedTransaction},
     object::Owner,
     parse_sui_type_tag, SUI_FRAMEWORK_ADDRESS,
 };
@@ -312,6 +314,34 @@ pub enum SuiClientCommands {
         gas_budget: u64,
     },
 
+    /// Compose a programmable transaction from a sequence of commands, where each command can
+    /// use the results of the commands before it, and execute it as a single transaction.
+    #[clap(name = "ptb")]
+    Ptb {
+        /// The commands to run in order, each given as one quoted string:
+        /// "split-coin <coin> <amount>", "merge-coins <target> <coin>...",
+        /// "transfer-objects <recipient> <object>...", "publish <package_path>" or
+        /// "move-call <package>::<module>::<function> [type:<type>]... <arg>...".
+        /// Arguments are `gas`, `result:<i>` or `result:<i>.<j>` for the results of earlier
+        /// commands, `object:<id>`, or pure values such as `u64:100` and `address:0x..`
+        #[clap(long, multiple_occurrences = false, multiple_values = true)]
+        commands: Vec<PtbCommand>,
+
+        /// ID of the gas object for gas payment, in 20 bytes Hex string
+        /// If not provided, a gas object with at least gas_budget value will be selected
+        #[clap(long)]
+        gas: Option<ObjectID>,
+
+        /// Gas budget for this transaction
+        #[clap(long)]
+        gas_budget: u64,
+
+        /// Only output the BCS serialized transaction data, as base-64 encoded string,
+        /// so that it can be signed elsewhere and submitted with `execute-signed-tx`.
+        #[clap(long)]
+        serialize_only: bool,
+    },
+
     /// Obtain the Addresses managed by the client.
     #[clap(name = "addresses")]
     Addresses,
@@ -355,6 +385,16 @@ pub enum SuiClientCommands {
         limit: usize,
     },
 
+    /// Obtain the protocol config of the network, i.e. the limits and costs enforced by the
+    /// protocol.
+    #[clap(name = "protocol-config")]
+    ProtocolConfig {
+        /// The protocol version to show the config of. Defaults to the protocol version of the
+        /// current epoch.
+        #[clap(long)]
+        version: Option<u64>,
+    },
+
     /// Split a coin object into multiple coins.
     #[clap(group(ArgGroup::new("split").required(true).args(&["amounts", "count"])))]
     SplitCoin {
@@ -450,10 +490,68 @@ pub enum SuiClientCommands {
     },
 }
 
+/// Options shared by all subcommands that build a transaction, to inspect what the transaction
+/// would do instead of signing and submitting it.
+#[derive(Args, Clone, Copy, Debug, Default)]
+pub struct TransactionPreviewOpts {
+    /// Run the transaction without committing it, and show its effects, gas cost, created and
+    /// mutated objects and events.
+    #[clap(long, global = true)]
+    pub dry_run: bool,
+    /// Run the transaction in dev-inspect mode, and show its effects and the values returned
+    /// by its Move calls.
+    #[clap(long, global = true)]
+    pub dev_inspect: bool,
+}
+
+impl TransactionPreviewOpts {
+    /// Dry runs or dev-inspects `data` if requested, returning the result to show instead of
+    /// executing the transaction.
+    pub async fn preview(
+        &self,
+        context: &WalletContext,
+        data: &TransactionData,
+    ) -> Result<Option<SuiClientCommandResult>, anyhow::Error> {
+        ensure!(
+            !(self.dry_run && self.dev_inspect),
+            "Only one of --dry-run and --dev-inspect can be specified"
+        );
+        let client = context.get_client().await?;
+        Ok(if self.dry_run {
+            let effects = client.read_api().dry_run_transaction(data.clone()).await?;
+            Some(SuiClientCommandResult::DryRun(effects))
+        } else if self.dev_inspect {
+            let results = client
+                .read_api()
+                .dev_inspect_transaction(
+                    data.sender(),
+                    data.kind.clone(),
+                    Some(data.gas_price()),
+                    None,
+                )
+                .await?;
+            Some(SuiClientCommandResult::DevInspect(results))
+        } else {
+            None
+        })
+    }
+}
+
 impl SuiClientCommands {
     pub async fn execute(
         self,
         context: &mut WalletContext,
+    ) -> Result<SuiClientCommandResult, anyhow::Error> {
+        self.execute_with_preview(context, TransactionPreviewOpts::default())
+            .await
+    }
+
+    /// Executes the command, dry running or dev-inspecting the transaction it builds instead
+    /// of submitting it if requested by `opts`.
+    pub async fn execute_with_preview(
+        self,
+        context: &mut WalletContext,
+        opts: TransactionPreviewOpts,
     ) -> Result<SuiClientCommandResult, anyhow::Error> {
         let ret = Ok(match self {
             SuiClientCommands::Publish {
@@ -513,6 +611,9 @@ impl SuiClientCommands {
                     .transaction_builder()
                     .publish(sender, compiled_modules, gas, gas_budget)
                     .await?;
+                if let Some(preview) = opts.preview(context, &data).await? {
+                    return Ok(preview);
+                }
                 let signature =
                     context
                         .config
@@ -549,6 +650,15 @@ impl SuiClientCommands {
                 SuiClientCommandResult::DynamicFieldQuery(df_read)
             }
 
+            SuiClientCommands::ProtocolConfig { version } => {
+                let client = context.get_client().await?;
+                let config = client
+                    .read_api()
+                    .get_protocol_config(version.map(ProtocolVersion::new))
+                    .await?;
+                SuiClientCommandResult::ProtocolConfig(config)
+            }
+
             SuiClientCommands::Call {
                 package,
                 module,
@@ -558,10 +668,14 @@ impl SuiClientCommands {
                 gas_budget,
                 args,
             } => {
-                let response = call_move(
+                let data = move_call_data(
                     package, &module, &function, type_args, gas, gas_budget, args, context,
                 )
                 .await?;
+                if let Some(preview) = opts.preview(context, &data).await? {
+                    return Ok(preview);
+                }
+                let response = execute_move_call(data, context).await?;
                 SuiClientCommandResult::Call(response)
             }
 
@@ -579,6 +693,9 @@ impl SuiClientCommands {
                     .transaction_builder()
                     .transfer_object(from, object_id, gas, gas_budget, to)
                     .await?;
+                if let Some(preview) = opts.preview(context, &data).await? {
+                    return Ok(preview);
+                }
                 let signature =
                     context
                         .config
@@ -611,6 +728,9 @@ impl SuiClientCommands {
                     .transaction_builder()
                     .transfer_sui(from, object_id, gas_budget, to, amount)
                     .await?;
+                if let Some(preview) = opts.preview(context, &data).await? {
+                    return Ok(preview);
+                }
                 let signature =
                     context
                         .config
@@ -658,6 +778,9 @@ impl SuiClientCommands {
                     .transaction_builder()
                     .pay(from, input_coins, recipients, amounts, gas, gas_budget)
                     .await?;
+                if let Some(preview) = opts.preview(context, &data).await? {
+                    return Ok(preview);
+                }
                 let signature =
                     context
                         .config
@@ -707,6 +830,9 @@ impl SuiClientCommands {
                     .transaction_builder()
                     .pay_sui(signer, input_coins, recipients, amounts, gas_budget)
                     .await?;
+                if let Some(preview) = opts.preview(context, &data).await? {
+                    return Ok(preview);
+                }
                 let signature =
                     context
                         .config
@@ -744,6 +870,9 @@ impl SuiClientCommands {
                     .pay_all_sui(signer, input_coins, recipient, gas_budget)
                     .await?;
 
+                if let Some(preview) = opts.preview(context, &data).await? {
+                    return Ok(preview);
+                }
                 let signature =
                     context
                         .config
@@ -765,6 +894,60 @@ impl SuiClientCommands {
                 SuiClientCommandResult::PayAllSui(response)
             }
 
+            SuiClientCommands::Ptb {
+                commands,
+                gas,
+                gas_budget,
+                serialize_only,
+            } => {
+                ensure!(
+                    !commands.is_empty(),
+                    "Programmable transaction requires at least one command"
+                );
+                ensure!(
+                    !(serialize_only && (opts.dry_run || opts.dev_inspect)),
+                    "--serialize-only cannot be combined with --dry-run or --dev-inspect"
+                );
+                let sender = context.try_get_object_owner(&gas).await?;
+                let sender = sender.unwrap_or(context.active_address()?);
+
+                let client = context.get_client().await?;
+                let pt = build_programmable_transaction(&client, commands).await?;
+                let data = client
+                    .transaction_builder()
+                    .programmable(sender, pt, gas, gas_budget)
+                    .await?;
+
+                if serialize_only {
+                    return Ok(SuiClientCommandResult::SerializedTx(Base64::encode(
+                        bcs::to_bytes(&data)?,
+                    )));
+                }
+
+                if let Some(preview) = opts.preview(context, &data).await? {
+                    return Ok(preview);
+                }
+                let signature =
+                    context
+                        .config
+                        .keystore
+                        .sign_secure(&sender, &data, Intent::default())?;
+                let response = context
+                    .execute_transaction(
+                        Transaction::from_data(data, Intent::default(), vec![signature])
+                            .verify()?,
+                    )
+                    .await?;
+                let effects = &response.effects;
+                if matches!(effects.status, SuiExecutionStatus::Failure { .. }) {
+                    return Err(anyhow!(
+                        "Error executing programmable transaction: {:#?}",
+                        effects.status
+                    ));
+                }
+                SuiClientCommandResult::Ptb(response)
+            }
+
             SuiClientCommands::Addresses => SuiClientCommandResult::Addresses(
                 context.config.keystore.addresses(),
                 context.active_address().ok(),
@@ -830,6 +1013,9 @@ impl SuiClientCommands {
                         return Err(anyhow!("Exactly one of `count` and `amounts` must be present for split-coin command."));
                     }
                 };
+                if let Some(preview) = opts.preview(context, &data).await? {
+                    return Ok(preview);
+                }
                 let signature =
                     context
                         .config
@@ -855,6 +1041,9 @@ impl SuiClientCommands {
                     .transaction_builder()
                     .merge_coins(signer, primary_coin, coin_to_merge, gas, gas_budget)
                     .await?;
+                if let Some(preview) = opts.preview(context, &data).await? {
+                    return Ok(preview);
+                }
                 let signature =
                     context
                         .config
@@ -904,7 +1093,7 @@ impl SuiClientCommands {
                 for a in args_json.as_array().unwrap() {
                     args.push(SuiJsonValue::new(a.clone()).unwrap());
                 }
-                let response = call_move(
+                let data = move_call_data(
                     ObjectID::from(SUI_FRAMEWORK_ADDRESS),
                     "devnet_nft",
                     "mint",
@@ -915,6 +1104,10 @@ impl SuiClientCommands {
                     context,
                 )
                 .await?;
+                if let Some(preview) = opts.preview(context, &data).await? {
+                    return Ok(preview);
+                }
+                let response = execute_move_call(data, context).await?;
                 let nft_id = response
                     .effects
                     .created
@@ -1262,6 +1455,54 @@ impl Display for SuiClientCommandResult {
             SuiClientCommandResult::PayAllSui(response) => {
                 write!(writer, "{}", write_transaction_response(response)?)?;
             }
+            SuiClientCommandResult::Ptb(response) => {
+                write!(writer, "{}", write_transaction_response(response)?)?;
+            }
+            SuiClientCommandResult::DryRun(effects) => {
+                writeln!(
+                    writer,
+                    "{}",
+                    "----- Dry Run Transaction Effects ----".bold()
+                )?;
+                write!(writer, "{}", write_effects_preview(effects)?)?;
+            }
+            SuiClientCommandResult::DevInspect(results) => {
+                writeln!(
+                    writer,
+                    "{}",
+                    "----- Dev Inspect Transaction Effects ----".bold()
+                )?;
+                write!(writer, "{}", write_effects_preview(&results.effects)?)?;
+                writeln!(writer, "{}", "----- Dev Inspect Results ----".bold())?;
+                match &results.results {
+                    Ok(results) => {
+                        for (idx, result) in results {
+                            writeln!(writer, "Transaction {idx}:")?;
+                            for (bytes, type_) in &result.return_values {
+                                writeln!(
+                                    writer,
+                                    "  - Return value: {} , Type: {}",
+                                    Base64::encode(bytes),
+                                    type_
+                                )?;
+                            }
+                            for (arg_idx, bytes, type_) in &result.mutable_reference_outputs {
+                                writeln!(
+                                    writer,
+                                    "  - Mutable reference argument {}: {} , Type: {}",
+                                    arg_idx,
+                                    Base64::encode(bytes),
+                                    type_
+                                )?;
+                            }
+                        }
+                    }
+                    Err(e) => writeln!(writer, "Execution error: {e}")?,
+                }
+            }
+            SuiClientCommandResult::SerializedTx(data) => {
+                writeln!(writer, "Raw tx_bytes to execute: {}", data)?;
+            }
             SuiClientCommandResult::Addresses(addresses, active_address) => {
                 writeln!(writer, "Showing {} results.", addresses.len())?;
                 for address in addresses {
@@ -1327,6 +1568,24 @@ impl Display for SuiClientCommandResult {
                     writeln!(writer, "Next cursor: {cursor}")?;
                 }
             }
+            SuiClientCommandResult::ProtocolConfig(config) => {
+                writeln!(
+                    writer,
+                    "Protocol version: {}",
+                    config.protocol_version.as_u64()
+                )?;
+                writeln!(
+                    writer,
+                    "Supported protocol versions: {}..={}",
+                    config.min_supported_protocol_version.as_u64(),
+                    config.max_supported_protocol_version.as_u64()
+                )?;
+                let mut table: Table = table!(["Name", "Value"]);
+                for (name, value) in &config.attributes {
+                    table.add_row(row![name, value]);
+                }
+                write!(writer, "{table}")?;
+            }
             SuiClientCommandResult::SyncClientState => {
                 writeln!(writer, "Client state sync complete.")?;
             }
@@ -1410,6 +1669,23 @@ pub async fn call_move(
     args: Vec<SuiJsonValue>,
     context: &mut WalletContext,
 ) -> Result<SuiTransactionResponse, anyhow::Error> {
+    let data = move_call_data(
+        package, module, function, type_args, gas, gas_budget, args, context,
+    )
+    .await?;
+    execute_move_call(data, context).await
+}
+
+async fn move_call_data(
+    package: ObjectID,
+    module: &str,
+    function: &str,
+    type_args: Vec<TypeTag>,
+    gas: Option<ObjectID>,
+    gas_budget: u64,
+    args: Vec<SuiJsonValue>,
+    context: &mut WalletContext,
+) -> Result<TransactionData, anyhow::Error> {
     // Convert all numeric input to String, this will allow number input from the CLI without failing SuiJSON's checks.
     let args = args
         .into_iter()
@@ -1436,6 +1712,14 @@ pub async fn call_move(
             gas_budget,
         )
         .await?;
+    Ok(data)
+}
+
+async fn execute_move_call(
+    data: TransactionData,
+    context: &mut WalletContext,
+) -> Result<SuiTransactionResponse, anyhow::Error> {
+    let sender = data.sender();
     let signature = context
         .config
         .keystore
@@ -1470,6 +1754,25 @@ fn unwrap_or<'a>(val: &'a Option<String>, default: &'a str) -> &'a str {
     }
 }
 
+fn write_effects_preview(effects: &SuiTransactionEffects) -> Result<String, fmt::Error> {
+    let mut writer = String::new();
+    write!(writer, "{}", effects)?;
+    let gas = &effects.gas_used;
+    writeln!(
+        writer,
+        "Gas Cost: computation {} , storage {} , storage rebate {}",
+        gas.computation_cost, gas.storage_cost, gas.storage_rebate
+    )?;
+    if !effects.events.is_empty() {
+        writeln!(writer, "Events:")?;
+        for event in &effects.events {
+            let event = serde_json::to_string(event).map_err(|_| fmt::Error)?;
+            writeln!(writer, "  - {event}")?;
+        }
+    }
+    Ok(writer)
+}
+
 fn write_transaction_response(response: &SuiTransactionResponse) -> Result<String, fmt::Error> {
     let mut writer = String::new();
     writeln!(writer, "{}", "----- Transaction Data ----".bold())?;
@@ -1536,9 +1839,14 @@ pub enum SuiClientCommandResult {
     Pay(SuiTransactionResponse),
     PaySui(SuiTransactionResponse),
     PayAllSui(SuiTransactionResponse),
+    Ptb(SuiTransactionResponse),
+    DryRun(SuiTransactionEffects),
+    DevInspect(DevInspectResults),
+    SerializedTx(String),
     Addresses(Vec<SuiAddress>, Option<SuiAddress>),
     Objects(Vec<SuiObjectInfo>),
     DynamicFieldQuery(DynamicFieldPage),
+    ProtocolConfig(ProtocolConfigResponse),
     SyncClientState,
     NewAddress((SuiAddress, String, SignatureScheme)),
     Gas(Vec<GasCoin>),
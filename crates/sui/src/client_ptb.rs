@@ -0,0 +1,283 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Parsing and resolution of the commands accepted by `sui client ptb`.
+//!
+//! Each command is a single shell-quoted string, e.g.
+//! `"split-coin gas u64:1000"` or `"transfer-objects address:0x42 result:0"`.
+//! Arguments are written as:
+//! * `gas` - the gas coin
+//! * `result:<i>` / `result:<i>.<j>` - the (nested) result of the i-th command
+//! * `object:<id>` - an on-chain object, resolved to an owned or shared object input
+//! * `<u8|u16|u32|u64|u128|bool|address|string>:<value>` - a pure value
+
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, ensure};
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::TypeTag;
+use move_package::BuildConfig as MoveBuildConfig;
+use sui_framework::build_move_package;
+use sui_framework_build::compiled_package::BuildConfig;
+use sui_sdk::transaction_builder::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use sui_sdk::SuiClient;
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::messages::{Argument, CallArg, ProgrammableTransaction};
+use sui_types::parse_sui_type_tag;
+
+#[cfg(test)]
+#[path = "unit_tests/client_ptb_tests.rs"]
+mod client_ptb_tests;
+
+/// An argument to a `sui client ptb` command, before objects are resolved on-chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PtbArg {
+    GasCoin,
+    Result(u16),
+    NestedResult(u16, u16),
+    Object(ObjectID),
+    /// A BCS serialized pure value
+    Pure(Vec<u8>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PtbCommand {
+    SplitCoin {
+        coin: PtbArg,
+        amount: PtbArg,
+    },
+    MergeCoins {
+        target: PtbArg,
+        coins: Vec<PtbArg>,
+    },
+    TransferObjects {
+        recipient: PtbArg,
+        objects: Vec<PtbArg>,
+    },
+    MoveCall {
+        package: ObjectID,
+        module: Identifier,
+        function: Identifier,
+        type_args: Vec<TypeTag>,
+        args: Vec<PtbArg>,
+    },
+    Publish {
+        package_path: PathBuf,
+    },
+}
+
+impl FromStr for PtbArg {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "gas" {
+            return Ok(PtbArg::GasCoin);
+        }
+        let (kind, value) = s
+            .split_once(':')
+            .ok_or_else(|| anyhow!("Invalid argument [{s}], expected `<kind>:<value>` or `gas`"))?;
+        Ok(match kind {
+            "result" => match value.split_once('.') {
+                Some((i, j)) => PtbArg::NestedResult(i.parse()?, j.parse()?),
+                None => PtbArg::Result(value.parse()?),
+            },
+            "object" => PtbArg::Object(ObjectID::from_str(value)?),
+            "u8" => PtbArg::Pure(bcs::to_bytes(&value.parse::<u8>()?)?),
+            "u16" => PtbArg::Pure(bcs::to_bytes(&value.parse::<u16>()?)?),
+            "u32" => PtbArg::Pure(bcs::to_bytes(&value.parse::<u32>()?)?),
+            "u64" => PtbArg::Pure(bcs::to_bytes(&value.parse::<u64>()?)?),
+            "u128" => PtbArg::Pure(bcs::to_bytes(&value.parse::<u128>()?)?),
+            "bool" => PtbArg::Pure(bcs::to_bytes(&value.parse::<bool>()?)?),
+            "address" => PtbArg::Pure(bcs::to_bytes(&SuiAddress::from_str(value)?)?),
+            "string" => PtbArg::Pure(bcs::to_bytes(value)?),
+            _ => bail!("Unknown argument kind [{kind}] in [{s}]"),
+        })
+    }
+}
+
+impl FromStr for PtbCommand {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tokens = shell_words::split(s)?;
+        let (name, rest) = tokens
+            .split_first()
+            .ok_or_else(|| anyhow!("Empty programmable transaction command"))?;
+        let args = |tokens: &[String]| {
+            tokens
+                .iter()
+                .map(|t| PtbArg::from_str(t))
+                .collect::<Result<Vec<_>, _>>()
+        };
+        Ok(match name.as_str() {
+            "split-coin" => {
+                let [coin, amount] = <[PtbArg; 2]>::try_from(args(rest)?)
+                    .map_err(|_| anyhow!("split-coin expects <coin> <amount>"))?;
+                PtbCommand::SplitCoin { coin, amount }
+            }
+            "merge-coins" => {
+                ensure!(
+                    rest.len() >= 2,
+                    "merge-coins expects <target> <coin> [<coin>...]"
+                );
+                let mut coins = args(rest)?;
+                let target = coins.remove(0);
+                PtbCommand::MergeCoins { target, coins }
+            }
+            "transfer-objects" => {
+                ensure!(
+                    rest.len() >= 2,
+                    "transfer-objects expects <recipient> <object> [<object>...]"
+                );
+                let mut objects = args(rest)?;
+                let recipient = objects.remove(0);
+                PtbCommand::TransferObjects { recipient, objects }
+            }
+            "move-call" => {
+                let (target, rest) = rest.split_first().ok_or_else(|| {
+                    anyhow!("move-call expects <package>::<module>::<function> [type:<type>...] [<arg>...]")
+                })?;
+                let mut parts = target.splitn(3, "::");
+                let (Some(package), Some(module), Some(function)) =
+                    (parts.next(), parts.next(), parts.next()) else {
+                    bail!("Invalid move-call target [{target}], expected <package>::<module>::<function>")
+                };
+                let mut type_args = vec![];
+                let mut call_args = vec![];
+                for token in rest {
+                    match token.strip_prefix("type:") {
+                        Some(ty) => type_args.push(parse_sui_type_tag(ty)?),
+                        None => call_args.push(PtbArg::from_str(token)?),
+                    }
+                }
+                PtbCommand::MoveCall {
+                    package: ObjectID::from_str(package)?,
+                    module: Identifier::new(module)?,
+                    function: Identifier::new(function)?,
+                    type_args,
+                    args: call_args,
+                }
+            }
+            "publish" => {
+                let [package_path] = <[String; 1]>::try_from(rest.to_vec())
+                    .map_err(|_| anyhow!("publish expects <package_path>"))?;
+                PtbCommand::Publish {
+                    package_path: package_path.into(),
+                }
+            }
+            _ => bail!(
+                "Unknown command [{name}], expected one of split-coin, merge-coins, \
+                 transfer-objects, move-call or publish"
+            ),
+        })
+    }
+}
+
+impl Display for PtbCommand {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            PtbCommand::SplitCoin { .. } => "split-coin",
+            PtbCommand::MergeCoins { .. } => "merge-coins",
+            PtbCommand::TransferObjects { .. } => "transfer-objects",
+            PtbCommand::MoveCall { .. } => "move-call",
+            PtbCommand::Publish { .. } => "publish",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Resolves `commands` into a `ProgrammableTransaction`, fetching object references from
+/// the network and compiling any packages to publish.
+pub async fn build_programmable_transaction(
+    client: &SuiClient,
+    commands: Vec<PtbCommand>,
+) -> Result<ProgrammableTransaction, anyhow::Error> {
+    let mut builder = ProgrammableTransactionBuilder::new();
+    for (idx, command) in commands.into_iter().enumerate() {
+        let name = command.to_string();
+        add_command(client, &mut builder, command)
+            .await
+            .map_err(|e| anyhow!("Invalid command {idx} ({name}): {e}"))?;
+    }
+    Ok(builder.finish())
+}
+
+async fn add_command(
+    client: &SuiClient,
+    builder: &mut ProgrammableTransactionBuilder,
+    command: PtbCommand,
+) -> Result<Argument, anyhow::Error> {
+    match command {
+        PtbCommand::SplitCoin { coin, amount } => {
+            let coin = resolve_arg(client, builder, coin).await?;
+            let amount = resolve_arg(client, builder, amount).await?;
+            builder.split_coin(coin, amount)
+        }
+        PtbCommand::MergeCoins { target, coins } => {
+            let target = resolve_arg(client, builder, target).await?;
+            let coins = resolve_args(client, builder, coins).await?;
+            builder.merge_coins(target, coins)
+        }
+        PtbCommand::TransferObjects { recipient, objects } => {
+            let objects = resolve_args(client, builder, objects).await?;
+            let recipient = resolve_arg(client, builder, recipient).await?;
+            builder.transfer_objects(objects, recipient)
+        }
+        PtbCommand::MoveCall {
+            package,
+            module,
+            function,
+            type_args,
+            args,
+        } => {
+            let args = resolve_args(client, builder, args).await?;
+            builder.move_call(package, module, function, type_args, args)
+        }
+        PtbCommand::Publish { package_path } => {
+            let build_config = MoveBuildConfig {
+                lock_file: Some(package_path.join("Move.lock")),
+                ..Default::default()
+            };
+            let compiled_package = build_move_package(
+                &package_path,
+                BuildConfig {
+                    config: build_config,
+                    run_bytecode_verifier: true,
+                    print_diags_to_stderr: true,
+                },
+            )?;
+            builder.publish(compiled_package.get_package_bytes(false))
+        }
+    }
+}
+
+async fn resolve_args(
+    client: &SuiClient,
+    builder: &mut ProgrammableTransactionBuilder,
+    args: Vec<PtbArg>,
+) -> Result<Vec<Argument>, anyhow::Error> {
+    let mut resolved = Vec::with_capacity(args.len());
+    for arg in args {
+        resolved.push(resolve_arg(client, builder, arg).await?);
+    }
+    Ok(resolved)
+}
+
+async fn resolve_arg(
+    client: &SuiClient,
+    builder: &mut ProgrammableTransactionBuilder,
+    arg: PtbArg,
+) -> Result<Argument, anyhow::Error> {
+    Ok(match arg {
+        PtbArg::GasCoin => Argument::GasCoin,
+        PtbArg::Result(i) => Argument::Result(i),
+        PtbArg::NestedResult(i, j) => Argument::NestedResult(i, j),
+        PtbArg::Pure(bytes) => builder.input(CallArg::Pure(bytes))?,
+        PtbArg::Object(id) => {
+            let obj_arg = client.transaction_builder().object_arg(id).await?;
+            builder.obj(obj_arg)?
+        }
+    })
+}
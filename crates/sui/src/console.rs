@@ -12,7 +12,9 @@ use clap::Parser;
 use colored::Colorize;
 
 use crate::client_commands::SwitchResponse;
-use crate::client_commands::{SuiClientCommandResult, SuiClientCommands, WalletContext};
+use crate::client_commands::{
+    SuiClientCommandResult, SuiClientCommands, TransactionPreviewOpts, WalletContext,
+};
 use crate::shell::{
     install_shell_plugins, AsyncHandler, CacheKey, CommandStructure, CompletionCache, Shell,
 };
@@ -31,6 +33,8 @@ pub struct ConsoleOpts {
     /// Returns command outputs in JSON format.
     #[clap(long, global = true)]
     pub json: bool,
+    #[clap(flatten)]
+    pub preview: TransactionPreviewOpts,
 }
 
 pub async fn start_console(
@@ -120,7 +124,10 @@ async fn handle_command(
     completion_cache: CompletionCache,
 ) -> Result<bool, anyhow::Error> {
     let wallet_opts = wallet_opts?;
-    let result = wallet_opts.command.execute(context).await?;
+    let result = wallet_opts
+        .command
+        .execute_with_preview(context, wallet_opts.preview)
+        .await?;
 
     // Update completion cache
     // TODO: Completion data are keyed by strings, are there ways to make it more error proof?
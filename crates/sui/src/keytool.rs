@@ -1,12 +1,16 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
-use anyhow::anyhow;
+use anyhow::{anyhow, bail};
 use bip32::DerivationPath;
 use clap::*;
 use fastcrypto::encoding::{decode_bytes_hex, Base64, Encoding};
 use fastcrypto::traits::KeyPair;
 use std::fs;
 use std::path::{Path, PathBuf};
+use sui_keys::encrypted_keystore::{
+    EncryptedFileKeystore, DEFAULT_KDF_ITERATIONS, SUI_KEYSTORE_NEW_PASSPHRASE_ENV,
+    SUI_KEYSTORE_PASSPHRASE_ENV,
+};
 use sui_keys::key_derive::generate_new_key;
 use sui_keys::keypair_file::{
     read_authority_keypair_from_file, read_keypair_from_file, write_authority_keypair_to_file,
@@ -19,6 +23,9 @@ use sui_types::multisig::{MultiSig, MultiSigPublicKey, ThresholdUnit, WeightUnit
 use sui_types::signature::GenericSignature;
 use tracing::info;
 
+use crate::config::SuiClientConfig;
+use sui_config::{sui_config_dir, Config, PersistedConfig, SUI_CLIENT_CONFIG};
+
 use sui_keys::keystore::{AccountKeystore, Keystore};
 use sui_types::base_types::SuiAddress;
 use sui_types::crypto::{get_authority_key_pair, EncodeDecodeBase64, SignatureScheme, SuiKeyPair};
@@ -104,6 +111,21 @@ pub enum KeyToolCommand {
         #[clap(long)]
         threshold: ThresholdUnit,
     },
+
+    /// Encrypt the private keys in sui.keystore at rest, using a passphrase read from the
+    /// SUI_KEYSTORE_PASSPHRASE environment variable. The keystore file is atomically replaced
+    /// by its encrypted version, and the `keystore` entry of the client config is switched
+    /// from `File` to `Encrypted` if it points to the same file.
+    Encrypt {
+        /// The client config to update, defaults to client.yaml in the Sui config directory.
+        #[clap(long)]
+        client_config: Option<PathBuf>,
+    },
+
+    /// Re-encrypt an encrypted keystore with a new passphrase read from the
+    /// SUI_KEYSTORE_NEW_PASSPHRASE environment variable. The current passphrase is read
+    /// from SUI_KEYSTORE_PASSPHRASE.
+    ChangePassphrase,
 }
 
 impl KeyToolCommand {
@@ -265,12 +287,82 @@ impl KeyToolCommand {
                 println!("MultiSig parsed: {:?}", generic_sig);
                 println!("MultiSig serialized: {:?}", generic_sig.encode_base64());
             }
+            KeyToolCommand::Encrypt { client_config } => {
+                let file_keystore = match keystore {
+                    Keystore::File(file_keystore) => file_keystore,
+                    Keystore::Encrypted(_) => bail!("Keystore is already encrypted"),
+                    Keystore::InMem(_) | Keystore::External(_) => {
+                        bail!("Only a file based keystore can be encrypted")
+                    }
+                };
+                let path = file_keystore
+                    .path()
+                    .cloned()
+                    .ok_or_else(|| anyhow!("Keystore has no path to write to"))?;
+                let passphrase = std::env::var(SUI_KEYSTORE_PASSPHRASE_ENV).map_err(|_| {
+                    anyhow!("Set {SUI_KEYSTORE_PASSPHRASE_ENV} to the passphrase for the keystore")
+                })?;
+                let client_config = match client_config {
+                    Some(client_config) => client_config,
+                    None => sui_config_dir()?.join(SUI_CLIENT_CONFIG),
+                };
+                // The client config has to be read before the keystore is encrypted, as it can
+                // no longer be loaded with a `File` keystore afterwards.
+                let client_config = load_client_config_using_keystore(&client_config, &path)?;
+                let encrypted = EncryptedFileKeystore::from_file_keystore(
+                    file_keystore,
+                    &path,
+                    &passphrase,
+                    DEFAULT_KDF_ITERATIONS,
+                )?;
+                println!("Encrypted {} keys in {:?}", encrypted.keys().len(), path);
+                if let Some(mut client_config) = client_config {
+                    client_config.keystore = Keystore::from(EncryptedFileKeystore::open(&path)?);
+                    client_config.save()?;
+                    println!(
+                        "Client config {:?} now uses the encrypted keystore",
+                        client_config.path()
+                    );
+                }
+                *keystore = Keystore::from(encrypted);
+            }
+            KeyToolCommand::ChangePassphrase => {
+                let Keystore::Encrypted(encrypted) = keystore else {
+                    bail!("Keystore is not encrypted, use `sui keytool encrypt` to encrypt it")
+                };
+                let new_passphrase =
+                    std::env::var(SUI_KEYSTORE_NEW_PASSPHRASE_ENV).map_err(|_| {
+                        anyhow!("Set {SUI_KEYSTORE_NEW_PASSPHRASE_ENV} to the new passphrase")
+                    })?;
+                encrypted.change_passphrase(&new_passphrase)?;
+                println!("Keystore passphrase changed");
+            }
         }
 
         Ok(())
     }
 }
 
+/// Loads the client config at `client_config` if it uses the unencrypted keystore at
+/// `keystore_path`.
+fn load_client_config_using_keystore(
+    client_config: &Path,
+    keystore_path: &Path,
+) -> Result<Option<PersistedConfig<SuiClientConfig>>, anyhow::Error> {
+    if !client_config.exists() {
+        return Ok(None);
+    }
+    let config: SuiClientConfig = PersistedConfig::read(client_config)?;
+    match &config.keystore {
+        Keystore::File(file_keystore)
+            if file_keystore.path().map(PathBuf::as_path) == Some(keystore_path) =>
+        {
+            Ok(Some(config.persisted(client_config)))
+        }
+        _ => Ok(None),
+    }
+}
+
 fn store_and_print_keypair(address: SuiAddress, keypair: SuiKeyPair) {
     let path_str = format!("{}.key", address).to_lowercase();
     let path = Path::new(&path_str);
@@ -18,11 +18,12 @@ use sui_config::{
     sui_config_dir, Config, PersistedConfig, FULL_NODE_DB_PATH, SUI_CLIENT_CONFIG,
     SUI_FULLNODE_CONFIG, SUI_NETWORK_CONFIG,
 };
+use sui_keys::encrypted_keystore::EncryptedFileKeystore;
 use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, Keystore};
 use sui_swarm::memory::Swarm;
 use sui_types::crypto::{SignatureScheme, SuiKeyPair};
 
-use crate::client_commands::{SuiClientCommands, WalletContext};
+use crate::client_commands::{SuiClientCommands, TransactionPreviewOpts, WalletContext};
 use crate::config::{SuiClientConfig, SuiEnv};
 use crate::console::start_console;
 use crate::genesis_ceremony::{run, Ceremony};
@@ -93,6 +94,8 @@ pub enum SuiCommand {
         json: bool,
         #[clap(short = 'y', long = "yes")]
         accept_defaults: bool,
+        #[clap(flatten)]
+        preview: TransactionPreviewOpts,
     },
 
     /// Tool to build and test Move applications.
@@ -199,7 +202,12 @@ impl SuiCommand {
             SuiCommand::KeyTool { keystore_path, cmd } => {
                 let keystore_path =
                     keystore_path.unwrap_or(sui_config_dir()?.join(SUI_KEYSTORE_FILENAME));
-                let mut keystore = Keystore::from(FileBasedKeystore::new(&keystore_path)?);
+                let mut keystore =
+                    if EncryptedFileKeystore::is_encrypted_keystore_file(&keystore_path) {
+                        Keystore::from(EncryptedFileKeystore::load(&keystore_path)?)
+                    } else {
+                        Keystore::from(FileBasedKeystore::new(&keystore_path)?)
+                    };
                 cmd.execute(&mut keystore)
             }
             SuiCommand::Console { config } => {
@@ -213,12 +221,15 @@ impl SuiCommand {
                 cmd,
                 json,
                 accept_defaults,
+                preview,
             } => {
                 let config_path = config.unwrap_or(sui_config_dir()?.join(SUI_CLIENT_CONFIG));
                 prompt_if_no_config(&config_path, accept_defaults).await?;
                 let mut context = WalletContext::new(&config_path, None).await?;
                 if let Some(cmd) = cmd {
-                    cmd.execute(&mut context).await?.print(!json);
+                    cmd.execute_with_preview(&mut context, preview)
+                        .await?
+                        .print(!json);
                 } else {
                     // Print help
                     let mut app: Command = SuiCommand::command();
@@ -10,7 +10,9 @@ use tokio::time::sleep;
 
 use sui::client_commands::SwitchResponse;
 use sui::{
-    client_commands::{SuiClientCommandResult, SuiClientCommands, WalletContext},
+    client_commands::{
+        SuiClientCommandResult, SuiClientCommands, TransactionPreviewOpts, WalletContext,
+    },
     config::SuiClientConfig,
     sui_commands::SuiCommand,
 };
@@ -1157,6 +1159,69 @@ async fn test_serialize_tx() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[sim_test]
+async fn test_transfer_dry_run_and_dev_inspect() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new().build().await?;
+    let address = test_cluster.get_address_0();
+    let context = &mut test_cluster.wallet;
+    let client = context.get_client().await?;
+    let object_refs = client
+        .read_api()
+        .get_objects_owned_by_address(address)
+        .await?;
+    let gas = object_refs.first().unwrap().object_id;
+    let object_to_send = object_refs.get(1).unwrap().object_id;
+    let recipient = SuiAddress::random_for_testing_only();
+
+    let transfer = || SuiClientCommands::Transfer {
+        to: recipient,
+        object_id: object_to_send,
+        gas: Some(gas),
+        gas_budget: 50000,
+    };
+
+    let resp = transfer()
+        .execute_with_preview(
+            context,
+            TransactionPreviewOpts {
+                dry_run: true,
+                dev_inspect: false,
+            },
+        )
+        .await?;
+    let SuiClientCommandResult::DryRun(effects) = resp else {
+        panic!("Expected dry run result, got {resp:?}")
+    };
+    assert!(effects.status.is_ok());
+    assert!(effects.gas_used.computation_cost > 0);
+
+    let resp = transfer()
+        .execute_with_preview(
+            context,
+            TransactionPreviewOpts {
+                dry_run: false,
+                dev_inspect: true,
+            },
+        )
+        .await?;
+    assert!(matches!(resp, SuiClientCommandResult::DevInspect(_)));
+
+    // Neither preview commits the transfer
+    assert_eq!(context.get_object_owner(&object_to_send).await?, address);
+
+    assert!(transfer()
+        .execute_with_preview(
+            context,
+            TransactionPreviewOpts {
+                dry_run: true,
+                dev_inspect: true,
+            },
+        )
+        .await
+        .is_err());
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_delegation_with_none_amount() -> Result<(), anyhow::Error> {
     let mut test_cluster = TestClusterBuilder::new().build().await?;
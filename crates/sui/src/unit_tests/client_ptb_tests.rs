@@ -0,0 +1,79 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::str::FromStr;
+
+use super::{PtbArg, PtbCommand};
+use move_core_types::identifier::Identifier;
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::parse_sui_type_tag;
+
+#[test]
+fn test_parse_args() -> Result<(), anyhow::Error> {
+    assert_eq!(PtbArg::from_str("gas")?, PtbArg::GasCoin);
+    assert_eq!(PtbArg::from_str("result:3")?, PtbArg::Result(3));
+    assert_eq!(PtbArg::from_str("result:3.1")?, PtbArg::NestedResult(3, 1));
+    assert_eq!(
+        PtbArg::from_str("u64:1000")?,
+        PtbArg::Pure(bcs::to_bytes(&1000u64)?)
+    );
+    assert_eq!(
+        PtbArg::from_str("bool:true")?,
+        PtbArg::Pure(bcs::to_bytes(&true)?)
+    );
+    let address = SuiAddress::random_for_testing_only();
+    assert_eq!(
+        PtbArg::from_str(&format!("address:{address}"))?,
+        PtbArg::Pure(bcs::to_bytes(&address)?)
+    );
+    assert_eq!(
+        PtbArg::from_str("object:0x2")?,
+        PtbArg::Object(ObjectID::from_str("0x2")?)
+    );
+
+    assert!(PtbArg::from_str("1000").is_err());
+    assert!(PtbArg::from_str("u8:256").is_err());
+    assert!(PtbArg::from_str("float:1.0").is_err());
+    Ok(())
+}
+
+#[test]
+fn test_parse_commands() -> Result<(), anyhow::Error> {
+    assert_eq!(
+        PtbCommand::from_str("split-coin gas u64:10")?,
+        PtbCommand::SplitCoin {
+            coin: PtbArg::GasCoin,
+            amount: PtbArg::Pure(bcs::to_bytes(&10u64)?),
+        }
+    );
+    assert_eq!(
+        PtbCommand::from_str("merge-coins gas result:0 result:1")?,
+        PtbCommand::MergeCoins {
+            target: PtbArg::GasCoin,
+            coins: vec![PtbArg::Result(0), PtbArg::Result(1)],
+        }
+    );
+    assert_eq!(
+        PtbCommand::from_str("move-call 0x2::coin::value type:0x2::sui::SUI result:0")?,
+        PtbCommand::MoveCall {
+            package: ObjectID::from_str("0x2")?,
+            module: Identifier::new("coin")?,
+            function: Identifier::new("value")?,
+            type_args: vec![parse_sui_type_tag("0x2::sui::SUI")?],
+            args: vec![PtbArg::Result(0)],
+        }
+    );
+    assert_eq!(
+        PtbCommand::from_str("publish 'path with spaces'")?,
+        PtbCommand::Publish {
+            package_path: "path with spaces".into(),
+        }
+    );
+
+    assert!(PtbCommand::from_str("").is_err());
+    assert!(PtbCommand::from_str("split-coin gas").is_err());
+    assert!(PtbCommand::from_str("transfer-objects address:0x2").is_err());
+    assert!(PtbCommand::from_str("move-call 0x2::coin").is_err());
+    assert!(PtbCommand::from_str("swap gas").is_err());
+    Ok(())
+}
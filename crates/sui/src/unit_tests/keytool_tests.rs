@@ -8,10 +8,13 @@ use crate::keytool::read_keypair_from_file;
 
 use super::write_keypair_to_file;
 use super::KeyToolCommand;
+use crate::config::SuiClientConfig;
 use fastcrypto::encoding::Base64;
 use fastcrypto::encoding::Encoding;
 use rand::rngs::StdRng;
 use rand::SeedableRng;
+use sui_config::{Config, PersistedConfig};
+use sui_keys::encrypted_keystore::{EncryptedFileKeystore, SUI_KEYSTORE_PASSPHRASE_ENV};
 use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, InMemKeystore, Keystore};
 use sui_types::base_types::ObjectDigest;
 use sui_types::base_types::ObjectID;
@@ -345,3 +348,46 @@ fn test_sign_command() -> Result<(), anyhow::Error> {
     .execute(&mut keystore)?;
     Ok(())
 }
+
+#[test]
+fn test_encrypt_keystore_updates_client_config() -> Result<(), anyhow::Error> {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let client_config_path = temp_dir.path().join("client.yaml");
+
+    let mut keystore = Keystore::from(FileBasedKeystore::new(&keystore_path)?);
+    keystore.add_key(SuiKeyPair::Ed25519(get_key_pair().1))?;
+    let address = keystore.addresses()[0];
+    SuiClientConfig::new(Keystore::from(FileBasedKeystore::new(&keystore_path)?))
+        .persisted(&client_config_path)
+        .save()?;
+
+    std::env::set_var(SUI_KEYSTORE_PASSPHRASE_ENV, "correct horse battery staple");
+    KeyToolCommand::Encrypt {
+        client_config: Some(client_config_path.clone()),
+    }
+    .execute(&mut keystore)?;
+    assert!(matches!(keystore, Keystore::Encrypted(_)));
+
+    // The keystore was replaced in place, without leaving the temporary file behind.
+    assert!(EncryptedFileKeystore::is_encrypted_keystore_file(
+        &keystore_path
+    ));
+    assert!(!keystore_path.with_extension("tmp").exists());
+
+    // The client config now loads the encrypted keystore, with the same keys.
+    let client_config: SuiClientConfig = PersistedConfig::read(&client_config_path)?;
+    let Keystore::Encrypted(encrypted) = &client_config.keystore else {
+        panic!("client config still uses an unencrypted keystore");
+    };
+    assert_eq!(encrypted.path(), Some(&keystore_path));
+    assert_eq!(client_config.keystore.addresses(), vec![address]);
+
+    // Encrypting again is rejected.
+    assert!(KeyToolCommand::Encrypt {
+        client_config: Some(client_config_path),
+    }
+    .execute(&mut keystore)
+    .is_err());
+    Ok(())
+}
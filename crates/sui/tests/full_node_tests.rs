@@ -236,6 +236,121 @@ async fn test_full_node_move_function_index() -> Result<(), anyhow::Error> {
     assert_eq!(txes.len(), 2);
     assert_eq!(txes[1], digest);
 
+    let package_calls_from_sender = TransactionQuery::MatchAll(vec![
+        TransactionQuery::FromAddress(sender),
+        TransactionQuery::MoveFunction {
+            package: package_ref.0,
+            module: None,
+            function: None,
+        },
+    ]);
+    let txes =
+        node.state()
+            .get_transactions(package_calls_from_sender.clone(), None, None, false)?;
+    assert_eq!(txes.len(), 2);
+    assert_eq!(txes[1], digest);
+
+    let txes = node
+        .state()
+        .get_transactions(package_calls_from_sender, None, Some(1), true)?;
+    assert_eq!(txes, vec![digest]);
+
+    let txes = node.state().get_transactions(
+        TransactionQuery::MatchAll(vec![
+            TransactionQuery::FromAddress(SuiAddress::random_for_testing_only()),
+            TransactionQuery::MoveFunction {
+                package: package_ref.0,
+                module: None,
+                function: None,
+            },
+        ]),
+        None,
+        None,
+        false,
+    )?;
+    assert!(txes.is_empty());
+
+    let txes = node.state().get_transactions(
+        TransactionQuery::MatchAll(vec![
+            TransactionQuery::MutatedObject(counter_ref.0),
+            TransactionQuery::MoveFunction {
+                package: package_ref.0,
+                module: Some("counter".to_string()),
+                function: Some("increment".to_string()),
+            },
+        ]),
+        None,
+        None,
+        false,
+    )?;
+    assert_eq!(txes, vec![digest]);
+
+    // Both the creation and the increment of the counter
+    let txes = node.state().get_transactions(
+        TransactionQuery::MatchAny(vec![
+            TransactionQuery::MutatedObject(counter_ref.0),
+            TransactionQuery::MoveFunction {
+                package: package_ref.0,
+                module: Some("counter".to_string()),
+                function: Some("increment".to_string()),
+            },
+        ]),
+        None,
+        None,
+        false,
+    )?;
+    assert_eq!(txes.len(), 2);
+    assert_eq!(txes[1], digest);
+
+    let checkpoint = timeout(Duration::from_secs(60), async {
+        loop {
+            if let Some(checkpoint) = node.state().get_transaction_checkpoint(&digest).unwrap() {
+                break checkpoint.sequence_number();
+            }
+            sleep(Duration::from_millis(100)).await;
+        }
+    })
+    .await?;
+
+    let txes = node.state().get_transactions(
+        TransactionQuery::Checkpoint {
+            start: checkpoint,
+            end: checkpoint,
+        },
+        None,
+        None,
+        false,
+    )?;
+    assert!(txes.contains(&digest));
+
+    let txes = node.state().get_transactions(
+        TransactionQuery::MatchAll(vec![
+            TransactionQuery::Checkpoint {
+                start: 0,
+                end: checkpoint,
+            },
+            TransactionQuery::FromAddress(sender),
+        ]),
+        None,
+        None,
+        false,
+    )?;
+    assert_eq!(txes.last(), Some(&digest));
+
+    let txes = node.state().get_transactions(
+        TransactionQuery::MatchAll(vec![
+            TransactionQuery::Checkpoint {
+                start: checkpoint + 1,
+                end: checkpoint + 1,
+            },
+            TransactionQuery::InputObject(counter_ref.0),
+        ]),
+        None,
+        None,
+        false,
+    )?;
+    assert!(!txes.contains(&digest));
+
     Ok(())
 }
 
@@ -141,6 +141,16 @@ pub struct TelemetryGuards {
 pub struct FilterHandle(reload::Handle<EnvFilter, Registry>);
 
 impl FilterHandle {
+    /// Creates a handle to a log filter without installing a global subscriber, e.g. to test code
+    /// that reads or updates the filter. The filter belongs to the returned subscriber, which must
+    /// be kept alive for the handle to work.
+    pub fn new_for_testing<S: AsRef<str>>(
+        directives: S,
+    ) -> (impl tracing::Subscriber + Send + Sync, Self) {
+        let (filter, handle) = reload::Layer::new(EnvFilter::new(directives));
+        (Registry::default().with(filter), Self(handle))
+    }
+
     pub fn update<S: AsRef<str>>(&self, directives: S) -> Result<(), BoxError> {
         let filter = EnvFilter::try_new(directives)?;
         self.0.reload(filter)?;
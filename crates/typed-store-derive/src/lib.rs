@@ -159,6 +159,12 @@ fn extract_generics_names(generics: &Generics) -> Vec<Ident> {
 /// 3. Auto-generated `read_only_mode` handle
 /// 4. Auto-generated memory stats method
 /// 5. Other convenience features
+/// 6. Auto-generated consistent snapshot of all tables
+///
+/// The tables of a `DBMapUtils` struct are always backed by RocksDB: `DBMapUtils` has no option to
+/// select the storage backend of a table, and backing individual `DBMap` tables with sled is not
+/// supported. To back tables with sled, derive `SallyDB` instead and open the struct with
+/// `SallyDBOptions::Sled`, which backs all of its columns with sled.
 ///
 /// 1. Flexible configuration:
 /// a. Static options specified at struct definition
@@ -273,6 +279,12 @@ fn extract_generics_names(generics: &Generics) -> Vec<Ident> {
 /// 5. Other convenience features
 /// `Tables::describe_tables` is used to get a list of the table names and key-value types as string in a BTreeMap
 ///
+/// 6. Auto-generated consistent snapshot of all tables
+/// For structs of DBMap<K, V>, `self.snapshot` returns a `TablesSnapshot` struct with the same fields,
+/// each a `typed_store::rocks::DBMapSnapshot<K, V>` that exposes the read API of the table.
+/// All of them read the DB as of a single RocksDB snapshot, so that reads across tables are consistent
+/// with each other even when the tables are written to concurrently.
+///
 /// // Bad usage example
 /// // Structs fields most only be of type Store<K, V> or DMBap<K, V>
 /// // This will fail to compile with error `All struct members must be of type Store<K, V> or DMBap<K, V>`
@@ -339,6 +351,43 @@ pub fn derive_dbmap_utils_general(input: TokenStream) -> TokenStream {
     let secondary_db_map_struct_name: proc_macro2::TokenStream =
         secondary_db_map_struct_name_str.parse().unwrap();
 
+    // Snapshots are only supported by RocksDB, so they are only generated for structs of DBMaps
+    let snapshot_section = if simple_field_type_name_str == "DBMap" {
+        let snapshot_struct_name_str = format!("{}Snapshot", name);
+        let snapshot_struct_name: proc_macro2::TokenStream =
+            snapshot_struct_name_str.parse().unwrap();
+        let first_field_name = field_names.first().unwrap();
+
+        quote! {
+            // <----------- This section generates the consistent snapshot of the tables -------------->
+
+            /// A view of all the tables as of a single snapshot of the DB
+            pub struct #snapshot_struct_name<'a #(, #generics_names)*> {
+                #(
+                    pub #field_names : typed_store::rocks::DBMapSnapshot<'a, #key_names, #value_names>,
+                )*
+            }
+
+            impl <
+                    #(
+                        #generics_names: #generics_bounds_token,
+                    )*
+                > #name #generics {
+                /// Takes a snapshot of the DB, through which all the tables can be read consistently
+                pub fn snapshot(&self) -> Result<#snapshot_struct_name<'_ #(, #generics_names)*>, typed_store::rocks::TypedStoreError> {
+                    let snapshot = typed_store::rocks::DBSnapshot::new(&self.#first_field_name.rocksdb);
+                    Ok(#snapshot_struct_name {
+                        #(
+                            #field_names: snapshot.map(&self.#field_names)?,
+                        )*
+                    })
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
     TokenStream::from(quote! {
 
         // <----------- This section generates the configurator struct -------------->
@@ -512,6 +561,8 @@ pub fn derive_dbmap_utils_general(input: TokenStream) -> TokenStream {
         }
 
 
+        #snapshot_section
+
         // <----------- This section generates the features that use read-only open logic -------------->
         /// Create an intermediate struct used to open the DBMap tables in secondary mode
         /// This is only used internally
@@ -645,6 +696,10 @@ pub fn derive_dbmap_utils_general(input: TokenStream) -> TokenStream {
     })
 }
 
+/// Derives `open_tables_read_write` for a struct whose members are `SallyColumn<K, V>`.
+/// Opened with `SallyDBOptions::RocksDB` the columns are backed by column families of a single
+/// RocksDB database, with `SallyDBOptions::Sled` by trees of a single sled database and with
+/// `SallyDBOptions::TestDB` by in-memory tables.
 #[proc_macro_derive(SallyDB, attributes(default_options_override_fn))]
 pub fn derive_sallydb_general(input: TokenStream) -> TokenStream {
     //log_syntax!("here");
@@ -811,6 +866,27 @@ pub fn derive_sallydb_general(input: TokenStream) -> TokenStream {
                             SallyColumn::RocksDB((DBMap::#inner_types::reopen(&db, Some(stringify!(#field_names)), rwopt_cfs.get(stringify!(#field_names)).unwrap_or(&typed_store::rocks::ReadWriteOptions::default())).expect(&format!("Cannot open {} CF.", stringify!(#field_names))[..]), typed_store::sally::SallyConfig::default()))
                             ),*);
 
+                        Self {
+                            #(
+                                #field_names,
+                            )*
+                        }
+                    },
+                    typed_store::sally::SallyDBOptions::Sled(path) => {
+                        // Each column is backed by its own tree of a single sled db
+                        let db = typed_store::sled_db::open_trees(&path, &[
+                            #(
+                                stringify!(#field_names),
+                            )*
+                        ]).expect("Cannot open DB.");
+                        let (
+                            #(
+                                #field_names
+                            ),*
+                        ) = (#(
+                            SallyColumn::Sled((typed_store::sled_db::SledMap::#inner_types::reopen(&db, Some(stringify!(#field_names))).expect(&format!("Cannot open {} tree.", stringify!(#field_names))[..]), typed_store::sally::SallyConfig::default()))
+                            ),*);
+
                         Self {
                             #(
                                 #field_names,
@@ -27,6 +27,7 @@ pub mod metrics;
 pub mod rocks;
 use crate::rocks::RocksDB;
 pub mod sally;
+pub mod sled_db;
 pub mod test_db;
 pub use metrics::DBMetrics;
 
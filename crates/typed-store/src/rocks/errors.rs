@@ -13,12 +13,16 @@ use thiserror::Error;
 pub enum TypedStoreError {
     #[error("rocksdb error: {0}")]
     RocksDBError(String),
+    #[error("sled error: {0}")]
+    SledDBError(String),
     #[error("(de)serialization error: {0}")]
     SerializationError(String),
     #[error("the column family {0} was not registered with the database")]
     UnregisteredColumn(String),
     #[error("a batch operation can't operate across databases")]
     CrossDBBatch,
+    #[error("a snapshot can't be read across databases")]
+    CrossDBSnapshot,
     #[error("Metric reporting thread failed with error")]
     MetricsReporting,
     #[error("Transaction should be retried")]
@@ -44,6 +48,12 @@ impl From<RocksError> for TypedStoreError {
     }
 }
 
+impl From<sled::Error> for TypedStoreError {
+    fn from(err: sled::Error) -> Self {
+        TypedStoreError::SledDBError(format!("{err}"))
+    }
+}
+
 impl Display for RocksErrorDef {
     fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
         self.message.fmt(formatter)
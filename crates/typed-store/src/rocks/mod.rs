@@ -334,6 +334,16 @@ impl RocksDB {
         }
     }
 
+    /// Takes a snapshot of the whole database, across all column families.
+    pub fn snapshot(&self) -> RocksDBSnapshot<'_> {
+        match self {
+            Self::DBWithThreadMode(db) => RocksDBSnapshot::DB(db.underlying.snapshot()),
+            Self::OptimisticTransactionDB(db) => {
+                RocksDBSnapshot::OptimisticTransactionDB(db.underlying.snapshot())
+            }
+        }
+    }
+
     pub fn raw_iterator_cf<'a: 'b, 'b>(
         &'a self,
         cf_handle: &impl AsColumnFamilyRef,
@@ -1312,6 +1322,167 @@ impl<'a> DBTransaction<'a> {
     }
 }
 
+pub enum RocksDBSnapshot<'a> {
+    DB(rocksdb::SnapshotWithThreadMode<'a, DBWithThreadMode<MultiThreaded>>),
+    OptimisticTransactionDB(
+        rocksdb::SnapshotWithThreadMode<'a, rocksdb::OptimisticTransactionDB<MultiThreaded>>,
+    ),
+}
+
+impl<'a> RocksDBSnapshot<'a> {
+    /// Makes reads with these options see the database as of the snapshot.
+    pub fn set_on(&self, readopts: &mut ReadOptions) {
+        match self {
+            Self::DB(snapshot) => readopts.set_snapshot(snapshot),
+            Self::OptimisticTransactionDB(snapshot) => readopts.set_snapshot(snapshot),
+        }
+    }
+}
+
+/// A consistent, read-only view of a database, shared by all of its column families.
+///
+/// Unlike reads from several [DBMap]s, which each see the latest state of the database, reads
+/// from the [DBMapSnapshot]s obtained through [DBSnapshot::map] never observe writes committed
+/// after the snapshot was taken. The snapshot is released when the last of them is dropped.
+#[derive(Clone)]
+pub struct DBSnapshot<'a> {
+    rocksdb: &'a Arc<RocksDB>,
+    snapshot: Arc<RocksDBSnapshot<'a>>,
+}
+
+impl<'a> DBSnapshot<'a> {
+    pub fn new(db: &'a Arc<RocksDB>) -> Self {
+        Self {
+            rocksdb: db,
+            snapshot: Arc::new(db.snapshot()),
+        }
+    }
+
+    /// Returns a read-only view of `db` as of this snapshot. `db` must be a column family of
+    /// the database the snapshot was taken from.
+    pub fn map<K, V>(
+        &self,
+        db: &'a DBMap<K, V>,
+    ) -> Result<DBMapSnapshot<'a, K, V>, TypedStoreError> {
+        if !Arc::ptr_eq(&db.rocksdb, self.rocksdb) {
+            return Err(TypedStoreError::CrossDBSnapshot);
+        }
+        Ok(DBMapSnapshot {
+            db,
+            snapshot: self.snapshot.clone(),
+        })
+    }
+}
+
+/// A column family as of a [DBSnapshot]. It exposes the read API of [Map].
+pub struct DBMapSnapshot<'a, K, V> {
+    db: &'a DBMap<K, V>,
+    snapshot: Arc<RocksDBSnapshot<'a>>,
+}
+
+impl<'a, K, V> DBMapSnapshot<'a, K, V>
+where
+    K: Serialize + DeserializeOwned,
+    V: Serialize + DeserializeOwned,
+{
+    fn readopts(&self) -> ReadOptions {
+        let mut readopts = self.db.opts.readopts();
+        self.snapshot.set_on(&mut readopts);
+        readopts
+    }
+
+    /// Returns true if the map contains a value for the specified key.
+    pub fn contains_key(&self, key: &K) -> Result<bool, TypedStoreError> {
+        Ok(self.get_raw_bytes(key)?.is_some())
+    }
+
+    /// Returns the value for the given key from the map, if it exists.
+    pub fn get(&self, key: &K) -> Result<Option<V>, TypedStoreError> {
+        match self.get_raw_bytes(key)? {
+            Some(data) => Ok(Some(bincode::deserialize(&data)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the raw value (bincode serialized bytes) for the given key from the map, if it exists.
+    pub fn get_raw_bytes(&self, key: &K) -> Result<Option<Vec<u8>>, TypedStoreError> {
+        let key_buf = be_fix_int_ser(key)?;
+        let res = self
+            .db
+            .rocksdb
+            .get_pinned_cf(&self.db.cf(), &key_buf, &self.readopts())?;
+        Ok(res.map(|data| data.to_vec()))
+    }
+
+    /// Returns a vector of values corresponding to the keys provided.
+    pub fn multi_get<J>(
+        &self,
+        keys: impl IntoIterator<Item = J>,
+    ) -> Result<Vec<Option<V>>, TypedStoreError>
+    where
+        J: Borrow<K>,
+    {
+        let cf = self.db.cf();
+        let keys_bytes: Result<Vec<_>, TypedStoreError> = keys
+            .into_iter()
+            .map(|k| Ok((&cf, be_fix_int_ser(k.borrow())?)))
+            .collect();
+
+        let results = self.db.rocksdb.multi_get_cf(keys_bytes?, &self.readopts());
+
+        results
+            .into_iter()
+            .map(|value_byte| match value_byte? {
+                Some(data) => Ok(Some(bincode::deserialize(&data)?)),
+                None => Ok(None),
+            })
+            .collect()
+    }
+
+    /// Returns true if the map is empty, otherwise false.
+    pub fn is_empty(&self) -> bool {
+        self.iter().next().is_none()
+    }
+
+    /// Returns an iterator visiting each key-value pair in the map.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        let mut db_iter = self
+            .db
+            .rocksdb
+            .raw_iterator_cf(&self.db.cf(), self.readopts());
+        db_iter.seek_to_first();
+
+        Iter::new(
+            db_iter,
+            self.db.cf.clone(),
+            &self.db.db_metrics,
+            &self.db.iter_bytes_sample_interval,
+        )
+    }
+
+    /// Returns an iterator over each key in the map.
+    pub fn keys(&self) -> Keys<'_, K> {
+        let mut db_iter = self
+            .db
+            .rocksdb
+            .raw_iterator_cf(&self.db.cf(), self.readopts());
+        db_iter.seek_to_first();
+
+        Keys::new(db_iter)
+    }
+
+    /// Returns an iterator over each value in the map.
+    pub fn values(&self) -> Values<'_, V> {
+        let mut db_iter = self
+            .db
+            .rocksdb
+            .raw_iterator_cf(&self.db.cf(), self.readopts());
+        db_iter.seek_to_first();
+
+        Values::new(db_iter)
+    }
+}
+
 macro_rules! delegate_iter_call {
     ($self:ident.$method:ident($($args:ident),*)) => {
         match $self {
@@ -10,12 +10,6 @@ fn temp_dir() -> std::path::PathBuf {
         .into_path()
 }
 
-#[rstest]
-#[tokio::test]
-async fn test_open(#[values(true, false)] is_transactional: bool) {
-    let _db = open_map::<_, u32, String>(temp_dir(), None, is_transactional);
-}
-
 #[rstest]
 #[tokio::test]
 async fn test_reopen(#[values(true, false)] is_transactional: bool) {
@@ -57,522 +51,6 @@ async fn test_reopen_macro() {
     assert!(db_map_2.multi_insert(keys_vals_cf2).is_ok());
 }
 
-#[rstest]
-#[tokio::test]
-async fn test_wrong_reopen(#[values(true, false)] is_transactional: bool) {
-    let rocks = open_rocksdb(temp_dir(), &["foo", "bar", "baz"], is_transactional);
-    let db = DBMap::<u8, u8>::reopen(&rocks, Some("quux"), &ReadWriteOptions::default());
-    assert!(db.is_err());
-}
-
-#[rstest]
-#[tokio::test]
-async fn test_contains_key(#[values(true, false)] is_transactional: bool) {
-    let db = open_map(temp_dir(), None, is_transactional);
-
-    db.insert(&123456789, &"123456789".to_string())
-        .expect("Failed to insert");
-    assert!(db
-        .contains_key(&123456789)
-        .expect("Failed to call contains key"));
-    assert!(!db
-        .contains_key(&000000000)
-        .expect("Failed to call contains key"));
-}
-
-#[rstest]
-#[tokio::test]
-async fn test_get(#[values(true, false)] is_transactional: bool) {
-    let db = open_map(temp_dir(), None, is_transactional);
-
-    db.insert(&123456789, &"123456789".to_string())
-        .expect("Failed to insert");
-    assert_eq!(
-        Some("123456789".to_string()),
-        db.get(&123456789).expect("Failed to get")
-    );
-    assert_eq!(None, db.get(&000000000).expect("Failed to get"));
-}
-
-#[rstest]
-#[tokio::test]
-async fn test_get_raw(#[values(true, false)] is_transactional: bool) {
-    let db = open_map(temp_dir(), None, is_transactional);
-
-    db.insert(&123456789, &"123456789".to_string())
-        .expect("Failed to insert");
-
-    let val_bytes = db
-        .get_raw_bytes(&123456789)
-        .expect("Failed to get_raw_bytes")
-        .unwrap();
-
-    assert_eq!(
-        bincode::serialize(&"123456789".to_string()).unwrap(),
-        val_bytes
-    );
-    assert_eq!(
-        None,
-        db.get_raw_bytes(&000000000)
-            .expect("Failed to get_raw_bytes")
-    );
-}
-
-#[rstest]
-#[tokio::test]
-async fn test_multi_get(#[values(true, false)] is_transactional: bool) {
-    let db = open_map(temp_dir(), None, is_transactional);
-
-    db.insert(&123, &"123".to_string())
-        .expect("Failed to insert");
-    db.insert(&456, &"456".to_string())
-        .expect("Failed to insert");
-
-    let result = db.multi_get([123, 456, 789]).expect("Failed to multi get");
-
-    assert_eq!(result.len(), 3);
-    assert_eq!(result[0], Some("123".to_string()));
-    assert_eq!(result[1], Some("456".to_string()));
-    assert_eq!(result[2], None);
-}
-
-#[rstest]
-#[tokio::test]
-async fn test_skip(#[values(true, false)] is_transactional: bool) {
-    let db = open_map(temp_dir(), None, is_transactional);
-
-    db.insert(&123, &"123".to_string())
-        .expect("Failed to insert");
-    db.insert(&456, &"456".to_string())
-        .expect("Failed to insert");
-    db.insert(&789, &"789".to_string())
-        .expect("Failed to insert");
-
-    // Skip all smaller
-    let key_vals: Vec<_> = db.iter().skip_to(&456).expect("Seek failed").collect();
-    assert_eq!(key_vals.len(), 2);
-    assert_eq!(key_vals[0], (456, "456".to_string()));
-    assert_eq!(key_vals[1], (789, "789".to_string()));
-
-    // Skip all smaller: same for the keys iterator
-    let keys: Vec<_> = db.keys().skip_to(&456).expect("Seek failed").collect();
-    assert_eq!(keys.len(), 2);
-    assert_eq!(keys[0], (456));
-    assert_eq!(keys[1], (789));
-
-    // Skip to the end
-    assert_eq!(db.iter().skip_to(&999).expect("Seek failed").count(), 0);
-    // same for the keys
-    assert_eq!(db.keys().skip_to(&999).expect("Seek failed").count(), 0);
-
-    // Skip to last
-    assert_eq!(
-        db.iter().skip_to_last().next(),
-        Some((789, "789".to_string()))
-    );
-    // same for the keys
-    assert_eq!(db.keys().skip_to_last().next(), Some(789));
-
-    // Skip to successor of first value
-    assert_eq!(db.iter().skip_to(&000).expect("Skip failed").count(), 3);
-    assert_eq!(db.keys().skip_to(&000).expect("Skip failed").count(), 3);
-}
-
-#[rstest]
-#[tokio::test]
-async fn test_skip_to_previous_simple(#[values(true, false)] is_transactional: bool) {
-    let db = open_map(temp_dir(), None, is_transactional);
-
-    db.insert(&123, &"123".to_string())
-        .expect("Failed to insert");
-    db.insert(&456, &"456".to_string())
-        .expect("Failed to insert");
-    db.insert(&789, &"789".to_string())
-        .expect("Failed to insert");
-
-    // Skip to the one before the end
-    let key_vals: Vec<_> = db
-        .iter()
-        .skip_prior_to(&999)
-        .expect("Seek failed")
-        .collect();
-    assert_eq!(key_vals.len(), 1);
-    assert_eq!(key_vals[0], (789, "789".to_string()));
-    // Same for the keys iterator
-    let keys: Vec<_> = db
-        .keys()
-        .skip_prior_to(&999)
-        .expect("Seek failed")
-        .collect();
-    assert_eq!(keys.len(), 1);
-    assert_eq!(keys[0], (789));
-
-    // Skip to prior of first value
-    // Note: returns an empty iterator!
-    assert_eq!(
-        db.iter().skip_prior_to(&000).expect("Seek failed").count(),
-        0
-    );
-    // Same for the keys iterator
-    assert_eq!(
-        db.keys().skip_prior_to(&000).expect("Seek failed").count(),
-        0
-    );
-}
-
-#[rstest]
-#[tokio::test]
-async fn test_iter_skip_to_previous_gap(#[values(true, false)] is_transactional: bool) {
-    let db = open_map(temp_dir(), None, is_transactional);
-
-    for i in 1..100 {
-        if i != 50 {
-            db.insert(&i, &i.to_string()).unwrap();
-        }
-    }
-
-    // Skip prior to will return an iterator starting with an "unexpected" key if the sought one is not in the table
-    let db_iter = db.iter().skip_prior_to(&50).unwrap();
-
-    assert_eq!(
-        (49..50)
-            .chain(51..100)
-            .map(|i| (i, i.to_string()))
-            .collect::<Vec<_>>(),
-        db_iter.collect::<Vec<_>>()
-    );
-    // Same logic in the keys iterator
-    let db_iter = db.keys().skip_prior_to(&50).unwrap();
-
-    assert_eq!(
-        (49..50).chain(51..100).collect::<Vec<_>>(),
-        db_iter.collect::<Vec<_>>()
-    );
-}
-
-#[rstest]
-#[tokio::test]
-async fn test_remove(#[values(true, false)] is_transactional: bool) {
-    let db = open_map(temp_dir(), None, is_transactional);
-
-    db.insert(&123456789, &"123456789".to_string())
-        .expect("Failed to insert");
-    assert!(db.get(&123456789).expect("Failed to get").is_some());
-
-    db.remove(&123456789).expect("Failed to remove");
-    assert!(db.get(&123456789).expect("Failed to get").is_none());
-}
-
-#[rstest]
-#[tokio::test]
-async fn test_iter(#[values(true, false)] is_transactional: bool) {
-    let db = open_map(temp_dir(), None, is_transactional);
-    db.insert(&123456789, &"123456789".to_string())
-        .expect("Failed to insert");
-
-    let mut iter = db.iter();
-    assert_eq!(Some((123456789, "123456789".to_string())), iter.next());
-    assert_eq!(None, iter.next());
-}
-
-#[rstest]
-#[tokio::test]
-async fn test_iter_reverse(#[values(true, false)] is_transactional: bool) {
-    let db = open_map(temp_dir(), None, is_transactional);
-
-    db.insert(&1, &"1".to_string()).expect("Failed to insert");
-    db.insert(&2, &"2".to_string()).expect("Failed to insert");
-    db.insert(&3, &"3".to_string()).expect("Failed to insert");
-
-    let mut iter = db.iter().skip_to_last().reverse();
-    assert_eq!(Some((3, "3".to_string())), iter.next());
-    assert_eq!(Some((2, "2".to_string())), iter.next());
-    assert_eq!(Some((1, "1".to_string())), iter.next());
-    assert_eq!(None, iter.next());
-
-    let mut iter = db.iter().skip_to(&2).unwrap().reverse();
-    assert_eq!(Some((2, "2".to_string())), iter.next());
-    assert_eq!(Some((1, "1".to_string())), iter.next());
-    assert_eq!(None, iter.next());
-}
-
-#[rstest]
-#[tokio::test]
-async fn test_keys(#[values(true, false)] is_transactional: bool) {
-    let db = open_map(temp_dir(), None, is_transactional);
-
-    db.insert(&123456789, &"123456789".to_string())
-        .expect("Failed to insert");
-
-    let mut keys = db.keys();
-    assert_eq!(Some(123456789), keys.next());
-    assert_eq!(None, keys.next());
-}
-
-#[rstest]
-#[tokio::test]
-async fn test_values(#[values(true, false)] is_transactional: bool) {
-    let db = open_map(temp_dir(), None, is_transactional);
-
-    db.insert(&123456789, &"123456789".to_string())
-        .expect("Failed to insert");
-
-    let mut values = db.values();
-    assert_eq!(Some("123456789".to_string()), values.next());
-    assert_eq!(None, values.next());
-}
-
-#[rstest]
-#[tokio::test]
-async fn test_try_extend(#[values(true, false)] is_transactional: bool) {
-    let mut db = open_map(temp_dir(), None, is_transactional);
-    let mut keys_vals = (1..100).map(|i| (i, i.to_string()));
-
-    db.try_extend(&mut keys_vals)
-        .expect("Failed to extend the DB with (k, v) pairs");
-    for (k, v) in keys_vals {
-        let val = db.get(&k).expect("Failed to get inserted key");
-        assert_eq!(Some(v), val);
-    }
-}
-
-#[rstest]
-#[tokio::test]
-async fn test_try_extend_from_slice(#[values(true, false)] is_transactional: bool) {
-    let mut db = open_map(temp_dir(), None, is_transactional);
-    let keys_vals = (1..100).map(|i| (i, i.to_string()));
-
-    db.try_extend_from_slice(&keys_vals.clone().collect::<Vec<_>>()[..])
-        .expect("Failed to extend the DB with (k, v) pairs");
-    for (k, v) in keys_vals {
-        let val = db.get(&k).expect("Failed to get inserted key");
-        assert_eq!(Some(v), val);
-    }
-}
-
-#[rstest]
-#[tokio::test]
-async fn test_insert_batch(#[values(true, false)] is_transactional: bool) {
-    let db = open_map(temp_dir(), None, is_transactional);
-    let keys_vals = (1..100).map(|i| (i, i.to_string()));
-    let insert_batch = db
-        .batch()
-        .insert_batch(&db, keys_vals.clone())
-        .expect("Failed to batch insert");
-    insert_batch.write().expect("Failed to execute batch");
-    for (k, v) in keys_vals {
-        let val = db.get(&k).expect("Failed to get inserted key");
-        assert_eq!(Some(v), val);
-    }
-}
-
-#[rstest]
-#[tokio::test]
-async fn test_insert_batch_across_cf(#[values(true, false)] is_transactional: bool) {
-    let rocks = open_rocksdb(temp_dir(), &["First_CF", "Second_CF"], is_transactional);
-
-    let db_cf_1 = DBMap::reopen(&rocks, Some("First_CF"), &ReadWriteOptions::default())
-        .expect("Failed to open storage");
-    let keys_vals_1 = (1..100).map(|i| (i, i.to_string()));
-
-    let db_cf_2 = DBMap::reopen(&rocks, Some("Second_CF"), &ReadWriteOptions::default())
-        .expect("Failed to open storage");
-    let keys_vals_2 = (1000..1100).map(|i| (i, i.to_string()));
-
-    let batch = db_cf_1
-        .batch()
-        .insert_batch(&db_cf_1, keys_vals_1.clone())
-        .expect("Failed to batch insert")
-        .insert_batch(&db_cf_2, keys_vals_2.clone())
-        .expect("Failed to batch insert");
-
-    batch.write().expect("Failed to execute batch");
-    for (k, v) in keys_vals_1 {
-        let val = db_cf_1.get(&k).expect("Failed to get inserted key");
-        assert_eq!(Some(v), val);
-    }
-
-    for (k, v) in keys_vals_2 {
-        let val = db_cf_2.get(&k).expect("Failed to get inserted key");
-        assert_eq!(Some(v), val);
-    }
-}
-
-#[rstest]
-#[tokio::test]
-async fn test_insert_batch_across_different_db(#[values(true, false)] is_transactional: bool) {
-    let rocks = open_rocksdb(temp_dir(), &["First_CF", "Second_CF"], is_transactional);
-    let rocks2 = open_rocksdb(temp_dir(), &["First_CF", "Second_CF"], is_transactional);
-
-    let db_cf_1: DBMap<i32, String> =
-        DBMap::reopen(&rocks, Some("First_CF"), &ReadWriteOptions::default())
-            .expect("Failed to open storage");
-    let keys_vals_1 = (1..100).map(|i| (i, i.to_string()));
-
-    let db_cf_2: DBMap<i32, String> =
-        DBMap::reopen(&rocks2, Some("Second_CF"), &ReadWriteOptions::default())
-            .expect("Failed to open storage");
-    let keys_vals_2 = (1000..1100).map(|i| (i, i.to_string()));
-
-    assert!(db_cf_1
-        .batch()
-        .insert_batch(&db_cf_1, keys_vals_1)
-        .expect("Failed to batch insert")
-        .insert_batch(&db_cf_2, keys_vals_2)
-        .is_err());
-}
-
-#[tokio::test]
-async fn test_delete_batch() {
-    let db = DBMap::<i32, String>::open(
-        temp_dir(),
-        MetricConf::default(),
-        None,
-        None,
-        &ReadWriteOptions::default(),
-    )
-    .expect("Failed to open storage");
-
-    let keys_vals = (1..100).map(|i| (i, i.to_string()));
-    let insert_batch = db
-        .batch()
-        .insert_batch(&db, keys_vals)
-        .expect("Failed to batch insert");
-
-    // delete the odd-index keys
-    let deletion_keys = (1..100).step_by(2);
-    let delete_batch = insert_batch
-        .delete_batch(&db, deletion_keys)
-        .expect("Failed to batch delete");
-
-    delete_batch.write().expect("Failed to execute batch");
-
-    for k in db.keys() {
-        assert_eq!(k % 2, 0);
-    }
-}
-
-#[tokio::test]
-async fn test_delete_range() {
-    let db: DBMap<i32, String> = DBMap::open(
-        temp_dir(),
-        MetricConf::default(),
-        None,
-        None,
-        &ReadWriteOptions::default(),
-    )
-    .expect("Failed to open storage");
-
-    // Note that the last element is (100, "100".to_owned()) here
-    let keys_vals = (0..101).map(|i| (i, i.to_string()));
-    let insert_batch = db
-        .batch()
-        .insert_batch(&db, keys_vals)
-        .expect("Failed to batch insert");
-
-    let delete_range_batch = insert_batch
-        .delete_range(&db, &50, &100)
-        .expect("Failed to delete range");
-
-    delete_range_batch.write().expect("Failed to execute batch");
-
-    for k in 0..50 {
-        assert!(db.contains_key(&k).expect("Failed to query legal key"),);
-    }
-    for k in 50..100 {
-        assert!(!db.contains_key(&k).expect("Failed to query legal key"));
-    }
-
-    // range operator is not inclusive of to
-    assert!(db.contains_key(&100).expect("Failed to query legal key"));
-}
-
-#[tokio::test]
-async fn test_clear() {
-    let db = DBMap::<i32, String>::open(
-        temp_dir(),
-        MetricConf::default(),
-        None,
-        Some("table"),
-        &ReadWriteOptions::default(),
-    )
-    .expect("Failed to open storage");
-    // Test clear of empty map
-    let _ = db.clear();
-
-    let keys_vals = (0..101).map(|i| (i, i.to_string()));
-    let insert_batch = db
-        .batch()
-        .insert_batch(&db, keys_vals)
-        .expect("Failed to batch insert");
-
-    insert_batch.write().expect("Failed to execute batch");
-
-    // Check we have multiple entries
-    assert!(db.iter().count() > 1);
-    let _ = db.clear();
-    assert_eq!(db.iter().count(), 0);
-    // Clear again to ensure safety when clearing empty map
-    let _ = db.clear();
-    assert_eq!(db.iter().count(), 0);
-    // Clear with one item
-    let _ = db.insert(&1, &"e".to_string());
-    assert_eq!(db.iter().count(), 1);
-    let _ = db.clear();
-    assert_eq!(db.iter().count(), 0);
-}
-
-#[tokio::test]
-async fn test_is_empty() {
-    let db = DBMap::<i32, String>::open(
-        temp_dir(),
-        MetricConf::default(),
-        None,
-        Some("table"),
-        &ReadWriteOptions::default(),
-    )
-    .expect("Failed to open storage");
-
-    // Test empty map is truly empty
-    assert!(db.is_empty());
-    let _ = db.clear();
-    assert!(db.is_empty());
-
-    let keys_vals = (0..101).map(|i| (i, i.to_string()));
-    let insert_batch = db
-        .batch()
-        .insert_batch(&db, keys_vals)
-        .expect("Failed to batch insert");
-
-    insert_batch.write().expect("Failed to execute batch");
-
-    // Check we have multiple entries and not empty
-    assert!(db.iter().count() > 1);
-    assert!(!db.is_empty());
-
-    // Clear again to ensure empty works after clearing
-    let _ = db.clear();
-    assert_eq!(db.iter().count(), 0);
-    assert!(db.is_empty());
-}
-
-#[rstest]
-#[tokio::test]
-async fn test_multi_insert(#[values(true, false)] is_transactional: bool) {
-    // Init a DB
-    let db: DBMap<i32, String> = open_map(temp_dir(), Some("table"), is_transactional);
-    // Create kv pairs
-    let keys_vals = (0..101).map(|i| (i, i.to_string()));
-
-    db.multi_insert(keys_vals.clone())
-        .expect("Failed to multi-insert");
-
-    for (k, v) in keys_vals {
-        let val = db.get(&k).expect("Failed to get inserted key");
-        assert_eq!(Some(v), val);
-    }
-}
-
 #[rstest]
 #[tokio::test]
 async fn test_checkpoint(#[values(true, false)] is_transactional: bool) {
@@ -611,34 +89,39 @@ async fn test_checkpoint(#[values(true, false)] is_transactional: bool) {
     }
 }
 
-#[rstest]
 #[tokio::test]
-async fn test_multi_remove(#[values(true, false)] is_transactional: bool) {
-    // Init a DB
-    let db: DBMap<i32, String> = open_map(temp_dir(), Some("table"), is_transactional);
+async fn test_delete_range() {
+    let db: DBMap<i32, String> = DBMap::open(
+        temp_dir(),
+        MetricConf::default(),
+        None,
+        None,
+        &ReadWriteOptions::default(),
+    )
+    .expect("Failed to open storage");
 
-    // Create kv pairs
+    // Note that the last element is (100, "100".to_owned()) here
     let keys_vals = (0..101).map(|i| (i, i.to_string()));
+    let insert_batch = db
+        .batch()
+        .insert_batch(&db, keys_vals)
+        .expect("Failed to batch insert");
 
-    db.multi_insert(keys_vals.clone())
-        .expect("Failed to multi-insert");
-
-    // Check insertion
-    for (k, v) in keys_vals.clone() {
-        let val = db.get(&k).expect("Failed to get inserted key");
-        assert_eq!(Some(v), val);
-    }
+    let delete_range_batch = insert_batch
+        .delete_range(&db, &50, &100)
+        .expect("Failed to delete range");
 
-    // Remove 50 items
-    db.multi_remove(keys_vals.clone().map(|kv| kv.0).take(50))
-        .expect("Failed to multi-remove");
-    assert_eq!(db.iter().count(), 101 - 50);
+    delete_range_batch.write().expect("Failed to execute batch");
 
-    // Check that the remaining are present
-    for (k, v) in keys_vals.skip(50) {
-        let val = db.get(&k).expect("Failed to get inserted key");
-        assert_eq!(Some(v), val);
+    for k in 0..50 {
+        assert!(db.contains_key(&k).expect("Failed to query legal key"),);
     }
+    for k in 50..100 {
+        assert!(!db.contains_key(&k).expect("Failed to query legal key"));
+    }
+
+    // range operator is not inclusive of to
+    assert!(db.contains_key(&100).expect("Failed to query legal key"));
 }
 
 #[tokio::test]
@@ -870,6 +353,57 @@ async fn test_transaction_read_your_write() {
     assert!(tx.commit().is_ok());
 }
 
+#[rstest]
+#[tokio::test]
+async fn test_snapshot(#[values(true, false)] is_transactional: bool) {
+    let rocksdb = open_rocksdb(temp_dir(), &["first", "second"], is_transactional);
+    let first = DBMap::<i32, String>::reopen(&rocksdb, Some("first"), &ReadWriteOptions::default())
+        .expect("Failed to re-open storage");
+    let second =
+        DBMap::<i32, String>::reopen(&rocksdb, Some("second"), &ReadWriteOptions::default())
+            .expect("Failed to re-open storage");
+    first.insert(&1, &"1".to_string()).unwrap();
+    second.insert(&1, &"1".to_string()).unwrap();
+    let other = open_map::<_, i32, String>(temp_dir(), None, is_transactional);
+
+    let snapshot = DBSnapshot::new(&rocksdb);
+    let first_snapshot = snapshot.map(&first).unwrap();
+    let second_snapshot = snapshot.map(&second).unwrap();
+
+    // Writes to either column family after the snapshot was taken are not visible through it.
+    first.insert(&1, &"11".to_string()).unwrap();
+    first.insert(&2, &"2".to_string()).unwrap();
+    second.remove(&1).unwrap();
+
+    assert_eq!(first_snapshot.get(&1).unwrap(), Some("1".to_string()));
+    assert!(!first_snapshot.contains_key(&2).unwrap());
+    assert_eq!(
+        first_snapshot.multi_get([1, 2]).unwrap(),
+        vec![Some("1".to_string()), None]
+    );
+    assert_eq!(
+        first_snapshot.iter().collect::<Vec<_>>(),
+        vec![(1, "1".to_string())]
+    );
+    assert_eq!(first_snapshot.keys().collect::<Vec<_>>(), vec![1]);
+    assert_eq!(
+        first_snapshot.values().collect::<Vec<_>>(),
+        vec!["1".to_string()]
+    );
+    assert_eq!(second_snapshot.get(&1).unwrap(), Some("1".to_string()));
+    assert!(!second_snapshot.is_empty());
+
+    // The maps themselves see the latest state.
+    assert_eq!(first.get(&1).unwrap(), Some("11".to_string()));
+    assert!(second.is_empty());
+
+    // Maps of another database can't be read through the snapshot.
+    assert!(matches!(
+        snapshot.map(&other),
+        Err(TypedStoreError::CrossDBSnapshot)
+    ));
+}
+
 #[tokio::test]
 async fn open_as_secondary_test() {
     let primary_path = temp_dir();
@@ -962,3 +496,41 @@ fn open_rocksdb<P: AsRef<Path>>(path: P, opt_cfs: &[&str], is_transactional: boo
         open_cf(path, None, MetricConf::default(), opt_cfs).expect("failed to open rocksdb")
     }
 }
+
+// The `Map` tests shared with the other backends, against both kinds of RocksDB databases.
+// Range deletions are not supported by transactional databases, and are tested separately.
+mod map_tests {
+    use super::*;
+
+    fn open_map<P: AsRef<Path>, K, V>(path: P, opt_cf: Option<&str>) -> DBMap<K, V> {
+        super::open_map(path, opt_cf, false)
+    }
+
+    fn open_db<P: AsRef<Path>>(path: P, opt_cfs: &[&str]) -> Arc<RocksDB> {
+        open_rocksdb(path, opt_cfs, false)
+    }
+
+    fn reopen_map<K, V>(db: &Arc<RocksDB>, cf: &str) -> Result<DBMap<K, V>, TypedStoreError> {
+        DBMap::reopen(db, Some(cf), &ReadWriteOptions::default())
+    }
+
+    include!("../tests/map_tests.rs");
+}
+
+mod transactional_map_tests {
+    use super::*;
+
+    fn open_map<P: AsRef<Path>, K, V>(path: P, opt_cf: Option<&str>) -> DBMap<K, V> {
+        super::open_map(path, opt_cf, true)
+    }
+
+    fn open_db<P: AsRef<Path>>(path: P, opt_cfs: &[&str]) -> Arc<RocksDB> {
+        open_rocksdb(path, opt_cfs, true)
+    }
+
+    fn reopen_map<K, V>(db: &Arc<RocksDB>, cf: &str) -> Result<DBMap<K, V>, TypedStoreError> {
+        DBMap::reopen(db, Some(cf), &ReadWriteOptions::default())
+    }
+
+    include!("../tests/map_tests.rs");
+}
@@ -19,7 +19,7 @@
 //! use crate::typed_store::Map;
 //!
 //! // `ExampleTable` is a sally db instance where each column is first initialized with TestDB
-//! // (btree map) backend and later switched to a RocksDB column family
+//! // (btree map) backend, later switched to a RocksDB column family and then to a sled tree
 //!
 //! #[derive(SallyDB)]
 //! pub struct ExampleTable {
@@ -45,6 +45,10 @@
 //!     let primary_path = tempfile::tempdir().expect("Failed to open db path").into_path();
 //!     table = ExampleTable::init(SallyDBOptions::RocksDB((primary_path, MetricConf::default(), RocksDBAccessType::Primary, None, None)));
 //!     insert_key_vals(&table).await;
+//!     // switch to sled backend
+//!     let sled_path = tempfile::tempdir().expect("Failed to open db path").into_path();
+//!     table = ExampleTable::init(SallyDBOptions::Sled(sled_path));
+//!     insert_key_vals(&table).await;
 //!     Ok(())
 //! }
 //! ```
@@ -53,6 +57,10 @@ use crate::{
         default_db_options, keys::Keys, values::Values, DBBatch, DBMap, DBOptions,
         RocksDBAccessType, TypedStoreError,
     },
+    sled_db::{
+        iter::{Iter as SledIter, Keys as SledKeys, RevIter as SledRevIter, Values as SledValues},
+        SledBatch, SledMap,
+    },
     test_db::{TestDB, TestDBIter, TestDBKeys, TestDBValues, TestDBWriteBatch},
     traits::{AsyncMap, Map},
 };
@@ -93,6 +101,7 @@ impl Default for SallyConfig {
 pub enum SallyColumn<K, V> {
     RocksDB((DBMap<K, V>, SallyConfig)),
     TestDB((TestDB<K, V>, SallyConfig)),
+    Sled((SledMap<K, V>, SallyConfig)),
 }
 
 impl<K, V> SallyColumn<K, V> {
@@ -104,6 +113,9 @@ impl<K, V> SallyColumn<K, V> {
     pub fn new_testdb(db: TestDB<K, V>) -> Self {
         SallyColumn::TestDB((db, SallyConfig::default()))
     }
+    pub fn new_sled(db: SledMap<K, V>) -> Self {
+        SallyColumn::Sled((db, SallyConfig::default()))
+    }
     pub fn batch(&self) -> SallyWriteBatch {
         match self {
             SallyColumn::RocksDB((
@@ -118,6 +130,12 @@ impl<K, V> SallyColumn<K, V> {
                     mode: SallyRunMode::FallbackToDB,
                 },
             )) => SallyWriteBatch::TestDB(test_db.batch()),
+            SallyColumn::Sled((
+                sled_map,
+                SallyConfig {
+                    mode: SallyRunMode::FallbackToDB,
+                },
+            )) => SallyWriteBatch::Sled(sled_map.batch()),
         }
     }
 }
@@ -147,6 +165,12 @@ where
                     mode: SallyRunMode::FallbackToDB,
                 },
             )) => test_db.contains_key(key),
+            SallyColumn::Sled((
+                sled_map,
+                SallyConfig {
+                    mode: SallyRunMode::FallbackToDB,
+                },
+            )) => sled_map.contains_key(key),
         }
     }
     async fn get(&self, key: &K) -> Result<Option<V>, TypedStoreError> {
@@ -163,6 +187,12 @@ where
                     mode: SallyRunMode::FallbackToDB,
                 },
             )) => test_db.get(key),
+            SallyColumn::Sled((
+                sled_map,
+                SallyConfig {
+                    mode: SallyRunMode::FallbackToDB,
+                },
+            )) => sled_map.get(key),
         }
     }
     async fn get_raw_bytes(&self, key: &K) -> Result<Option<Vec<u8>>, TypedStoreError> {
@@ -179,6 +209,12 @@ where
                     mode: SallyRunMode::FallbackToDB,
                 },
             )) => test_db.get_raw_bytes(key),
+            SallyColumn::Sled((
+                sled_map,
+                SallyConfig {
+                    mode: SallyRunMode::FallbackToDB,
+                },
+            )) => sled_map.get_raw_bytes(key),
         }
     }
     async fn is_empty(&self) -> bool {
@@ -195,6 +231,12 @@ where
                     mode: SallyRunMode::FallbackToDB,
                 },
             )) => test_db.is_empty(),
+            SallyColumn::Sled((
+                sled_map,
+                SallyConfig {
+                    mode: SallyRunMode::FallbackToDB,
+                },
+            )) => sled_map.is_empty(),
         }
     }
     async fn iter(&'a self) -> Self::Iterator {
@@ -211,6 +253,12 @@ where
                     mode: SallyRunMode::FallbackToDB,
                 },
             )) => SallyIter::TestDB(test_db.iter()),
+            SallyColumn::Sled((
+                sled_map,
+                SallyConfig {
+                    mode: SallyRunMode::FallbackToDB,
+                },
+            )) => SallyIter::Sled(sled_map.iter()),
         }
     }
     async fn keys(&'a self) -> Self::Keys {
@@ -227,6 +275,12 @@ where
                     mode: SallyRunMode::FallbackToDB,
                 },
             )) => SallyKeys::TestDB(test_db.keys()),
+            SallyColumn::Sled((
+                sled_map,
+                SallyConfig {
+                    mode: SallyRunMode::FallbackToDB,
+                },
+            )) => SallyKeys::Sled(sled_map.keys()),
         }
     }
     async fn values(&'a self) -> Self::Values {
@@ -243,6 +297,12 @@ where
                     mode: SallyRunMode::FallbackToDB,
                 },
             )) => SallyValues::TestDB(test_db.values()),
+            SallyColumn::Sled((
+                sled_map,
+                SallyConfig {
+                    mode: SallyRunMode::FallbackToDB,
+                },
+            )) => SallyValues::Sled(sled_map.values()),
         }
     }
     async fn multi_get<J>(
@@ -265,6 +325,12 @@ where
                     mode: SallyRunMode::FallbackToDB,
                 },
             )) => test_db.multi_get(keys),
+            SallyColumn::Sled((
+                sled_map,
+                SallyConfig {
+                    mode: SallyRunMode::FallbackToDB,
+                },
+            )) => sled_map.multi_get(keys),
         }
     }
     async fn try_catch_up_with_primary(&self) -> Result<(), Self::Error> {
@@ -281,6 +347,12 @@ where
                     mode: SallyRunMode::FallbackToDB,
                 },
             )) => Ok(test_db.try_catch_up_with_primary()?),
+            SallyColumn::Sled((
+                sled_map,
+                SallyConfig {
+                    mode: SallyRunMode::FallbackToDB,
+                },
+            )) => Ok(sled_map.try_catch_up_with_primary()?),
         }
     }
 }
@@ -311,6 +383,12 @@ where
                     mode: SallyRunMode::FallbackToDB,
                 },
             )) => test_db.try_extend(iter),
+            SallyColumn::Sled((
+                sled_map,
+                SallyConfig {
+                    mode: SallyRunMode::FallbackToDB,
+                },
+            )) => sled_map.try_extend(iter),
         }
     }
     fn try_extend_from_slice(&mut self, slice: &[(J, U)]) -> Result<(), Self::Error> {
@@ -327,6 +405,12 @@ where
                     mode: SallyRunMode::FallbackToDB,
                 },
             )) => test_db.try_extend_from_slice(slice),
+            SallyColumn::Sled((
+                sled_map,
+                SallyConfig {
+                    mode: SallyRunMode::FallbackToDB,
+                },
+            )) => sled_map.try_extend_from_slice(slice),
         }
     }
 }
@@ -339,6 +423,8 @@ pub enum SallyWriteBatch {
     RocksDB(DBBatch),
     // Write batch for btree map based backend
     TestDB(TestDBWriteBatch),
+    // Write batch for sled backend, which can span trees of the same sled db
+    Sled(SledBatch),
 }
 
 impl SallyWriteBatch {
@@ -346,6 +432,7 @@ impl SallyWriteBatch {
         match self {
             SallyWriteBatch::RocksDB(db_batch) => db_batch.write(),
             SallyWriteBatch::TestDB(write_batch) => write_batch.write(),
+            SallyWriteBatch::Sled(sled_batch) => sled_batch.write(),
         }
     }
     /// Deletes a set of keys given as an iterator
@@ -361,6 +448,9 @@ impl SallyWriteBatch {
             (SallyWriteBatch::TestDB(write_batch), SallyColumn::TestDB((test_db, _))) => {
                 write_batch.delete_batch(test_db, purged_vals)
             }
+            (SallyWriteBatch::Sled(sled_batch), SallyColumn::Sled((sled_map, _))) => {
+                sled_batch.delete_batch_non_consuming(sled_map, purged_vals)
+            }
             _ => unimplemented!(),
         }
     }
@@ -378,6 +468,9 @@ impl SallyWriteBatch {
             (SallyWriteBatch::TestDB(write_batch), SallyColumn::TestDB((test_db, _))) => {
                 write_batch.delete_range(test_db, from, to)
             }
+            (SallyWriteBatch::Sled(sled_batch), SallyColumn::Sled((sled_map, _))) => {
+                sled_batch.delete_range_non_consuming(sled_map, from, to)
+            }
             _ => unimplemented!(),
         }
     }
@@ -394,6 +487,9 @@ impl SallyWriteBatch {
             (SallyWriteBatch::TestDB(write_batch), SallyColumn::TestDB((test_db, _))) => {
                 write_batch.insert_batch(test_db, new_vals)
             }
+            (SallyWriteBatch::Sled(sled_batch), SallyColumn::Sled((sled_map, _))) => {
+                sled_batch.insert_batch_non_consuming(sled_map, new_vals)
+            }
             _ => unimplemented!(),
         }
     }
@@ -404,6 +500,7 @@ pub enum SallyIter<'a, K, V> {
     // Iter for a rocksdb backed sally column when `fallback_to_db` is true
     RocksDB(RocksDBIter<'a, K, V>),
     TestDB(TestDBIter<'a, K, V>),
+    Sled(SledIter<K, V>),
 }
 
 impl<'a, K: DeserializeOwned, V: DeserializeOwned> Iterator for SallyIter<'a, K, V> {
@@ -412,6 +509,7 @@ impl<'a, K: DeserializeOwned, V: DeserializeOwned> Iterator for SallyIter<'a, K,
         match self {
             SallyIter::RocksDB(iter) => iter.next(),
             SallyIter::TestDB(iter) => iter.next(),
+            SallyIter::Sled(iter) => iter.next(),
         }
     }
 }
@@ -424,6 +522,7 @@ impl<'a, K: Serialize, V> SallyIter<'a, K, V> {
         let iter = match self {
             SallyIter::RocksDB(iter) => SallyIter::RocksDB(iter.skip_to(key)?),
             SallyIter::TestDB(iter) => SallyIter::TestDB(iter.skip_to(key)?),
+            SallyIter::Sled(iter) => SallyIter::Sled(iter.skip_to(key)?),
         };
         Ok(iter)
     }
@@ -435,6 +534,7 @@ impl<'a, K: Serialize, V> SallyIter<'a, K, V> {
         let iter = match self {
             SallyIter::RocksDB(iter) => SallyIter::RocksDB(iter.skip_prior_to(key)?),
             SallyIter::TestDB(iter) => SallyIter::TestDB(iter.skip_prior_to(key)?),
+            SallyIter::Sled(iter) => SallyIter::Sled(iter.skip_prior_to(key)?),
         };
         Ok(iter)
     }
@@ -444,6 +544,7 @@ impl<'a, K: Serialize, V> SallyIter<'a, K, V> {
         match self {
             SallyIter::RocksDB(iter) => SallyIter::RocksDB(iter.skip_to_last()),
             SallyIter::TestDB(iter) => SallyIter::TestDB(iter.skip_to_last()),
+            SallyIter::Sled(iter) => SallyIter::Sled(iter.skip_to_last()),
         }
     }
 
@@ -454,6 +555,7 @@ impl<'a, K: Serialize, V> SallyIter<'a, K, V> {
         match self {
             SallyIter::RocksDB(iter) => SallyRevIter::RocksDB(iter.reverse()),
             SallyIter::TestDB(iter) => SallyRevIter::TestDB(iter.reverse()),
+            SallyIter::Sled(iter) => SallyRevIter::Sled(iter.reverse()),
         }
     }
 }
@@ -462,6 +564,7 @@ pub enum SallyRevIter<'a, K, V> {
     // Iter for a rocksdb backed sally column when `fallback_to_db` is true
     RocksDB(RevIter<'a, K, V>),
     TestDB(TestDBRevIter<'a, K, V>),
+    Sled(SledRevIter<K, V>),
 }
 
 impl<'a, K: DeserializeOwned, V: DeserializeOwned> Iterator for SallyRevIter<'a, K, V> {
@@ -472,6 +575,7 @@ impl<'a, K: DeserializeOwned, V: DeserializeOwned> Iterator for SallyRevIter<'a,
         match self {
             SallyRevIter::RocksDB(rev_iter) => rev_iter.next(),
             SallyRevIter::TestDB(rev_iter) => rev_iter.next(),
+            SallyRevIter::Sled(rev_iter) => rev_iter.next(),
         }
     }
 }
@@ -481,6 +585,7 @@ pub enum SallyKeys<'a, K> {
     // Iter for a rocksdb backed sally column when `fallback_to_db` is true
     RocksDB(Keys<'a, K>),
     TestDB(TestDBKeys<'a, K>),
+    Sled(SledKeys<K>),
 }
 
 impl<'a, K: DeserializeOwned> Iterator for SallyKeys<'a, K> {
@@ -490,6 +595,7 @@ impl<'a, K: DeserializeOwned> Iterator for SallyKeys<'a, K> {
         match self {
             SallyKeys::RocksDB(keys) => keys.next(),
             SallyKeys::TestDB(iter) => iter.next(),
+            SallyKeys::Sled(iter) => iter.next(),
         }
     }
 }
@@ -499,6 +605,7 @@ pub enum SallyValues<'a, V> {
     // Iter for a rocksdb backed sally column when `fallback_to_db` is true
     RocksDB(Values<'a, V>),
     TestDB(TestDBValues<'a, V>),
+    Sled(SledValues<V>),
 }
 
 impl<'a, V: DeserializeOwned> Iterator for SallyValues<'a, V> {
@@ -508,6 +615,7 @@ impl<'a, V: DeserializeOwned> Iterator for SallyValues<'a, V> {
         match self {
             SallyValues::RocksDB(values) => values.next(),
             SallyValues::TestDB(iter) => iter.next(),
+            SallyValues::Sled(iter) => iter.next(),
         }
     }
 }
@@ -525,6 +633,9 @@ pub enum SallyDBOptions {
         ),
     ),
     TestDB,
+    // Options when sally db instance is backed by a sled db at the given path, with one tree per
+    // column
+    Sled(PathBuf),
 }
 
 /// Options to configure a sally db instance for performing read only operations at the global level
@@ -0,0 +1,259 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+use std::{marker::PhantomData, ops::Bound};
+
+use bincode::Options;
+use serde::{de::DeserializeOwned, Serialize};
+use sled::IVec;
+
+use crate::rocks::{be_fix_int_ser, TypedStoreError};
+
+/// The direction a [RawIter] iterates in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Direction {
+    Forward,
+    Reverse,
+}
+
+/// A cursor over the raw key-value pairs of a tree, with the semantics of a RocksDB raw iterator:
+/// it is positioned on an entry, which `next` yields before moving to the following entry in the
+/// direction of the iteration, and reversing it iterates backwards from the entry it is on.
+pub struct RawIter {
+    tree: sled::Tree,
+    /// The entry the iterator is positioned on, `None` once it ran past either end of the tree.
+    current: Option<(IVec, IVec)>,
+    /// The entries after `current`, in the direction of the iteration.
+    rest: sled::Iter,
+    direction: Direction,
+}
+
+impl RawIter {
+    pub(crate) fn new(tree: sled::Tree) -> Self {
+        let first = tree.first();
+        let mut iter = Self {
+            rest: tree.iter(),
+            tree,
+            current: None,
+            direction: Direction::Forward,
+        };
+        // Like RocksDB iterators, storage errors leave the iterator empty.
+        iter.set_current(first.ok().flatten());
+        iter
+    }
+
+    /// Positions the iterator on `entry`, and continues the iteration from it in the current
+    /// direction.
+    fn set_current(&mut self, entry: Option<(IVec, IVec)>) {
+        if let Some((key, _)) = &entry {
+            let key = Bound::Excluded(key.clone());
+            self.rest = match self.direction {
+                Direction::Forward => self.tree.range((key, Bound::Unbounded)),
+                Direction::Reverse => self.tree.range((Bound::Unbounded, key)),
+            };
+        }
+        self.current = entry;
+    }
+
+    /// Positions the iterator at the first key greater than or equal to `key`.
+    fn seek(&mut self, key: Vec<u8>) -> Result<(), TypedStoreError> {
+        let entry = self.tree.range(key..).next().transpose()?;
+        self.set_current(entry);
+        Ok(())
+    }
+
+    /// Positions the iterator at the last key less than or equal to `key`.
+    fn seek_for_prev(&mut self, key: Vec<u8>) -> Result<(), TypedStoreError> {
+        let entry = self.tree.range(..=key).next_back().transpose()?;
+        self.set_current(entry);
+        Ok(())
+    }
+
+    /// Positions the iterator at the last key of the tree.
+    fn seek_to_last(&mut self) -> Result<(), TypedStoreError> {
+        let entry = self.tree.last()?;
+        self.set_current(entry);
+        Ok(())
+    }
+
+    /// Iterates backwards from the entry the iterator is positioned on.
+    fn reverse(&mut self) {
+        self.direction = Direction::Reverse;
+        let current = self.current.take();
+        self.set_current(current);
+    }
+
+    fn next(&mut self) -> Option<(IVec, IVec)> {
+        let current = self.current.take()?;
+        let next = match self.direction {
+            Direction::Forward => self.rest.next(),
+            Direction::Reverse => self.rest.next_back(),
+        };
+        // Like RocksDB iterators, storage errors end the iteration.
+        self.current = next.and_then(|entry| entry.ok());
+        Some(current)
+    }
+}
+
+/// An iterator over all key-value pairs in a data map.
+pub struct Iter<K, V> {
+    db_iter: RawIter,
+    _phantom: PhantomData<(K, V)>,
+}
+
+impl<K, V> Iter<K, V> {
+    pub(crate) fn new(db_iter: RawIter) -> Self {
+        Self {
+            db_iter,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<K: DeserializeOwned, V: DeserializeOwned> Iterator for Iter<K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (raw_key, raw_value) = self.db_iter.next()?;
+        let config = bincode::DefaultOptions::new()
+            .with_big_endian()
+            .with_fixint_encoding();
+        let key = config.deserialize(&raw_key).ok();
+        let value = bincode::deserialize(&raw_value).ok();
+        key.and_then(|k| value.map(|v| (k, v)))
+    }
+}
+
+impl<K: Serialize, V> Iter<K, V> {
+    /// Skips all the elements that are smaller than the given key,
+    /// and either lands on the key or the first one greater than
+    /// the key.
+    pub fn skip_to(mut self, key: &K) -> Result<Self, TypedStoreError> {
+        self.db_iter.seek(be_fix_int_ser(key)?)?;
+        Ok(self)
+    }
+
+    /// Moves the iterator the element given or
+    /// the one prior to it if it does not exist. If there is
+    /// no element prior to it, it returns an empty iterator.
+    pub fn skip_prior_to(mut self, key: &K) -> Result<Self, TypedStoreError> {
+        self.db_iter.seek_for_prev(be_fix_int_ser(key)?)?;
+        Ok(self)
+    }
+
+    /// Seeks to the last key in the database (at this tree).
+    pub fn skip_to_last(mut self) -> Self {
+        // An error leaves the iterator empty, as it would for a RocksDB iterator.
+        if self.db_iter.seek_to_last().is_err() {
+            self.db_iter.set_current(None);
+        }
+        self
+    }
+
+    /// Will make the direction of the iteration reverse and will
+    /// create a new `RevIter` to consume. Every call to `next` method
+    /// will give the next element from the end.
+    pub fn reverse(mut self) -> RevIter<K, V> {
+        self.db_iter.reverse();
+        RevIter::new(self)
+    }
+}
+
+/// An iterator with a reverted direction to the original. The `RevIter`
+/// is hosting an iteration which is consuming in the opposing direction.
+/// It's not possible to do further manipulation (ex re-reverse) to the
+/// iterator.
+pub struct RevIter<K, V> {
+    iter: Iter<K, V>,
+}
+
+impl<K, V> RevIter<K, V> {
+    fn new(iter: Iter<K, V>) -> Self {
+        Self { iter }
+    }
+}
+
+impl<K: DeserializeOwned, V: DeserializeOwned> Iterator for RevIter<K, V> {
+    type Item = (K, V);
+
+    /// Will give the next item backwards
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+}
+
+/// An iterator over the keys of a data map.
+pub struct Keys<K> {
+    db_iter: RawIter,
+    _phantom: PhantomData<K>,
+}
+
+impl<K> Keys<K> {
+    pub(crate) fn new(db_iter: RawIter) -> Self {
+        Self {
+            db_iter,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<K: DeserializeOwned> Iterator for Keys<K> {
+    type Item = K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (raw_key, _) = self.db_iter.next()?;
+        let config = bincode::DefaultOptions::new()
+            .with_big_endian()
+            .with_fixint_encoding();
+        config.deserialize(&raw_key).ok()
+    }
+}
+
+impl<K: Serialize> Keys<K> {
+    /// Skips all the elements that are smaller than the given key,
+    /// and either lands on the key or the first one greater than
+    /// the key.
+    pub fn skip_to(mut self, key: &K) -> Result<Self, TypedStoreError> {
+        self.db_iter.seek(be_fix_int_ser(key)?)?;
+        Ok(self)
+    }
+
+    /// Moves the iterator the element given or
+    /// the one prior to it if it does not exist. If there is
+    /// no element prior to it, it returns an empty iterator.
+    pub fn skip_prior_to(mut self, key: &K) -> Result<Self, TypedStoreError> {
+        self.db_iter.seek_for_prev(be_fix_int_ser(key)?)?;
+        Ok(self)
+    }
+
+    /// Seeks to the last key in the database (at this tree).
+    pub fn skip_to_last(mut self) -> Self {
+        if self.db_iter.seek_to_last().is_err() {
+            self.db_iter.set_current(None);
+        }
+        self
+    }
+}
+
+/// An iterator over the values of a data map.
+pub struct Values<V> {
+    db_iter: RawIter,
+    _phantom: PhantomData<V>,
+}
+
+impl<V> Values<V> {
+    pub(crate) fn new(db_iter: RawIter) -> Self {
+        Self {
+            db_iter,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<V: DeserializeOwned> Iterator for Values<V> {
+    type Item = V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (_, raw_value) = self.db_iter.next()?;
+        bincode::deserialize(&raw_value).ok()
+    }
+}
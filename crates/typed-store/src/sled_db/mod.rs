@@ -0,0 +1,335 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A [Map] backend built on [sled](https://docs.rs/sled), an embedded B-tree store.
+//!
+//! [SledMap] mirrors the interface of [DBMap](crate::rocks::DBMap): a database holds one tree
+//! per table (the equivalent of a RocksDB column family), keys are big endian encoded so that
+//! they iterate in order, and [SledBatch] writes to several tables of the same database
+//! atomically.
+pub(crate) mod iter;
+
+use crate::{
+    rocks::{be_fix_int_ser, TypedStoreError},
+    traits::Map,
+};
+use collectable::TryExtend;
+use serde::{de::DeserializeOwned, Serialize};
+use sled::transaction::{TransactionError, TransactionResult};
+use sled::Transactional;
+use std::{borrow::Borrow, collections::BTreeMap, marker::PhantomData, path::Path, sync::Arc};
+use tracing::instrument;
+
+use self::iter::{Iter, Keys, RawIter, Values};
+
+#[cfg(test)]
+mod tests;
+
+/// The tree used when no table name is given, like the default column family of RocksDB.
+pub const DEFAULT_TREE_NAME: &str = "__sled__default";
+
+/// Opens a sled database, creating the given trees if they do not exist.
+pub fn open_trees<P: AsRef<Path>>(
+    path: P,
+    trees: &[&str],
+) -> Result<Arc<sled::Db>, TypedStoreError> {
+    let db = sled::open(path)?;
+    for tree in trees {
+        db.open_tree(tree)?;
+    }
+    Ok(Arc::new(db))
+}
+
+/// An interface to a sled database, keyed by a tree
+#[derive(Clone, Debug)]
+pub struct SledMap<K, V> {
+    pub db: Arc<sled::Db>,
+    tree: sled::Tree,
+    _phantom: PhantomData<fn(K) -> V>,
+}
+
+impl<K, V> SledMap<K, V> {
+    /// Opens a database from a path, with an optional tree.
+    #[instrument(level="debug", skip_all, fields(path = ?path.as_ref(), tree = ?opt_tree), err)]
+    pub fn open<P: AsRef<Path>>(path: P, opt_tree: Option<&str>) -> Result<Self, TypedStoreError> {
+        let name = opt_tree.unwrap_or(DEFAULT_TREE_NAME);
+        let db = open_trees(path, &[name])?;
+        Self::reopen(&db, opt_tree)
+    }
+
+    /// Reopens an open database as a typed map operating under a specific tree. The tree must
+    /// have been created when the database was opened.
+    #[instrument(level = "debug", skip(db), err)]
+    pub fn reopen(db: &Arc<sled::Db>, opt_tree: Option<&str>) -> Result<Self, TypedStoreError> {
+        let name = opt_tree.unwrap_or(DEFAULT_TREE_NAME);
+        if !db
+            .tree_names()
+            .iter()
+            .any(|tree| tree.as_ref() == name.as_bytes())
+        {
+            return Err(TypedStoreError::UnregisteredColumn(name.to_owned()));
+        }
+        Ok(SledMap {
+            db: db.clone(),
+            tree: db.open_tree(name)?,
+            _phantom: PhantomData,
+        })
+    }
+
+    pub fn batch(&self) -> SledBatch {
+        SledBatch::new(&self.db)
+    }
+
+    pub fn flush(&self) -> Result<(), TypedStoreError> {
+        self.tree.flush()?;
+        Ok(())
+    }
+}
+
+impl<'a, K, V> Map<'a, K, V> for SledMap<K, V>
+where
+    K: Serialize + DeserializeOwned,
+    V: Serialize + DeserializeOwned,
+{
+    type Error = TypedStoreError;
+    type Iterator = Iter<K, V>;
+    type Keys = Keys<K>;
+    type Values = Values<V>;
+
+    fn contains_key(&self, key: &K) -> Result<bool, TypedStoreError> {
+        Ok(self.tree.contains_key(be_fix_int_ser(key)?)?)
+    }
+
+    fn get(&self, key: &K) -> Result<Option<V>, TypedStoreError> {
+        self.tree
+            .get(be_fix_int_ser(key)?)?
+            .map(|raw_value| bincode::deserialize(&raw_value).map_err(|e| e.into()))
+            .transpose()
+    }
+
+    fn get_raw_bytes(&self, key: &K) -> Result<Option<Vec<u8>>, TypedStoreError> {
+        Ok(self
+            .tree
+            .get(be_fix_int_ser(key)?)?
+            .map(|raw_value| raw_value.to_vec()))
+    }
+
+    fn insert(&self, key: &K, value: &V) -> Result<(), TypedStoreError> {
+        self.tree
+            .insert(be_fix_int_ser(key)?, bincode::serialize(value)?)?;
+        Ok(())
+    }
+
+    fn remove(&self, key: &K) -> Result<(), TypedStoreError> {
+        self.tree.remove(be_fix_int_ser(key)?)?;
+        Ok(())
+    }
+
+    fn clear(&self) -> Result<(), TypedStoreError> {
+        self.tree.clear()?;
+        Ok(())
+    }
+
+    fn is_empty(&self) -> bool {
+        self.tree.is_empty()
+    }
+
+    fn iter(&'a self) -> Self::Iterator {
+        Iter::new(RawIter::new(self.tree.clone()))
+    }
+
+    fn keys(&'a self) -> Self::Keys {
+        Keys::new(RawIter::new(self.tree.clone()))
+    }
+
+    fn values(&'a self) -> Self::Values {
+        Values::new(RawIter::new(self.tree.clone()))
+    }
+
+    fn try_catch_up_with_primary(&self) -> Result<(), Self::Error> {
+        // A sled database is only ever opened by a single process.
+        Ok(())
+    }
+}
+
+impl<J, K, U, V> TryExtend<(J, U)> for SledMap<K, V>
+where
+    J: Borrow<K>,
+    U: Borrow<V>,
+    K: Serialize,
+    V: Serialize,
+{
+    type Error = TypedStoreError;
+
+    fn try_extend<T>(&mut self, iter: &mut T) -> Result<(), Self::Error>
+    where
+        T: Iterator<Item = (J, U)>,
+    {
+        let batch = self.batch().insert_batch(self, iter)?;
+        batch.write()
+    }
+
+    fn try_extend_from_slice(&mut self, slice: &[(J, U)]) -> Result<(), Self::Error> {
+        let slice_of_refs = slice.iter().map(|(k, v)| (k.borrow(), v.borrow()));
+        let batch = self.batch().insert_batch(self, slice_of_refs)?;
+        batch.write()
+    }
+}
+
+/// Provides a mutable struct to form a collection of database write operations, and execute them.
+///
+/// Batches can span several trees of the same database, and are written in a single sled
+/// transaction. Range deletions are resolved to the keys in the range when they are added to
+/// the batch, including the keys inserted earlier in the same batch.
+pub struct SledBatch {
+    db: Arc<sled::Db>,
+    /// The pending writes of every tree touched by the batch, keyed by tree name. A `None`
+    /// value deletes the key.
+    writes: BTreeMap<Vec<u8>, (sled::Tree, BTreeMap<Vec<u8>, Option<Vec<u8>>>)>,
+}
+
+impl SledBatch {
+    /// Create a new batch associated with a database.
+    pub fn new(db: &Arc<sled::Db>) -> Self {
+        SledBatch {
+            db: db.clone(),
+            writes: BTreeMap::new(),
+        }
+    }
+
+    fn tree_writes<K, V>(
+        &mut self,
+        map: &SledMap<K, V>,
+    ) -> Result<&mut BTreeMap<Vec<u8>, Option<Vec<u8>>>, TypedStoreError> {
+        if !Arc::ptr_eq(&map.db, &self.db) {
+            return Err(TypedStoreError::CrossDBBatch);
+        }
+        let (_, writes) = self
+            .writes
+            .entry(map.tree.name().to_vec())
+            .or_insert_with(|| (map.tree.clone(), BTreeMap::new()));
+        Ok(writes)
+    }
+
+    /// Consume the batch and write its operations to the database
+    #[instrument(level = "trace", skip_all, err)]
+    pub fn write(self) -> Result<(), TypedStoreError> {
+        let (trees, batches): (Vec<_>, Vec<_>) = self
+            .writes
+            .into_values()
+            .map(|(tree, writes)| {
+                let mut batch = sled::Batch::default();
+                for (key, value) in writes {
+                    match value {
+                        Some(value) => batch.insert(key, value),
+                        None => batch.remove(key),
+                    }
+                }
+                (tree, batch)
+            })
+            .unzip();
+        if trees.is_empty() {
+            return Ok(());
+        }
+
+        let trees: Vec<_> = trees.iter().collect();
+        let result: TransactionResult<(), ()> = trees.as_slice().transaction(|views| {
+            for (view, batch) in views.iter().zip(&batches) {
+                view.apply_batch(batch)?;
+            }
+            Ok(())
+        });
+        match result {
+            Ok(()) => Ok(()),
+            Err(TransactionError::Storage(e)) => Err(e.into()),
+            Err(TransactionError::Abort(())) => unreachable!("batch transactions never abort"),
+        }
+    }
+
+    /// Deletes a set of keys given as an iterator
+    pub fn delete_batch_non_consuming<J: Borrow<K>, K: Serialize, V>(
+        &mut self,
+        db: &SledMap<K, V>,
+        purged_vals: impl IntoIterator<Item = J>,
+    ) -> Result<(), TypedStoreError> {
+        let writes = self.tree_writes(db)?;
+        purged_vals
+            .into_iter()
+            .try_for_each::<_, Result<_, TypedStoreError>>(|k| {
+                writes.insert(be_fix_int_ser(k.borrow())?, None);
+                Ok(())
+            })
+    }
+
+    /// Deletes a range of keys between `from` (inclusive) and `to` (non-inclusive)
+    pub fn delete_range_non_consuming<K: Serialize, V>(
+        &mut self,
+        db: &SledMap<K, V>,
+        from: &K,
+        to: &K,
+    ) -> Result<(), TypedStoreError> {
+        let from_buf = be_fix_int_ser(from)?;
+        let to_buf = be_fix_int_ser(to)?;
+        if from_buf >= to_buf {
+            return Ok(());
+        }
+
+        let writes = self.tree_writes(db)?;
+        for (_, value) in writes.range_mut(from_buf.clone()..to_buf.clone()) {
+            *value = None;
+        }
+        for key in db.tree.range(from_buf..to_buf).keys() {
+            writes.insert(key?.to_vec(), None);
+        }
+        Ok(())
+    }
+
+    /// inserts a range of (key, value) pairs given as an iterator
+    pub fn insert_batch_non_consuming<J: Borrow<K>, K: Serialize, U: Borrow<V>, V: Serialize>(
+        &mut self,
+        db: &SledMap<K, V>,
+        new_vals: impl IntoIterator<Item = (J, U)>,
+    ) -> Result<(), TypedStoreError> {
+        let writes = self.tree_writes(db)?;
+        new_vals
+            .into_iter()
+            .try_for_each::<_, Result<_, TypedStoreError>>(|(k, v)| {
+                writes.insert(
+                    be_fix_int_ser(k.borrow())?,
+                    Some(bincode::serialize(v.borrow())?),
+                );
+                Ok(())
+            })
+    }
+
+    /// Deletes a set of keys given as an iterator
+    pub fn delete_batch<J: Borrow<K>, K: Serialize, V>(
+        mut self,
+        db: &SledMap<K, V>,
+        purged_vals: impl IntoIterator<Item = J>,
+    ) -> Result<Self, TypedStoreError> {
+        self.delete_batch_non_consuming(db, purged_vals)?;
+        Ok(self)
+    }
+
+    /// Deletes a range of keys between `from` (inclusive) and `to` (non-inclusive)
+    pub fn delete_range<K: Serialize, V>(
+        mut self,
+        db: &SledMap<K, V>,
+        from: &K,
+        to: &K,
+    ) -> Result<Self, TypedStoreError> {
+        self.delete_range_non_consuming(db, from, to)?;
+        Ok(self)
+    }
+
+    /// inserts a range of (key, value) pairs given as an iterator
+    pub fn insert_batch<J: Borrow<K>, K: Serialize, U: Borrow<V>, V: Serialize>(
+        mut self,
+        db: &SledMap<K, V>,
+        new_vals: impl IntoIterator<Item = (J, U)>,
+    ) -> Result<Self, TypedStoreError> {
+        self.insert_batch_non_consuming(db, new_vals)?;
+        Ok(self)
+    }
+}
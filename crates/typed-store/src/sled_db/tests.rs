@@ -0,0 +1,86 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+use super::*;
+
+fn temp_dir() -> std::path::PathBuf {
+    tempfile::tempdir()
+        .expect("Failed to open temporary directory")
+        .into_path()
+}
+
+fn open_map<P: AsRef<Path>, K, V>(path: P, opt_tree: Option<&str>) -> SledMap<K, V> {
+    SledMap::open(path, opt_tree).expect("failed to open sled")
+}
+
+fn open_db<P: AsRef<Path>>(path: P, trees: &[&str]) -> Arc<sled::Db> {
+    open_trees(path, trees).expect("failed to open sled")
+}
+
+fn reopen_map<K, V>(db: &Arc<sled::Db>, tree: &str) -> Result<SledMap<K, V>, TypedStoreError> {
+    SledMap::reopen(db, Some(tree))
+}
+
+include!("../tests/map_tests.rs");
+
+#[tokio::test]
+async fn test_reopen() {
+    let path = temp_dir();
+    {
+        let db = open_map::<_, u32, String>(&path, Some("table"));
+        db.insert(&123456789, &"123456789".to_string())
+            .expect("Failed to insert");
+        db.flush().expect("Failed to flush");
+    }
+    let db = open_map::<_, u32, String>(&path, Some("table"));
+    assert!(db
+        .contains_key(&123456789)
+        .expect("Failed to retrieve item in storage"));
+}
+
+#[tokio::test]
+async fn test_delete_range() {
+    let db = open_map::<_, i32, String>(temp_dir(), None);
+
+    // Note that the last element is (100, "100".to_owned()) here
+    let keys_vals = (0..101).map(|i| (i, i.to_string()));
+    let insert_batch = db
+        .batch()
+        .insert_batch(&db, keys_vals)
+        .expect("Failed to batch insert");
+
+    let delete_range_batch = insert_batch
+        .delete_range(&db, &50, &100)
+        .expect("Failed to delete range");
+
+    delete_range_batch.write().expect("Failed to execute batch");
+
+    for k in 0..50 {
+        assert!(db.contains_key(&k).expect("Failed to query legal key"),);
+    }
+    for k in 50..100 {
+        assert!(!db.contains_key(&k).expect("Failed to query legal key"));
+    }
+
+    // range operator is not inclusive of to
+    assert!(db.contains_key(&100).expect("Failed to query legal key"));
+}
+
+#[tokio::test]
+async fn test_delete_range_in_batch() {
+    let db = open_map::<_, i32, String>(temp_dir(), None);
+    db.insert(&10, &"10".to_string()).expect("Failed to insert");
+
+    // Range deletions also remove the keys inserted earlier in the same batch, but not the ones
+    // inserted after them.
+    db.batch()
+        .insert_batch(&db, (0..5).map(|i| (i, i.to_string())))
+        .expect("Failed to batch insert")
+        .delete_range(&db, &3, &20)
+        .expect("Failed to delete range")
+        .insert_batch(&db, [(4, "4".to_string())])
+        .expect("Failed to batch insert")
+        .write()
+        .expect("Failed to execute batch");
+
+    assert_eq!(db.keys().collect::<Vec<_>>(), vec![0, 1, 2, 4]);
+}
@@ -0,0 +1,510 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Tests of the `Map` interface shared by all the backends. They are included in the tests module
+// of each backend, which provides the following helpers:
+// - `temp_dir()`, returning a fresh directory,
+// - `open_map(path, opt_table)`, opening a map on a new database,
+// - `open_db(path, tables)`, opening a database with the given tables,
+// - `reopen_map(&db, table)`, opening a map on a table of an open database.
+
+#[tokio::test]
+async fn test_open() {
+    let _db = open_map::<_, u32, String>(temp_dir(), None);
+}
+
+#[tokio::test]
+async fn test_wrong_reopen() {
+    let store = open_db(temp_dir(), &["foo", "bar", "baz"]);
+    let db = reopen_map::<u8, u8>(&store, "quux");
+    assert!(db.is_err());
+}
+
+#[tokio::test]
+async fn test_contains_key() {
+    let db = open_map(temp_dir(), None);
+
+    db.insert(&123456789, &"123456789".to_string())
+        .expect("Failed to insert");
+    assert!(db
+        .contains_key(&123456789)
+        .expect("Failed to call contains key"));
+    assert!(!db
+        .contains_key(&000000000)
+        .expect("Failed to call contains key"));
+}
+
+#[tokio::test]
+async fn test_get() {
+    let db = open_map(temp_dir(), None);
+
+    db.insert(&123456789, &"123456789".to_string())
+        .expect("Failed to insert");
+    assert_eq!(
+        Some("123456789".to_string()),
+        db.get(&123456789).expect("Failed to get")
+    );
+    assert_eq!(None, db.get(&000000000).expect("Failed to get"));
+}
+
+#[tokio::test]
+async fn test_get_raw() {
+    let db = open_map(temp_dir(), None);
+
+    db.insert(&123456789, &"123456789".to_string())
+        .expect("Failed to insert");
+
+    let val_bytes = db
+        .get_raw_bytes(&123456789)
+        .expect("Failed to get_raw_bytes")
+        .unwrap();
+
+    assert_eq!(
+        bincode::serialize(&"123456789".to_string()).unwrap(),
+        val_bytes
+    );
+    assert_eq!(
+        None,
+        db.get_raw_bytes(&000000000)
+            .expect("Failed to get_raw_bytes")
+    );
+}
+
+#[tokio::test]
+async fn test_multi_get() {
+    let db = open_map(temp_dir(), None);
+
+    db.insert(&123, &"123".to_string())
+        .expect("Failed to insert");
+    db.insert(&456, &"456".to_string())
+        .expect("Failed to insert");
+
+    let result = db.multi_get([123, 456, 789]).expect("Failed to multi get");
+
+    assert_eq!(result.len(), 3);
+    assert_eq!(result[0], Some("123".to_string()));
+    assert_eq!(result[1], Some("456".to_string()));
+    assert_eq!(result[2], None);
+}
+
+#[tokio::test]
+async fn test_skip() {
+    let db = open_map(temp_dir(), None);
+
+    db.insert(&123, &"123".to_string())
+        .expect("Failed to insert");
+    db.insert(&456, &"456".to_string())
+        .expect("Failed to insert");
+    db.insert(&789, &"789".to_string())
+        .expect("Failed to insert");
+
+    // Skip all smaller
+    let key_vals: Vec<_> = db.iter().skip_to(&456).expect("Seek failed").collect();
+    assert_eq!(key_vals.len(), 2);
+    assert_eq!(key_vals[0], (456, "456".to_string()));
+    assert_eq!(key_vals[1], (789, "789".to_string()));
+
+    // Skip all smaller: same for the keys iterator
+    let keys: Vec<_> = db.keys().skip_to(&456).expect("Seek failed").collect();
+    assert_eq!(keys.len(), 2);
+    assert_eq!(keys[0], (456));
+    assert_eq!(keys[1], (789));
+
+    // Skip to the end
+    assert_eq!(db.iter().skip_to(&999).expect("Seek failed").count(), 0);
+    // same for the keys
+    assert_eq!(db.keys().skip_to(&999).expect("Seek failed").count(), 0);
+
+    // Skip to last
+    assert_eq!(
+        db.iter().skip_to_last().next(),
+        Some((789, "789".to_string()))
+    );
+    // same for the keys
+    assert_eq!(db.keys().skip_to_last().next(), Some(789));
+
+    // Skip to successor of first value
+    assert_eq!(db.iter().skip_to(&000).expect("Skip failed").count(), 3);
+    assert_eq!(db.keys().skip_to(&000).expect("Skip failed").count(), 3);
+}
+
+#[tokio::test]
+async fn test_skip_to_previous_simple() {
+    let db = open_map(temp_dir(), None);
+
+    db.insert(&123, &"123".to_string())
+        .expect("Failed to insert");
+    db.insert(&456, &"456".to_string())
+        .expect("Failed to insert");
+    db.insert(&789, &"789".to_string())
+        .expect("Failed to insert");
+
+    // Skip to the one before the end
+    let key_vals: Vec<_> = db
+        .iter()
+        .skip_prior_to(&999)
+        .expect("Seek failed")
+        .collect();
+    assert_eq!(key_vals.len(), 1);
+    assert_eq!(key_vals[0], (789, "789".to_string()));
+    // Same for the keys iterator
+    let keys: Vec<_> = db
+        .keys()
+        .skip_prior_to(&999)
+        .expect("Seek failed")
+        .collect();
+    assert_eq!(keys.len(), 1);
+    assert_eq!(keys[0], (789));
+
+    // Skip to prior of first value
+    // Note: returns an empty iterator!
+    assert_eq!(
+        db.iter().skip_prior_to(&000).expect("Seek failed").count(),
+        0
+    );
+    // Same for the keys iterator
+    assert_eq!(
+        db.keys().skip_prior_to(&000).expect("Seek failed").count(),
+        0
+    );
+}
+
+#[tokio::test]
+async fn test_iter_skip_to_previous_gap() {
+    let db = open_map(temp_dir(), None);
+
+    for i in 1..100 {
+        if i != 50 {
+            db.insert(&i, &i.to_string()).unwrap();
+        }
+    }
+
+    // Skip prior to will return an iterator starting with an "unexpected" key if the sought one is not in the table
+    let db_iter = db.iter().skip_prior_to(&50).unwrap();
+
+    assert_eq!(
+        (49..50)
+            .chain(51..100)
+            .map(|i| (i, i.to_string()))
+            .collect::<Vec<_>>(),
+        db_iter.collect::<Vec<_>>()
+    );
+    // Same logic in the keys iterator
+    let db_iter = db.keys().skip_prior_to(&50).unwrap();
+
+    assert_eq!(
+        (49..50).chain(51..100).collect::<Vec<_>>(),
+        db_iter.collect::<Vec<_>>()
+    );
+}
+
+#[tokio::test]
+async fn test_remove() {
+    let db = open_map(temp_dir(), None);
+
+    db.insert(&123456789, &"123456789".to_string())
+        .expect("Failed to insert");
+    assert!(db.get(&123456789).expect("Failed to get").is_some());
+
+    db.remove(&123456789).expect("Failed to remove");
+    assert!(db.get(&123456789).expect("Failed to get").is_none());
+}
+
+#[tokio::test]
+async fn test_iter() {
+    let db = open_map(temp_dir(), None);
+    db.insert(&123456789, &"123456789".to_string())
+        .expect("Failed to insert");
+
+    let mut iter = db.iter();
+    assert_eq!(Some((123456789, "123456789".to_string())), iter.next());
+    assert_eq!(None, iter.next());
+}
+
+#[tokio::test]
+async fn test_iter_reverse() {
+    let db = open_map(temp_dir(), None);
+
+    db.insert(&1, &"1".to_string()).expect("Failed to insert");
+    db.insert(&2, &"2".to_string()).expect("Failed to insert");
+    db.insert(&3, &"3".to_string()).expect("Failed to insert");
+
+    let mut iter = db.iter().skip_to_last().reverse();
+    assert_eq!(Some((3, "3".to_string())), iter.next());
+    assert_eq!(Some((2, "2".to_string())), iter.next());
+    assert_eq!(Some((1, "1".to_string())), iter.next());
+    assert_eq!(None, iter.next());
+
+    let mut iter = db.iter().skip_to(&2).unwrap().reverse();
+    assert_eq!(Some((2, "2".to_string())), iter.next());
+    assert_eq!(Some((1, "1".to_string())), iter.next());
+    assert_eq!(None, iter.next());
+}
+
+#[tokio::test]
+async fn test_iter_reverse_from_position() {
+    let db = open_map(temp_dir(), None);
+
+    db.insert(&1, &"1".to_string()).expect("Failed to insert");
+    db.insert(&3, &"3".to_string()).expect("Failed to insert");
+    db.insert(&5, &"5".to_string()).expect("Failed to insert");
+
+    // Skipping to a missing key lands on the next key, from which the reversed iteration starts.
+    let keys: Vec<_> = db
+        .iter()
+        .skip_to(&2)
+        .unwrap()
+        .reverse()
+        .map(|(k, _)| k)
+        .collect();
+    assert_eq!(keys, vec![3, 1]);
+
+    // Reversing after iterating forward starts from the key the iteration stopped at.
+    let mut iter = db.iter();
+    assert_eq!(Some((1, "1".to_string())), iter.next());
+    assert_eq!(Some((3, "3".to_string())), iter.next());
+    let keys: Vec<_> = iter.reverse().map(|(k, _)| k).collect();
+    assert_eq!(keys, vec![5, 3, 1]);
+
+    // Reversing an exhausted iteration yields nothing.
+    let mut iter = db.iter().skip_to(&4).unwrap();
+    assert_eq!(Some((5, "5".to_string())), iter.next());
+    assert_eq!(None, iter.next());
+    assert_eq!(iter.reverse().next(), None);
+}
+
+#[tokio::test]
+async fn test_keys() {
+    let db = open_map(temp_dir(), None);
+
+    db.insert(&123456789, &"123456789".to_string())
+        .expect("Failed to insert");
+
+    let mut keys = db.keys();
+    assert_eq!(Some(123456789), keys.next());
+    assert_eq!(None, keys.next());
+}
+
+#[tokio::test]
+async fn test_values() {
+    let db = open_map(temp_dir(), None);
+
+    db.insert(&123456789, &"123456789".to_string())
+        .expect("Failed to insert");
+
+    let mut values = db.values();
+    assert_eq!(Some("123456789".to_string()), values.next());
+    assert_eq!(None, values.next());
+}
+
+#[tokio::test]
+async fn test_try_extend() {
+    let mut db = open_map(temp_dir(), None);
+    let mut keys_vals = (1..100).map(|i| (i, i.to_string()));
+
+    db.try_extend(&mut keys_vals)
+        .expect("Failed to extend the DB with (k, v) pairs");
+    for (k, v) in keys_vals {
+        let val = db.get(&k).expect("Failed to get inserted key");
+        assert_eq!(Some(v), val);
+    }
+}
+
+#[tokio::test]
+async fn test_try_extend_from_slice() {
+    let mut db = open_map(temp_dir(), None);
+    let keys_vals = (1..100).map(|i| (i, i.to_string()));
+
+    db.try_extend_from_slice(&keys_vals.clone().collect::<Vec<_>>()[..])
+        .expect("Failed to extend the DB with (k, v) pairs");
+    for (k, v) in keys_vals {
+        let val = db.get(&k).expect("Failed to get inserted key");
+        assert_eq!(Some(v), val);
+    }
+}
+
+#[tokio::test]
+async fn test_insert_batch() {
+    let db = open_map(temp_dir(), None);
+    let keys_vals = (1..100).map(|i| (i, i.to_string()));
+    let insert_batch = db
+        .batch()
+        .insert_batch(&db, keys_vals.clone())
+        .expect("Failed to batch insert");
+    insert_batch.write().expect("Failed to execute batch");
+    for (k, v) in keys_vals {
+        let val = db.get(&k).expect("Failed to get inserted key");
+        assert_eq!(Some(v), val);
+    }
+}
+
+#[tokio::test]
+async fn test_insert_batch_across_cf() {
+    let store = open_db(temp_dir(), &["First_CF", "Second_CF"]);
+
+    let db_cf_1 = reopen_map(&store, "First_CF").expect("Failed to open storage");
+    let keys_vals_1 = (1..100).map(|i| (i, i.to_string()));
+
+    let db_cf_2 = reopen_map(&store, "Second_CF").expect("Failed to open storage");
+    let keys_vals_2 = (1000..1100).map(|i| (i, i.to_string()));
+
+    let batch = db_cf_1
+        .batch()
+        .insert_batch(&db_cf_1, keys_vals_1.clone())
+        .expect("Failed to batch insert")
+        .insert_batch(&db_cf_2, keys_vals_2.clone())
+        .expect("Failed to batch insert");
+
+    batch.write().expect("Failed to execute batch");
+    for (k, v) in keys_vals_1 {
+        let val = db_cf_1.get(&k).expect("Failed to get inserted key");
+        assert_eq!(Some(v), val);
+    }
+
+    for (k, v) in keys_vals_2 {
+        let val = db_cf_2.get(&k).expect("Failed to get inserted key");
+        assert_eq!(Some(v), val);
+    }
+}
+
+#[tokio::test]
+async fn test_insert_batch_across_different_db() {
+    let store = open_db(temp_dir(), &["First_CF", "Second_CF"]);
+    let store2 = open_db(temp_dir(), &["First_CF", "Second_CF"]);
+
+    let db_cf_1 = reopen_map::<i32, String>(&store, "First_CF").expect("Failed to open storage");
+    let keys_vals_1 = (1..100).map(|i| (i, i.to_string()));
+
+    let db_cf_2 = reopen_map::<i32, String>(&store2, "Second_CF").expect("Failed to open storage");
+    let keys_vals_2 = (1000..1100).map(|i| (i, i.to_string()));
+
+    assert!(db_cf_1
+        .batch()
+        .insert_batch(&db_cf_1, keys_vals_1)
+        .expect("Failed to batch insert")
+        .insert_batch(&db_cf_2, keys_vals_2)
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_delete_batch() {
+    let db = open_map::<_, i32, String>(temp_dir(), None);
+
+    let keys_vals = (1..100).map(|i| (i, i.to_string()));
+    let insert_batch = db
+        .batch()
+        .insert_batch(&db, keys_vals)
+        .expect("Failed to batch insert");
+
+    // delete the odd-index keys
+    let deletion_keys = (1..100).step_by(2);
+    let delete_batch = insert_batch
+        .delete_batch(&db, deletion_keys)
+        .expect("Failed to batch delete");
+
+    delete_batch.write().expect("Failed to execute batch");
+
+    for k in db.keys() {
+        assert_eq!(k % 2, 0);
+    }
+}
+
+#[tokio::test]
+async fn test_clear() {
+    let db = open_map::<_, i32, String>(temp_dir(), Some("table"));
+    // Test clear of empty map
+    let _ = db.clear();
+
+    let keys_vals = (0..101).map(|i| (i, i.to_string()));
+    let insert_batch = db
+        .batch()
+        .insert_batch(&db, keys_vals)
+        .expect("Failed to batch insert");
+
+    insert_batch.write().expect("Failed to execute batch");
+
+    // Check we have multiple entries
+    assert!(db.iter().count() > 1);
+    let _ = db.clear();
+    assert_eq!(db.iter().count(), 0);
+    // Clear again to ensure safety when clearing empty map
+    let _ = db.clear();
+    assert_eq!(db.iter().count(), 0);
+    // Clear with one item
+    let _ = db.insert(&1, &"e".to_string());
+    assert_eq!(db.iter().count(), 1);
+    let _ = db.clear();
+    assert_eq!(db.iter().count(), 0);
+}
+
+#[tokio::test]
+async fn test_is_empty() {
+    let db = open_map::<_, i32, String>(temp_dir(), Some("table"));
+
+    // Test empty map is truly empty
+    assert!(db.is_empty());
+    let _ = db.clear();
+    assert!(db.is_empty());
+
+    let keys_vals = (0..101).map(|i| (i, i.to_string()));
+    let insert_batch = db
+        .batch()
+        .insert_batch(&db, keys_vals)
+        .expect("Failed to batch insert");
+
+    insert_batch.write().expect("Failed to execute batch");
+
+    // Check we have multiple entries and not empty
+    assert!(db.iter().count() > 1);
+    assert!(!db.is_empty());
+
+    // Clear again to ensure empty works after clearing
+    let _ = db.clear();
+    assert_eq!(db.iter().count(), 0);
+    assert!(db.is_empty());
+}
+
+#[tokio::test]
+async fn test_multi_insert() {
+    // Init a DB
+    let db = open_map::<_, i32, String>(temp_dir(), Some("table"));
+    // Create kv pairs
+    let keys_vals = (0..101).map(|i| (i, i.to_string()));
+
+    db.multi_insert(keys_vals.clone())
+        .expect("Failed to multi-insert");
+
+    for (k, v) in keys_vals {
+        let val = db.get(&k).expect("Failed to get inserted key");
+        assert_eq!(Some(v), val);
+    }
+}
+
+#[tokio::test]
+async fn test_multi_remove() {
+    // Init a DB
+    let db = open_map::<_, i32, String>(temp_dir(), Some("table"));
+
+    // Create kv pairs
+    let keys_vals = (0..101).map(|i| (i, i.to_string()));
+
+    db.multi_insert(keys_vals.clone())
+        .expect("Failed to multi-insert");
+
+    // Check insertion
+    for (k, v) in keys_vals.clone() {
+        let val = db.get(&k).expect("Failed to get inserted key");
+        assert_eq!(Some(v), val);
+    }
+
+    // Remove 50 items
+    db.multi_remove(keys_vals.clone().map(|kv| kv.0).take(50))
+        .expect("Failed to multi-remove");
+    assert_eq!(db.iter().count(), 101 - 50);
+
+    // Check that the remaining are present
+    for (k, v) in keys_vals.skip(50) {
+        let val = db.get(&k).expect("Failed to get inserted key");
+        assert_eq!(Some(v), val);
+    }
+}
@@ -18,6 +18,7 @@ use typed_store::rocks::{be_fix_int_ser, MetricConf};
 use typed_store::sally::SallyColumn;
 use typed_store::sally::SallyDBOptions;
 use typed_store::sally::SallyReadOnlyDBOptions;
+use typed_store::traits::AsyncMap;
 use typed_store::traits::Map;
 use typed_store::traits::TableSummary;
 use typed_store::traits::TypedStoreDebug;
@@ -165,6 +166,43 @@ async fn macro_test() {
     assert_eq!(format!("\"8\""), *m.get(&"\"8\"".to_string()).unwrap());
 }
 
+#[tokio::test]
+async fn macro_snapshot_test() {
+    let tbls = Tables::open_tables_read_write(temp_dir(), MetricConf::default(), None, None);
+    tbls.table1
+        .insert(&"1".to_string(), &"1".to_string())
+        .unwrap();
+    tbls.table2.insert(&1, &"1".to_string()).unwrap();
+
+    let snapshot = tbls.snapshot().expect("Failed to take snapshot");
+    tbls.table1
+        .insert(&"1".to_string(), &"11".to_string())
+        .unwrap();
+    tbls.table2.insert(&2, &"2".to_string()).unwrap();
+
+    // Both tables are read as of the time the snapshot was taken
+    assert_eq!(
+        snapshot.table1.get(&"1".to_string()).unwrap(),
+        Some("1".to_string())
+    );
+    assert_eq!(snapshot.table2.keys().collect::<Vec<_>>(), vec![1]);
+    assert_eq!(
+        tbls.table1.get(&"1".to_string()).unwrap(),
+        Some("11".to_string())
+    );
+    assert_eq!(tbls.table2.keys().collect::<Vec<_>>(), vec![1, 2]);
+
+    // Snapshots are generated for generic tables too
+    let tbls_generics = TablesGenerics::<u32, String>::open_tables_read_write(
+        temp_dir(),
+        MetricConf::default(),
+        None,
+        None,
+    );
+    let snapshot = tbls_generics.snapshot().expect("Failed to take snapshot");
+    assert!(snapshot.table2.is_empty());
+}
+
 #[derive(SallyDB)]
 pub struct SallyDBExample {
     col1: SallyColumn<String, String>,
@@ -252,6 +290,51 @@ async fn test_sallydb() {
     assert_eq!(format!("\"8\""), *m.get(&"\"8\"".to_string()).unwrap());
 }
 
+#[tokio::test]
+async fn test_sallydb_sled() {
+    let primary_path = temp_dir();
+    {
+        let example_db = SallyDBExample::init(SallyDBOptions::Sled(primary_path.clone()));
+
+        // Write to both columns, and delete some of the keys written to the second one
+        let keys_vals_1 = (1..10).map(|i| (i.to_string(), i.to_string()));
+        let mut wb = example_db.col1.batch();
+        wb.insert_batch(&example_db.col1, keys_vals_1)
+            .expect("Failed to insert");
+        let keys_vals_2 = (3..10).map(|i| (i, i.to_string()));
+        wb.insert_batch(&example_db.col2, keys_vals_2)
+            .expect("Failed to insert");
+        wb.delete_range(&example_db.col2, &5, &8)
+            .expect("Failed to delete range");
+        wb.write().await.expect("Failed to commit write batch");
+    }
+
+    // Reopen the db and check that the columns hold what was written to them
+    let example_db = SallyDBExample::init(SallyDBOptions::Sled(primary_path));
+    assert_eq!(
+        example_db.col1.keys().await.collect::<Vec<_>>(),
+        (1..10).map(|i| i.to_string()).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        example_db.col2.iter().await.collect::<Vec<_>>(),
+        vec![
+            (3, "3".to_string()),
+            (4, "4".to_string()),
+            (8, "8".to_string()),
+            (9, "9".to_string())
+        ]
+    );
+    assert_eq!(
+        example_db.col2.get(&4).await.expect("Failed to get"),
+        Some("4".to_string())
+    );
+    assert!(!example_db
+        .col2
+        .contains_key(&5)
+        .await
+        .expect("Failed to check key"));
+}
+
 #[tokio::test]
 async fn macro_transactional_test() {
     let key = "key".to_string();